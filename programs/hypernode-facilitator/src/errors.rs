@@ -31,4 +31,10 @@ pub enum FacilitatorError {
 
     #[msg("Insufficient escrow balance")]
     InsufficientBalance,
+
+    #[msg("Refund must go to a payer-owned token account of the escrow mint")]
+    InvalidRefundDestination,
+
+    #[msg("Payment intent already verified")]
+    AlreadyVerified,
 }
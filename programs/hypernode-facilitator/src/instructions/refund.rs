@@ -31,10 +31,14 @@ pub struct RefundPayment<'info> {
 
     pub payer: Signer<'info>,
 
+    /// Refund destination: must be the original payer's account for the escrow mint
     #[account(
         mut,
-        constraint = payer_token_account.owner == payer.key(),
-        constraint = payer_token_account.mint == escrow.mint
+        constraint = payment_intent.is_refund_destination(
+            &payer_token_account.owner,
+            &payer_token_account.mint,
+            &escrow.mint
+        ) @ FacilitatorError::InvalidRefundDestination
     )]
     pub payer_token_account: Account<'info, TokenAccount>,
 
@@ -24,11 +24,9 @@ pub fn handler(
     let clock = Clock::get()?;
 
     require!(!payment_intent.is_expired(clock.unix_timestamp), FacilitatorError::IntentExpired);
-    require!(payment_intent.status == PaymentStatus::Pending, FacilitatorError::InvalidStatus);
 
-    // Store signature and mark as verified
-    payment_intent.signature = signature;
-    payment_intent.status = PaymentStatus::Verified;
+    // Pending -> Verified only; re-verifying (or verifying a settled intent) fails
+    payment_intent.verify(signature)?;
 
     msg!("Payment intent verified: {:?}", payment_intent.id);
 
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::errors::FacilitatorError;
+
 #[account]
 #[derive(Default)]
 pub struct PaymentIntent {
@@ -51,6 +53,23 @@ impl PaymentIntent {
         current_time > self.expires_at
     }
 
+    /// Record the payment signature, moving the intent from Pending to Verified
+    /// Only a Pending intent can be verified, so replaying the call fails
+    pub fn verify(&mut self, signature: [u8; 64]) -> Result<()> {
+        match self.status {
+            PaymentStatus::Pending => {}
+            PaymentStatus::Verified => return err!(FacilitatorError::AlreadyVerified),
+            PaymentStatus::Claimed => return err!(FacilitatorError::AlreadyClaimed),
+            PaymentStatus::Refunded | PaymentStatus::Expired => {
+                return err!(FacilitatorError::InvalidStatus)
+            }
+        }
+
+        self.signature = signature;
+        self.status = PaymentStatus::Verified;
+        Ok(())
+    }
+
     pub fn can_claim(&self) -> bool {
         self.status == PaymentStatus::Verified
     }
@@ -58,6 +77,11 @@ impl PaymentIntent {
     pub fn can_refund(&self, current_time: i64) -> bool {
         self.is_expired(current_time) && self.status == PaymentStatus::Pending
     }
+
+    /// Refunds only go back to the original payer, in the escrowed token
+    pub fn is_refund_destination(&self, owner: &Pubkey, mint: &Pubkey, escrow_mint: &Pubkey) -> bool {
+        *owner == self.payer && mint == escrow_mint
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Default)]
@@ -69,3 +93,68 @@ pub enum PaymentStatus {
     Refunded,
     Expired,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_intent() -> PaymentIntent {
+        PaymentIntent {
+            id: [1u8; 16],
+            payer: Pubkey::new_unique(),
+            amount: 1_000,
+            job_id: [0u8; 32],
+            created_at: 0,
+            expires_at: 3600,
+            signature: [0u8; 64],
+            status: PaymentStatus::Pending,
+            escrow: Pubkey::new_unique(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_verify_pending_intent_accepted() {
+        let mut intent = pending_intent();
+
+        intent.verify([7u8; 64]).unwrap();
+        assert!(intent.status == PaymentStatus::Verified);
+        assert_eq!(intent.signature, [7u8; 64]);
+        assert!(intent.can_claim());
+    }
+
+    #[test]
+    fn test_reverify_rejected() {
+        let mut intent = pending_intent();
+        intent.verify([7u8; 64]).unwrap();
+
+        // Replay with another signature: rejected, original kept
+        assert!(intent.verify([9u8; 64]).is_err());
+        assert_eq!(intent.signature, [7u8; 64]);
+
+        for status in [PaymentStatus::Claimed, PaymentStatus::Refunded, PaymentStatus::Expired] {
+            intent.status = status.clone();
+            assert!(intent.verify([9u8; 64]).is_err());
+            assert!(intent.status == status);
+        }
+    }
+
+    #[test]
+    fn test_refund_to_non_payer_rejected() {
+        let intent = pending_intent();
+        let mint = Pubkey::new_unique();
+
+        assert!(!intent.is_refund_destination(&Pubkey::new_unique(), &mint, &mint));
+
+        // Payer-owned, but in another token
+        assert!(!intent.is_refund_destination(&intent.payer, &Pubkey::new_unique(), &mint));
+    }
+
+    #[test]
+    fn test_refund_to_payer_accepted() {
+        let intent = pending_intent();
+        let mint = Pubkey::new_unique();
+
+        assert!(intent.is_refund_destination(&intent.payer, &mint, &mint));
+    }
+}
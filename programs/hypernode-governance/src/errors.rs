@@ -29,9 +29,24 @@ pub enum GovernanceError {
     #[msg("Proposal has been cancelled")]
     ProposalCancelled,
 
-    #[msg("Title too long (max 100 characters)")]
+    #[msg("Title longer than the configured maximum")]
     TitleTooLong,
 
-    #[msg("Execution data too large (max 256 bytes)")]
+    #[msg("Execution data larger than the configured maximum")]
     ExecutionDataTooLarge,
+
+    #[msg("Execution data does not decode for this proposal type")]
+    InvalidExecutionData,
+
+    #[msg("Execution data values out of range")]
+    ExecutionDataOutOfRange,
+
+    #[msg("Proposal already finalized")]
+    AlreadyFinalized,
+
+    #[msg("Proposal size limits must be non-zero and within the hard bounds")]
+    InvalidProposalLimits,
+
+    #[msg("Execution target does not match the proposal")]
+    InvalidExecutionTarget,
 }
@@ -1,12 +1,20 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::state::*;
 use crate::errors::*;
 use hypernode_staking::program::HypernodeStaking;
-use hypernode_staking::StakeAccount;
+use hypernode_staking::{GlobalStakingStats, StakeAccount};
 
 /// Create new governance proposal
 #[derive(Accounts)]
-#[instruction(title: String, description: String)]
+#[instruction(
+    title: String,
+    description: String,
+    proposal_type: u8,
+    execution_data: Vec<u8>,
+    target_program: Pubkey,
+    target_accounts: Vec<Pubkey>
+)]
 pub struct CreateProposal<'info> {
     /// Governance config (stores proposal counter)
     #[account(
@@ -19,7 +27,7 @@ pub struct CreateProposal<'info> {
     #[account(
         init,
         payer = proposer,
-        space = Proposal::LEN,
+        space = Proposal::space(title.len(), execution_data.len(), target_accounts.len()),
         seeds = [b"proposal", &config.proposal_count.to_le_bytes()],
         bump
     )]
@@ -33,6 +41,14 @@ pub struct CreateProposal<'info> {
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
+    /// Staking aggregates (source of total voting power)
+    #[account(
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+        seeds::program = staking_program.key()
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
     #[account(mut)]
     pub proposer: Signer<'info>,
 
@@ -46,21 +62,23 @@ pub fn handler(
     description: String,
     proposal_type: u8,
     execution_data: Vec<u8>,
+    target_program: Pubkey,
+    target_accounts: Vec<Pubkey>,
 ) -> Result<()> {
     let config = &mut ctx.accounts.config;
     let proposal = &mut ctx.accounts.proposal;
     let stake_account = &ctx.accounts.stake_account;
     let clock = Clock::get()?;
 
-    // Validate title length
+    // Validate title length against the configured limit
     require!(
-        title.len() <= 100,
+        title.len() <= config.title_limit(),
         GovernanceError::TitleTooLong
     );
 
-    // Validate execution data size
+    // Validate execution data size against the configured limit
     require!(
-        execution_data.len() <= 256,
+        execution_data.len() <= config.execution_data_limit(),
         GovernanceError::ExecutionDataTooLarge
     );
 
@@ -82,9 +100,34 @@ pub fn handler(
         1 => ProposalType::StakingParameter,
         2 => ProposalType::TreasurySpend,
         3 => ProposalType::ProtocolUpgrade,
+        5 => ProposalType::ParameterUpdate,
         _ => ProposalType::Text,
     };
 
+    // Reject malformed or out-of-range payloads up front
+    prop_type.validate_execution_data(&execution_data)?;
+
+    // Bind execution to a specific program and accounts
+    prop_type.validate_target(&target_program, &target_accounts)?;
+
+    // Escrow the anti-spam deposit in the proposal account
+    let deposit = config.proposal_deposit;
+    if deposit > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.proposer.to_account_info(),
+                    to: proposal.to_account_info(),
+                },
+            ),
+            deposit,
+        )?;
+    }
+
+    // Refresh total voting power from staking aggregates
+    config.total_voting_power = ctx.accounts.global_stats.total_xhyper;
+
     // Get current proposal ID and increment counter
     let proposal_id = config.proposal_count;
     config.proposal_count += 1;
@@ -104,6 +147,9 @@ pub fn handler(
     proposal.votes_against = 0;
     proposal.voter_count = 0;
     proposal.status = ProposalStatus::Active;
+    proposal.deposit = deposit;
+    proposal.target_program = target_program;
+    proposal.target_accounts = target_accounts;
     proposal.bump = ctx.bumps.proposal;
 
     msg!("Proposal created with counter-based ID");
@@ -111,6 +157,7 @@ pub fn handler(
     msg!("Title: {}", title);
     msg!("Proposer: {}", proposal.proposer);
     msg!("Voting ends: {}", proposal.time_voting_ends);
+    msg!("Deposit: {} lamports", deposit);
 
     Ok(())
 }
@@ -1,42 +1,60 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::invoke_signed;
 use crate::state::*;
 use crate::errors::*;
 
 /// Execute a passed proposal
+/// The proposal's target accounts are passed as remaining accounts, in order
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
-    #[account(
-        mut,
-        constraint = proposal.status == ProposalStatus::Passed @ GovernanceError::ProposalNotPassed
-    )]
+    /// Status checked in the handler (Executed / Cancelled / not passed)
+    #[account(mut)]
     pub proposal: Account<'info, Proposal>,
 
     /// Anyone can execute a passed proposal
     #[account(mut)]
     pub executor: Signer<'info>,
+
+    /// Program the proposal targets (omitted for Text proposals)
+    /// CHECK: matched against proposal.target_program in the handler
+    pub target_program: Option<UncheckedAccount<'info>>,
 }
 
-pub fn handler(ctx: Context<ExecuteProposal>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
     let proposal = &mut ctx.accounts.proposal;
     let clock = Clock::get()?;
 
-    // Verify execution delay has passed
-    require!(
-        proposal.is_executable(clock.unix_timestamp),
-        GovernanceError::ExecutionDelayActive
-    );
+    // Verify the execution is aimed at the program and accounts fixed at creation
+    let target_accounts: Vec<Pubkey> = ctx.remaining_accounts.iter().map(|a| a.key()).collect();
+    proposal.verify_target(ctx.accounts.target_program.as_ref().map(|p| p.key), &target_accounts)?;
 
-    // Mark as executed
-    proposal.status = ProposalStatus::Executed;
+    // Verify the proposal passed and the execution delay has passed,
+    // and mark it executed BEFORE any external calls (reentrancy protection)
+    proposal.mark_executed(clock.unix_timestamp)?;
 
     msg!("Proposal executed");
     msg!("ID: {}", proposal.id);
     msg!("Type: {:?}", proposal.proposal_type);
     msg!("Executor: {}", ctx.accounts.executor.key());
 
-    // Note: Actual execution logic would invoke target programs
-    // based on proposal.execution_data using CPI
-    // For now, just marking as executed
+    // ParameterUpdate proposals call the target program, signed by the
+    // governance authority; typed proposals are recorded only
+    if proposal.proposal_type == ProposalType::ParameterUpdate {
+        let writable: Vec<bool> = ctx.remaining_accounts.iter().map(|a| a.is_writable).collect();
+        let ix = proposal.execution_instruction(&writable);
+
+        // Persist Executed before the CPI, so the target sees it and a
+        // re-entrant execute is rejected
+        proposal.exit(&crate::ID)?;
+
+        let target_program = ctx.accounts.target_program.as_ref()
+            .ok_or(error!(GovernanceError::InvalidExecutionTarget))?;
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(target_program.to_account_info());
+
+        let (_, bump) = governance_authority();
+        invoke_signed(&ix, &account_infos, &[&[GOVERNANCE_AUTHORITY_SEED, &[bump]]])?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Finalize a proposal after voting ends (permissionless)
+/// Sets Passed/Rejected and settles the deposit: refunded to the proposer
+/// if quorum was reached, forfeited to the treasury otherwise
+#[derive(Accounts)]
+pub struct FinalizeProposal<'info> {
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance config (treasury)
+    #[account(
+        seeds = [b"gov_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, GovernanceConfig>,
+
+    /// Proposer (deposit refund destination)
+    #[account(
+        mut,
+        address = proposal.proposer @ GovernanceError::Unauthorized
+    )]
+    pub proposer: SystemAccount<'info>,
+
+    /// Treasury (forfeited deposit destination)
+    #[account(
+        mut,
+        address = config.treasury @ GovernanceError::Unauthorized
+    )]
+    pub treasury: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizeProposal>) -> Result<()> {
+    let proposal = &mut ctx.accounts.proposal;
+    let clock = Clock::get()?;
+
+    require!(
+        proposal.status == ProposalStatus::Active || proposal.deposit > 0,
+        GovernanceError::AlreadyFinalized
+    );
+
+    proposal.finalize(clock.unix_timestamp)?;
+    let (deposit, refunded) = proposal.settle_deposit();
+
+    if deposit > 0 {
+        let destination = if refunded {
+            ctx.accounts.proposer.to_account_info()
+        } else {
+            ctx.accounts.treasury.to_account_info()
+        };
+        **proposal.to_account_info().try_borrow_mut_lamports()? -= deposit;
+        **destination.try_borrow_mut_lamports()? += deposit;
+    }
+
+    msg!("Proposal finalized");
+    msg!("ID: {}", proposal.id);
+    msg!(
+        "Deposit {} lamports {}",
+        deposit,
+        if refunded { "refunded to proposer" } else { "forfeited to treasury" }
+    );
+
+    Ok(())
+}
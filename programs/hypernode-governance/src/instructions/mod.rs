@@ -2,8 +2,16 @@ pub mod create_proposal;
 pub mod vote;
 pub mod execute_proposal;
 pub mod cancel_proposal;
+pub mod finalize_proposal;
+pub mod set_proposal_deposit;
+pub mod proposal_status_preview;
+pub mod set_proposal_limits;
 
 pub use create_proposal::*;
 pub use vote::*;
 pub use execute_proposal::*;
 pub use cancel_proposal::*;
+pub use finalize_proposal::*;
+pub use set_proposal_deposit::*;
+pub use proposal_status_preview::*;
+pub use set_proposal_limits::*;
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Read a proposal's current pass/fail projection (view)
+/// Uses the same quorum and majority math as finalize
+#[derive(Accounts)]
+pub struct ProposalStatusPreviewView<'info> {
+    pub proposal: Account<'info, Proposal>,
+}
+
+pub fn handler(ctx: Context<ProposalStatusPreviewView>) -> Result<ProposalStatusPreview> {
+    let clock = Clock::get()?;
+    let preview = ctx.accounts.proposal.status_preview(clock.unix_timestamp);
+
+    msg!("Proposal: {}", ctx.accounts.proposal.id);
+    msg!("Votes for: {} | against: {}", preview.votes_for, preview.votes_against);
+    msg!("Quorum met: {}", preview.quorum_met);
+    msg!("Projected to pass: {}", preview.projected_to_pass);
+    msg!("Time remaining: {} seconds", preview.time_remaining);
+
+    Ok(preview)
+}
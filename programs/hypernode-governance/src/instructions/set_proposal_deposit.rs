@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the proposal deposit and the treasury receiving forfeited deposits
+/// (config authority only)
+#[derive(Accounts)]
+pub struct SetProposalDeposit<'info> {
+    #[account(
+        mut,
+        seeds = [b"gov_config"],
+        bump = config.bump,
+        has_one = authority @ GovernanceError::Unauthorized
+    )]
+    pub config: Account<'info, GovernanceConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetProposalDeposit>,
+    proposal_deposit: u64,
+    treasury: Pubkey,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.proposal_deposit = proposal_deposit;
+    config.treasury = treasury;
+
+    msg!("Proposal deposit: {} lamports", proposal_deposit);
+    msg!("Treasury: {}", treasury);
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the maximum title and execution_data sizes of new proposals
+/// (config authority only)
+#[derive(Accounts)]
+pub struct SetProposalLimits<'info> {
+    #[account(
+        mut,
+        seeds = [b"gov_config"],
+        bump = config.bump,
+        has_one = authority @ GovernanceError::Unauthorized
+    )]
+    pub config: Account<'info, GovernanceConfig>,
+
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetProposalLimits>,
+    max_title_len: u16,
+    max_execution_data_len: u16,
+) -> Result<()> {
+    require!(
+        GovernanceConfig::is_valid_proposal_limits(max_title_len, max_execution_data_len),
+        GovernanceError::InvalidProposalLimits
+    );
+
+    let config = &mut ctx.accounts.config;
+
+    config.max_title_len = max_title_len;
+    config.max_execution_data_len = max_execution_data_len;
+
+    msg!("Max title length: {} bytes", max_title_len);
+    msg!("Max execution data: {} bytes", max_execution_data_len);
+
+    Ok(())
+}
@@ -13,20 +13,22 @@ pub struct Vote<'info> {
     )]
     pub proposal: Account<'info, Proposal>,
 
+    /// One vote per stake (keyed by stake account, not voter)
     #[account(
         init,
         payer = voter,
         space = VoteRecord::LEN,
-        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        seeds = [b"vote", proposal.key().as_ref(), stake_account.key().as_ref()],
         bump
     )]
     pub vote_record: Account<'info, VoteRecord>,
 
-    /// Voter's stake account
+    /// Stake account voting (voter must be its beneficiary, or its authority if none)
     #[account(
-        seeds = [b"stake", voter.key().as_ref()],
+        seeds = [b"stake", stake_account.authority.as_ref()],
         bump,
-        seeds::program = staking_program.key()
+        seeds::program = staking_program.key(),
+        constraint = stake_account.beneficiary_or_authority() == voter.key() @ GovernanceError::Unauthorized
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
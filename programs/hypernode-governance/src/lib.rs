@@ -20,8 +20,18 @@ pub mod hypernode_governance {
         description: String,
         proposal_type: u8,
         execution_data: Vec<u8>,
+        target_program: Pubkey,
+        target_accounts: Vec<Pubkey>,
     ) -> Result<()> {
-        instructions::create_proposal::handler(ctx, title, description, proposal_type, execution_data)
+        instructions::create_proposal::handler(
+            ctx,
+            title,
+            description,
+            proposal_type,
+            execution_data,
+            target_program,
+            target_accounts,
+        )
     }
 
     pub fn vote(
@@ -31,11 +41,41 @@ pub mod hypernode_governance {
         instructions::vote::handler(ctx, vote_choice)
     }
 
-    pub fn execute_proposal(ctx: Context<ExecuteProposal>) -> Result<()> {
+    pub fn execute_proposal<'info>(ctx: Context<'_, '_, 'info, 'info, ExecuteProposal<'info>>) -> Result<()> {
         instructions::execute_proposal::handler(ctx)
     }
 
     pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
         instructions::cancel_proposal::handler(ctx)
     }
+
+    /// Close voting and settle the proposal deposit (permissionless)
+    pub fn finalize_proposal(ctx: Context<FinalizeProposal>) -> Result<()> {
+        instructions::finalize_proposal::handler(ctx)
+    }
+
+    /// Set the proposal deposit and treasury (config authority only)
+    pub fn set_proposal_deposit(
+        ctx: Context<SetProposalDeposit>,
+        proposal_deposit: u64,
+        treasury: Pubkey,
+    ) -> Result<()> {
+        instructions::set_proposal_deposit::handler(ctx, proposal_deposit, treasury)
+    }
+
+    /// Proposal pass/fail projection view (votes, quorum, outcome if voting ended now)
+    pub fn proposal_status_preview(
+        ctx: Context<ProposalStatusPreviewView>,
+    ) -> Result<state::ProposalStatusPreview> {
+        instructions::proposal_status_preview::handler(ctx)
+    }
+
+    /// Set the maximum proposal title and execution_data sizes (config authority only)
+    pub fn set_proposal_limits(
+        ctx: Context<SetProposalLimits>,
+        max_title_len: u16,
+        max_execution_data_len: u16,
+    ) -> Result<()> {
+        instructions::set_proposal_limits::handler(ctx, max_title_len, max_execution_data_len)
+    }
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use crate::errors::GovernanceError;
 
 /// Governance configuration constants
 pub const PROPOSAL_THRESHOLD: u128 = 1_000_000; // 1M xHYPER to create proposal
@@ -6,6 +8,33 @@ pub const QUORUM_PERCENTAGE: u16 = 1000; // 10% quorum required
 pub const VOTING_PERIOD: i64 = 3 * 86400; // 3 days voting period
 pub const EXECUTION_DELAY: i64 = 1 * 86400; // 1 day delay after passing
 
+/// Bounds for typed execution payloads (checked at proposal creation)
+pub const MIN_JOB_TIMEOUT: i64 = 60; // 1 minute
+pub const MAX_JOB_TIMEOUT: i64 = 7 * 86400; // 7 days
+pub const MIN_STAKE_DURATION: i64 = 14 * 86400; // 2 weeks
+pub const MAX_STAKE_DURATION: i64 = 4 * 365 * 86400; // 4 years
+
+/// Default proposal size limits (GovernanceConfig fields left at 0 use these)
+pub const DEFAULT_MAX_TITLE_LEN: u16 = 100;
+pub const DEFAULT_MAX_EXECUTION_DATA_LEN: u16 = 256;
+
+/// Upper bounds for the configurable proposal size limits
+/// Keep the largest proposal well within the 10KB CPI account allocation
+pub const MAX_TITLE_LEN_LIMIT: u16 = 512;
+pub const MAX_EXECUTION_DATA_LEN_LIMIT: u16 = 4096;
+
+/// Most accounts an executable proposal can bind its execution to
+pub const MAX_TARGET_ACCOUNTS: usize = 8;
+
+/// Seed of the governance authority PDA
+/// Signs CPIs into other programs when executing passed proposals
+pub const GOVERNANCE_AUTHORITY_SEED: &[u8] = b"governance_authority";
+
+/// Address and bump of the governance authority PDA
+pub fn governance_authority() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[GOVERNANCE_AUTHORITY_SEED], &crate::ID)
+}
+
 /// Governance configuration account
 /// Stores global state including proposal counter
 #[account]
@@ -16,15 +45,69 @@ pub struct GovernanceConfig {
     /// Global proposal counter (prevents ID collisions)
     pub proposal_count: u64,
 
-    /// Total xHYPER voting power (updated periodically)
+    /// Total xHYPER voting power (synced from staking GlobalStakingStats)
     pub total_voting_power: u128,
 
+    /// Refundable SOL deposit escrowed by each new proposal (0 = none)
+    /// Returned if the proposal reaches quorum, forfeited to the treasury otherwise
+    pub proposal_deposit: u64,
+
+    /// Receives forfeited proposal deposits
+    pub treasury: Pubkey,
+
+    /// Longest proposal title in bytes (0 = DEFAULT_MAX_TITLE_LEN)
+    pub max_title_len: u16,
+
+    /// Largest proposal execution_data in bytes (0 = DEFAULT_MAX_EXECUTION_DATA_LEN)
+    pub max_execution_data_len: u16,
+
     /// PDA bump
     pub bump: u8,
 }
 
 impl GovernanceConfig {
-    pub const LEN: usize = 8 + 32 + 8 + 16 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 16 + 8 + 32 + 2 + 2 + 1;
+
+    /// Check proposal size limits are non-zero and within the hard bounds
+    pub fn is_valid_proposal_limits(max_title_len: u16, max_execution_data_len: u16) -> bool {
+        (1..=MAX_TITLE_LEN_LIMIT).contains(&max_title_len)
+            && (1..=MAX_EXECUTION_DATA_LEN_LIMIT).contains(&max_execution_data_len)
+    }
+
+    /// Longest title a new proposal may have
+    pub fn title_limit(&self) -> usize {
+        match self.max_title_len {
+            0 => DEFAULT_MAX_TITLE_LEN as usize,
+            len => len as usize,
+        }
+    }
+
+    /// Largest execution_data a new proposal may carry
+    pub fn execution_data_limit(&self) -> usize {
+        match self.max_execution_data_len {
+            0 => DEFAULT_MAX_EXECUTION_DATA_LEN as usize,
+            len => len as usize,
+        }
+    }
+}
+
+/// Pass/fail projection returned by proposal_status_preview
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ProposalStatusPreview {
+    /// Votes in favor so far (weighted by xHYPER)
+    pub votes_for: u128,
+
+    /// Votes against so far (weighted by xHYPER)
+    pub votes_against: u128,
+
+    /// Whether enough xHYPER has voted to meet quorum
+    pub quorum_met: bool,
+
+    /// Whether the proposal would pass if voting ended now
+    pub projected_to_pass: bool,
+
+    /// Seconds until voting ends (0 once it has)
+    pub time_remaining: i64,
 }
 
 /// Governance proposal
@@ -36,7 +119,7 @@ pub struct Proposal {
     /// Proposer
     pub proposer: Pubkey,
 
-    /// Title (max 100 chars)
+    /// Title (max GovernanceConfig::title_limit bytes)
     pub title: String,
 
     /// Description IPFS CID
@@ -45,7 +128,7 @@ pub struct Proposal {
     /// Proposal type
     pub proposal_type: ProposalType,
 
-    /// Execution data (serialized instruction data)
+    /// Execution data (serialized instruction data, max GovernanceConfig::execution_data_limit bytes)
     pub execution_data: Vec<u8>,
 
     /// Voting start time
@@ -72,12 +155,29 @@ pub struct Proposal {
     /// Status
     pub status: ProposalStatus,
 
+    /// Deposit (lamports) escrowed in this account, 0 once settled
+    pub deposit: u64,
+
+    /// Program execute_proposal must CPI into (default for Text proposals)
+    pub target_program: Pubkey,
+
+    /// Accounts the execution CPI must be made against, in order
+    /// (max MAX_TARGET_ACCOUNTS, empty for Text proposals)
+    pub target_accounts: Vec<Pubkey>,
+
     /// PDA bump
     pub bump: u8,
 }
 
 impl Proposal {
-    pub const LEN: usize = 8 + 8 + 32 + 100 + 32 + 1 + (4 + 256) + 8 + 8 + 8 + 16 + 16 + 16 + 8 + 1 + 1;
+    /// Account size without the title, execution_data and target_accounts contents
+    pub const BASE_LEN: usize = 8 + 8 + 32 + 4 + 32 + 1 + 4 + 8 + 8 + 8 + 16 + 16 + 16 + 8 + 1 + 8 + 32 + 4 + 1;
+
+    /// Account size for a proposal with the given title, execution_data and target_accounts lengths
+    /// Proposals are allocated to fit, so the configured limits don't need a fixed size
+    pub fn space(title_len: usize, execution_data_len: usize, target_accounts_len: usize) -> usize {
+        Self::BASE_LEN + title_len + execution_data_len + 32 * target_accounts_len
+    }
 
     /// Check if proposal passed quorum and majority
     pub fn has_passed(&self) -> bool {
@@ -87,16 +187,59 @@ impl Proposal {
             return false;
         }
 
-        // Check quorum (at least 10% of total xHYPER voted)
+        self.would_pass()
+    }
+
+    /// Check quorum (at least 10% of total xHYPER voted) and majority (>50%)
+    /// on the votes cast so far
+    pub fn would_pass(&self) -> bool {
+        self.reached_quorum() && self.votes_for > self.votes_against
+    }
+
+    /// Current votes and the outcome if voting ended now
+    pub fn status_preview(&self, current_time: i64) -> ProposalStatusPreview {
+        ProposalStatusPreview {
+            votes_for: self.votes_for,
+            votes_against: self.votes_against,
+            quorum_met: self.reached_quorum(),
+            projected_to_pass: self.would_pass(),
+            time_remaining: (self.time_voting_ends - current_time).max(0),
+        }
+    }
+
+    /// Check if enough xHYPER voted (either way) to meet quorum
+    pub fn reached_quorum(&self) -> bool {
         let total_votes = self.votes_for + self.votes_against;
         let quorum_required = (self.total_voting_power * QUORUM_PERCENTAGE as u128) / 10000;
+        total_votes >= quorum_required
+    }
 
-        if total_votes < quorum_required {
-            return false;
+    /// Close voting once the period is over
+    /// Active proposals become Passed (quorum and majority) or Rejected
+    pub fn finalize(&mut self, current_time: i64) -> Result<()> {
+        require!(
+            current_time >= self.time_voting_ends,
+            GovernanceError::VotingStillActive
+        );
+
+        if self.status == ProposalStatus::Active {
+            if self.would_pass() {
+                self.status = ProposalStatus::Passed;
+                self.time_executable = current_time + EXECUTION_DELAY;
+            } else {
+                self.status = ProposalStatus::Rejected;
+            }
         }
+        Ok(())
+    }
 
-        // Check majority (>50%)
-        self.votes_for > self.votes_against
+    /// Settle the escrowed deposit after voting
+    /// Returns (amount, refunded): refunded to the proposer if quorum was reached,
+    /// otherwise forfeited to the treasury
+    pub fn settle_deposit(&mut self) -> (u64, bool) {
+        let amount = self.deposit;
+        self.deposit = 0;
+        (amount, self.reached_quorum())
     }
 
     /// Check if proposal is executable
@@ -104,6 +247,74 @@ impl Proposal {
         self.status == ProposalStatus::Passed
             && current_time >= self.time_executable
     }
+
+    /// Claim the proposal for execution
+    /// Flips status to Executed up front, so it must run before any CPI:
+    /// a replayed or re-entrant execute then finds it already Executed
+    pub fn mark_executed(&mut self, current_time: i64) -> Result<()> {
+        match self.status {
+            ProposalStatus::Executed => return Err(GovernanceError::AlreadyExecuted.into()),
+            ProposalStatus::Cancelled => return Err(GovernanceError::ProposalCancelled.into()),
+            ProposalStatus::Passed => {}
+            _ => return Err(GovernanceError::ProposalNotPassed.into()),
+        }
+        require!(
+            self.is_executable(current_time),
+            GovernanceError::ExecutionDelayActive
+        );
+
+        self.status = ProposalStatus::Executed;
+        Ok(())
+    }
+
+    /// Check an execution is aimed at the target stored at creation
+    /// The program and accounts must match exactly (same accounts, same order);
+    /// Text proposals have no target, so none may be passed
+    pub fn verify_target(&self, program: Option<&Pubkey>, accounts: &[Pubkey]) -> Result<()> {
+        let expected_program = match self.proposal_type {
+            ProposalType::Text => None,
+            _ => Some(&self.target_program),
+        };
+        require!(
+            program == expected_program && accounts == self.target_accounts.as_slice(),
+            GovernanceError::InvalidExecutionTarget
+        );
+        Ok(())
+    }
+
+    /// Check an executed ParameterUpdate authorizes this exact call
+    /// Target programs use it to bind a governance-only update to the
+    /// proposal voted on: same program, an account it named, same instruction data
+    pub fn authorizes(&self, program: &Pubkey, account: &Pubkey, instruction_data: &[u8]) -> bool {
+        self.status == ProposalStatus::Executed
+            && self.proposal_type == ProposalType::ParameterUpdate
+            && self.target_program == *program
+            && self.target_accounts.contains(account)
+            && self.execution_data == instruction_data
+    }
+
+    /// Instruction a ParameterUpdate execution CPIs into the target program
+    /// execution_data is the target instruction data as is; accounts are the
+    /// target accounts with the given writability, and the governance authority
+    /// PDA among them is marked as the signer
+    pub fn execution_instruction(&self, writable: &[bool]) -> Instruction {
+        let (authority, _) = governance_authority();
+        let accounts = self
+            .target_accounts
+            .iter()
+            .zip(writable)
+            .map(|(key, &is_writable)| AccountMeta {
+                pubkey: *key,
+                is_signer: *key == authority,
+                is_writable,
+            })
+            .collect();
+        Instruction {
+            program_id: self.target_program,
+            accounts,
+            data: self.execution_data.clone(),
+        }
+    }
 }
 
 /// Vote record - tracks individual votes
@@ -132,6 +343,64 @@ impl VoteRecord {
     pub const LEN: usize = 8 + 32 + 32 + 1 + 16 + 8 + 1;
 }
 
+/// MarketParameter execution payload
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct MarketParameterPayload {
+    pub job_price: u64,
+    pub job_timeout: i64,
+    pub node_xhyper_minimum: u128,
+}
+
+impl MarketParameterPayload {
+    pub fn is_valid(&self) -> bool {
+        self.job_price > 0
+            && (MIN_JOB_TIMEOUT..=MAX_JOB_TIMEOUT).contains(&self.job_timeout)
+    }
+}
+
+/// StakingParameter execution payload
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct StakingParameterPayload {
+    pub min_stake_duration: i64,
+    pub max_stake_duration: i64,
+    pub min_stake_amount: u64,
+}
+
+impl StakingParameterPayload {
+    pub fn is_valid(&self) -> bool {
+        self.min_stake_duration >= MIN_STAKE_DURATION
+            && self.max_stake_duration <= MAX_STAKE_DURATION
+            && self.min_stake_duration <= self.max_stake_duration
+            && self.min_stake_amount > 0
+    }
+}
+
+/// TreasurySpend execution payload
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct TreasurySpendPayload {
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+impl TreasurySpendPayload {
+    pub fn is_valid(&self) -> bool {
+        self.recipient != Pubkey::default() && self.amount > 0
+    }
+}
+
+/// ProtocolUpgrade execution payload
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
+pub struct ProtocolUpgradePayload {
+    pub program_id: Pubkey,
+    pub buffer: Pubkey,
+}
+
+impl ProtocolUpgradePayload {
+    pub fn is_valid(&self) -> bool {
+        self.program_id != Pubkey::default() && self.buffer != Pubkey::default()
+    }
+}
+
 /// Proposal types
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Debug)]
 pub enum ProposalType {
@@ -149,6 +418,53 @@ pub enum ProposalType {
 
     /// General text proposal (no execution)
     Text,
+
+    /// Call a governance-only instruction of another program
+    /// (execution_data is its instruction data, signed for by the governance authority)
+    ParameterUpdate,
+}
+
+impl ProposalType {
+    /// Decode and range-check execution_data for this proposal type
+    /// Payloads are Borsh-encoded and must be consumed exactly;
+    /// Text proposals carry no execution data
+    pub fn validate_execution_data(&self, data: &[u8]) -> Result<()> {
+        let valid = match self {
+            ProposalType::MarketParameter => decode::<MarketParameterPayload>(data)?.is_valid(),
+            ProposalType::StakingParameter => decode::<StakingParameterPayload>(data)?.is_valid(),
+            ProposalType::TreasurySpend => decode::<TreasurySpendPayload>(data)?.is_valid(),
+            ProposalType::ProtocolUpgrade => decode::<ProtocolUpgradePayload>(data)?.is_valid(),
+            ProposalType::Text => data.is_empty(),
+            ProposalType::ParameterUpdate => data.len() >= 8,
+        };
+        require!(valid, GovernanceError::ExecutionDataOutOfRange);
+        Ok(())
+    }
+
+    /// Check the execution target given at creation
+    /// Executable types must name a program and 1..=MAX_TARGET_ACCOUNTS accounts;
+    /// Text proposals name neither, and ParameterUpdate accounts must include the
+    /// governance authority that signs the call
+    pub fn validate_target(&self, program: &Pubkey, accounts: &[Pubkey]) -> Result<()> {
+        let valid = match self {
+            ProposalType::Text => *program == Pubkey::default() && accounts.is_empty(),
+            ProposalType::ParameterUpdate => {
+                *program != Pubkey::default()
+                    && (1..=MAX_TARGET_ACCOUNTS).contains(&accounts.len())
+                    && accounts.contains(&governance_authority().0)
+            }
+            _ => {
+                *program != Pubkey::default()
+                    && (1..=MAX_TARGET_ACCOUNTS).contains(&accounts.len())
+            }
+        };
+        require!(valid, GovernanceError::InvalidExecutionTarget);
+        Ok(())
+    }
+}
+
+fn decode<T: AnchorDeserialize>(data: &[u8]) -> Result<T> {
+    T::try_from_slice(data).map_err(|_| error!(GovernanceError::InvalidExecutionData))
 }
 
 /// Proposal status
@@ -172,3 +488,342 @@ pub enum ProposalStatus {
     /// Expired (not executed in time)
     Expired,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_valid_market_payload_accepted() {
+        let payload = MarketParameterPayload {
+            job_price: 1_000_000,
+            job_timeout: 3600,
+            node_xhyper_minimum: 100_000_000,
+        };
+        let data = payload.try_to_vec().unwrap();
+
+        assert!(ProposalType::MarketParameter.validate_execution_data(&data).is_ok());
+    }
+
+    #[test]
+    fn test_out_of_range_market_payload_rejected() {
+        let payload = MarketParameterPayload {
+            job_price: 1_000_000,
+            job_timeout: MAX_JOB_TIMEOUT + 1,
+            node_xhyper_minimum: 0,
+        };
+        let data = payload.try_to_vec().unwrap();
+
+        assert!(ProposalType::MarketParameter.validate_execution_data(&data).is_err());
+    }
+
+    #[test]
+    fn test_malformed_payload_rejected() {
+        let payload = StakingParameterPayload {
+            min_stake_duration: MIN_STAKE_DURATION,
+            max_stake_duration: MAX_STAKE_DURATION,
+            min_stake_amount: 1,
+        };
+        let mut data = payload.try_to_vec().unwrap();
+        assert!(ProposalType::StakingParameter.validate_execution_data(&data).is_ok());
+
+        // Truncated or trailing bytes don't decode
+        assert!(ProposalType::StakingParameter.validate_execution_data(&data[..8]).is_err());
+        data.push(0);
+        assert!(ProposalType::StakingParameter.validate_execution_data(&data).is_err());
+
+        // Text proposals carry no data
+        assert!(ProposalType::Text.validate_execution_data(&[]).is_ok());
+        assert!(ProposalType::Text.validate_execution_data(&[1]).is_err());
+    }
+
+    fn passed_proposal() -> Proposal {
+        Proposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            title: "Raise job timeout".to_string(),
+            description_cid: [0u8; 32],
+            proposal_type: ProposalType::Text,
+            execution_data: vec![],
+            time_created: 0,
+            time_voting_ends: VOTING_PERIOD,
+            time_executable: VOTING_PERIOD + EXECUTION_DELAY,
+            total_voting_power: 100,
+            votes_for: 60,
+            votes_against: 0,
+            voter_count: 1,
+            status: ProposalStatus::Passed,
+            deposit: 0,
+            target_program: Pubkey::default(),
+            target_accounts: vec![],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_double_execution_rejected() {
+        let mut proposal = passed_proposal();
+        let now = proposal.time_executable;
+
+        assert!(proposal.mark_executed(now).is_ok());
+        assert!(proposal.status == ProposalStatus::Executed);
+
+        // Replay of the same execution
+        assert_eq!(
+            proposal.mark_executed(now + 1).unwrap_err(),
+            GovernanceError::AlreadyExecuted.into()
+        );
+        assert!(proposal.status == ProposalStatus::Executed);
+    }
+
+    #[test]
+    fn test_cancelled_or_unpassed_proposal_not_executed() {
+        let mut proposal = passed_proposal();
+        proposal.status = ProposalStatus::Cancelled;
+        assert_eq!(
+            proposal.mark_executed(proposal.time_executable).unwrap_err(),
+            GovernanceError::ProposalCancelled.into()
+        );
+
+        proposal.status = ProposalStatus::Active;
+        assert_eq!(
+            proposal.mark_executed(proposal.time_executable).unwrap_err(),
+            GovernanceError::ProposalNotPassed.into()
+        );
+
+        // Still inside the execution delay: status untouched
+        proposal.status = ProposalStatus::Passed;
+        assert_eq!(
+            proposal.mark_executed(proposal.time_executable - 1).unwrap_err(),
+            GovernanceError::ExecutionDelayActive.into()
+        );
+        assert!(proposal.status == ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_status_preview_flips_as_votes_accumulate() {
+        let mut proposal = passed_proposal();
+        proposal.status = ProposalStatus::Active;
+        proposal.votes_for = 0;
+        proposal.voter_count = 0;
+
+        // No votes yet: no quorum, projected to fail
+        let preview = proposal.status_preview(VOTING_PERIOD - 100);
+        assert!(!preview.quorum_met);
+        assert!(!preview.projected_to_pass);
+        assert_eq!(preview.time_remaining, 100);
+
+        // Majority for, but below the 10% quorum
+        proposal.votes_for = 9;
+        assert!(!proposal.status_preview(0).projected_to_pass);
+
+        // Quorum reached with a majority: on track to pass
+        proposal.votes_for = 10;
+        let preview = proposal.status_preview(0);
+        assert!(preview.quorum_met);
+        assert!(preview.projected_to_pass);
+        assert_eq!((preview.votes_for, preview.votes_against), (10, 0));
+
+        // Against votes overtake: flips to fail, quorum still met
+        proposal.votes_against = 11;
+        let preview = proposal.status_preview(0);
+        assert!(preview.quorum_met);
+        assert!(!preview.projected_to_pass);
+
+        // A tie fails too; more for votes flip it back
+        proposal.votes_for = 11;
+        assert!(!proposal.status_preview(0).projected_to_pass);
+        proposal.votes_for = 12;
+        assert!(proposal.status_preview(0).projected_to_pass);
+
+        // The projection matches what finalize decides
+        let preview = proposal.status_preview(VOTING_PERIOD + 5);
+        assert_eq!(preview.time_remaining, 0);
+        proposal.finalize(VOTING_PERIOD + 5).unwrap();
+        assert!(proposal.status == ProposalStatus::Passed);
+    }
+
+    #[test]
+    fn test_deposit_refunded_when_quorum_reached() {
+        let mut proposal = passed_proposal();
+        proposal.status = ProposalStatus::Active;
+        proposal.deposit = 1_000_000_000;
+
+        assert_eq!(
+            proposal.finalize(VOTING_PERIOD - 1).unwrap_err(),
+            GovernanceError::VotingStillActive.into()
+        );
+
+        proposal.finalize(VOTING_PERIOD).unwrap();
+        assert!(proposal.status == ProposalStatus::Passed);
+        assert_eq!(proposal.settle_deposit(), (1_000_000_000, true));
+        assert_eq!(proposal.deposit, 0);
+    }
+
+    #[test]
+    fn test_deposit_forfeited_when_quorum_missed() {
+        let mut proposal = passed_proposal();
+        proposal.status = ProposalStatus::Active;
+        proposal.deposit = 1_000_000_000;
+        proposal.votes_for = 9; // 9% of 100 < 10% quorum
+
+        proposal.finalize(VOTING_PERIOD).unwrap();
+        assert!(proposal.status == ProposalStatus::Rejected);
+        assert_eq!(proposal.settle_deposit(), (1_000_000_000, false));
+
+        // Quorum reached but voted down: still refunded (not spam)
+        let mut rejected = passed_proposal();
+        rejected.status = ProposalStatus::Active;
+        rejected.deposit = 500;
+        rejected.votes_against = 90;
+        rejected.finalize(VOTING_PERIOD).unwrap();
+        assert!(rejected.status == ProposalStatus::Rejected);
+        assert_eq!(rejected.settle_deposit(), (500, true));
+    }
+
+    #[test]
+    fn test_proposal_at_configured_limits() {
+        let mut config = GovernanceConfig {
+            authority: Pubkey::new_unique(),
+            proposal_count: 0,
+            total_voting_power: 0,
+            proposal_deposit: 0,
+            treasury: Pubkey::new_unique(),
+            max_title_len: 0,
+            max_execution_data_len: 0,
+            bump: 255,
+        };
+
+        // Unset limits fall back to the defaults
+        assert_eq!(config.title_limit(), DEFAULT_MAX_TITLE_LEN as usize);
+        assert_eq!(config.execution_data_limit(), DEFAULT_MAX_EXECUTION_DATA_LEN as usize);
+
+        config.max_title_len = 200;
+        config.max_execution_data_len = 1024;
+
+        // A proposal using the full configured sizes fits its allocation exactly
+        let mut proposal = passed_proposal();
+        proposal.title = "t".repeat(config.title_limit());
+        proposal.execution_data = vec![7u8; config.execution_data_limit()];
+        proposal.target_accounts = vec![Pubkey::new_unique(); MAX_TARGET_ACCOUNTS];
+
+        let size = 8 + proposal.try_to_vec().unwrap().len();
+        assert_eq!(
+            size,
+            Proposal::space(proposal.title.len(), proposal.execution_data.len(), proposal.target_accounts.len())
+        );
+        assert!(size <= 10_240);
+
+        // The largest allowed limits still fit a CPI allocation
+        assert!(
+            Proposal::space(
+                MAX_TITLE_LEN_LIMIT as usize,
+                MAX_EXECUTION_DATA_LEN_LIMIT as usize,
+                MAX_TARGET_ACCOUNTS
+            ) <= 10_240
+        );
+    }
+
+    #[test]
+    fn test_proposal_limit_validation() {
+        assert!(GovernanceConfig::is_valid_proposal_limits(DEFAULT_MAX_TITLE_LEN, DEFAULT_MAX_EXECUTION_DATA_LEN));
+        assert!(GovernanceConfig::is_valid_proposal_limits(MAX_TITLE_LEN_LIMIT, MAX_EXECUTION_DATA_LEN_LIMIT));
+        assert!(!GovernanceConfig::is_valid_proposal_limits(0, DEFAULT_MAX_EXECUTION_DATA_LEN));
+        assert!(!GovernanceConfig::is_valid_proposal_limits(DEFAULT_MAX_TITLE_LEN, 0));
+        assert!(!GovernanceConfig::is_valid_proposal_limits(MAX_TITLE_LEN_LIMIT + 1, 1));
+        assert!(!GovernanceConfig::is_valid_proposal_limits(1, MAX_EXECUTION_DATA_LEN_LIMIT + 1));
+    }
+
+    #[test]
+    fn test_execution_against_wrong_target_rejected() {
+        let market_program = Pubkey::new_unique();
+        let market = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+
+        let mut proposal = passed_proposal();
+        proposal.proposal_type = ProposalType::MarketParameter;
+        proposal.target_program = market_program;
+        proposal.target_accounts = vec![market, config];
+
+        // The target fixed at creation
+        assert!(proposal.verify_target(Some(&market_program), &[market, config]).is_ok());
+
+        // Some other market, another program, missing, reordered or extra accounts
+        let wrong_targets: [(Option<&Pubkey>, Vec<Pubkey>); 5] = [
+            (Some(&market_program), vec![Pubkey::new_unique(), config]),
+            (Some(&Pubkey::new_unique()), vec![market, config]),
+            (None, vec![market, config]),
+            (Some(&market_program), vec![config, market]),
+            (Some(&market_program), vec![market, config, Pubkey::new_unique()]),
+        ];
+        for (program, accounts) in wrong_targets {
+            assert_eq!(
+                proposal.verify_target(program, &accounts).unwrap_err(),
+                GovernanceError::InvalidExecutionTarget.into()
+            );
+        }
+
+        // Rejected before the proposal is claimed, so the right target can still execute it
+        assert!(proposal.status == ProposalStatus::Passed);
+
+        // Text proposals execute with no target at all
+        let text = passed_proposal();
+        assert!(text.verify_target(None, &[]).is_ok());
+        assert!(text.verify_target(Some(&Pubkey::default()), &[]).is_err());
+        assert!(text.verify_target(None, &[market]).is_err());
+    }
+
+    #[test]
+    fn test_execution_target_required_at_creation() {
+        let program = Pubkey::new_unique();
+        let accounts = [Pubkey::new_unique()];
+
+        assert!(ProposalType::StakingParameter.validate_target(&program, &accounts).is_ok());
+        assert!(ProposalType::StakingParameter.validate_target(&Pubkey::default(), &accounts).is_err());
+        assert!(ProposalType::StakingParameter.validate_target(&program, &[]).is_err());
+        let too_many = vec![Pubkey::new_unique(); MAX_TARGET_ACCOUNTS + 1];
+        assert!(ProposalType::TreasurySpend.validate_target(&program, &too_many).is_err());
+
+        assert!(ProposalType::Text.validate_target(&Pubkey::default(), &[]).is_ok());
+        assert!(ProposalType::Text.validate_target(&program, &[]).is_err());
+        assert!(ProposalType::Text.validate_target(&Pubkey::default(), &accounts).is_err());
+    }
+
+    #[test]
+    fn test_parameter_update_signed_by_governance_authority() {
+        let slashing_program = Pubkey::new_unique();
+        let config = Pubkey::new_unique();
+        let (authority, _) = governance_authority();
+        let data = vec![7u8; 12];
+
+        // Instruction data must at least carry a discriminator
+        assert!(ProposalType::ParameterUpdate.validate_execution_data(&data).is_ok());
+        assert!(ProposalType::ParameterUpdate.validate_execution_data(&data[..7]).is_err());
+
+        // The governance authority must be among the target accounts
+        assert!(ProposalType::ParameterUpdate
+            .validate_target(&slashing_program, &[config, authority])
+            .is_ok());
+        assert_eq!(
+            ProposalType::ParameterUpdate
+                .validate_target(&slashing_program, &[config])
+                .unwrap_err(),
+            GovernanceError::InvalidExecutionTarget.into()
+        );
+
+        let mut proposal = passed_proposal();
+        proposal.proposal_type = ProposalType::ParameterUpdate;
+        proposal.execution_data = data.clone();
+        proposal.target_program = slashing_program;
+        proposal.target_accounts = vec![config, authority];
+
+        // The CPI goes to the target program with the proposal's data,
+        // and only the governance authority signs
+        let ix = proposal.execution_instruction(&[true, false]);
+        assert_eq!(ix.program_id, slashing_program);
+        assert_eq!(ix.data, data);
+        assert_eq!(ix.accounts.len(), 2);
+        assert!(ix.accounts[0].pubkey == config && ix.accounts[0].is_writable && !ix.accounts[0].is_signer);
+        assert!(ix.accounts[1].pubkey == authority && !ix.accounts[1].is_writable && ix.accounts[1].is_signer);
+    }
+}
@@ -38,4 +38,79 @@ pub enum JobError {
 
     #[msg("Job has not expired yet (cannot recover)")]
     JobNotExpired,
+
+    #[msg("Result does not match the job's result commitment")]
+    ResultCommitmentMismatch,
+
+    #[msg("Token account mint does not match the market mint")]
+    InvalidMint,
+
+    #[msg("Encryption keys must be set together (client and node) or both omitted")]
+    EncryptionKeyMismatch,
+
+    #[msg("Escrowed amount is below the settlement price")]
+    InvalidEscrow,
+
+    #[msg("Timeout outside the market's min/max bounds")]
+    TimeoutOutOfBounds,
+
+    #[msg("Client is not on this private market's allow-list")]
+    ClientNotAllowed,
+
+    #[msg("Only the market authority can do this")]
+    UnauthorizedMarketAuthority,
+
+    #[msg("Vault is not this market's escrow or is not held by the market")]
+    InvalidVault,
+
+    #[msg("Token account is not owned by the payout recipient")]
+    InvalidPayoutAccount,
+
+    #[msg("Node authority is blacklisted")]
+    NodeBlacklisted,
+
+    #[msg("Blacklist account could not be read")]
+    InvalidBlacklist,
+
+    #[msg("Market price is above the client's max_price")]
+    PriceAboveMax,
+
+    #[msg("Job is not the one waiting at the head of the queue")]
+    JobNotInQueue,
+
+    #[msg("Assignment receipt account is missing or not this job's")]
+    InvalidAssignment,
+
+    #[msg("Payout cliff must be between 0 and 7 days")]
+    InvalidPayoutCliff,
+
+    #[msg("Payout schedule account is missing or not this job's")]
+    InvalidPayoutSchedule,
+
+    #[msg("Payout has not vested yet")]
+    PayoutNotVested,
+
+    #[msg("Fraud report could not be read")]
+    InvalidFraudReport,
+
+    #[msg("No confirmed fraud report against the node during vesting")]
+    ClawbackNotAllowed,
+
+    #[msg("Crank reward must be at most 500 bps")]
+    InvalidCrankReward,
+
+    #[msg("Signer is not the job's client")]
+    UnauthorizedClient,
+
+    #[msg("Budget increase must add tokens or time without overflowing")]
+    InvalidBudgetIncrease,
+
+    #[msg("Payout is held by an open fraud report")]
+    PayoutHeld,
+
+    #[msg("No open fraud report citing the job while the payment vests")]
+    HoldNotAllowed,
+
+    #[msg("No eligible node or job in the queue")]
+    NoEligibleMatch,
 }
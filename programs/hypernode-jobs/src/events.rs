@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Event emitted when job completes successfully
+#[event]
+pub struct JobCompletedEvent {
+    pub job: Pubkey,
+    pub market: Pubkey,
+    pub job_id: String,
+    pub node: Pubkey,
+    pub price: u64,
+    pub duration: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when job fails
+#[event]
+pub struct JobFailedEvent {
+    pub job: Pubkey,
+    pub market: Pubkey,
+    pub job_id: String,
+    pub node: Pubkey,
+    pub duration: i64,
+    pub timestamp: i64,
+}
+
+/// Event emitted when job is recovered
+#[event]
+pub struct RecoverEvent {
+    pub job: Pubkey,
+    pub market: Pubkey,
+    pub job_id: String,
+    pub client: Pubkey,
+    pub amount: u64,
+    pub reason: String,
+    pub timestamp: i64,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Add a client to a market's allow-list
+///
+/// Creates the (market, client) AllowedClient PDA checked by submit_job
+/// on private markets.
+pub fn add_allowed_client(ctx: Context<AddAllowedClient>, client: Pubkey) -> Result<()> {
+    let entry = &mut ctx.accounts.allowed_client;
+    let clock = Clock::get()?;
+
+    entry.market = ctx.accounts.market.key();
+    entry.client = client;
+    entry.added_at = clock.unix_timestamp;
+    entry.bump = ctx.bumps.allowed_client;
+
+    msg!("Client {} allowed on market '{}'", client, ctx.accounts.market.market_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(client: Pubkey)]
+pub struct AddAllowedClient<'info> {
+    /// Market whose allow-list is updated
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = authority @ JobError::UnauthorizedMarketAuthority
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Allow-list entry (PDA)
+    #[account(
+        init,
+        payer = authority,
+        space = AllowedClient::SPACE,
+        seeds = [b"allowed_client", market.key().as_ref(), client.as_ref()],
+        bump
+    )]
+    pub allowed_client: Account<'info, AllowedClient>,
+
+    /// Market authority (pays for the entry)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
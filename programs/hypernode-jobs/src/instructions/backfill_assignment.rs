@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Write the Assignment receipt of a job matched before receipts existed
+///
+/// Permissionless: a Running job with no receipt can't be finished or
+/// recovered, so anyone can create it from the node and start time the
+/// job already records. The caller pays the rent and gets it back when
+/// the job settles.
+pub fn backfill_assignment(ctx: Context<BackfillAssignment>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let job = &ctx.accounts.job;
+    let assignment = &ctx.accounts.assignment;
+
+    let (expected, bump) = Pubkey::find_program_address(
+        &[Assignment::SEED, market.key().as_ref(), job.key().as_ref()],
+        &crate::ID,
+    );
+    require!(assignment.key() == expected, JobError::InvalidAssignment);
+
+    // Validation: Running job without a receipt (creating an existing one fails)
+    let receipt = Assignment::of(job, job.key(), ctx.accounts.caller.key(), bump)
+        .ok_or(JobError::JobNotRunning)?;
+    receipt.create(
+        &assignment.to_account_info(),
+        &ctx.accounts.caller.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+    )?;
+
+    msg!(
+        "Backfilled assignment of job {} to node {} (started {})",
+        job.job_id,
+        receipt.node,
+        receipt.assigned_at
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct BackfillAssignment<'info> {
+    /// Market the job belongs to
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Running job missing its receipt
+    #[account(
+        seeds = [b"job", market.key().as_ref(), job.job_id.as_bytes()],
+        bump = job.bump
+    )]
+    pub job: Account<'info, Job>,
+
+    /// Assignment receipt to create (["assignment", market, job])
+    /// CHECK: address checked and account created in the handler
+    #[account(mut)]
+    pub assignment: UncheckedAccount<'info>,
+
+    /// Anyone can backfill; pays the receipt's rent
+    #[account(mut)]
+    pub caller: Signer<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Claim a vested node payment
+///
+/// Pays the PayoutSchedule amount from the market vault to the node once
+/// the cliff has passed, then closes the schedule (rent to the node).
+/// A payment held by a fraud report can only be claimed once that report
+/// has been dismissed.
+pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let schedule = &ctx.accounts.payout_schedule;
+    let clock = Clock::get()?;
+
+    // Validation: Cliff must have passed
+    require!(
+        schedule.is_vested(clock.unix_timestamp),
+        JobError::PayoutNotVested
+    );
+
+    // Validation: A holding report must have been dismissed (or closed)
+    if let Some(held_by) = schedule.held_by {
+        let report_account = ctx.accounts.fraud_report.as_ref().ok_or(JobError::PayoutHeld)?;
+        require_keys_eq!(report_account.key(), held_by, JobError::PayoutHeld);
+
+        let report = if report_account.owner == &SLASHING_PROGRAM_ID {
+            let data = report_account.try_borrow_data()?;
+            FraudReportView::read(&data)
+        } else {
+            None
+        };
+        require!(schedule.is_released(report.as_ref()), JobError::PayoutHeld);
+    }
+
+    let market_id = market.market_id.as_bytes();
+    let seeds = &[b"market", market_id, &[market.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.node_token_account.to_account_info(),
+        authority: ctx.accounts.market.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+    token::transfer(cpi_ctx, schedule.amount)?;
+
+    msg!(
+        "Node {} claimed vested payout of {} for job {}",
+        schedule.node,
+        schedule.amount,
+        schedule.job
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimPayout<'info> {
+    /// Market the job belongs to
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Vested payout (closed to the node)
+    #[account(
+        mut,
+        seeds = [PayoutSchedule::SEED, market.key().as_ref(), payout_schedule.job.as_ref()],
+        bump = payout_schedule.bump,
+        constraint = payout_schedule.node == node_authority.key() @ JobError::UnauthorizedNode,
+        close = node_authority
+    )]
+    pub payout_schedule: Account<'info, PayoutSchedule>,
+
+    /// Node owed the payment
+    #[account(mut)]
+    pub node_authority: Signer<'info>,
+
+    /// Fraud report holding the payment, required if it is held
+    /// CHECK: matched against payout_schedule.held_by, parsed in the handler
+    pub fraud_report: Option<UncheckedAccount<'info>>,
+
+    /// Node's token account (payment destination)
+    #[account(
+        mut,
+        constraint = node_token_account.owner == node_authority.key() @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&node_token_account.mint) @ JobError::InvalidMint
+    )]
+    pub node_token_account: Account<'info, TokenAccount>,
+
+    /// Market vault (escrow)
+    #[account(
+        mut,
+        constraint = market.is_vault(&market.key(), &vault.key(), &vault.owner) @ JobError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+}
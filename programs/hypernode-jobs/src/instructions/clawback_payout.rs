@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Claw back a vesting node payment after confirmed fraud
+///
+/// Permissionless: anyone can present a Slashing Program fraud report
+/// citing the job, filed after it finished and confirmed before the
+/// payment vested (or holding it, see hold_payout). The held amount is
+/// refunded to the client and the schedule closed (rent to the client).
+pub fn clawback_payout(ctx: Context<ClawbackPayout>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let schedule = &ctx.accounts.payout_schedule;
+    let clock = Clock::get()?;
+
+    let report = {
+        let data = ctx.accounts.fraud_report.try_borrow_data()?;
+        FraudReportView::read(&data).ok_or(JobError::InvalidFraudReport)?
+    };
+
+    // Validation: Confirmed fraud on this job while the payment was vesting or held
+    require!(
+        schedule.can_claw_back(&report, &ctx.accounts.fraud_report.key(), clock.unix_timestamp),
+        JobError::ClawbackNotAllowed
+    );
+
+    let market_id = market.market_id.as_bytes();
+    let seeds = &[b"market", market_id, &[market.bump]];
+    let signer = &[&seeds[..]];
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.vault.to_account_info(),
+        to: ctx.accounts.client_token_account.to_account_info(),
+        authority: ctx.accounts.market.to_account_info(),
+    };
+    let cpi_program = ctx.accounts.token_program.to_account_info();
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+    token::transfer(cpi_ctx, schedule.amount)?;
+
+    msg!(
+        "Clawed back {} from node {} for job {} - refunded to client {}",
+        schedule.amount,
+        schedule.node,
+        schedule.job,
+        schedule.client
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClawbackPayout<'info> {
+    /// Market the job belongs to
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Vesting payout (closed to the client)
+    #[account(
+        mut,
+        seeds = [PayoutSchedule::SEED, market.key().as_ref(), payout_schedule.job.as_ref()],
+        bump = payout_schedule.bump,
+        close = client
+    )]
+    pub payout_schedule: Account<'info, PayoutSchedule>,
+
+    /// Fraud report citing the job
+    /// CHECK: owned by the Slashing Program, parsed in the handler
+    #[account(owner = SLASHING_PROGRAM_ID @ JobError::InvalidFraudReport)]
+    pub fraud_report: UncheckedAccount<'info>,
+
+    /// Client of the job (receives the schedule's rent)
+    #[account(mut, address = payout_schedule.client @ JobError::InvalidPayoutAccount)]
+    pub client: SystemAccount<'info>,
+
+    /// Client's token account (refund destination)
+    #[account(
+        mut,
+        constraint = client_token_account.owner == payout_schedule.client @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&client_token_account.mint) @ JobError::InvalidMint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// Market vault (escrow)
+    #[account(
+        mut,
+        constraint = market.is_vault(&market.key(), &vault.key(), &vault.owner) @ JobError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Anyone can submit the clawback
+    pub caller: Signer<'info>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+}
@@ -6,52 +6,40 @@ use crate::errors::JobError;
 /// Finish a job and release payment to node
 ///
 /// This implements trustless payment settlement:
-/// - Validates node authorization
-/// - Verifies IPFS result hash
-/// - Transfers payment from escrow to node
-/// - Updates job state and stats
+/// - Validates node authorization (against the job and its Assignment receipt)
+/// - Verifies IPFS result hash (and the client's commitment, if any)
+/// - Records the node's ephemeral key for encrypted results
+/// - Transfers payment from escrow to node, or holds it on a PayoutSchedule
+///   when the market vests the job's payout
+/// - Refunds any overpayment (escrowed - price) to the client
+/// - Updates job state, persisted before any token transfer or CPI
+/// - Closes the Assignment receipt (rent back to whoever paid for it)
 pub fn finish(
     ctx: Context<Finish>,
     ipfs_result: [u8; 32],
     success: bool,
+    node_ephemeral_key: Option<[u8; 32]>,
 ) -> Result<()> {
-    let job = &mut ctx.accounts.job;
     let market = &ctx.accounts.market;
     let clock = Clock::get()?;
-
-    // Validation: Only assigned node can finish
-    require!(
-        job.node == Some(ctx.accounts.node_authority.key()),
-        JobError::UnauthorizedNode
-    );
-
-    // Validation: Job must be running
-    require!(
-        job.state == JobState::Running,
-        JobError::JobNotRunning
-    );
-
-    // Validation: IPFS result hash must not be empty
-    require!(
-        ipfs_result != [0u8; 32],
-        JobError::InvalidIpfsHash
-    );
-
-    // Validation: Check timeout (optional - could allow late submission)
-    let elapsed = clock.unix_timestamp - job.started_at.unwrap_or(0);
-    require!(
-        elapsed <= job.timeout,
-        JobError::JobExpired
-    );
-
-    // Update job state
-    job.ipfs_result = ipfs_result;
-    job.completed_at = Some(clock.unix_timestamp);
+    let elapsed = clock.unix_timestamp - ctx.accounts.job.started_at.unwrap_or(0);
+
+    // Validate and settle: Running -> Completed / Failed
+    let (node_payment, refund) = ctx.accounts.job.finish(
+        &ctx.accounts.node_authority.key(),
+        ipfs_result,
+        success,
+        node_ephemeral_key,
+        clock.unix_timestamp,
+    )?;
+
+    // Persist the settled job BEFORE external calls (reentrancy protection):
+    // a finish re-entered from the token program reads it from account data
+    // and finds it no longer Running
+    ctx.accounts.job.exit(&crate::ID)?;
+    let job = &ctx.accounts.job;
 
     if success {
-        job.state = JobState::Completed;
-
-        // Transfer payment from vault to node (trustless settlement)
         let market_id = market.market_id.as_bytes();
         let seeds = &[
             b"market",
@@ -60,34 +48,72 @@ pub fn finish(
         ];
         let signer = &[&seeds[..]];
 
-        let cpi_accounts = Transfer {
-            from: ctx.accounts.vault.to_account_info(),
-            to: ctx.accounts.node_token_account.to_account_info(),
-            authority: ctx.accounts.market.to_account_info(),
-        };
-        let cpi_program = ctx.accounts.token_program.to_account_info();
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-
-        token::transfer(cpi_ctx, job.price)?;
+        if market.vests_payout(job.price) {
+            // High-value job: hold the payment in the vault until the cliff passes
+            let schedule = ctx.accounts.payout_schedule.as_ref().ok_or(JobError::InvalidPayoutSchedule)?;
+            let (expected, bump) = Pubkey::find_program_address(
+                &[PayoutSchedule::SEED, market.key().as_ref(), job.key().as_ref()],
+                &crate::ID,
+            );
+            require!(schedule.key() == expected, JobError::InvalidPayoutSchedule);
+            let payout = PayoutSchedule::of(
+                job,
+                job.key(),
+                node_payment,
+                clock.unix_timestamp,
+                market.payout_cliff,
+                bump,
+            )
+            .ok_or(JobError::InvalidPayoutSchedule)?;
+            payout.create(
+                &schedule.to_account_info(),
+                &ctx.accounts.node_authority.to_account_info(),
+                &ctx.accounts.system_program.to_account_info(),
+            )?;
+
+            msg!("Node payment of {} vesting until {}", node_payment, payout.vests_at);
+        } else {
+            // Transfer payment from vault to node (trustless settlement)
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.node_token_account.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+            token::transfer(cpi_ctx, node_payment)?;
+        }
+
+        // Refund overpayment to client
+        if refund > 0 {
+            let cpi_accounts = Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.client_token_account.to_account_info(),
+                authority: ctx.accounts.market.to_account_info(),
+            };
+            let cpi_program = ctx.accounts.token_program.to_account_info();
+            let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+            token::transfer(cpi_ctx, refund)?;
+        }
 
         msg!(
-            "Job {} completed successfully. Paid {} to node {}",
+            "Job {} completed successfully. Settled {} to node {}, refunded {} to client",
             job.job_id,
-            job.price,
-            ctx.accounts.node_authority.key()
+            node_payment,
+            ctx.accounts.node_authority.key(),
+            refund
         );
 
         // Emit success event
-        emit!(JobCompletedEvent {
-            job: job.key(),
-            node: ctx.accounts.node_authority.key(),
-            price: job.price,
-            duration: elapsed,
-            timestamp: clock.unix_timestamp,
-        });
+        emit!(job.completed_event(
+            job.key(),
+            ctx.accounts.node_authority.key(),
+            elapsed,
+            clock.unix_timestamp,
+        ));
     } else {
-        job.state = JobState::Failed;
-
         // On failure, refund client (trustless refund)
         let market_id = market.market_id.as_bytes();
         let seeds = &[
@@ -105,22 +131,22 @@ pub fn finish(
         let cpi_program = ctx.accounts.token_program.to_account_info();
         let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-        token::transfer(cpi_ctx, job.price)?;
+        token::transfer(cpi_ctx, refund)?;
 
         msg!(
             "Job {} failed. Refunded {} to client {}",
             job.job_id,
-            job.price,
+            refund,
             job.client
         );
 
         // Emit failure event
-        emit!(JobFailedEvent {
-            job: job.key(),
-            node: ctx.accounts.node_authority.key(),
-            duration: elapsed,
-            timestamp: clock.unix_timestamp,
-        });
+        emit!(job.failed_event(
+            job.key(),
+            ctx.accounts.node_authority.key(),
+            elapsed,
+            clock.unix_timestamp,
+        ));
     }
 
     Ok(())
@@ -144,48 +170,58 @@ pub struct Finish<'info> {
     pub job: Account<'info, Job>,
 
     /// Node finishing the job
+    /// Pays for the payout schedule when the payment vests
+    #[account(mut)]
     pub node_authority: Signer<'info>,
 
+    /// Assignment receipt; only the node it records can finish the job
+    /// Closed at settlement
+    #[account(
+        mut,
+        close = receipt_payer,
+        seeds = [Assignment::SEED, market.key().as_ref(), job.key().as_ref()],
+        bump = assignment.bump,
+        constraint = assignment.node == node_authority.key() @ JobError::UnauthorizedNode
+    )]
+    pub assignment: Account<'info, Assignment>,
+
+    /// Account that paid for the assignment receipt (rent refund)
+    /// CHECK: must be the payer recorded on the receipt
+    #[account(mut, address = assignment.payer @ JobError::InvalidAssignment)]
+    pub receipt_payer: UncheckedAccount<'info>,
+
     /// Node's token account (payment destination)
     #[account(
         mut,
-        constraint = node_token_account.owner == node_authority.key()
+        constraint = node_token_account.owner == node_authority.key() @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&node_token_account.mint) @ JobError::InvalidMint
     )]
     pub node_token_account: Account<'info, TokenAccount>,
 
     /// Client's token account (refund destination if failed)
     #[account(
         mut,
-        constraint = client_token_account.owner == job.client
+        constraint = job.is_refund_account(&client_token_account.owner) @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&client_token_account.mint) @ JobError::InvalidMint
     )]
     pub client_token_account: Account<'info, TokenAccount>,
 
     /// Market vault (escrow)
     #[account(
         mut,
-        constraint = vault.key() == market.vault
+        constraint = market.is_vault(&market.key(), &vault.key(), &vault.owner) @ JobError::InvalidVault
     )]
     pub vault: Account<'info, TokenAccount>,
 
+    /// Payout schedule of the job (["payout", market, job])
+    /// Required when the market vests this job's payout
+    /// CHECK: address checked and account created in the handler when vesting
+    #[account(mut)]
+    pub payout_schedule: Option<UncheckedAccount<'info>>,
+
     /// SPL Token program
     pub token_program: Program<'info, Token>,
-}
-
-/// Event emitted when job completes successfully
-#[event]
-pub struct JobCompletedEvent {
-    pub job: Pubkey,
-    pub node: Pubkey,
-    pub price: u64,
-    pub duration: i64,
-    pub timestamp: i64,
-}
 
-/// Event emitted when job fails
-#[event]
-pub struct JobFailedEvent {
-    pub job: Pubkey,
-    pub node: Pubkey,
-    pub duration: i64,
-    pub timestamp: i64,
+    /// System program
+    pub system_program: Program<'info, System>,
 }
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Hold a vesting node payment while a fraud report is open
+///
+/// Permissionless: anyone can present a Slashing Program fraud report
+/// citing the job that is still pending, confirmed or under appeal before
+/// the payment vests. claim_payout then waits for the report to be
+/// dismissed, and clawback_payout can still refund the client once it is
+/// confirmed, even past the cliff.
+pub fn hold_payout(ctx: Context<HoldPayout>) -> Result<()> {
+    let schedule = &mut ctx.accounts.payout_schedule;
+    let clock = Clock::get()?;
+
+    let report = {
+        let data = ctx.accounts.fraud_report.try_borrow_data()?;
+        FraudReportView::read(&data).ok_or(JobError::InvalidFraudReport)?
+    };
+
+    // Validation: Open report on this job while the payment is vesting
+    require!(
+        schedule.can_hold(&report, clock.unix_timestamp),
+        JobError::HoldNotAllowed
+    );
+
+    schedule.held_by = Some(ctx.accounts.fraud_report.key());
+
+    msg!(
+        "Payout of {} for job {} held by fraud report {}",
+        schedule.amount,
+        schedule.job,
+        ctx.accounts.fraud_report.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct HoldPayout<'info> {
+    /// Market the job belongs to
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Vesting payout to hold
+    #[account(
+        mut,
+        seeds = [PayoutSchedule::SEED, market.key().as_ref(), payout_schedule.job.as_ref()],
+        bump = payout_schedule.bump
+    )]
+    pub payout_schedule: Account<'info, PayoutSchedule>,
+
+    /// Open fraud report citing the job
+    /// CHECK: owned by the Slashing Program, parsed in the handler
+    #[account(owner = SLASHING_PROGRAM_ID @ JobError::InvalidFraudReport)]
+    pub fraud_report: UncheckedAccount<'info>,
+
+    /// Anyone can submit the hold
+    pub caller: Signer<'info>,
+}
@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Increase the budget of a running job
+///
+/// For a job that needs more than it was scoped for (e.g. a longer run):
+/// - The client escrows `amount` more tokens in the market vault
+/// - job.price and job.escrowed grow by `amount`, so finish pays the node
+///   the larger price (a failed finish or recover refunds it to the client)
+/// - job.timeout grows by `extra_timeout` seconds, capped by the market's
+///   max_timeout
+///
+/// The assigned node co-signs: it consents to the new scope before it is
+/// held to the longer run.
+pub fn increase_job_budget(
+    ctx: Context<IncreaseJobBudget>,
+    amount: u64,
+    extra_timeout: i64,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let job = &mut ctx.accounts.job;
+
+    // Validation: Running job, and the new timeout within the market's bounds
+    let timeout = job.increase_budget(amount, extra_timeout)?;
+    require!(market.timeout_in_bounds(timeout), JobError::TimeoutOutOfBounds);
+
+    // The receipt records what the node is owed at settlement
+    ctx.accounts.assignment.price = job.price;
+
+    // Transfer the extra payment to vault (escrow)
+    if amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.client_token_account.to_account_info(),
+            to: ctx.accounts.vault.to_account_info(),
+            authority: ctx.accounts.client.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new(cpi_program, cpi_accounts);
+        token::transfer(cpi_ctx, amount)?;
+    }
+
+    msg!(
+        "Job {} budget increased by {} (price {}, escrowed {}), timeout now {}s",
+        job.job_id,
+        amount,
+        job.price,
+        job.escrowed,
+        job.timeout
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct IncreaseJobBudget<'info> {
+    /// Market this job belongs to
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Running job to top up
+    #[account(
+        mut,
+        seeds = [b"job", market.key().as_ref(), job.job_id.as_bytes()],
+        bump = job.bump,
+        constraint = job.client == client.key() @ JobError::UnauthorizedClient
+    )]
+    pub job: Account<'info, Job>,
+
+    /// Assignment receipt; the node it records must consent
+    #[account(
+        mut,
+        seeds = [Assignment::SEED, market.key().as_ref(), job.key().as_ref()],
+        bump = assignment.bump,
+        constraint = assignment.node == node_authority.key() @ JobError::UnauthorizedNode
+    )]
+    pub assignment: Account<'info, Assignment>,
+
+    /// Client who submitted the job
+    pub client: Signer<'info>,
+
+    /// Node running the job (consents to the new scope)
+    pub node_authority: Signer<'info>,
+
+    /// Client's token account (payment source)
+    #[account(
+        mut,
+        constraint = client_token_account.owner == client.key() @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&client_token_account.mint) @ JobError::InvalidMint
+    )]
+    pub client_token_account: Account<'info, TokenAccount>,
+
+    /// Market vault (escrow)
+    #[account(
+        mut,
+        constraint = market.is_vault(&market.key(), &vault.key(), &vault.owner) @ JobError::InvalidVault
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// SPL Token program
+    pub token_program: Program<'info, Token>,
+}
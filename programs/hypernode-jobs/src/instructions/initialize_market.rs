@@ -6,12 +6,18 @@ use crate::state::*;
 ///
 /// Creates the market account and associated vault for escrow.
 /// Markets can have different parameters (price, timeout, requirements).
+/// The account is sized for `max_queue_size` queue entries, up to the
+/// account creation limit (Market::MAX_INIT_QUEUE_SIZE); larger queues
+/// grow the account as they fill.
 pub fn initialize_market(
     ctx: Context<InitializeMarket>,
     market_id: String,
     job_price: u64,
     job_timeout: i64,
     node_stake_minimum: u64,
+    min_timeout: i64,
+    max_timeout: i64,
+    max_queue_size: u16,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
 
@@ -22,6 +28,18 @@ pub fn initialize_market(
     );
     require!(job_price > 0, MarketError::InvalidPrice);
     require!(job_timeout > 0, MarketError::InvalidTimeout);
+    require!(
+        Market::is_valid_timeout_bounds(min_timeout, max_timeout),
+        MarketError::InvalidTimeoutBounds
+    );
+    require!(
+        (min_timeout..=max_timeout).contains(&job_timeout),
+        MarketError::InvalidTimeoutBounds
+    );
+    require!(
+        Market::is_valid_max_queue_size(max_queue_size),
+        MarketError::InvalidMaxQueueSize
+    );
 
     // Initialize market
     market.authority = ctx.accounts.authority.key();
@@ -30,28 +48,47 @@ pub fn initialize_market(
     market.job_price = job_price;
     market.job_timeout = job_timeout;
     market.node_stake_minimum = node_stake_minimum;
+    market.min_timeout = min_timeout;
+    market.max_timeout = max_timeout;
     market.vault = ctx.accounts.vault.key();
+    market.token_mint = ctx.accounts.token_mint.key();
+    market.is_private = false;
     market.market_id = market_id.clone();
     market.total_jobs = 0;
     market.total_nodes = 0;
+    market.payout_cliff = 0;
+    market.vesting_threshold = 0;
+    market.crank_reward_bps = 0;
+    market.max_queue_size = max_queue_size;
     market.bump = ctx.bumps.market;
 
     msg!("Market '{}' initialized", market_id);
     msg!("Job price: {}", job_price);
     msg!("Job timeout: {} seconds", job_timeout);
+    msg!("Timeout bounds: {}..={} seconds", min_timeout, max_timeout);
     msg!("Node stake minimum: {}", node_stake_minimum);
+    msg!("Max queue size: {}", max_queue_size);
+    msg!("Token mint: {}", market.token_mint);
 
     Ok(())
 }
 
 #[derive(Accounts)]
-#[instruction(market_id: String)]
+#[instruction(
+    market_id: String,
+    job_price: u64,
+    job_timeout: i64,
+    node_stake_minimum: u64,
+    min_timeout: i64,
+    max_timeout: i64,
+    max_queue_size: u16
+)]
 pub struct InitializeMarket<'info> {
     /// Market account (PDA)
     #[account(
         init,
         payer = authority,
-        space = Market::SPACE,
+        space = Market::init_space(max_queue_size as usize),
         seeds = [b"market", market_id.as_bytes()],
         bump
     )]
@@ -95,4 +132,10 @@ pub enum MarketError {
 
     #[msg("Job timeout must be greater than zero")]
     InvalidTimeout,
+
+    #[msg("Timeout bounds must satisfy 0 < min_timeout <= job_timeout <= max_timeout")]
+    InvalidTimeoutBounds,
+
+    #[msg("Max queue size must be between 1 and MAX_QUEUE_SIZE")]
+    InvalidMaxQueueSize,
 }
@@ -3,9 +3,29 @@ pub mod submit_job;
 pub mod work;
 pub mod finish;
 pub mod recover;
+pub mod set_market_private;
+pub mod add_allowed_client;
+pub mod remove_allowed_client;
+pub mod set_payout_vesting;
+pub mod claim_payout;
+pub mod clawback_payout;
+pub mod hold_payout;
+pub mod set_crank_reward;
+pub mod increase_job_budget;
+pub mod backfill_assignment;
 
 pub use initialize_market::*;
 pub use submit_job::*;
 pub use work::*;
 pub use finish::*;
 pub use recover::*;
+pub use set_market_private::*;
+pub use add_allowed_client::*;
+pub use remove_allowed_client::*;
+pub use set_payout_vesting::*;
+pub use claim_payout::*;
+pub use clawback_payout::*;
+pub use hold_payout::*;
+pub use set_crank_reward::*;
+pub use increase_job_budget::*;
+pub use backfill_assignment::*;
@@ -9,6 +9,13 @@ use crate::errors::JobError;
 /// - Exceeded timeout while in Running state
 /// - Were never claimed (stuck in queue)
 ///
+/// A running job's timeout counts from its Assignment receipt, which is
+/// closed here (rent back to whoever paid for it).
+///
+/// The caller earns the market's crank reward (crank_reward_bps of the job
+/// price) from the escrow, paid to their token account if they pass one;
+/// the client is refunded the rest.
+///
 /// This prevents DoS attacks where:
 /// - Nodes claim jobs but never finish them
 /// - Jobs sit in queue forever
@@ -20,10 +27,15 @@ pub fn recover(ctx: Context<Recover>) -> Result<()> {
     // Validation: Job must be in recoverable state
     let is_expired = match job.state {
         JobState::Running => {
-            // Job is running but exceeded timeout
+            // Job is running but exceeded timeout, counted from its assignment receipt
+            let assignment = ctx
+                .accounts
+                .assignment
+                .as_ref()
+                .ok_or(JobError::InvalidAssignment)?;
             let running_duration = clock
                 .unix_timestamp
-                .checked_sub(job.started_at.unwrap_or(0))
+                .checked_sub(assignment.assigned_at)
                 .unwrap_or(0);
             running_duration > job.timeout
         }
@@ -40,11 +52,52 @@ pub fn recover(ctx: Context<Recover>) -> Result<()> {
 
     require!(is_expired, JobError::JobNotExpired);
 
-    // Refund client
+    // Close the receipt of a running job
+    if let Some(assignment) = &ctx.accounts.assignment {
+        let receipt_payer = ctx
+            .accounts
+            .receipt_payer
+            .as_ref()
+            .ok_or(JobError::InvalidAssignment)?;
+        require_keys_eq!(receipt_payer.key(), assignment.payer, JobError::InvalidAssignment);
+        assignment.close(receipt_payer.to_account_info())?;
+    }
+
+    // Reason is based on the state before recovery
+    let reason = if job.state == JobState::Running {
+        "timeout_exceeded"
+    } else {
+        "stuck_in_queue"
+    };
+
+    // Crank reward for the caller (forgone without a token account to pay it to)
+    let (crank_reward, refund) = match ctx.accounts.caller_token_account {
+        Some(_) => market.recover_split(job.price, job.escrowed),
+        None => (0, job.escrowed),
+    };
+
+    // Update job state
+    job.state = JobState::Failed;
+    job.completed_at = Some(clock.unix_timestamp);
+
     let market_id = market.market_id.as_bytes();
     let seeds = &[b"market", market_id, &[market.bump]];
     let signer = &[&seeds[..]];
 
+    // Pay the caller
+    if let (Some(caller_token_account), true) = (&ctx.accounts.caller_token_account, crank_reward > 0) {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.vault.to_account_info(),
+            to: caller_token_account.to_account_info(),
+            authority: ctx.accounts.market.to_account_info(),
+        };
+        let cpi_program = ctx.accounts.token_program.to_account_info();
+        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
+
+        token::transfer(cpi_ctx, crank_reward)?;
+    }
+
+    // Refund client
     let cpi_accounts = Transfer {
         from: ctx.accounts.vault.to_account_info(),
         to: ctx.accounts.client_token_account.to_account_info(),
@@ -53,32 +106,19 @@ pub fn recover(ctx: Context<Recover>) -> Result<()> {
     let cpi_program = ctx.accounts.token_program.to_account_info();
     let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
 
-    token::transfer(cpi_ctx, job.price)?;
-
-    // Update job state
-    job.state = JobState::Failed;
-    job.completed_at = Some(clock.unix_timestamp);
+    token::transfer(cpi_ctx, refund)?;
 
     msg!(
-        "Recovered job {} - refunded {} to client {}",
+        "Recovered job {} - refunded {} to client {}, crank reward {} to {}",
         job.job_id,
-        job.price,
-        job.client
+        refund,
+        job.client,
+        crank_reward,
+        ctx.accounts.caller.key()
     );
 
     // Emit event
-    emit!(RecoverEvent {
-        job: job.key(),
-        client: job.client,
-        amount: job.price,
-        reason: if matches!(job.state, JobState::Running) {
-            "timeout_exceeded"
-        } else {
-            "stuck_in_queue"
-        }
-        .to_string(),
-        timestamp: clock.unix_timestamp,
-    });
+    emit!(job.recover_event(job.key(), reason, clock.unix_timestamp));
 
     Ok(())
 }
@@ -100,33 +140,45 @@ pub struct Recover<'info> {
     )]
     pub job: Account<'info, Job>,
 
+    /// Assignment receipt (required for running jobs), closed on recovery
+    #[account(
+        mut,
+        seeds = [Assignment::SEED, market.key().as_ref(), job.key().as_ref()],
+        bump = assignment.bump
+    )]
+    pub assignment: Option<Account<'info, Assignment>>,
+
+    /// Account that paid for the assignment receipt (required with it)
+    /// CHECK: matched against the receipt's payer in the handler
+    #[account(mut)]
+    pub receipt_payer: Option<UncheckedAccount<'info>>,
+
     /// Client's token account (refund destination)
     #[account(
         mut,
-        constraint = client_token_account.owner == job.client
+        constraint = job.is_refund_account(&client_token_account.owner) @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&client_token_account.mint) @ JobError::InvalidMint
     )]
     pub client_token_account: Account<'info, TokenAccount>,
 
     /// Market vault (escrow)
     #[account(
         mut,
-        constraint = vault.key() == market.vault
+        constraint = market.is_vault(&market.key(), &vault.key(), &vault.owner) @ JobError::InvalidVault
     )]
     pub vault: Account<'info, TokenAccount>,
 
     /// Anyone can call recover (permissionless)
     pub caller: Signer<'info>,
 
+    /// Caller's token account (crank reward destination, optional)
+    #[account(
+        mut,
+        constraint = caller_token_account.owner == caller.key() @ JobError::InvalidPayoutAccount,
+        constraint = market.accepts_mint(&caller_token_account.mint) @ JobError::InvalidMint
+    )]
+    pub caller_token_account: Option<Account<'info, TokenAccount>>,
+
     /// SPL Token program
     pub token_program: Program<'info, Token>,
 }
-
-/// Event emitted when job is recovered
-#[event]
-pub struct RecoverEvent {
-    pub job: Pubkey,
-    pub client: Pubkey,
-    pub amount: u64,
-    pub reason: String,
-    pub timestamp: i64,
-}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Remove a client from a market's allow-list
+///
+/// Closes the AllowedClient PDA; rent goes back to the market authority.
+/// Jobs the client already submitted are unaffected.
+pub fn remove_allowed_client(ctx: Context<RemoveAllowedClient>) -> Result<()> {
+    msg!(
+        "Client {} removed from market '{}'",
+        ctx.accounts.allowed_client.client,
+        ctx.accounts.market.market_id
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RemoveAllowedClient<'info> {
+    /// Market whose allow-list is updated
+    #[account(
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = authority @ JobError::UnauthorizedMarketAuthority
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Allow-list entry to close
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"allowed_client", market.key().as_ref(), allowed_client.client.as_ref()],
+        bump = allowed_client.bump
+    )]
+    pub allowed_client: Account<'info, AllowedClient>,
+
+    /// Market authority (receives the entry's rent)
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Set the crank reward for recovering expired jobs
+///
+/// recover is permissionless; the reward pays its caller `crank_reward_bps`
+/// of the job price out of the recovered escrow, so expired jobs get
+/// cleaned up. The client is refunded the rest. 0 turns it off.
+pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_bps: u16) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Validation: Reward must be at most MAX_CRANK_REWARD_BPS
+    require!(
+        Market::is_valid_crank_reward(crank_reward_bps),
+        JobError::InvalidCrankReward
+    );
+
+    market.crank_reward_bps = crank_reward_bps;
+
+    msg!(
+        "Market '{}' crank reward: {} bps",
+        market.market_id,
+        crank_reward_bps
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCrankReward<'info> {
+    /// Market to update
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = authority @ JobError::UnauthorizedMarketAuthority
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market authority
+    pub authority: Signer<'info>,
+}
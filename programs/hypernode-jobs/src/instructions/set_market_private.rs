@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Make a market private or public
+///
+/// Private markets only accept jobs from clients on the allow-list
+/// (add_allowed_client). Public markets skip the check.
+pub fn set_market_private(ctx: Context<SetMarketPrivate>, is_private: bool) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    market.is_private = is_private;
+
+    msg!("Market '{}' private: {}", market.market_id, is_private);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMarketPrivate<'info> {
+    /// Market to update
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = authority @ JobError::UnauthorizedMarketAuthority
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market authority
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::JobError;
+
+/// Configure payout vesting for a market
+///
+/// Successful jobs priced at or above `vesting_threshold` pay the node
+/// through a PayoutSchedule claimable after `payout_cliff` seconds instead
+/// of immediately. A cliff of 0 turns vesting off.
+pub fn set_payout_vesting(
+    ctx: Context<SetPayoutVesting>,
+    payout_cliff: i64,
+    vesting_threshold: u64,
+) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+
+    // Validation: Cliff must be 0 (off) up to MAX_PAYOUT_CLIFF
+    require!(
+        Market::is_valid_payout_cliff(payout_cliff),
+        JobError::InvalidPayoutCliff
+    );
+
+    market.payout_cliff = payout_cliff;
+    market.vesting_threshold = vesting_threshold;
+
+    msg!(
+        "Market '{}' payout cliff: {}s for jobs priced from {}",
+        market.market_id,
+        payout_cliff,
+        vesting_threshold
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetPayoutVesting<'info> {
+    /// Market to update
+    #[account(
+        mut,
+        seeds = [b"market", market.market_id.as_bytes()],
+        bump = market.bump,
+        has_one = authority @ JobError::UnauthorizedMarketAuthority
+    )]
+    pub market: Account<'info, Market>,
+
+    /// Market authority
+    pub authority: Signer<'info>,
+}
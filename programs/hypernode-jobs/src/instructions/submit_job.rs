@@ -8,26 +8,55 @@ use crate::errors::JobError;
 /// This implements the Nosana-style dynamic queue system:
 /// - If nodes are waiting (QueueType::Node), assigns immediately
 /// - Otherwise, adds job to queue (QueueType::Job)
+///
+/// An optional result_commitment (sha256 of the expected result CID)
+/// binds the payout in finish to that result.
+///
+/// An optional client_encryption_key requests a result encrypted to the
+/// client (ECDH off-chain with the node's ephemeral key set in finish).
+///
+/// Private markets only accept clients with an AllowedClient entry.
+///
+/// An immediate assignment goes to the longest-waiting node that isn't on
+/// the Nodes Program blacklist, and also creates the job's Assignment
+/// receipt. Nodes banned while queued are skipped.
+///
+/// The market account grows as the queue fills (paid by the client).
+///
+/// max_price caps the market price the client accepts, so a price raised
+/// while the transaction is in flight makes it fail instead of overcharging.
 pub fn submit_job(
     ctx: Context<SubmitJob>,
     job_id: String,
     ipfs_job: [u8; 32],
     price: u64,
     timeout: i64,
+    result_commitment: Option<[u8; 32]>,
+    client_encryption_key: Option<[u8; 32]>,
+    max_price: u64,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let job = &mut ctx.accounts.job;
     let clock = Clock::get()?;
 
     // Validation (Szabo principle: verify everything)
+    require!(
+        market.admits_client(
+            ctx.accounts.allowed_client.as_deref(),
+            &ctx.accounts.client.key()
+        ),
+        JobError::ClientNotAllowed
+    );
     require!(
         job_id.len() <= Job::MAX_JOB_ID_LEN,
         JobError::JobIdTooLong
     );
+    require!(market.price_within(max_price), JobError::PriceAboveMax);
     require!(price >= market.job_price, JobError::PriceTooLow);
     require!(timeout > 0, JobError::InvalidTimeout);
+    require!(market.timeout_in_bounds(timeout), JobError::TimeoutOutOfBounds);
     require!(
-        market.queue.len() < Market::MAX_QUEUE_SIZE,
+        market.has_queue_capacity(),
         JobError::QueueFull
     );
 
@@ -37,6 +66,11 @@ pub fn submit_job(
         JobError::InvalidIpfsHash
     );
 
+    require!(
+        Job::is_valid_key(&client_encryption_key),
+        JobError::EncryptionKeyMismatch
+    );
+
     // Transfer payment to vault (escrow)
     let cpi_accounts = Transfer {
         from: ctx.accounts.client_token_account.to_account_info(),
@@ -52,9 +86,14 @@ pub fn submit_job(
     job.client = ctx.accounts.client.key();
     job.ipfs_job = ipfs_job;
     job.ipfs_result = [0u8; 32]; // Empty until finished
-    job.price = price;
+    // Charged at settlement; any overpayment is refunded in finish
+    job.price = market.job_price;
+    job.escrowed = price;
     job.timeout = timeout;
     job.node = None;
+    job.result_commitment = result_commitment;
+    job.client_encryption_key = client_encryption_key;
+    job.node_ephemeral_key = None;
     job.created_at = clock.unix_timestamp;
     job.started_at = None;
     job.completed_at = None;
@@ -64,24 +103,19 @@ pub fn submit_job(
     // Dynamic queue logic (Nosana pattern)
     match market.queue_type {
         QueueType::Node => {
-            // Node is waiting! Assign immediately (trustless matching)
-            require!(!market.queue.is_empty(), JobError::QueueEmpty);
-
-            let node_pubkey = market.queue.remove(0);
-            job.node = Some(node_pubkey);
-            job.state = JobState::Running;
-            job.started_at = Some(clock.unix_timestamp);
-
-            // Update queue type if empty
-            if market.queue.is_empty() {
-                market.queue_type = QueueType::Empty;
-            }
+            // Node is waiting! Assign the longest-waiting unbanned one (trustless matching)
+            let blacklist = ctx.accounts.blacklist.try_borrow_data()?;
+            let node_pubkey = market
+                .take_match(|entry| is_blacklisted(&blacklist, &entry.key) == Some(false))
+                .ok_or(JobError::NoEligibleMatch)?
+                .key;
+            job.assign(node_pubkey, clock.unix_timestamp);
 
             msg!("Job {} assigned immediately to node {}", job.job_id, node_pubkey);
         }
         _ => {
             // No nodes available, enter job queue
-            market.queue.push(job.key());
+            market.enqueue(job.key(), clock.unix_timestamp);
             market.queue_type = QueueType::Job;
             job.state = JobState::Queued;
 
@@ -89,6 +123,15 @@ pub fn submit_job(
         }
     }
 
+    // Write the assignment receipt when the job was matched straight away
+    if let Some(receipt) = Assignment::of(job, job.key(), ctx.accounts.client.key(), ctx.bumps.assignment) {
+        receipt.create(
+            &ctx.accounts.assignment.to_account_info(),
+            &ctx.accounts.client.to_account_info(),
+            &ctx.accounts.system_program.to_account_info(),
+        )?;
+    }
+
     // Update stats
     market.total_jobs = market.total_jobs.checked_add(1).unwrap();
 
@@ -98,9 +141,12 @@ pub fn submit_job(
 #[derive(Accounts)]
 #[instruction(job_id: String)]
 pub struct SubmitJob<'info> {
-    /// Market to submit job to
+    /// Market to submit job to (grown to fit another queue entry)
     #[account(
         mut,
+        realloc = market.queue_space(market.to_account_info().data_len()),
+        realloc::payer = client,
+        realloc::zero = false,
         seeds = [b"market", market.market_id.as_bytes()],
         bump = market.bump
     )]
@@ -116,14 +162,31 @@ pub struct SubmitJob<'info> {
     )]
     pub job: Account<'info, Job>,
 
+    /// Assignment receipt, created only if a node is waiting and takes the job at once
+    /// CHECK: PDA checked; created in the handler on immediate assignment
+    #[account(
+        mut,
+        seeds = [Assignment::SEED, market.key().as_ref(), job.key().as_ref()],
+        bump
+    )]
+    pub assignment: UncheckedAccount<'info>,
+
     /// Client submitting the job
     #[account(mut)]
     pub client: Signer<'info>,
 
+    /// Client's allow-list entry (required on private markets)
+    #[account(
+        seeds = [b"allowed_client", market.key().as_ref(), client.key().as_ref()],
+        bump = allowed_client.bump
+    )]
+    pub allowed_client: Option<Account<'info, AllowedClient>>,
+
     /// Client's token account (payment source)
     #[account(
         mut,
-        constraint = client_token_account.owner == client.key()
+        constraint = client_token_account.owner == client.key(),
+        constraint = market.accepts_mint(&client_token_account.mint) @ JobError::InvalidMint
     )]
     pub client_token_account: Account<'info, TokenAccount>,
 
@@ -134,6 +197,16 @@ pub struct SubmitJob<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
 
+    /// Global node blacklist (Nodes Program)
+    /// CHECK: Owner and PDA are checked; the entries are read in the handler
+    #[account(
+        seeds = [b"blacklist"],
+        bump,
+        seeds::program = NODES_PROGRAM_ID,
+        owner = NODES_PROGRAM_ID
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
     /// SPL Token program
     pub token_program: Program<'info, Token>,
 
@@ -7,44 +7,70 @@ use crate::errors::JobError;
 /// Dynamic queue behavior:
 /// - If jobs are waiting (QueueType::Job), assigns immediately
 /// - Otherwise, adds node to queue (QueueType::Node)
+///
+/// A node claiming a job passes that job (the longest-waiting job still in
+/// its queue window, see Market::max_queue_wait) and its assignment
+/// address; the job is assigned and the receipt created. Jobs stuck past
+/// the window are skipped and left for recover.
+///
+/// The market account grows as the queue fills (paid by the node).
 pub fn work(ctx: Context<Work>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let node_pubkey = ctx.accounts.node_authority.key();
     let clock = Clock::get()?;
 
+    // Validation: node authority not banned
+    let banned = {
+        let data = ctx.accounts.blacklist.try_borrow_data()?;
+        is_blacklisted(&data, &node_pubkey).ok_or(JobError::InvalidBlacklist)?
+    };
+    require!(!banned, JobError::NodeBlacklisted);
+
     // Validation: queue not full
     require!(
-        market.queue.len() < Market::MAX_QUEUE_SIZE,
+        market.has_queue_capacity(),
         JobError::QueueFull
     );
 
     // Validation: node not already in queue
     require!(
-        !market.queue.contains(&node_pubkey),
+        !market.is_queued(&node_pubkey),
         JobError::NodeAlreadyInQueue
     );
 
     // Update market stats
-    if market.total_nodes == 0 || !market.queue.contains(&node_pubkey) {
+    if market.total_nodes == 0 || !market.is_queued(&node_pubkey) {
         market.total_nodes = market.total_nodes.checked_add(1).unwrap();
     }
 
     // Dynamic queue logic (Nosana pattern - opposite of submit_job)
     match market.queue_type {
         QueueType::Job => {
-            // Job is waiting! Claim immediately (trustless matching)
-            require!(!market.queue.is_empty(), JobError::QueueEmpty);
-
-            let job_pubkey = market.queue.remove(0);
+            // Job is waiting! Claim the longest-waiting live one (trustless matching)
+            let max_wait = market.max_queue_wait();
+            let job_pubkey = market
+                .take_match(|entry| entry.is_within(max_wait, clock.unix_timestamp))
+                .ok_or(JobError::NoEligibleMatch)?
+                .key;
 
-            // Note: The actual job assignment happens in the client
-            // The job account will be updated via a separate instruction
-            // This keeps the work() instruction simple and gas-efficient
+            // Assign the claimed job and write its receipt
+            let job = ctx.accounts.job.as_mut().ok_or(JobError::JobNotInQueue)?;
+            require!(job.key() == job_pubkey, JobError::JobNotInQueue);
+            job.assign(node_pubkey, clock.unix_timestamp);
 
-            // Update queue type if empty
-            if market.queue.is_empty() {
-                market.queue_type = QueueType::Empty;
-            }
+            let assignment = ctx.accounts.assignment.as_ref().ok_or(JobError::InvalidAssignment)?;
+            let (expected, bump) = Pubkey::find_program_address(
+                &[Assignment::SEED, market.key().as_ref(), job_pubkey.as_ref()],
+                &crate::ID,
+            );
+            require!(assignment.key() == expected, JobError::InvalidAssignment);
+            Assignment::of(job, job_pubkey, node_pubkey, bump)
+                .ok_or(JobError::InvalidAssignment)?
+                .create(
+                    &assignment.to_account_info(),
+                    &ctx.accounts.node_authority.to_account_info(),
+                    &ctx.accounts.system_program.to_account_info(),
+                )?;
 
             msg!(
                 "Node {} claimed job {} immediately",
@@ -61,7 +87,7 @@ pub fn work(ctx: Context<Work>) -> Result<()> {
         }
         _ => {
             // No jobs available, enter node queue
-            market.queue.push(node_pubkey);
+            market.enqueue(node_pubkey, clock.unix_timestamp);
             market.queue_type = QueueType::Node;
 
             msg!(
@@ -77,9 +103,12 @@ pub fn work(ctx: Context<Work>) -> Result<()> {
 
 #[derive(Accounts)]
 pub struct Work<'info> {
-    /// Market to work on
+    /// Market to work on (grown to fit another queue entry)
     #[account(
         mut,
+        realloc = market.queue_space(market.to_account_info().data_len()),
+        realloc::payer = node_authority,
+        realloc::zero = false,
         seeds = [b"market", market.market_id.as_bytes()],
         bump = market.bump
     )]
@@ -88,7 +117,34 @@ pub struct Work<'info> {
     /// Node authority (from hypernode-nodes program)
     /// We don't load the full Node account here to save CU
     /// Validation happens in hypernode-nodes program
+    /// Pays for the assignment receipt when claiming a job
+    #[account(mut)]
     pub node_authority: Signer<'info>,
+
+    /// Job being claimed (required when jobs are waiting: the longest-waiting live job)
+    #[account(
+        mut,
+        constraint = job.market == market.key() @ JobError::JobNotInQueue
+    )]
+    pub job: Option<Account<'info, Job>>,
+
+    /// Assignment receipt of the claimed job (["assignment", market, job])
+    /// CHECK: address checked and account created in the handler when claiming
+    #[account(mut)]
+    pub assignment: Option<UncheckedAccount<'info>>,
+
+    /// Global node blacklist (Nodes Program)
+    /// CHECK: Owner and PDA are checked; the entries are read in the handler
+    #[account(
+        seeds = [b"blacklist"],
+        bump,
+        seeds::program = NODES_PROGRAM_ID,
+        owner = NODES_PROGRAM_ID
+    )]
+    pub blacklist: UncheckedAccount<'info>,
+
+    /// System program
+    pub system_program: Program<'info, System>,
 }
 
 /// Event emitted when job is assigned to node
@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 pub mod state;
 pub mod instructions;
 pub mod errors;
+pub mod events;
 
 use instructions::*;
 
@@ -18,7 +19,12 @@ declare_id!("7ateB21tp2kKRCT9mgD6XE7bbntpAont2rjSKpj395hN");
 /// - submit_job: Client submits job with payment to escrow
 /// - work: Node enters queue or claims available job
 /// - finish: Node submits result and receives payment
-/// - recover: Refund expired jobs (anti-DoS)
+/// - recover: Refund expired jobs (anti-DoS), paying the caller a crank reward
+/// - set_market_private / add_allowed_client / remove_allowed_client:
+///   Restrict a market to an allow-list of clients
+/// - set_payout_vesting / claim_payout / clawback_payout:
+///   Hold high-value payouts for a cliff, refundable on confirmed fraud
+/// - hold_payout: Keep a vesting payout held while a fraud report is open
 ///
 /// Architecture Principles:
 /// - Trustless: Queue-based matching on-chain
@@ -33,14 +39,29 @@ pub mod hypernode_jobs {
     ///
     /// Creates market account and vault for escrow.
     /// Markets can have custom parameters (price, timeout, stake requirements).
+    /// Job timeouts must fall within [min_timeout, max_timeout].
+    /// The queue holds at most max_queue_size nodes or jobs (1..=314).
+    /// Markets above 250 entries grow as the queue fills.
     pub fn initialize_market(
         ctx: Context<InitializeMarket>,
         market_id: String,
         job_price: u64,
         job_timeout: i64,
         node_stake_minimum: u64,
+        min_timeout: i64,
+        max_timeout: i64,
+        max_queue_size: u16,
     ) -> Result<()> {
-        instructions::initialize_market(ctx, market_id, job_price, job_timeout, node_stake_minimum)
+        instructions::initialize_market(
+            ctx,
+            market_id,
+            job_price,
+            job_timeout,
+            node_stake_minimum,
+            min_timeout,
+            max_timeout,
+            max_queue_size,
+        )
     }
 
     /// Submit a new job to the market
@@ -50,6 +71,9 @@ pub mod hypernode_jobs {
     /// - ipfs_job: IPFS hash of job definition (content-addressed)
     /// - price: Payment amount (transferred to escrow)
     /// - timeout: Maximum execution time in seconds
+    /// - result_commitment: Optional sha256 of the expected result CID
+    /// - client_encryption_key: Optional client public key for an encrypted result
+    /// - max_price: Highest market price the client accepts (rejects in-flight price bumps)
     ///
     /// Dynamic behavior:
     /// - If nodes waiting → assigns immediately
@@ -60,8 +84,20 @@ pub mod hypernode_jobs {
         ipfs_job: [u8; 32],
         price: u64,
         timeout: i64,
+        result_commitment: Option<[u8; 32]>,
+        client_encryption_key: Option<[u8; 32]>,
+        max_price: u64,
     ) -> Result<()> {
-        instructions::submit_job(ctx, job_id, ipfs_job, price, timeout)
+        instructions::submit_job(
+            ctx,
+            job_id,
+            ipfs_job,
+            price,
+            timeout,
+            result_commitment,
+            client_encryption_key,
+            max_price,
+        )
     }
 
     /// Node enters work queue or claims job
@@ -69,7 +105,7 @@ pub mod hypernode_jobs {
     /// Node calls this to indicate availability.
     ///
     /// Dynamic behavior:
-    /// - If jobs waiting → claims immediately
+    /// - If jobs waiting → claims immediately (assigns the job, creates its Assignment receipt)
     /// - Otherwise → enters node queue
     pub fn work(ctx: Context<Work>) -> Result<()> {
         instructions::work(ctx)
@@ -80,16 +116,18 @@ pub mod hypernode_jobs {
     /// Node calls this after completing execution:
     /// - ipfs_result: IPFS hash of execution result
     /// - success: true = pay node, false = refund client
+    /// - node_ephemeral_key: Node's ephemeral public key if the result is encrypted
     ///
     /// Payment settlement:
-    /// - Success → escrow transfers to node
+    /// - Success → escrow transfers to node (or vests, see set_payout_vesting)
     /// - Failure → escrow refunds client
     pub fn finish(
         ctx: Context<Finish>,
         ipfs_result: [u8; 32],
         success: bool,
+        node_ephemeral_key: Option<[u8; 32]>,
     ) -> Result<()> {
-        instructions::finish(ctx, ipfs_result, success)
+        instructions::finish(ctx, ipfs_result, success, node_ephemeral_key)
     }
 
     /// Recover funds from expired jobs
@@ -102,4 +140,93 @@ pub mod hypernode_jobs {
     pub fn recover(ctx: Context<Recover>) -> Result<()> {
         instructions::recover(ctx)
     }
+
+    /// Make a market private (allow-listed clients only) or public again
+    ///
+    /// Market authority only.
+    pub fn set_market_private(ctx: Context<SetMarketPrivate>, is_private: bool) -> Result<()> {
+        instructions::set_market_private(ctx, is_private)
+    }
+
+    /// Add a client to the market's allow-list
+    ///
+    /// Market authority only. Creates the client's AllowedClient PDA.
+    pub fn add_allowed_client(ctx: Context<AddAllowedClient>, client: Pubkey) -> Result<()> {
+        instructions::add_allowed_client(ctx, client)
+    }
+
+    /// Remove a client from the market's allow-list
+    ///
+    /// Market authority only. Closes the AllowedClient PDA (rent to authority).
+    pub fn remove_allowed_client(ctx: Context<RemoveAllowedClient>) -> Result<()> {
+        instructions::remove_allowed_client(ctx)
+    }
+
+    /// Configure payout vesting for the market
+    ///
+    /// Market authority only. Jobs priced at or above vesting_threshold pay
+    /// the node through a PayoutSchedule claimable after payout_cliff
+    /// seconds (max 7 days, 0 = off).
+    pub fn set_payout_vesting(
+        ctx: Context<SetPayoutVesting>,
+        payout_cliff: i64,
+        vesting_threshold: u64,
+    ) -> Result<()> {
+        instructions::set_payout_vesting(ctx, payout_cliff, vesting_threshold)
+    }
+
+    /// Claim a vested node payment
+    ///
+    /// Node only, once the cliff has passed. Closes the PayoutSchedule.
+    pub fn claim_payout(ctx: Context<ClaimPayout>) -> Result<()> {
+        instructions::claim_payout(ctx)
+    }
+
+    /// Claw back a vesting payment to the client
+    ///
+    /// Permissionless. Requires a Slashing Program fraud report citing the
+    /// job, filed after it finished and confirmed before it vested (or
+    /// holding it).
+    pub fn clawback_payout(ctx: Context<ClawbackPayout>) -> Result<()> {
+        instructions::clawback_payout(ctx)
+    }
+
+    /// Hold a vesting payment while a fraud report is open
+    ///
+    /// Permissionless. Requires a pending, confirmed or appealed Slashing
+    /// Program fraud report citing the job, before it vests. The node can
+    /// claim once the report is dismissed.
+    pub fn hold_payout(ctx: Context<HoldPayout>) -> Result<()> {
+        instructions::hold_payout(ctx)
+    }
+
+    /// Set the crank reward paid to recover callers
+    ///
+    /// Market authority only. The caller of recover gets crank_reward_bps
+    /// of the job price from the escrow (max 500, 0 = off).
+    pub fn set_crank_reward(ctx: Context<SetCrankReward>, crank_reward_bps: u16) -> Result<()> {
+        instructions::set_crank_reward(ctx, crank_reward_bps)
+    }
+
+    /// Increase a running job's budget
+    ///
+    /// Client and assigned node sign. The client escrows `amount` more
+    /// tokens, paid to the node at finish, and extends the timeout by
+    /// `extra_timeout` seconds (within the market's timeout bounds).
+    pub fn increase_job_budget(
+        ctx: Context<IncreaseJobBudget>,
+        amount: u64,
+        extra_timeout: i64,
+    ) -> Result<()> {
+        instructions::increase_job_budget(ctx, amount, extra_timeout)
+    }
+
+    /// Backfill a running job's assignment receipt
+    ///
+    /// Permissionless. For jobs matched before receipts existed, so they
+    /// can still be finished or recovered; the caller's rent is refunded
+    /// at settlement.
+    pub fn backfill_assignment(ctx: Context<BackfillAssignment>) -> Result<()> {
+        instructions::backfill_assignment(ctx)
+    }
 }
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+/// Allow-list entry for a private market
+///
+/// One PDA per (market, client): the set of entries under a market is its
+/// client whitelist. Managed by the market authority (add/remove_allowed_client).
+#[account]
+pub struct AllowedClient {
+    /// Market the client may submit jobs to
+    pub market: Pubkey,
+
+    /// Authorized client
+    pub client: Pubkey,
+
+    /// When the client was added
+    pub added_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl AllowedClient {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // market
+        32 + // client
+        8 + // added_at
+        1; // bump
+}
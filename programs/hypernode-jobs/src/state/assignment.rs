@@ -0,0 +1,227 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use crate::state::job::{Job, JobState};
+
+/// Assignment receipt for a job
+///
+/// One PDA per (market, job), written when the job is matched to a node
+/// (immediately in submit_job, or by a node claiming it in work). Both
+/// parties can point to it later: finish only pays the recorded node and
+/// recover measures the timeout from the recorded start.
+///
+/// Settlement (finish or recover) closes the receipt and returns its rent
+/// to whoever paid for it. Running jobs matched before receipts existed
+/// get one from backfill_assignment.
+#[account]
+pub struct Assignment {
+    /// Market the job belongs to
+    pub market: Pubkey,
+
+    /// Assigned job
+    pub job: Pubkey,
+
+    /// Node the job was assigned to
+    pub node: Pubkey,
+
+    /// When the job was assigned
+    pub assigned_at: i64,
+
+    /// Escrow held for the job at assignment
+    pub escrowed: u64,
+
+    /// Price the node is owed at settlement
+    pub price: u64,
+
+    /// Account that paid the receipt's rent (refunded when it is closed)
+    pub payer: Pubkey,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Assignment {
+    pub const SEED: &'static [u8] = b"assignment";
+
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // market
+        32 + // job
+        32 + // node
+        8 + // assigned_at
+        8 + // escrowed
+        8 + // price
+        32 + // payer
+        1; // bump
+
+    /// Receipt for `job` (at `job_key`) just assigned to its node,
+    /// paid for by `payer`
+    pub fn of(job: &Job, job_key: Pubkey, payer: Pubkey, bump: u8) -> Option<Self> {
+        if job.state != JobState::Running {
+            return None;
+        }
+        Some(Self {
+            market: job.market,
+            job: job_key,
+            node: job.node?,
+            assigned_at: job.started_at?,
+            escrowed: job.escrowed,
+            price: job.price,
+            payer,
+            bump,
+        })
+    }
+
+    /// Create the assignment PDA and write the receipt
+    /// `assignment` must be the uninitialized ["assignment", market, job] address
+    pub fn create<'info>(
+        &self,
+        assignment: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let seeds: &[&[u8]] = &[
+            Self::SEED,
+            self.market.as_ref(),
+            self.job.as_ref(),
+            &[self.bump],
+        ];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: assignment.clone(),
+                },
+                &[seeds],
+            ),
+            Rent::get()?.minimum_balance(Self::SPACE),
+            Self::SPACE as u64,
+            &crate::ID,
+        )?;
+
+        let mut data = assignment.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::market::{Market, QueueType};
+
+    fn market(queue_type: QueueType, queued: Pubkey) -> Market {
+        let mut market = Market {
+            authority: Pubkey::new_unique(),
+            queue_type: QueueType::Empty,
+            queue: Vec::new(),
+            job_price: 1_000,
+            job_timeout: 3600,
+            node_stake_minimum: 0,
+            min_timeout: 60,
+            max_timeout: 86_400,
+            vault: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            is_private: false,
+            market_id: "market".to_string(),
+            total_jobs: 0,
+            total_nodes: 0,
+            payout_cliff: 0,
+            vesting_threshold: 0,
+            crank_reward_bps: 0,
+            max_queue_size: Market::MAX_QUEUE_SIZE as u16,
+            bump: 255,
+        };
+        market.enqueue(queued, 10);
+        market.queue_type = queue_type;
+        market
+    }
+
+    fn queued_job(market: Pubkey) -> Job {
+        Job {
+            market,
+            client: Pubkey::new_unique(),
+            ipfs_job: [1u8; 32],
+            ipfs_result: [0u8; 32],
+            price: 1_000,
+            escrowed: 1_500,
+            state: JobState::Queued,
+            timeout: 3600,
+            node: None,
+            result_commitment: None,
+            client_encryption_key: None,
+            node_ephemeral_key: None,
+            created_at: 20,
+            started_at: None,
+            completed_at: None,
+            job_id: "job-1".to_string(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_assignment_recorded_on_immediate_match() {
+        let node = Pubkey::new_unique();
+        let mut market = market(QueueType::Node, node);
+        let (market_key, job_key) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut job = queued_job(market_key);
+
+        let client = job.client;
+
+        // No receipt while the job waits for a node
+        assert!(Assignment::of(&job, job_key, client, 254).is_none());
+
+        // submit_job finds a node waiting and assigns straight away
+        let matched = market.take_match(|_| true).unwrap().key;
+        job.assign(matched, 30);
+
+        let receipt = Assignment::of(&job, job_key, client, 254).unwrap();
+        assert_eq!(receipt.market, market_key);
+        assert_eq!(receipt.job, job_key);
+        assert_eq!(receipt.node, node);
+        assert_eq!(receipt.assigned_at, 30);
+        assert_eq!((receipt.escrowed, receipt.price), (1_500, 1_000));
+        assert_eq!((receipt.payer, receipt.bump), (client, 254));
+
+        let mut data = Vec::new();
+        receipt.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Assignment::SPACE);
+    }
+
+    #[test]
+    fn test_assignment_recorded_on_work_claim() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market(QueueType::Job, job_key);
+        let mut job = queued_job(Pubkey::new_unique());
+        let node = Pubkey::new_unique();
+
+        // work: the node claims the longest-waiting job
+        let claimed = market.take_match(|_| true).unwrap().key;
+        assert_eq!(claimed, job_key);
+        job.assign(node, 40);
+
+        let receipt = Assignment::of(&job, job_key, node, 253).unwrap();
+        assert_eq!(receipt.job, job_key);
+        assert_eq!(receipt.node, node);
+        assert_eq!(receipt.assigned_at, 40);
+        assert_eq!(receipt.escrowed, job.escrowed);
+        assert_eq!(receipt.payer, node);
+        assert!(market.queue.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_running_job_backfilled() {
+        // Assigned before receipts existed: only the job records the match
+        let node = Pubkey::new_unique();
+        let mut job = queued_job(Pubkey::new_unique());
+        job.assign(node, 50);
+
+        // Anyone can backfill it from the job, so finish and recover work again
+        let caller = Pubkey::new_unique();
+        let receipt = Assignment::of(&job, Pubkey::new_unique(), caller, 252).unwrap();
+        assert_eq!((receipt.node, receipt.assigned_at), (node, 50));
+        assert_eq!(receipt.payer, caller);
+
+        // Settled jobs need no receipt
+        job.state = JobState::Completed;
+        assert!(Assignment::of(&job, Pubkey::new_unique(), caller, 252).is_none());
+    }
+}
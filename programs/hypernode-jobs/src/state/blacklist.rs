@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+/// Nodes Program (owner of the global node blacklist)
+pub const NODES_PROGRAM_ID: Pubkey = pubkey!("5X4cihCXyDe23U4nzv6uobwp5CHLCPaye5bQ2PNB7srE");
+
+/// Anchor discriminator of the Nodes Program's Blacklist account
+/// (first 8 bytes of sha256("account:Blacklist"))
+pub const BLACKLIST_DISCRIMINATOR: [u8; 8] = [131, 9, 212, 250, 58, 186, 247, 3];
+
+/// Check a node authority against raw Nodes Program Blacklist data
+///
+/// Layout: discriminator 8 + authorities (u32 length + 32 bytes each).
+/// Read by hand since this program doesn't link the Nodes Program.
+/// Returns None if the data isn't a Blacklist.
+pub fn is_blacklisted(data: &[u8], authority: &Pubkey) -> Option<bool> {
+    if data.get(..8)? != BLACKLIST_DISCRIMINATOR {
+        return None;
+    }
+    let len = u32::from_le_bytes(data.get(8..12)?.try_into().ok()?) as usize;
+    let entries = data.get(12..12 + len.checked_mul(32)?)?;
+    Some(entries.chunks_exact(32).any(|entry| entry == authority.as_ref()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blacklist_data(banned: &[Pubkey]) -> Vec<u8> {
+        let mut data = BLACKLIST_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&(banned.len() as u32).to_le_bytes());
+        for authority in banned {
+            data.extend_from_slice(authority.as_ref());
+        }
+        data.push(255); // bump
+        data
+    }
+
+    #[test]
+    fn test_blacklisted_node_cannot_enter_work_queue() {
+        let banned = Pubkey::new_unique();
+        let honest = Pubkey::new_unique();
+        let data = blacklist_data(&[Pubkey::new_unique(), banned]);
+
+        assert_eq!(is_blacklisted(&data, &banned), Some(true));
+        assert_eq!(is_blacklisted(&data, &honest), Some(false));
+        assert_eq!(is_blacklisted(&blacklist_data(&[]), &banned), Some(false));
+    }
+
+    #[test]
+    fn test_malformed_blacklist_rejected() {
+        let banned = Pubkey::new_unique();
+        let mut data = blacklist_data(&[banned]);
+
+        // Truncated entries
+        assert_eq!(is_blacklisted(&data[..20], &banned), None);
+
+        // Some other account
+        data[0] ^= 1;
+        assert_eq!(is_blacklisted(&data, &banned), None);
+    }
+}
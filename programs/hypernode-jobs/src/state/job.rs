@@ -1,4 +1,7 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use crate::errors::JobError;
+use crate::events::{JobCompletedEvent, JobFailedEvent, RecoverEvent};
 
 /// Job account with IPFS hashes
 #[account]
@@ -15,9 +18,13 @@ pub struct Job {
     /// IPFS hash of result (set when finished)
     pub ipfs_result: [u8; 32],
 
-    /// Payment amount
+    /// Payment charged at settlement (market job price)
     pub price: u64,
 
+    /// Amount the client escrowed at submit (>= price)
+    /// The difference is refunded to the client on completion
+    pub escrowed: u64,
+
     /// Job state
     pub state: JobState,
 
@@ -27,6 +34,16 @@ pub struct Job {
     /// Assigned node (if any)
     pub node: Option<Pubkey>,
 
+    /// Optional commitment to the expected result (sha256 of ipfs_result)
+    /// When set, finish only pays out if the revealed result matches
+    pub result_commitment: Option<[u8; 32]>,
+
+    /// Private inference (off-chain ECDH): client's public key, set at submit
+    pub client_encryption_key: Option<[u8; 32]>,
+
+    /// Node's ephemeral public key for the encrypted result, set at finish
+    pub node_ephemeral_key: Option<[u8; 32]>,
+
     /// Timestamps
     pub created_at: i64,
     pub started_at: Option<i64>,
@@ -48,14 +65,169 @@ impl Job {
         32 + // ipfs_job
         32 + // ipfs_result
         8 + // price
+        8 + // escrowed
         1 + // state
         8 + // timeout
         1 + 32 + // node (Option<Pubkey>)
+        1 + 32 + // result_commitment (Option<[u8; 32]>)
+        1 + 32 + // client_encryption_key (Option<[u8; 32]>)
+        1 + 32 + // node_ephemeral_key (Option<[u8; 32]>)
         8 + // created_at
         1 + 8 + // started_at (Option<i64>)
         1 + 8 + // completed_at (Option<i64>)
         4 + Self::MAX_JOB_ID_LEN + // job_id
         1; // bump
+
+    /// Check a revealed result against the client's commitment
+    /// Jobs without a commitment accept any result
+    pub fn verify_result(&self, ipfs_result: &[u8; 32]) -> bool {
+        match self.result_commitment {
+            Some(commitment) => hash(ipfs_result).to_bytes() == commitment,
+            None => true,
+        }
+    }
+
+    /// Split the escrow on completion into (node payment, client refund)
+    pub fn settlement(&self) -> Result<(u64, u64)> {
+        let refund = self
+            .escrowed
+            .checked_sub(self.price)
+            .ok_or(JobError::InvalidEscrow)?;
+        Ok((self.price, refund))
+    }
+
+    /// Settle a running job for its node: Running -> Completed or Failed
+    /// Validates the finish and records the result, returning the
+    /// (node payment, client refund) to transfer. Called before any token
+    /// moves, so a re-entrant finish finds the job no longer Running
+    pub fn finish(
+        &mut self,
+        node: &Pubkey,
+        ipfs_result: [u8; 32],
+        success: bool,
+        node_ephemeral_key: Option<[u8; 32]>,
+        now: i64,
+    ) -> Result<(u64, u64)> {
+        // Only the assigned node can finish, and only while running
+        require!(self.node == Some(*node), JobError::UnauthorizedNode);
+        require!(self.state == JobState::Running, JobError::JobNotRunning);
+
+        // IPFS result hash must not be empty
+        require!(ipfs_result != [0u8; 32], JobError::InvalidIpfsHash);
+
+        // Result must match the client's commitment to get paid
+        require!(
+            !success || self.verify_result(&ipfs_result),
+            JobError::ResultCommitmentMismatch
+        );
+
+        // Check timeout (optional - could allow late submission)
+        let elapsed = now - self.started_at.unwrap_or(0);
+        require!(elapsed <= self.timeout, JobError::JobExpired);
+
+        self.ipfs_result = ipfs_result;
+        self.completed_at = Some(now);
+
+        if !success {
+            // On failure, refund the client's whole escrow
+            self.state = JobState::Failed;
+            return Ok((0, self.escrowed));
+        }
+
+        // Encrypted results need the node's ephemeral key (and only then)
+        self.set_node_ephemeral_key(node_ephemeral_key)?;
+        let settlement = self.settlement()?;
+        self.state = JobState::Completed;
+        Ok(settlement)
+    }
+
+    /// Event for a successful finish (indexed by market and job_id)
+    pub fn completed_event(&self, job: Pubkey, node: Pubkey, duration: i64, timestamp: i64) -> JobCompletedEvent {
+        JobCompletedEvent {
+            job,
+            market: self.market,
+            job_id: self.job_id.clone(),
+            node,
+            price: self.price,
+            duration,
+            timestamp,
+        }
+    }
+
+    /// Event for a failed finish
+    pub fn failed_event(&self, job: Pubkey, node: Pubkey, duration: i64, timestamp: i64) -> JobFailedEvent {
+        JobFailedEvent {
+            job,
+            market: self.market,
+            job_id: self.job_id.clone(),
+            node,
+            duration,
+            timestamp,
+        }
+    }
+
+    /// Event for a recovered (refunded) job
+    pub fn recover_event(&self, job: Pubkey, reason: &str, timestamp: i64) -> RecoverEvent {
+        RecoverEvent {
+            job,
+            market: self.market,
+            job_id: self.job_id.clone(),
+            client: self.client,
+            amount: self.escrowed,
+            reason: reason.to_string(),
+            timestamp,
+        }
+    }
+
+    /// Raise a running job's budget (scope increase agreed with its node)
+    /// Adds `amount` to both the price and the escrow, so the node is paid it
+    /// at finish, and `extra_timeout` seconds to the timeout.
+    /// Returns the new timeout, which the caller checks against the market's bounds
+    pub fn increase_budget(&mut self, amount: u64, extra_timeout: i64) -> Result<i64> {
+        require!(self.state == JobState::Running, JobError::JobNotRunning);
+        require!(
+            extra_timeout >= 0 && (amount > 0 || extra_timeout > 0),
+            JobError::InvalidBudgetIncrease
+        );
+
+        let price = self.price.checked_add(amount).ok_or(JobError::InvalidBudgetIncrease)?;
+        let escrowed = self.escrowed.checked_add(amount).ok_or(JobError::InvalidBudgetIncrease)?;
+        let timeout = self.timeout.checked_add(extra_timeout).ok_or(JobError::InvalidBudgetIncrease)?;
+
+        self.price = price;
+        self.escrowed = escrowed;
+        self.timeout = timeout;
+        Ok(timeout)
+    }
+
+    /// Check a token account owner may receive the client's refund
+    pub fn is_refund_account(&self, owner: &Pubkey) -> bool {
+        *owner == self.client
+    }
+
+    /// Assign the job to a node: Queued -> Running
+    pub fn assign(&mut self, node: Pubkey, now: i64) {
+        self.node = Some(node);
+        self.state = JobState::Running;
+        self.started_at = Some(now);
+    }
+
+    /// Check an encryption key is not all zeros
+    pub fn is_valid_key(key: &Option<[u8; 32]>) -> bool {
+        key.map_or(true, |k| k != [0u8; 32])
+    }
+
+    /// Record the node's ephemeral key for an encrypted result
+    /// Keys must be present together (encrypted) or both absent (plaintext)
+    pub fn set_node_ephemeral_key(&mut self, node_ephemeral_key: Option<[u8; 32]>) -> Result<()> {
+        require!(
+            Self::is_valid_key(&node_ephemeral_key)
+                && self.client_encryption_key.is_some() == node_ephemeral_key.is_some(),
+            JobError::EncryptionKeyMismatch
+        );
+        self.node_ephemeral_key = node_ephemeral_key;
+        Ok(())
+    }
 }
 
 /// Job lifecycle states (simplified from Nosana)
@@ -95,3 +267,226 @@ impl Run {
         1 + 8 + // duration (Option<i64>)
         1; // bump
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job_with_commitment(result_commitment: Option<[u8; 32]>) -> Job {
+        Job {
+            market: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            ipfs_job: [1u8; 32],
+            ipfs_result: [0u8; 32],
+            price: 1_000,
+            escrowed: 1_000,
+            state: JobState::Running,
+            timeout: 3600,
+            node: Some(Pubkey::new_unique()),
+            result_commitment,
+            client_encryption_key: None,
+            node_ephemeral_key: None,
+            created_at: 0,
+            started_at: Some(0),
+            completed_at: None,
+            job_id: "job-1".to_string(),
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_matching_reveal_accepted() {
+        let result = [7u8; 32];
+        let job = job_with_commitment(Some(hash(&result).to_bytes()));
+
+        assert!(job.verify_result(&result));
+    }
+
+    #[test]
+    fn test_mismatched_reveal_rejected() {
+        let job = job_with_commitment(Some(hash(&[7u8; 32]).to_bytes()));
+
+        assert!(!job.verify_result(&[8u8; 32]));
+    }
+
+    #[test]
+    fn test_no_commitment_accepts_any_result() {
+        let job = job_with_commitment(None);
+
+        assert!(job.verify_result(&[8u8; 32]));
+    }
+
+    #[test]
+    fn test_encryption_keys_round_trip() {
+        let client_key = [3u8; 32];
+        let ephemeral_key = [4u8; 32];
+        let mut job = job_with_commitment(None);
+
+        // submit: client key stored
+        assert!(Job::is_valid_key(&Some(client_key)));
+        job.client_encryption_key = Some(client_key);
+
+        // finish: node ephemeral key stored alongside
+        assert!(job.set_node_ephemeral_key(Some(ephemeral_key)).is_ok());
+
+        let decoded = Job::try_from_slice(&job.try_to_vec().unwrap()).unwrap();
+        assert_eq!(decoded.client_encryption_key, Some(client_key));
+        assert_eq!(decoded.node_ephemeral_key, Some(ephemeral_key));
+    }
+
+    #[test]
+    fn test_encryption_keys_must_be_paired() {
+        // Client asked for encryption, node didn't provide a key
+        let mut job = job_with_commitment(None);
+        job.client_encryption_key = Some([3u8; 32]);
+        assert!(job.set_node_ephemeral_key(None).is_err());
+        assert!(job.set_node_ephemeral_key(Some([0u8; 32])).is_err());
+
+        // Plaintext job, node sends a key anyway
+        let mut job = job_with_commitment(None);
+        assert!(job.set_node_ephemeral_key(Some([4u8; 32])).is_err());
+        assert!(job.set_node_ephemeral_key(None).is_ok());
+    }
+
+    #[test]
+    fn test_overpaid_job_refunds_difference_on_completion() {
+        let mut job = job_with_commitment(None);
+        job.price = 1_000;
+        job.escrowed = 1_500;
+
+        let (node_payment, refund) = job.settlement().unwrap();
+        assert_eq!(node_payment, 1_000);
+        assert_eq!(refund, 500);
+        assert_eq!(node_payment + refund, job.escrowed);
+    }
+
+    #[test]
+    fn test_exact_payment_has_no_refund() {
+        let job = job_with_commitment(None);
+
+        assert_eq!(job.settlement().unwrap(), (1_000, 0));
+    }
+
+    #[test]
+    fn test_underfunded_escrow_rejected() {
+        let mut job = job_with_commitment(None);
+        job.escrowed = 999;
+
+        assert!(job.settlement().is_err());
+    }
+
+    #[test]
+    fn test_reentrant_finish_rejected_once_settled() {
+        let mut job = job_with_commitment(None);
+        job.escrowed = 1_500;
+        let node = job.node.unwrap();
+
+        // The account data a CPI'd token program could re-enter against
+        let mut account_data = Vec::new();
+        job.try_serialize(&mut account_data).unwrap();
+
+        // Malicious token program mock: re-enters finish while a transfer runs,
+        // loading the job from account data as the program would
+        let reenter = |data: &[u8]| -> Result<(u64, u64)> {
+            let mut job = Job::try_deserialize(&mut &data[..])?;
+            job.finish(&node, [9u8; 32], true, None, 100)
+        };
+
+        // finish settles the job, then persists it before the first transfer
+        let (node_payment, refund) = job.finish(&node, [9u8; 32], true, None, 100).unwrap();
+        assert_eq!((node_payment, refund), (1_000, 500));
+        assert!(job.state == JobState::Completed);
+
+        // Had the transfer run first, the stale data would pay the node again
+        assert!(reenter(&account_data).is_ok());
+
+        account_data.clear();
+        job.try_serialize(&mut account_data).unwrap();
+        assert_eq!(
+            reenter(&account_data).unwrap_err(),
+            JobError::JobNotRunning.into()
+        );
+
+        // Same for a failed finish and its refund
+        let mut job = job_with_commitment(None);
+        job.node = Some(node);
+        assert_eq!(job.finish(&node, [9u8; 32], false, None, 100).unwrap(), (0, 1_000));
+        assert!(job.state == JobState::Failed);
+        account_data.clear();
+        job.try_serialize(&mut account_data).unwrap();
+        assert!(reenter(&account_data).is_err());
+    }
+
+    #[test]
+    fn test_budget_increase_raises_payout_at_finish() {
+        let mut job = job_with_commitment(None);
+        job.escrowed = 1_500;
+        let node = job.node.unwrap();
+
+        // Client adds 2_000 tokens and an hour for the extra work
+        assert_eq!(job.increase_budget(2_000, 3_600).unwrap(), 7_200);
+        assert_eq!((job.price, job.escrowed, job.timeout), (3_000, 3_500, 7_200));
+
+        // Past the original timeout, within the new one: the node gets the larger price
+        let (node_payment, refund) = job.finish(&node, [9u8; 32], true, None, 5_000).unwrap();
+        assert_eq!((node_payment, refund), (3_000, 500));
+        assert_eq!(node_payment + refund, job.escrowed);
+    }
+
+    #[test]
+    fn test_budget_increase_only_on_running_jobs() {
+        let mut job = job_with_commitment(None);
+
+        // Nothing added, or time taken away
+        assert_eq!(job.increase_budget(0, 0).unwrap_err(), JobError::InvalidBudgetIncrease.into());
+        assert_eq!(job.increase_budget(500, -60).unwrap_err(), JobError::InvalidBudgetIncrease.into());
+        assert_eq!(
+            job.increase_budget(u64::MAX, 0).unwrap_err(),
+            JobError::InvalidBudgetIncrease.into()
+        );
+        assert_eq!((job.price, job.escrowed, job.timeout), (1_000, 1_000, 3_600));
+
+        // Time alone is fine
+        assert_eq!(job.increase_budget(0, 600).unwrap(), 4_200);
+
+        // Settled jobs can't be topped up
+        let node = job.node.unwrap();
+        job.finish(&node, [9u8; 32], true, None, 100).unwrap();
+        assert_eq!(job.increase_budget(500, 0).unwrap_err(), JobError::JobNotRunning.into());
+
+        job.state = JobState::Queued;
+        assert_eq!(job.increase_budget(500, 0).unwrap_err(), JobError::JobNotRunning.into());
+    }
+
+    #[test]
+    fn test_refund_to_foreign_token_account_rejected() {
+        let job = job_with_commitment(None);
+
+        // finish and recover refund the client only into its own token account
+        assert!(job.is_refund_account(&job.client));
+        assert!(!job.is_refund_account(&Pubkey::new_unique()));
+        assert!(!job.is_refund_account(&job.node.unwrap()));
+    }
+
+    #[test]
+    fn test_events_carry_market_and_job_id() {
+        let job = job_with_commitment(None);
+        let job_key = Pubkey::new_unique();
+        let node = job.node.unwrap();
+
+        let completed = job.completed_event(job_key, node, 120, 1_000);
+        assert_eq!(completed.market, job.market);
+        assert_eq!(completed.job_id, "job-1");
+        assert_eq!(completed.price, job.price);
+
+        let failed = job.failed_event(job_key, node, 120, 1_000);
+        assert_eq!(failed.market, job.market);
+        assert_eq!(failed.job_id, "job-1");
+
+        let recovered = job.recover_event(job_key, "timeout_exceeded", 1_000);
+        assert_eq!(recovered.market, job.market);
+        assert_eq!(recovered.job_id, "job-1");
+        assert_eq!(recovered.amount, job.escrowed);
+        assert_eq!(recovered.reason, "timeout_exceeded");
+    }
+}
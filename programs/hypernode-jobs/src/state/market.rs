@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use crate::state::AllowedClient;
 
 /// Market manages the dynamic queue system (Nosana-style)
 #[account]
@@ -9,17 +10,26 @@ pub struct Market {
     /// Current queue type (dynamic based on supply/demand)
     pub queue_type: QueueType,
 
-    /// Queue of either nodes or jobs (max 314 items due to account size limit)
-    pub queue: Vec<Pubkey>,
+    /// Queue of either nodes or jobs with their enqueue time
+    /// (at most max_queue_size entries)
+    pub queue: Vec<QueueEntry>,
 
     /// Market parameters
     pub job_price: u64,        // Base price in lamports
     pub job_timeout: i64,      // Default timeout in seconds
     pub node_stake_minimum: u64, // Minimum stake required
+    pub min_timeout: i64,      // Shortest job timeout accepted
+    pub max_timeout: i64,      // Longest job timeout accepted (bounds escrow lock)
 
     /// Payment vault for this market
     pub vault: Pubkey,
 
+    /// Token mint accepted for payments (vault mint)
+    pub token_mint: Pubkey,
+
+    /// Private market: only allow-listed clients can submit jobs
+    pub is_private: bool,
+
     /// Market identifier
     pub market_id: String,
 
@@ -27,26 +37,215 @@ pub struct Market {
     pub total_jobs: u64,
     pub total_nodes: u64,
 
+    /// Payout vesting: node payments of jobs priced at or above
+    /// vesting_threshold are held for payout_cliff seconds after finish
+    /// (0 = always paid on finish)
+    pub payout_cliff: i64,
+    pub vesting_threshold: u64,
+
+    /// Share of a recovered job's price paid to whoever calls recover,
+    /// in basis points (0 = no crank reward)
+    pub crank_reward_bps: u16,
+
+    /// Queue capacity, set at initialize_market (the account is sized for it)
+    pub max_queue_size: u16,
+
     /// PDA bump
     pub bump: u8,
 }
 
 impl Market {
     pub const MAX_MARKET_ID_LEN: usize = 32;
-    pub const MAX_QUEUE_SIZE: usize = 314; // Account size limit
+    pub const MAX_QUEUE_SIZE: usize = 314;
+    pub const MAX_INIT_QUEUE_SIZE: usize = 250; // Account creation limit (10KB)
+    pub const MAX_PAYOUT_CLIFF: i64 = 7 * 86400; // Longest a node's pay can be held
+    pub const MAX_CRANK_REWARD_BPS: u16 = 500; // Crank reward is at most 5% of the price
+
+    /// Size of a market with the largest queue
+    pub const SPACE: usize = Self::space(Self::MAX_QUEUE_SIZE);
 
-    pub const SPACE: usize = 8 + // discriminator
+    /// Size of a market whose queue holds `max_queue_size` entries
+    pub const fn space(max_queue_size: usize) -> usize {
+        8 + // discriminator
         32 + // authority
         1 + // queue_type
-        4 + (32 * Self::MAX_QUEUE_SIZE) + // queue (vec of pubkeys)
+        4 + (QueueEntry::SIZE * max_queue_size) + // queue (vec of entries)
         8 + // job_price
         8 + // job_timeout
         8 + // node_stake_minimum
+        8 + // min_timeout
+        8 + // max_timeout
         32 + // vault
+        32 + // token_mint
+        1 + // is_private
         4 + Self::MAX_MARKET_ID_LEN + // market_id
         8 + // total_jobs
         8 + // total_nodes
-        1; // bump
+        8 + // payout_cliff
+        8 + // vesting_threshold
+        2 + // crank_reward_bps
+        2 + // max_queue_size
+        1 // bump
+    }
+
+    /// Size a market is created with: room for `max_queue_size` entries,
+    /// up to what fits in a newly created account (MAX_INIT_QUEUE_SIZE)
+    pub const fn init_space(max_queue_size: usize) -> usize {
+        if max_queue_size < Self::MAX_INIT_QUEUE_SIZE {
+            Self::space(max_queue_size)
+        } else {
+            Self::space(Self::MAX_INIT_QUEUE_SIZE)
+        }
+    }
+
+    /// Size the market needs before queuing one more entry
+    /// Never shrinks the account (`data_len` is its current size), and
+    /// never grows it past room for max_queue_size entries
+    pub fn queue_space(&self, data_len: usize) -> usize {
+        let entries = (self.queue.len() + 1).min(self.max_queue_size as usize);
+        Self::space(entries).max(data_len)
+    }
+
+    /// Check a requested queue capacity is within 1..=MAX_QUEUE_SIZE
+    pub fn is_valid_max_queue_size(max_queue_size: u16) -> bool {
+        (1..=Self::MAX_QUEUE_SIZE).contains(&(max_queue_size as usize))
+    }
+
+    /// Check another node or job fits in the queue
+    pub fn has_queue_capacity(&self) -> bool {
+        self.queue.len() < self.max_queue_size as usize
+    }
+
+    /// Check timeout bounds are well formed
+    pub fn is_valid_timeout_bounds(min_timeout: i64, max_timeout: i64) -> bool {
+        min_timeout > 0 && min_timeout <= max_timeout
+    }
+
+    /// Check a job timeout is within the market's bounds
+    pub fn timeout_in_bounds(&self, timeout: i64) -> bool {
+        (self.min_timeout..=self.max_timeout).contains(&timeout)
+    }
+
+    /// Check the market's current price is within what the client agreed to pay
+    /// Guards against `job_price` changing between signing and execution
+    pub fn price_within(&self, max_price: u64) -> bool {
+        self.job_price <= max_price
+    }
+
+    /// Check a payout cliff is within 0..=MAX_PAYOUT_CLIFF (0 disables vesting)
+    pub fn is_valid_payout_cliff(payout_cliff: i64) -> bool {
+        (0..=Self::MAX_PAYOUT_CLIFF).contains(&payout_cliff)
+    }
+
+    /// Check a job's node payment is held on a PayoutSchedule instead of paid on finish
+    pub fn vests_payout(&self, price: u64) -> bool {
+        self.payout_cliff > 0 && price >= self.vesting_threshold
+    }
+
+    /// Check a crank reward is within 0..=MAX_CRANK_REWARD_BPS
+    pub fn is_valid_crank_reward(crank_reward_bps: u16) -> bool {
+        crank_reward_bps <= Self::MAX_CRANK_REWARD_BPS
+    }
+
+    /// Split a recovered job's escrow into (crank reward, client refund)
+    /// The caller gets crank_reward_bps of the job price, capped at the
+    /// escrow; the client gets the rest back
+    pub fn recover_split(&self, price: u64, escrowed: u64) -> (u64, u64) {
+        let reward = (price as u128 * self.crank_reward_bps as u128 / 10_000) as u64;
+        let reward = reward.min(escrowed);
+        (reward, escrowed - reward)
+    }
+
+    /// Check a payment token account uses this market's mint
+    pub fn accepts_mint(&self, mint: &Pubkey) -> bool {
+        *mint == self.token_mint
+    }
+
+    /// Check a token account is this market's escrow vault
+    /// The vault must also be held by the market PDA (`market`), which signs
+    /// vault transfers, so a look-alike account can't stand in for it
+    pub fn is_vault(&self, market: &Pubkey, vault: &Pubkey, vault_authority: &Pubkey) -> bool {
+        *vault == self.vault && vault_authority == market
+    }
+
+    /// Check a client may submit jobs to this market
+    /// Public markets admit anyone; private ones need the client's allow-list entry
+    pub fn admits_client(&self, allowed: Option<&AllowedClient>, client: &Pubkey) -> bool {
+        !self.is_private || allowed.is_some_and(|entry| entry.client == *client)
+    }
+
+    /// Longest a job can wait in the queue and still be matched
+    /// Past twice the longest timeout any job is stuck in the queue and
+    /// only left for recover to refund
+    pub fn max_queue_wait(&self) -> i64 {
+        self.max_timeout.saturating_mul(2)
+    }
+
+    /// Check if a node or job is already queued
+    pub fn is_queued(&self, key: &Pubkey) -> bool {
+        self.queue.iter().any(|entry| entry.key == *key)
+    }
+
+    /// Append a node or job to the queue
+    pub fn enqueue(&mut self, key: Pubkey, now: i64) {
+        self.queue.push(QueueEntry { key, enqueued_at: now });
+    }
+
+    /// Index of the next entry to match (anti-starvation aging)
+    ///
+    /// Picks the longest-waiting entry among those accepted by `eligible`,
+    /// so an entry skipped as ineligible keeps its age and is matched first
+    /// once it becomes eligible. Ties keep queue order.
+    ///
+    /// Precedence: aging is the only ordering today. Any future priority
+    /// boost must only reorder entries within the same wait, never ahead
+    /// of an older eligible entry, so waits stay bounded.
+    pub fn select_match<F>(&self, eligible: F) -> Option<usize>
+    where
+        F: Fn(&QueueEntry) -> bool,
+    {
+        self.queue
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| eligible(entry))
+            .min_by_key(|(index, entry)| (entry.enqueued_at, *index))
+            .map(|(index, _)| index)
+    }
+
+    /// Remove and return the next entry to match
+    /// Resets the queue type once the queue drains
+    pub fn take_match<F>(&mut self, eligible: F) -> Option<QueueEntry>
+    where
+        F: Fn(&QueueEntry) -> bool,
+    {
+        let index = self.select_match(eligible)?;
+        let entry = self.queue.remove(index);
+
+        if self.queue.is_empty() {
+            self.queue_type = QueueType::Empty;
+        }
+
+        Some(entry)
+    }
+}
+
+/// Queue entry (node or job) with the time it entered the queue
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct QueueEntry {
+    /// Node authority or job account
+    pub key: Pubkey,
+
+    /// When the entry was queued (used for aging)
+    pub enqueued_at: i64,
+}
+
+impl QueueEntry {
+    pub const SIZE: usize = 32 + 8;
+
+    /// Check the entry has waited at most `max_wait` seconds
+    pub fn is_within(&self, max_wait: i64, now: i64) -> bool {
+        now.saturating_sub(self.enqueued_at) <= max_wait
+    }
 }
 
 /// Dynamic queue type (Nosana pattern)
@@ -56,3 +255,269 @@ pub enum QueueType {
     Node,   // Supply > Demand (nodes waiting for jobs)
     Job,    // Demand > Supply (jobs waiting for nodes)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_market() -> Market {
+        Market {
+            authority: Pubkey::new_unique(),
+            queue_type: QueueType::Empty,
+            queue: Vec::new(),
+            job_price: 1_000,
+            job_timeout: 3600,
+            node_stake_minimum: 0,
+            min_timeout: 60,
+            max_timeout: 86_400,
+            vault: Pubkey::new_unique(),
+            token_mint: Pubkey::new_unique(),
+            is_private: false,
+            market_id: "market".to_string(),
+            total_jobs: 0,
+            total_nodes: 0,
+            payout_cliff: 0,
+            vesting_threshold: 0,
+            crank_reward_bps: 0,
+            max_queue_size: Market::MAX_QUEUE_SIZE as u16,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_space_fits_account_limit() {
+        assert!(Market::init_space(Market::MAX_QUEUE_SIZE) <= 10_240);
+        assert_eq!(Market::init_space(2), Market::space(2));
+    }
+
+    #[test]
+    fn test_full_size_queue_grows_past_creation_limit() {
+        let mut market = empty_market();
+        assert_eq!(Market::MAX_QUEUE_SIZE, 314);
+
+        // Created at the creation limit, grown one entry at a time after that
+        let mut data_len = Market::init_space(Market::MAX_QUEUE_SIZE);
+        for i in 0..Market::MAX_QUEUE_SIZE {
+            data_len = market.queue_space(data_len);
+            assert!(market.has_queue_capacity());
+            market.enqueue(Pubkey::new_unique(), i as i64);
+        }
+        assert_eq!(data_len, Market::SPACE);
+        assert!(!market.has_queue_capacity());
+
+        // A full queue doesn't grow any further, and draining never shrinks it
+        assert_eq!(market.queue_space(data_len), Market::SPACE);
+        market.queue.clear();
+        assert_eq!(market.queue_space(data_len), Market::SPACE);
+    }
+
+    #[test]
+    fn test_matching_skips_jobs_past_their_queue_window() {
+        let mut market = empty_market();
+        market.queue_type = QueueType::Job;
+        let stale = Pubkey::new_unique();
+        let live = Pubkey::new_unique();
+        market.enqueue(stale, 0);
+        market.enqueue(live, 100_000);
+
+        // Past twice max_timeout the older job is only recoverable
+        let max_wait = market.max_queue_wait();
+        let now = max_wait + 1;
+        let entry = market.take_match(|e| e.is_within(max_wait, now)).unwrap();
+        assert_eq!(entry.key, live);
+        assert!(market.take_match(|e| e.is_within(max_wait, now)).is_none());
+        assert!(market.is_queued(&stale));
+    }
+
+    #[test]
+    fn test_small_capacity_market_rejects_overflow_job() {
+        let mut market = empty_market();
+        market.max_queue_size = 2;
+
+        market.enqueue(Pubkey::new_unique(), 10);
+        assert!(market.has_queue_capacity());
+        market.enqueue(Pubkey::new_unique(), 20);
+        assert!(!market.has_queue_capacity());
+
+        // The account is sized for exactly that many entries
+        market.market_id = "x".repeat(Market::MAX_MARKET_ID_LEN);
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Market::space(2));
+
+        assert!(!Market::is_valid_max_queue_size(0));
+        assert!(!Market::is_valid_max_queue_size(Market::MAX_QUEUE_SIZE as u16 + 1));
+    }
+
+    #[test]
+    fn test_large_capacity_market_accepts_more_jobs() {
+        let mut market = empty_market();
+        market.max_queue_size = 200;
+
+        for i in 0..199 {
+            market.enqueue(Pubkey::new_unique(), i);
+        }
+        assert!(market.has_queue_capacity());
+        market.enqueue(Pubkey::new_unique(), 199);
+        assert!(!market.has_queue_capacity());
+        assert!(Market::is_valid_max_queue_size(Market::MAX_QUEUE_SIZE as u16));
+    }
+
+    #[test]
+    fn test_longest_waiting_eligible_entry_matched_first() {
+        let mut market = empty_market();
+        let old = Pubkey::new_unique();
+        let newer = Pubkey::new_unique();
+        market.enqueue(newer, 200);
+        market.enqueue(old, 100);
+
+        let entry = market.take_match(|_| true).unwrap();
+        assert_eq!(entry.key, old);
+        assert_eq!(market.queue.len(), 1);
+    }
+
+    #[test]
+    fn test_long_waiting_entry_not_indefinitely_skipped() {
+        let mut market = empty_market();
+        market.queue_type = QueueType::Node;
+        let starving = Pubkey::new_unique();
+        market.enqueue(starving, 0);
+
+        // While ineligible the old entry is skipped, and newer ones keep arriving
+        for round in 1..=5 {
+            market.enqueue(Pubkey::new_unique(), round * 60);
+            let entry = market.take_match(|e| e.key != starving).unwrap();
+            assert_ne!(entry.key, starving);
+        }
+
+        // More newcomers, but once eligible the oldest entry wins immediately
+        market.enqueue(Pubkey::new_unique(), 600);
+        market.enqueue(Pubkey::new_unique(), 660);
+        let entry = market.take_match(|_| true).unwrap();
+        assert_eq!(entry.key, starving);
+        assert_eq!(entry.enqueued_at, 0);
+    }
+
+    #[test]
+    fn test_take_match_resets_queue_type_when_drained() {
+        let mut market = empty_market();
+        market.queue_type = QueueType::Job;
+        market.enqueue(Pubkey::new_unique(), 0);
+
+        assert!(market.take_match(|_| true).is_some());
+        assert!(market.queue_type == QueueType::Empty);
+        assert!(market.take_match(|_| true).is_none());
+    }
+
+    #[test]
+    fn test_wrong_mint_token_account_rejected() {
+        let market = empty_market();
+
+        assert!(market.accepts_mint(&market.token_mint));
+        assert!(!market.accepts_mint(&Pubkey::new_unique()));
+        assert!(!market.accepts_mint(&Pubkey::default()));
+    }
+
+    #[test]
+    fn test_timeout_within_bounds() {
+        let market = empty_market();
+
+        assert!(market.timeout_in_bounds(60));
+        assert!(market.timeout_in_bounds(3600));
+        assert!(market.timeout_in_bounds(86_400));
+
+        assert!(!market.timeout_in_bounds(59));
+        assert!(!market.timeout_in_bounds(86_401));
+        assert!(!market.timeout_in_bounds(0));
+    }
+
+    #[test]
+    fn test_timeout_bounds_validation() {
+        assert!(Market::is_valid_timeout_bounds(60, 60));
+        assert!(Market::is_valid_timeout_bounds(60, 3600));
+        assert!(!Market::is_valid_timeout_bounds(3600, 60));
+        assert!(!Market::is_valid_timeout_bounds(0, 3600));
+    }
+
+    #[test]
+    fn test_private_market_rejects_unlisted_client() {
+        let mut market = empty_market();
+        market.is_private = true;
+        let client = Pubkey::new_unique();
+
+        assert!(!market.admits_client(None, &client));
+
+        // Another client's entry doesn't count
+        let other = AllowedClient {
+            market: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            added_at: 0,
+            bump: 255,
+        };
+        assert!(!market.admits_client(Some(&other), &client));
+
+        let entry = AllowedClient { client, ..other };
+        assert!(market.admits_client(Some(&entry), &client));
+    }
+
+    #[test]
+    fn test_public_market_admits_any_client() {
+        let market = empty_market();
+
+        assert!(market.admits_client(None, &Pubkey::new_unique()));
+    }
+
+    #[test]
+    fn test_vault_not_held_by_market_rejected() {
+        let market = empty_market();
+        let market_key = Pubkey::new_unique();
+
+        assert!(market.is_vault(&market_key, &market.vault, &market_key));
+
+        // Right address, wrong token authority
+        assert!(!market.is_vault(&market_key, &market.vault, &Pubkey::new_unique()));
+
+        // Held by the market, but not its vault
+        assert!(!market.is_vault(&market_key, &Pubkey::new_unique(), &market_key));
+    }
+
+    #[test]
+    fn test_price_bump_beyond_max_price_rejected() {
+        let mut market = empty_market();
+        let max_price = market.job_price;
+
+        assert!(market.price_within(max_price));
+
+        // Governance raises the price while the submission is in flight
+        market.job_price = max_price + 1;
+        assert!(!market.price_within(max_price));
+
+        // A client that allowed headroom is still accepted
+        assert!(market.price_within(max_price * 2));
+    }
+
+    #[test]
+    fn test_recover_pays_crank_reward_and_refunds_remainder() {
+        let mut market = empty_market();
+        market.crank_reward_bps = 100; // 1%
+
+        // The caller gets 1% of the price, the client the rest of the escrow
+        assert_eq!(market.recover_split(1_000_000, 1_000_000), (10_000, 990_000));
+
+        // Overpaid escrow: the reward is still a share of the price
+        assert_eq!(market.recover_split(1_000_000, 1_500_000), (10_000, 1_490_000));
+
+        // Nothing is created or lost
+        let (reward, refund) = market.recover_split(333_333, 400_000);
+        assert_eq!(reward, 3_333);
+        assert_eq!(reward + refund, 400_000);
+
+        // Without a crank reward the client gets everything back
+        market.crank_reward_bps = 0;
+        assert_eq!(market.recover_split(1_000_000, 1_000_000), (0, 1_000_000));
+
+        assert!(Market::is_valid_crank_reward(0));
+        assert!(Market::is_valid_crank_reward(Market::MAX_CRANK_REWARD_BPS));
+        assert!(!Market::is_valid_crank_reward(Market::MAX_CRANK_REWARD_BPS + 1));
+    }
+}
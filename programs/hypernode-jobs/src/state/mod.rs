@@ -1,5 +1,13 @@
 pub mod market;
 pub mod job;
+pub mod allowed_client;
+pub mod blacklist;
+pub mod assignment;
+pub mod payout;
 
 pub use market::*;
 pub use job::*;
+pub use allowed_client::*;
+pub use blacklist::*;
+pub use assignment::*;
+pub use payout::*;
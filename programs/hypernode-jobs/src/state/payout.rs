@@ -0,0 +1,372 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, CreateAccount};
+use crate::state::job::Job;
+
+/// Slashing Program (owner of fraud reports)
+pub const SLASHING_PROGRAM_ID: Pubkey = pubkey!("83rLt9YBCTkaAX6vLUuEAQE7QdhofvQWUhjybXVr7nCL");
+
+/// Anchor discriminator of the Slashing Program's FraudReport account
+/// (first 8 bytes of sha256("account:FraudReport"))
+pub const FRAUD_REPORT_DISCRIMINATOR: [u8; 8] = [136, 69, 45, 181, 210, 24, 121, 177];
+
+/// FraudReport status codes (ReportStatus variant indices)
+const REPORT_STATUS_PENDING: u8 = 0;
+const REPORT_STATUS_CONFIRMED: u8 = 1;
+const REPORT_STATUS_EXECUTED: u8 = 2;
+const REPORT_STATUS_APPEALED: u8 = 3;
+const REPORT_STATUS_DISMISSED: u8 = 4;
+
+/// The parts of a Slashing Program FraudReport a clawback needs
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FraudReportView {
+    /// Reported node
+    pub node: Pubkey,
+
+    /// When the report was filed
+    pub time_reported: i64,
+
+    /// ReportStatus as its variant index
+    pub status: u8,
+
+    /// Account the report cites as evidence (the reported job)
+    pub evidence_account: Pubkey,
+}
+
+impl FraudReportView {
+    /// Read raw Slashing Program FraudReport data
+    ///
+    /// Layout: discriminator 8 + node 32 + reporter 32 + evidence_cid 32 +
+    /// fraud_type 1 + time_reported 8 + validator_count 1 + validators
+    /// (u32 length + 32 each) + proposed_amounts (u32 length + 8 each) + status 1 +
+    /// slash_amount 8 + appeal_ends 8 + evidence_account 32.
+    /// Read by hand since this program doesn't link the Slashing Program.
+    /// Returns None if the data isn't a FraudReport.
+    pub fn read(data: &[u8]) -> Option<Self> {
+        if data.get(..8)? != FRAUD_REPORT_DISCRIMINATOR {
+            return None;
+        }
+        let node = Pubkey::try_from(data.get(8..40)?).ok()?;
+        let time_reported = i64::from_le_bytes(data.get(105..113)?.try_into().ok()?);
+
+        let validators_len = u32::from_le_bytes(data.get(114..118)?.try_into().ok()?) as usize;
+        let amounts_at = 118usize.checked_add(validators_len.checked_mul(32)?)?;
+        let amounts_len =
+            u32::from_le_bytes(data.get(amounts_at..amounts_at + 4)?.try_into().ok()?) as usize;
+        let status_at = (amounts_at + 4).checked_add(amounts_len.checked_mul(8)?)?;
+        let status = *data.get(status_at)?;
+        let evidence_at = status_at.checked_add(17)?;
+        let evidence_account = Pubkey::try_from(data.get(evidence_at..evidence_at + 32)?).ok()?;
+
+        Some(Self { node, time_reported, status, evidence_account })
+    }
+
+    /// Validators confirmed the fraud (whether or not it was slashed yet)
+    pub fn is_confirmed(&self) -> bool {
+        matches!(self.status, REPORT_STATUS_CONFIRMED | REPORT_STATUS_EXECUTED)
+    }
+
+    /// Not resolved yet: pending, confirmed but not slashed, or under appeal
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            REPORT_STATUS_PENDING | REPORT_STATUS_CONFIRMED | REPORT_STATUS_APPEALED
+        )
+    }
+
+    /// Dismissed as baseless (or overturned on appeal)
+    pub fn is_dismissed(&self) -> bool {
+        self.status == REPORT_STATUS_DISMISSED
+    }
+}
+
+/// Vested node payment for a finished job
+///
+/// One PDA per (market, job), written by finish instead of paying the node
+/// when the market vests the job's payout. The tokens stay in the market
+/// vault: the node collects them with claim_payout once vests_at passes,
+/// and a fraud report citing the job confirmed in the meantime lets
+/// clawback_payout return them to the client. A report still open at the
+/// cliff holds the payment (hold_payout) until it is resolved.
+#[account]
+pub struct PayoutSchedule {
+    /// Market the job belongs to
+    pub market: Pubkey,
+
+    /// Finished job
+    pub job: Pubkey,
+
+    /// Node owed the payment
+    pub node: Pubkey,
+
+    /// Client refunded on clawback
+    pub client: Pubkey,
+
+    /// Held node payment
+    pub amount: u64,
+
+    /// When the job finished (start of vesting)
+    pub created_at: i64,
+
+    /// When the node can claim
+    pub vests_at: i64,
+
+    /// Open fraud report holding the payment past the cliff (None = not held)
+    pub held_by: Option<Pubkey>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl PayoutSchedule {
+    pub const SEED: &'static [u8] = b"payout";
+
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // market
+        32 + // job
+        32 + // node
+        32 + // client
+        8 + // amount
+        8 + // created_at
+        8 + // vests_at
+        (1 + 32) + // held_by
+        1; // bump
+
+    /// Schedule for `job` (at `job_key`) finished at `now`, vesting after `cliff`
+    pub fn of(job: &Job, job_key: Pubkey, amount: u64, now: i64, cliff: i64, bump: u8) -> Option<Self> {
+        Some(Self {
+            market: job.market,
+            job: job_key,
+            node: job.node?,
+            client: job.client,
+            amount,
+            created_at: now,
+            vests_at: now.checked_add(cliff)?,
+            held_by: None,
+            bump,
+        })
+    }
+
+    /// Whether the node can claim at `current_time`
+    pub fn is_vested(&self, current_time: i64) -> bool {
+        current_time >= self.vests_at
+    }
+
+    /// Whether `report` is about this payment: filed against this node,
+    /// citing this job, after the job finished
+    pub fn is_reported_by(&self, report: &FraudReportView) -> bool {
+        report.node == self.node
+            && report.evidence_account == self.job
+            && report.time_reported >= self.created_at
+    }
+
+    /// Whether `report` can hold the payment at `current_time`
+    /// An open report about this job, while still vesting and not already held
+    pub fn can_hold(&self, report: &FraudReportView, current_time: i64) -> bool {
+        self.held_by.is_none()
+            && !self.is_vested(current_time)
+            && report.is_open()
+            && self.is_reported_by(report)
+    }
+
+    /// Whether `report` (at `report_key`) lets the payment be clawed back at `current_time`
+    /// A confirmed report about this job, while still vesting or holding the payment
+    pub fn can_claw_back(&self, report: &FraudReportView, report_key: &Pubkey, current_time: i64) -> bool {
+        (!self.is_vested(current_time) || self.held_by == Some(*report_key))
+            && report.is_confirmed()
+            && self.is_reported_by(report)
+    }
+
+    /// Whether the node can claim given the holding report's current state
+    /// Unheld payments can always be claimed; a held one once its report was
+    /// dismissed (`None`: the Slashing Program closed it)
+    pub fn is_released(&self, holding_report: Option<&FraudReportView>) -> bool {
+        self.held_by.is_none() || holding_report.is_none_or(|report| report.is_dismissed())
+    }
+
+    /// Create the schedule PDA and write it
+    /// `schedule` must be the uninitialized ["payout", market, job] address
+    pub fn create<'info>(
+        &self,
+        schedule: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let seeds: &[&[u8]] = &[
+            Self::SEED,
+            self.market.as_ref(),
+            self.job.as_ref(),
+            &[self.bump],
+        ];
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                CreateAccount {
+                    from: payer.clone(),
+                    to: schedule.clone(),
+                },
+                &[seeds],
+            ),
+            Rent::get()?.minimum_balance(Self::SPACE),
+            Self::SPACE as u64,
+            &crate::ID,
+        )?;
+
+        let mut data = schedule.try_borrow_mut_data()?;
+        self.try_serialize(&mut &mut data[..])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::job::JobState;
+
+    fn finished_job(node: Pubkey) -> Job {
+        Job {
+            market: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            ipfs_job: [1u8; 32],
+            ipfs_result: [2u8; 32],
+            price: 1_000_000,
+            escrowed: 1_000_000,
+            state: JobState::Completed,
+            timeout: 3600,
+            node: Some(node),
+            result_commitment: None,
+            client_encryption_key: None,
+            node_ephemeral_key: None,
+            created_at: 10,
+            started_at: Some(20),
+            completed_at: Some(1_000),
+            job_id: "job-1".to_string(),
+            bump: 255,
+        }
+    }
+
+    /// Raw FraudReport data as the Slashing Program lays it out
+    fn report_data(node: Pubkey, job: Pubkey, time_reported: i64, validators: usize, status: u8) -> Vec<u8> {
+        let mut data = FRAUD_REPORT_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(node.as_ref());
+        data.extend_from_slice(Pubkey::new_unique().as_ref()); // reporter
+        data.extend_from_slice(&[7u8; 32]); // evidence_cid
+        data.push(0); // fraud_type
+        data.extend_from_slice(&time_reported.to_le_bytes());
+        data.push(validators as u8);
+        data.extend_from_slice(&(validators as u32).to_le_bytes());
+        for _ in 0..validators {
+            data.extend_from_slice(Pubkey::new_unique().as_ref());
+        }
+        data.extend_from_slice(&(validators as u32).to_le_bytes());
+        for _ in 0..validators {
+            data.extend_from_slice(&500u64.to_le_bytes());
+        }
+        data.push(status);
+        data.extend_from_slice(&500u64.to_le_bytes()); // slash_amount
+        data.extend_from_slice(&time_reported.to_le_bytes()); // appeal_ends
+        data.extend_from_slice(job.as_ref()); // evidence_account
+        data.extend_from_slice(&[0u8; 64]); // remaining fields
+        data
+    }
+
+    #[test]
+    fn test_vested_payout_claimable_after_cliff() {
+        let node = Pubkey::new_unique();
+        let job = finished_job(node);
+        let job_key = Pubkey::new_unique();
+
+        let schedule = PayoutSchedule::of(&job, job_key, job.price, 1_000, 86_400, 254).unwrap();
+        assert_eq!((schedule.node, schedule.client), (node, job.client));
+        assert_eq!((schedule.amount, schedule.vests_at), (1_000_000, 87_400));
+
+        assert!(!schedule.is_vested(87_399));
+        assert!(schedule.is_vested(87_400));
+        assert!(schedule.is_released(None));
+
+        // A report that was never confirmed can't claw the payment back
+        let pending = FraudReportView::read(&report_data(node, job_key, 2_000, 1, 0)).unwrap();
+        assert!(!schedule.can_claw_back(&pending, &Pubkey::new_unique(), 3_000));
+
+        // SPACE fits a held schedule
+        let mut held = schedule.clone();
+        held.held_by = Some(Pubkey::new_unique());
+        let mut data = Vec::new();
+        held.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), PayoutSchedule::SPACE);
+    }
+
+    #[test]
+    fn test_payout_clawed_back_when_fraud_confirmed_during_vesting() {
+        let node = Pubkey::new_unique();
+        let job = finished_job(node);
+        let job_key = Pubkey::new_unique();
+        let report_key = Pubkey::new_unique();
+        let schedule = PayoutSchedule::of(&job, job_key, job.price, 1_000, 86_400, 254).unwrap();
+
+        // Reported an hour after the job finished, confirmed by three validators
+        let confirmed = FraudReportView::read(&report_data(node, job_key, 4_600, 3, 1)).unwrap();
+        assert_eq!(confirmed.node, node);
+        assert_eq!(confirmed.time_reported, 4_600);
+        assert_eq!(confirmed.evidence_account, job_key);
+        assert!(confirmed.is_confirmed());
+        assert!(schedule.can_claw_back(&confirmed, &report_key, 10_000));
+
+        // Already slashed also counts
+        let executed = FraudReportView::read(&report_data(node, job_key, 4_600, 3, 2)).unwrap();
+        assert!(schedule.can_claw_back(&executed, &report_key, 10_000));
+
+        // Not once vested, not for another node, not for fraud reported before the job finished
+        assert!(!schedule.can_claw_back(&confirmed, &report_key, schedule.vests_at));
+        let other = FraudReportView::read(&report_data(Pubkey::new_unique(), job_key, 4_600, 3, 1)).unwrap();
+        assert!(!schedule.can_claw_back(&other, &report_key, 10_000));
+        let earlier = FraudReportView::read(&report_data(node, job_key, 900, 3, 1)).unwrap();
+        assert!(!schedule.can_claw_back(&earlier, &report_key, 10_000));
+
+        // Nor for fraud on another of the node's jobs: only the reported job is clawed back
+        let other_job = FraudReportView::read(&report_data(node, Pubkey::new_unique(), 4_600, 3, 1)).unwrap();
+        assert!(!schedule.can_claw_back(&other_job, &report_key, 10_000));
+
+        // Some other account, or truncated data
+        let mut data = report_data(node, job_key, 4_600, 3, 1);
+        assert!(FraudReportView::read(&data[..120]).is_none());
+        data[0] ^= 1;
+        assert!(FraudReportView::read(&data).is_none());
+    }
+
+    #[test]
+    fn test_open_report_holds_payout_past_cliff() {
+        let node = Pubkey::new_unique();
+        let job = finished_job(node);
+        let job_key = Pubkey::new_unique();
+        let report_key = Pubkey::new_unique();
+        let mut schedule = PayoutSchedule::of(&job, job_key, job.price, 1_000, 86_400, 254).unwrap();
+
+        // Pending and appealed reports are still open; a dismissed one isn't
+        let pending = FraudReportView::read(&report_data(node, job_key, 4_600, 1, 0)).unwrap();
+        let appealed = FraudReportView::read(&report_data(node, job_key, 4_600, 3, 3)).unwrap();
+        let dismissed = FraudReportView::read(&report_data(node, job_key, 4_600, 3, 4)).unwrap();
+        assert!(schedule.can_hold(&pending, 10_000));
+        assert!(schedule.can_hold(&appealed, 10_000));
+        assert!(!schedule.can_hold(&dismissed, 10_000));
+
+        // Only before the cliff, and only for this job
+        assert!(!schedule.can_hold(&pending, schedule.vests_at));
+        let other_job = FraudReportView::read(&report_data(node, Pubkey::new_unique(), 4_600, 1, 0)).unwrap();
+        assert!(!schedule.can_hold(&other_job, 10_000));
+
+        // Held: the node can't claim while the report is open, even after the cliff
+        schedule.held_by = Some(report_key);
+        assert!(!schedule.can_hold(&pending, 10_000));
+        assert!(!schedule.is_released(Some(&pending)));
+        assert!(!schedule.is_released(Some(&appealed)));
+
+        // Confirmed after the cliff: the holding report can still claw it back
+        let confirmed = FraudReportView::read(&report_data(node, job_key, 4_600, 3, 1)).unwrap();
+        assert!(!schedule.is_released(Some(&confirmed)));
+        assert!(schedule.can_claw_back(&confirmed, &report_key, schedule.vests_at + 1));
+        assert!(!schedule.can_claw_back(&confirmed, &Pubkey::new_unique(), schedule.vests_at + 1));
+
+        // Dismissed (or closed by the Slashing Program): the node can claim
+        assert!(schedule.is_released(Some(&dismissed)));
+        assert!(schedule.is_released(None));
+    }
+}
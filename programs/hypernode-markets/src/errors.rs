@@ -46,4 +46,145 @@ pub enum MarketError {
 
     #[msg("Invalid vault owner")]
     InvalidVaultOwner,
+
+    #[msg("Node lacks required capabilities")]
+    MissingCapabilities,
+
+    #[msg("Job not found in market queue")]
+    JobNotInQueue,
+
+    #[msg("Too many node accounts passed to prune_queue")]
+    PruneBatchTooLarge,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Queue type inconsistent with queue contents")]
+    QueueInvariantViolated,
+
+    #[msg("Node has no free job slots")]
+    NodeAtCapacity,
+
+    #[msg("Node not found in market queue")]
+    NodeNotInQueue,
+
+    #[msg("Queue entry still references a live job")]
+    QueueEntryLive,
+
+    #[msg("Job is not completed")]
+    JobNotCompleted,
+
+    #[msg("Job has no held rewards fee")]
+    NoHeldFee,
+
+    #[msg("Job is under dispute")]
+    JobDisputed,
+
+    #[msg("Job is not under dispute")]
+    JobNotDisputed,
+
+    #[msg("Dispute window has closed")]
+    DisputeWindowClosed,
+
+    #[msg("Dispute window is still open")]
+    DisputeWindowOpen,
+
+    #[msg("Invalid dispute window")]
+    InvalidDisputeWindow,
+
+    #[msg("SLA deadline must be positive and below the job timeout")]
+    InvalidSlaDeadline,
+
+    #[msg("Job price is below the node's minimum job price")]
+    BelowNodeMinPrice,
+
+    #[msg("Market fee plus rewards fee cannot exceed 100%")]
+    InvalidMarketFee,
+
+    #[msg("Invalid node selection mode")]
+    InvalidSelectionMode,
+
+    #[msg("Random selection needs the SlotHashes sysvar")]
+    MissingSelectionEntropy,
+
+    #[msg("Node was not selected for this job")]
+    NodeNotSelected,
+
+    #[msg("Reputation accounts must match the queued candidates")]
+    InvalidSelectionCandidates,
+
+    #[msg("Node software version is below the market minimum")]
+    NodeVersionTooOld,
+
+    #[msg("Job result is not pending review")]
+    JobNotPendingReview,
+
+    #[msg("Node hardware is below the market minimum")]
+    NodeHardwareBelowMinimum,
+
+    #[msg("Cancellation fee exceeds MAX_CANCELLATION_FEE_BPS")]
+    InvalidCancellationFee,
+
+    #[msg("Invalid signer set or threshold")]
+    InvalidAuthorityConfig,
+
+    #[msg("Not enough authority signers approved this action")]
+    ThresholdNotMet,
+
+    #[msg("Tier stake multiplier exceeds MAX_TIER_STAKE_BPS")]
+    InvalidTierStakeBps,
+
+    #[msg("Node authority is blacklisted")]
+    NodeBlacklisted,
+
+    #[msg("Node share exceeds 10000 bps")]
+    InvalidNodeShare,
+
+    #[msg("Node is reserved by another client")]
+    NodeReserved,
+
+    #[msg("Invalid reservation window or account")]
+    InvalidReservation,
+
+    #[msg("Max queue size must be between 1 and MAX_QUEUE_SIZE")]
+    InvalidMaxQueueSize,
+
+    #[msg("Max retries exceeds MAX_JOB_RETRIES")]
+    InvalidMaxRetries,
+
+    #[msg("Job has no retries left")]
+    RetryBudgetExhausted,
+
+    #[msg("Node is already listed or busy up to its slot capacity")]
+    NodeAlreadyBooked,
+
+    #[msg("Market still holds escrow for unsettled jobs")]
+    EscrowOutstanding,
+
+    #[msg("No dust to sweep")]
+    NoDustToSweep,
+
+    #[msg("Job scheduling hints are out of range")]
+    InvalidJobHints,
+
+    #[msg("Job input does not fit the node's RAM")]
+    NodeCapacityExceeded,
+
+    #[msg("Rating must be between MIN_JOB_RATING and MAX_JOB_RATING")]
+    InvalidRating,
+
+    #[msg("Job has already been rated")]
+    JobAlreadyRated,
+
+    #[msg("Response EMA alpha must be 1-10000 bps and the decay half-life non-negative")]
+    InvalidResponseEma,
+
+    #[msg("Fee token account must be a wrapped SOL account owned by the market vault")]
+    InvalidFeeTokenAccount,
+
+    #[msg("Rewards pool must pay out in wrapped SOL to take job fees")]
+    RewardMintNotNative,
+
+    #[msg("Node account is not the registration on record for this node")]
+    NodeAccountMismatch,
 }
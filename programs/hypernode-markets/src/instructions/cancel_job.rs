@@ -22,6 +22,14 @@ pub struct CancelJob<'info> {
     )]
     pub client: Signer<'info>,
 
+    /// Market authority (receives the cancellation fee)
+    /// CHECK: only credited; may be a threshold config PDA (see withdraw_authority_fees)
+    #[account(
+        mut,
+        address = market.authority @ MarketError::Unauthorized
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+
     /// Vault PDA for escrow payment
     #[account(
         mut,
@@ -30,6 +38,14 @@ pub struct CancelJob<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Market statistics (outcome and revenue counters)
+    #[account(
+        mut,
+        seeds = [MarketStats::SEED, market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Account<'info, MarketStats>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -40,21 +56,11 @@ pub fn handler(ctx: Context<CancelJob>) -> Result<()> {
     let vault = &ctx.accounts.vault;
     let clock = Clock::get()?;
 
-    // Verify job is queued (can only cancel queued jobs)
-    require!(job.is_queued(), MarketError::JobNotQueued);
-
-    // Remove job from market queue
+    // Verify job is still queued and remove it from the queue atomically
+    // Fails if a node claimed it first, so the refund can't race work_job
     let job_key = job.key();
-    market.queue_remove(job_key);
-
-    // Update queue type if empty
-    if market.queue_is_empty() {
-        market.queue_type = MarketAccount::QUEUE_TYPE_EMPTY;
-    }
-
-    // Update job state
-    job.state = JobState::Stopped;
-    job.time_end = clock.unix_timestamp;
+    market.cancel_queued_job(job, job_key, clock.unix_timestamp)?;
+    ctx.accounts.market_stats.record_cancelled()?;
 
     // Refund client from vault
     // PDA signer seeds
@@ -66,13 +72,20 @@ pub fn handler(ctx: Context<CancelJob>) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
 
-    // Transfer lamports from vault (PDA) to client
-    **vault.to_account_info().try_borrow_mut_lamports()? -= job.price;
-    **client.to_account_info().try_borrow_mut_lamports()? += job.price;
+    // No rewards or market fee on a job that never completed; the market's
+    // cancellation fee (0 by default) discourages submit/cancel spam
+    let (refund, cancellation_fee) = market.split_cancellation(job.abort_refund());
+    market.release_escrow(refund + cancellation_fee);
+
+    // Transfer lamports from vault (PDA) to client and market authority
+    **vault.to_account_info().try_borrow_mut_lamports()? -= refund + cancellation_fee;
+    **client.to_account_info().try_borrow_mut_lamports()? += refund;
+    **ctx.accounts.market_authority.to_account_info().try_borrow_mut_lamports()? += cancellation_fee;
 
     msg!("Job cancelled: {}", job.key());
     msg!("Client: {}", client.key());
-    msg!("Refund: {} lamports", job.price);
+    msg!("Refund: {} lamports", refund);
+    msg!("Cancellation fee: {} lamports", cancellation_fee);
 
     Ok(())
 }
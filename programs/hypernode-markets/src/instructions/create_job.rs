@@ -1,10 +1,17 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
+use anchor_lang::solana_program::sysvar::slot_hashes;
 use crate::state::*;
 use crate::errors::*;
+use hypernode_nodes::state::{Node, NodesConfig};
 
 /// Create a new job and add to market queue
 /// Client pays upfront into escrow vault
+/// With nodes waiting, the market's selection_mode picks the node offered
+/// the job now and stores it on the job; on reputation-weighted markets the
+/// NodeReputation and Nodes Program Node accounts of the first selection
+/// candidates are passed as remaining accounts, in (reputation, node) pairs
+/// in queue order, so their tier and geo-diversity boosts count
 #[derive(Accounts)]
 #[instruction(job_id: Pubkey)]
 pub struct CreateJob<'info> {
@@ -32,15 +39,32 @@ pub struct CreateJob<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// CHECK: SlotHashes sysvar (address checked); entropy for random node selection
+    /// Required unless the market selects nodes FIFO
+    #[account(address = slot_hashes::ID)]
+    pub slot_hashes: Option<UncheckedAccount<'info>>,
+
+    /// Nodes Program config (geo-diversity registry for weighted selection)
+    #[account(
+        seeds = [b"nodes_config"],
+        bump = nodes_config.bump,
+        seeds::program = hypernode_nodes::ID
+    )]
+    pub nodes_config: Account<'info, NodesConfig>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<CreateJob>,
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, CreateJob<'info>>,
     job_id: Pubkey,
     ipfs_job: [u8; 32],
     min_vram: u8,
     gpu_type: u8,
+    required_capabilities: u64,
+    sla_deadline: i64,
+    max_retries: u8,
+    hints: JobHints,
 ) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let job = &mut ctx.accounts.job;
@@ -49,6 +73,24 @@ pub fn handler(
     let clock = Clock::get()?;
     let bump = ctx.bumps.job;
 
+    // SLA deadline (optional) must fall before the hard timeout
+    require!(
+        JobAccount::is_valid_sla_deadline(sla_deadline, market.job_timeout),
+        MarketError::InvalidSlaDeadline
+    );
+
+    // Retry budget (optional) for re-queueing after a node fails the job
+    require!(
+        JobAccount::is_valid_max_retries(max_retries),
+        MarketError::InvalidMaxRetries
+    );
+
+    // Scheduling hints (optional) must be plausible for the job's timeout
+    require!(
+        hints.is_valid(market.job_timeout),
+        MarketError::InvalidJobHints
+    );
+
     // Transfer payment to vault (escrow)
     let payment_amount = market.job_price;
     system_program::transfer(
@@ -61,12 +103,14 @@ pub fn handler(
         ),
         payment_amount,
     )?;
+    market.hold_escrow(payment_amount)?;
 
     // Initialize job account
     job.id = job_id;
     job.market = market.key();
     job.client = client.key();
     job.node = None;
+    job.node_account = Pubkey::default();
     job.ipfs_job = ipfs_job;
     job.ipfs_result = [0u8; 32];
     job.price = payment_amount;
@@ -77,13 +121,39 @@ pub fn handler(
     job.time_end = 0;
     job.min_vram = min_vram;
     job.gpu_type = gpu_type;
+    job.required_capabilities = required_capabilities;
+    job.sla_deadline = sla_deadline;
+    job.held_rewards_fee = 0;
+    job.fee_release_at = 0;
+    job.selected_node = None;
+    job.max_retries = max_retries;
+    job.retry_count = 0;
+    job.set_hints(hints);
+    job.rating = 0;
+    job.dispute_reversed = false;
     job.bump = bump;
 
     // Add to market queue
     // If queue has nodes waiting, match immediately (handled in dual queue logic)
     if market.queue_type == MarketAccount::QUEUE_TYPE_NODES {
-        // Nodes are waiting - will be matched in work_job instruction
-        msg!("Nodes available, job can be matched immediately");
+        // Nodes are waiting - pick the one offered the job; it takes it in work_job
+        let entropy = match ctx.accounts.slot_hashes.as_ref() {
+            Some(slot_hashes) => selection_entropy(&slot_hashes.try_borrow_data()?, market.total_jobs)
+                .ok_or(MarketError::MissingSelectionEntropy)?,
+            None => {
+                require!(
+                    market.selection_mode == MarketAccount::SELECTION_FIFO,
+                    MarketError::MissingSelectionEntropy
+                );
+                0
+            }
+        };
+        let weights = candidate_weights(market, &ctx.accounts.nodes_config, ctx.remaining_accounts)?;
+        let selected = market
+            .select_node(entropy, &weights)
+            .ok_or(MarketError::NodeNotInQueue)?;
+        job.selected_node = Some(selected);
+        msg!("Nodes available, job offered to node {}", selected);
     } else {
         // Add job to queue
         market.queue_push(job.key())?;
@@ -100,3 +170,32 @@ pub fn handler(
 
     Ok(())
 }
+
+/// Selection weights of the queued candidates (reputation-weighted markets)
+/// Reads a (NodeReputation, Node) pair per candidate, in queue order, the
+/// Node being the registration the candidate listed with; empty otherwise
+fn candidate_weights<'info>(
+    market: &MarketAccount,
+    nodes_config: &NodesConfig,
+    candidates: &'info [AccountInfo<'info>],
+) -> Result<Vec<u64>> {
+    let mut weights = Vec::new();
+    if market.selection_mode != MarketAccount::SELECTION_REPUTATION_WEIGHTED {
+        return Ok(weights);
+    }
+
+    require!(
+        candidates.len() == 2 * market.selection_candidates(),
+        MarketError::InvalidSelectionCandidates
+    );
+    for (pair, queued) in candidates.chunks_exact(2).zip(market.queue.iter()) {
+        let reputation = Account::<NodeReputation>::try_from(&pair[0])?;
+        let node = Account::<Node>::try_from(&pair[1])?;
+        require!(
+            reputation.authority == *queued && reputation.node_account == node.key(),
+            MarketError::InvalidSelectionCandidates
+        );
+        weights.push(reputation.selection_weight(Some(nodes_config), &node.country));
+    }
+    Ok(weights)
+}
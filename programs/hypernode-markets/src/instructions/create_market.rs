@@ -1,15 +1,18 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Token, TokenAccount};
+use crate::errors::MarketError;
 use crate::state::*;
 
 /// Create a new GPU marketplace
 /// Authority can update market parameters later
+/// The account is sized for `max_queue_size` queue entries
 #[derive(Accounts)]
+#[instruction(job_price: u64, job_timeout: i64, node_xhyper_minimum: u128, max_queue_size: u16)]
 pub struct CreateMarket<'info> {
     #[account(
         init,
         payer = authority,
-        space = 8 + 32 + 8 + 8 + 16 + 1 + 1 + 8 + 8 + 32 + 4
+        space = MarketAccount::space(max_queue_size as usize)
     )]
     pub market: Account<'info, MarketAccount>,
 
@@ -33,7 +36,13 @@ pub fn handler(
     job_price: u64,
     job_timeout: i64,
     node_xhyper_minimum: u128,
+    max_queue_size: u16,
 ) -> Result<()> {
+    require!(
+        MarketAccount::is_valid_max_queue_size(max_queue_size),
+        MarketError::InvalidMaxQueueSize
+    );
+
     let market = &mut ctx.accounts.market;
 
     // Get vault bump from the PDA derivation
@@ -49,10 +58,24 @@ pub fn handler(
     market.total_jobs = 0;
     market.total_nodes = 0;
     market.vault = ctx.accounts.vault.key();
+    market.dispute_window = DEFAULT_DISPUTE_WINDOW;
+    market.market_fee_bps = 0;
+    market.selection_mode = MarketAccount::SELECTION_FIFO;
+    market.min_node_version = 0;
+    market.min_cpu_cores = 0;
+    market.min_ram_gb = 0;
+    market.min_gpu_cores = 0;
+    market.cancellation_fee_bps = 0;
+    market.tier_stake_bps = [0; REPUTATION_TIER_COUNT];
+    market.escrow_outstanding = 0;
+    market.response_ema_alpha_bps = DEFAULT_RESPONSE_EMA_ALPHA_BPS;
+    market.response_ema_half_life = DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE;
+    market.max_queue_size = max_queue_size;
     market.queue = Vec::new();
 
     msg!("Market created successfully");
     msg!("Job price: {} lamports | Timeout: {} sec | Min xHYPER: {}", job_price, job_timeout, node_xhyper_minimum);
+    msg!("Max queue size: {}", max_queue_size);
 
     Ok(())
 }
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Client disputes a completed job within the market's dispute window
+/// The held rewards fee stays in escrow until the market authority resolves it
+#[derive(Accounts)]
+pub struct DisputeJob<'info> {
+    #[account(
+        mut,
+        constraint = job.client == client.key() @ MarketError::Unauthorized
+    )]
+    pub job: Account<'info, JobAccount>,
+
+    /// Client who created the job
+    pub client: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DisputeJob>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    let clock = Clock::get()?;
+
+    job.dispute(clock.unix_timestamp)?;
+
+    msg!("Job disputed: {}", job.key());
+    msg!("Held rewards fee: {} lamports", job.held_rewards_fee);
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, spl_token::native_mint, SyncNative, Token, TokenAccount};
+use crate::state::*;
+use crate::errors::*;
+use hypernode_rewards::program::HypernodeRewards;
+use hypernode_rewards::{cpi::accounts::AddRewards, ReflectionAccount, RewardsConfig};
+
+/// Finalize a completed job once its dispute window has closed (permissionless)
+/// Releases the held rewards fee to the rewards pool: the lamports are
+/// wrapped into the vault's wSOL account and sent through add_rewards
+#[derive(Accounts)]
+pub struct FinalizeJob<'info> {
+    #[account(mut)]
+    pub job: Account<'info, JobAccount>,
+
+    #[account(
+        mut,
+        constraint = job.market == market.key() @ MarketError::MarketMismatch
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// Vault PDA holding the fee; signs the add_rewards transfer
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Wrapped SOL account owned by the vault, the fee's source in add_rewards
+    #[account(
+        mut,
+        constraint = fee_token_account.owner == vault.key() @ MarketError::InvalidFeeTokenAccount,
+        constraint = fee_token_account.mint == native_mint::ID @ MarketError::InvalidFeeTokenAccount
+    )]
+    pub fee_token_account: Account<'info, TokenAccount>,
+
+    /// Reflection account from Rewards Program (its pool must pay out in wrapped SOL)
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        seeds::program = rewards_program.key(),
+        constraint = reflection_account.reward_mint == native_mint::ID @ MarketError::RewardMintNotNative
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Rewards config from Rewards Program (staker/treasury split)
+    #[account(
+        seeds = [b"rewards_config"],
+        bump = rewards_config.bump,
+        seeds::program = rewards_program.key()
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    /// Rewards vault
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump,
+        seeds::program = rewards_program.key()
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+
+    /// Protocol treasury (checked against rewards_config by the Rewards Program)
+    #[account(mut)]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
+    pub rewards_program: Program<'info, HypernodeRewards>,
+
+    pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
+
+    /// Caller (anyone can finalize)
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FinalizeJob>) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    let clock = Clock::get()?;
+
+    let rewards_fee = job.release_rewards_fee(clock.unix_timestamp)?;
+    ctx.accounts.market.release_escrow(rewards_fee);
+
+    if rewards_fee > 0 {
+        // PDA signer seeds
+        let market_key = ctx.accounts.market.key();
+        let seeds = &[
+            b"vault",
+            market_key.as_ref(),
+            &[ctx.accounts.market.vault_bump],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        // Wrap the fee: move the lamports into the vault's wSOL account
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.fee_token_account.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            rewards_fee,
+        )?;
+        token::sync_native(CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            SyncNative {
+                account: ctx.accounts.fee_token_account.to_account_info(),
+            },
+        ))?;
+
+        // Hand it to the rewards pool (treasury share split off by the Rewards Program)
+        hypernode_rewards::cpi::add_rewards(
+            CpiContext::new_with_signer(
+                ctx.accounts.rewards_program.to_account_info(),
+                AddRewards {
+                    reflection_account: ctx.accounts.reflection_account.to_account_info(),
+                    rewards_config: ctx.accounts.rewards_config.to_account_info(),
+                    source_token_account: ctx.accounts.fee_token_account.to_account_info(),
+                    rewards_vault: ctx.accounts.rewards_vault.to_account_info(),
+                    treasury_token_account: ctx.accounts.treasury_token_account.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                    token_program: ctx.accounts.token_program.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            rewards_fee,
+        )?;
+    }
+
+    msg!("Job finalized: {}", job.key());
+    msg!("Rewards fee sent to the rewards pool: {} lamports", rewards_fee);
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{Token, TokenAccount};
 use crate::state::*;
 use crate::errors::*;
-use hypernode_rewards::program::HypernodeRewards;
-use hypernode_rewards::{ReflectionAccount, cpi::accounts::AddRewards};
+use hypernode_staking::program::HypernodeStaking;
+use hypernode_staking::StakeAccount;
+use hypernode_nodes::program::HypernodeNodes;
+use hypernode_nodes::state::{Node, MARKETS_STATS_AUTHORITY_SEED};
 
 /// Node finishes job and receives payment from escrow vault
 /// Result is stored as IPFS CID
@@ -28,6 +29,61 @@ pub struct FinishJob<'info> {
     #[account(mut)]
     pub node_account: SystemAccount<'info>,
 
+    /// Market authority (receives the market fee)
+    /// CHECK: only credited; may be a threshold config PDA (see withdraw_authority_fees)
+    #[account(
+        mut,
+        address = market.authority @ MarketError::Unauthorized
+    )]
+    pub market_authority: UncheckedAccount<'info>,
+
+    /// Client who created the job (receives withheld payout and SLA penalties)
+    #[account(
+        mut,
+        address = job.client @ MarketError::Unauthorized
+    )]
+    pub client: SystemAccount<'info>,
+
+    /// Node's stake account from Staking Program (re-checked at settlement)
+    /// May be omitted when the market has no stake minimum
+    #[account(
+        seeds = [b"stake", node.key().as_ref()],
+        bump,
+        seeds::program = staking_program.key()
+    )]
+    pub stake_account: Option<Account<'info, StakeAccount>>,
+
+    /// Staking program
+    pub staking_program: Program<'info, HypernodeStaking>,
+
+    /// Node's reputation
+    /// The completion (run time into the response EMA) and payout are recorded
+    /// and the job's slot is released; with auto_relist set the node is
+    /// queued again for its next job if its slots aren't all booked elsewhere
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Node's account from Nodes Program (the one it claimed the job with)
+    /// The completed job and payout are recorded on it via the update_stats CPI
+    #[account(
+        mut,
+        address = job.node_account @ MarketError::NodeAccountMismatch
+    )]
+    pub node_registration: Account<'info, Node>,
+
+    /// CHECK: PDA signing update_stats; the Nodes Program only accepts this program's
+    #[account(
+        seeds = [MARKETS_STATS_AUTHORITY_SEED],
+        bump
+    )]
+    pub stats_authority: UncheckedAccount<'info>,
+
+    pub nodes_program: Program<'info, HypernodeNodes>,
+
     /// Vault PDA for escrow payment
     #[account(
         mut,
@@ -37,19 +93,22 @@ pub struct FinishJob<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
-    /// Reflection account from Rewards Program (optional)
-    /// When provided, seeds should be [b"reflection"] with rewards_program as the program
-    #[account(mut)]
-    pub reflection_account: Option<Account<'info, ReflectionAccount>>,
-
-    /// Rewards vault (optional)
-    #[account(mut)]
-    pub rewards_vault: Option<Account<'info, TokenAccount>>,
+    /// CHECK: the node's reservation address in this market; empty when not reserved
+    /// An active reservation keeps the node out of the open queue (no auto-relist)
+    #[account(
+        seeds = [NodeReservation::SEED, market.key().as_ref(), node.key().as_ref()],
+        bump
+    )]
+    pub reservation: UncheckedAccount<'info>,
 
-    /// Rewards program (optional)
-    pub rewards_program: Option<Program<'info, HypernodeRewards>>,
+    /// Market statistics (outcome and revenue counters)
+    #[account(
+        mut,
+        seeds = [MarketStats::SEED, market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Account<'info, MarketStats>,
 
-    pub token_program: Option<Program<'info, Token>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -73,10 +132,10 @@ pub fn handler(
         MarketError::JobTimedOut
     );
 
-    // Update job state
+    // Record the result (the job moves to PendingReview once the fee is held below)
     job.ipfs_result = ipfs_result;
-    job.state = JobState::Completed;
     job.time_end = clock.unix_timestamp;
+    ctx.accounts.market_stats.record_completed(job.price, job.time_end - job.time_start)?;
 
     // Transfer payment from vault to node
     // PDA signer seeds
@@ -88,41 +147,111 @@ pub fn handler(
     ];
     let signer_seeds = &[&seeds[..]];
 
-    // Calculate rewards fee (1% of payment goes to rewards pool per whitepaper)
-    let rewards_fee = job.price / 100;
-    let node_payment = job.price - rewards_fee;
+    // Rewards fee (1% to the rewards pool per whitepaper) and the market's own fee
+    let (node_payment, rewards_fee, market_fee) = market.split_job_payment(job.price);
+
+    // Re-verify the node still meets its tier's stake minimum (it may have unstaked mid-job)
+    // Without a stake account the node only passes a zero minimum
+    let stake_account = ctx.accounts.stake_account.as_ref();
+    let stake_active = stake_account.is_some_and(|s| s.is_active());
+    let xhyper = stake_account.map_or(0, |s| s.xhyper);
+    let meets_minimum =
+        market.meets_stake_minimum(Some(ctx.accounts.reputation.tier), stake_active, xhyper);
+    let (node_payment, withheld) = market.apply_stake_policy(node_payment, meets_minimum);
+
+    // SLA penalty for finishing past the job's soft deadline, credited to the client
+    let sla_penalty = job.sla_penalty(node_payment, clock.unix_timestamp);
+    let node_payment = node_payment - sla_penalty;
 
     // Transfer payment to node
     **vault.to_account_info().try_borrow_mut_lamports()? -= node_payment;
     **node_account.to_account_info().try_borrow_mut_lamports()? += node_payment;
 
-    // Add rewards to pool if Rewards Program is provided
-    if let (Some(_reflection), Some(_rewards_vault), Some(rewards_program)) = (
-        &ctx.accounts.reflection_account,
-        &ctx.accounts.rewards_vault,
-        &ctx.accounts.rewards_program,
-    ) {
-        // For now, just log that rewards would be distributed
-        // Full CPI implementation would require token transfers
-        msg!("Rewards fee collected: {} lamports", rewards_fee);
-        msg!("Note: Full token-based rewards integration pending");
-
-        // TODO: Implement full CPI to add_rewards when using SPL tokens
-        // let cpi_accounts = AddRewards {
-        //     reflection_account: ctx.accounts.reflection_account.as_ref().unwrap().to_account_info(),
-        //     source_token_account: vault_token_account,
-        //     rewards_vault: ctx.accounts.rewards_vault.as_ref().unwrap().to_account_info(),
-        //     authority: market_authority,
-        //     token_program: ctx.accounts.token_program.as_ref().unwrap().to_account_info(),
-        // };
-        // let cpi_ctx = CpiContext::new(rewards_program.to_account_info(), cpi_accounts);
-        // hypernode_rewards::cpi::add_rewards(cpi_ctx, rewards_fee)?;
+    // Pay the market fee to the market authority
+    if market_fee > 0 {
+        **vault.to_account_info().try_borrow_mut_lamports()? -= market_fee;
+        **ctx.accounts.market_authority.to_account_info().try_borrow_mut_lamports()? += market_fee;
+    }
+
+    // Refund the withheld share to the client
+    if withheld > 0 {
+        **vault.to_account_info().try_borrow_mut_lamports()? -= withheld;
+        **ctx.accounts.client.to_account_info().try_borrow_mut_lamports()? += withheld;
+        msg!("Node under minimum stake, withheld: {} lamports", withheld);
+    }
+
+    if sla_penalty > 0 {
+        **vault.to_account_info().try_borrow_mut_lamports()? -= sla_penalty;
+        **ctx.accounts.client.to_account_info().try_borrow_mut_lamports()? += sla_penalty;
+        msg!("Finished past SLA deadline, penalty: {} lamports", sla_penalty);
+    }
+
+    // Record the completion and payout on the node's reputation
+    let reputation = &mut ctx.accounts.reputation;
+    let execution_secs = (job.time_end - job.time_start).max(0) as u64;
+    reputation.record_success(
+        execution_secs.saturating_mul(1000),
+        clock.unix_timestamp,
+        market.response_ema(),
+    );
+    reputation.record_earnings(node_payment, clock.unix_timestamp)?;
+    reputation.release_slot();
+
+    // Report the completion to the Nodes Program
+    let stats_seeds: &[&[u8]] = &[MARKETS_STATS_AUTHORITY_SEED, &[ctx.bumps.stats_authority]];
+    let stats_signer = &[stats_seeds];
+    let cpi_ctx = CpiContext::new_with_signer(
+        ctx.accounts.nodes_program.to_account_info(),
+        hypernode_nodes::cpi::accounts::UpdateStats {
+            node: ctx.accounts.node_registration.to_account_info(),
+            markets_authority: ctx.accounts.stats_authority.to_account_info(),
+        },
+        stats_signer,
+    );
+    hypernode_nodes::cpi::update_stats(cpi_ctx, true, node_payment)?;
+
+    // Hold the rewards fee in escrow until the dispute window closes
+    // (finalize_job releases it to the rewards pool, a reversed dispute refunds it)
+    job.hold_rewards_fee(rewards_fee, clock.unix_timestamp.saturating_add(market.dispute_window));
+    ctx.accounts.market.release_escrow(job.price - rewards_fee);
+
+    // Auto-relist: queue the node for its next job, under the same checks as list_node
+    let auto_relist = ctx.accounts.reputation.auto_relist;
+    let node_registration = &ctx.accounts.node_registration;
+    let node_version = Some(node_registration.version);
+    let node_hardware = Some(NodeHardware::of(node_registration));
+    let node_flagged = node_registration.is_flagged;
+    let max_slots = node_registration.max_slots;
+    let node_reserved = NodeReservation::load(&ctx.accounts.reservation)?
+        .is_some_and(|r| r.is_active(clock.unix_timestamp));
+    if auto_relist && node_reserved {
+        msg!("Node not relisted: reserved in this market");
+    } else if auto_relist {
+        let reputation = &mut ctx.accounts.reputation;
+        let market = &mut ctx.accounts.market;
+        // Stake minimum for the tier the completion just left the node at
+        // (no stake needed in a market without one)
+        if market.meets_stake_minimum(Some(reputation.tier), stake_active, xhyper)
+            && !node_flagged
+            && market.admits_node_version(node_version)
+            && market.admits_node_hardware(node_hardware)
+        {
+            if !reputation.has_free_capacity(max_slots) {
+                msg!("Node not relisted: slots booked in other markets");
+            } else if market.relist_node(node.key())? {
+                reputation.reserve_listing(max_slots)?;
+                msg!("Node relisted, queue length: {}", market.queue_len());
+            }
+        } else {
+            msg!("Node not relisted: fails the market's listing checks");
+        }
     }
 
     msg!("Job finished: {}", job.key());
     msg!("Node: {}", node.key());
     msg!("Node payment: {} lamports", node_payment);
-    msg!("Rewards fee: {} lamports", rewards_fee);
+    msg!("Market fee: {} lamports", market_fee);
+    msg!("Rewards fee held until {}: {} lamports", job.fee_release_at, rewards_fee);
     msg!("Duration: {} seconds", job.time_end - job.time_start);
     msg!("Result IPFS: {:?}", ipfs_result);
 
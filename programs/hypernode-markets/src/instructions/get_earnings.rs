@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Read a node's earnings (view)
+/// Returns lifetime earnings and earnings in the current 30-day window
+#[derive(Accounts)]
+pub struct GetEarnings<'info> {
+    #[account(
+        seeds = [b"reputation", reputation.authority.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+}
+
+pub fn handler(ctx: Context<GetEarnings>) -> Result<NodeEarnings> {
+    let clock = Clock::get()?;
+    let earnings = ctx.accounts.reputation.earnings(clock.unix_timestamp);
+
+    msg!("Node: {}", ctx.accounts.reputation.authority);
+    msg!("Lifetime earnings: {} lamports", earnings.lifetime);
+    msg!("Recent earnings: {} lamports", earnings.recent);
+
+    Ok(earnings)
+}
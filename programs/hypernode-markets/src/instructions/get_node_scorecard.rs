@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use hypernode_nodes::state::Node;
+use crate::state::*;
+use crate::errors::*;
+
+/// Read a node's full scorecard (view)
+/// Combines its reputation and earnings with the uptime, liveness and flags
+/// of its Nodes Program registration, so clients need a single call
+#[derive(Accounts)]
+pub struct GetNodeScorecard<'info> {
+    #[account(
+        seeds = [b"reputation", reputation.authority.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Node's registration in the Nodes Program
+    #[account(
+        constraint = node_account.authority == reputation.authority @ MarketError::Unauthorized
+    )]
+    pub node_account: Account<'info, Node>,
+}
+
+pub fn handler(ctx: Context<GetNodeScorecard>) -> Result<NodeScorecard> {
+    let clock = Clock::get()?;
+    let scorecard = ctx
+        .accounts
+        .reputation
+        .scorecard(&ctx.accounts.node_account, clock.unix_timestamp);
+
+    msg!("Node: {}", scorecard.authority);
+    msg!("Reputation: {} (tier {})", scorecard.reputation_score, scorecard.tier);
+    msg!("Completion rate: {} bps", scorecard.completion_rate);
+    msg!("Liveness: {}", scorecard.liveness_score);
+
+    Ok(scorecard)
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Create the statistics account of a market
+/// Permissionless; must exist before the market's jobs can settle
+#[derive(Accounts)]
+pub struct InitializeMarketStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = MarketStats::LEN,
+        seeds = [MarketStats::SEED, market.key().as_ref()],
+        bump
+    )]
+    pub market_stats: Account<'info, MarketStats>,
+
+    pub market: Account<'info, MarketAccount>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeMarketStats>) -> Result<()> {
+    let market_stats = &mut ctx.accounts.market_stats;
+
+    market_stats.market = ctx.accounts.market.key();
+    market_stats.completed_jobs = 0;
+    market_stats.failed_jobs = 0;
+    market_stats.timed_out_jobs = 0;
+    market_stats.cancelled_jobs = 0;
+    market_stats.total_volume = 0;
+    market_stats.total_job_duration = 0;
+    market_stats.bump = ctx.bumps.market_stats;
+
+    msg!("Market stats initialized for {}", market_stats.market);
+
+    Ok(())
+}
@@ -3,11 +3,16 @@ use crate::state::*;
 use crate::errors::*;
 use hypernode_staking::program::HypernodeStaking;
 use hypernode_staking::StakeAccount;
+use hypernode_nodes::state::{Blacklist, Node};
 
 /// Node registers itself in the queue when no jobs are available
 /// This completes the dual queue matching system:
 /// - If jobs are queued: nodes call work_job to claim them
 /// - If no jobs: nodes call list_node to wait for jobs
+///
+/// A node can wait in several markets at once, but only as long as its jobs
+/// and listings across all of them fit its slots (Node::max_slots), so two
+/// markets can never match it to more jobs than it can run
 #[derive(Accounts)]
 pub struct ListNode<'info> {
     #[account(mut)]
@@ -27,6 +32,43 @@ pub struct ListNode<'info> {
     /// Staking program
     pub staking_program: Program<'info, HypernodeStaking>,
 
+    /// Node's registration in the Nodes Program
+    /// Carries its flag, version, hardware and slot count; the node's
+    /// listings and jobs are bound to it (NodeReputation::node_account)
+    #[account(
+        seeds = [b"node", node_account.node_id.as_bytes()],
+        bump = node_account.bump,
+        seeds::program = hypernode_nodes::ID,
+        constraint = node_account.authority == node.key() @ MarketError::Unauthorized
+    )]
+    pub node_account: Account<'info, Node>,
+
+    /// Node's reputation
+    /// Counts the node's listings across markets; its tier scales the stake
+    /// minimum when the market sets tier_stake_bps
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Global node blacklist (Nodes Program); banned authorities can't join the queue
+    #[account(
+        seeds = [b"blacklist"],
+        bump = blacklist.bump,
+        seeds::program = hypernode_nodes::ID,
+        constraint = !blacklist.is_banned(&node.key()) @ MarketError::NodeBlacklisted
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    /// CHECK: the node's reservation address in this market; empty when not reserved
+    #[account(
+        seeds = [NodeReservation::SEED, market.key().as_ref(), node.key().as_ref()],
+        bump
+    )]
+    pub reservation: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
@@ -34,6 +76,10 @@ pub fn handler(ctx: Context<ListNode>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let node = &ctx.accounts.node;
     let stake_account = &ctx.accounts.stake_account;
+    let node_account = &ctx.accounts.node_account;
+
+    // Flagged nodes can't wait for jobs
+    require!(!node_account.is_flagged, MarketError::NodeNotEligible);
 
     // Verify node has sufficient xHYPER stake
     require!(
@@ -41,13 +87,40 @@ pub fn handler(ctx: Context<ListNode>) -> Result<()> {
         MarketError::InvalidNodeStake
     );
 
+    // Stake minimum scaled by the node's reputation tier
+    let required_xhyper = market.effective_stake_minimum(Some(ctx.accounts.reputation.tier));
     require!(
-        stake_account.xhyper >= market.node_xhyper_minimum,
+        stake_account.xhyper >= required_xhyper,
         MarketError::InvalidNodeStake
     );
 
+    // Outdated node software can't wait for jobs it may not be able to run
+    require!(
+        market.admits_node_version(Some(node_account.version)),
+        MarketError::NodeVersionTooOld
+    );
+
+    // Nodes below the market's hardware floor can't wait for its jobs
+    require!(
+        market.admits_node_hardware(Some(NodeHardware::of(node_account))),
+        MarketError::NodeHardwareBelowMinimum
+    );
+
+    // Reserved nodes stay off the open market until the window ends
+    let now = Clock::get()?.unix_timestamp;
+    require!(
+        NodeReservation::load(&ctx.accounts.reservation)?.is_none_or(|r| !r.is_active(now)),
+        MarketError::NodeReserved
+    );
+
     msg!("Node xHYPER: {}", stake_account.xhyper);
-    msg!("Required: {}", market.node_xhyper_minimum);
+    msg!("Required: {}", required_xhyper);
+
+    // Listings in other markets and running jobs must leave a slot free,
+    // all of them made with this registration
+    let max_slots = node_account.max_slots;
+    ctx.accounts.reputation.bind_node_account(node_account.key())?;
+    ctx.accounts.reputation.reserve_listing(max_slots)?;
 
     // Dual Queue Logic
     match market.queue_type {
@@ -74,6 +147,7 @@ pub fn handler(ctx: Context<ListNode>) -> Result<()> {
     }
 
     msg!("Queue length: {}", market.queue_len());
+    msg!("Active listings: {}", ctx.accounts.reputation.active_listings);
 
     Ok(())
 }
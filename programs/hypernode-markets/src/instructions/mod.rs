@@ -3,15 +3,71 @@ pub mod create_job;
 pub mod work_job;
 pub mod finish_job;
 pub mod list_node;
+pub mod unlist_node;
 pub mod timeout_job;
 pub mod cancel_job;
 pub mod update_reputation;
+pub mod prune_queue;
+pub mod get_earnings;
+pub mod get_node_scorecard;
+pub mod verify_queue_invariant;
+pub mod repair_queue;
+pub mod purge_dead_queue_entry;
+pub mod dispute_job;
+pub mod resolve_dispute;
+pub mod finalize_job;
+pub mod rate_job;
+pub mod set_dispute_window;
+pub mod set_market_fee;
+pub mod set_selection_mode;
+pub mod set_min_node_version;
+pub mod set_auto_relist;
+pub mod set_hardware_floor;
+pub mod set_cancellation_fee;
+pub mod update_authorities;
+pub mod withdraw_authority_fees;
+pub mod set_tier_stake_bps;
+pub mod mutual_cancel;
+pub mod reserve_node;
+pub mod release_reservation;
+pub mod initialize_market_stats;
+pub mod sweep_market_dust;
+pub mod sync_audit_bonus;
+pub mod set_response_ema;
 
 pub use create_market::*;
 pub use create_job::*;
 pub use work_job::*;
 pub use finish_job::*;
 pub use list_node::*;
+pub use unlist_node::*;
 pub use timeout_job::*;
 pub use cancel_job::*;
 pub use update_reputation::*;
+pub use prune_queue::*;
+pub use get_earnings::*;
+pub use get_node_scorecard::*;
+pub use verify_queue_invariant::*;
+pub use repair_queue::*;
+pub use purge_dead_queue_entry::*;
+pub use dispute_job::*;
+pub use resolve_dispute::*;
+pub use finalize_job::*;
+pub use rate_job::*;
+pub use set_dispute_window::*;
+pub use set_market_fee::*;
+pub use set_selection_mode::*;
+pub use set_min_node_version::*;
+pub use set_auto_relist::*;
+pub use set_hardware_floor::*;
+pub use set_cancellation_fee::*;
+pub use update_authorities::*;
+pub use withdraw_authority_fees::*;
+pub use set_tier_stake_bps::*;
+pub use mutual_cancel::*;
+pub use reserve_node::*;
+pub use release_reservation::*;
+pub use initialize_market_stats::*;
+pub use sweep_market_dust::*;
+pub use sync_audit_bonus::*;
+pub use set_response_ema::*;
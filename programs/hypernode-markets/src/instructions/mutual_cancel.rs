@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Abort a running job by agreement of client and node
+/// Both must sign; the escrow is split by node_share_bps and no failure
+/// is recorded against the node
+#[derive(Accounts)]
+pub struct MutualCancel<'info> {
+    #[account(mut)]
+    pub job: Account<'info, JobAccount>,
+
+    #[account(
+        mut,
+        constraint = job.market == market.key() @ MarketError::MarketMismatch
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// Client who created the job (receives the refund)
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// Node assigned to the job (receives its agreed share)
+    #[account(mut)]
+    pub node: Signer<'info>,
+
+    /// Node's reputation
+    /// The job's slot is released on it
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Vault PDA for escrow payment
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump,
+        constraint = *vault.owner == system_program.key() @ MarketError::InvalidVaultOwner
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Market statistics (outcome and revenue counters)
+    #[account(
+        mut,
+        seeds = [MarketStats::SEED, market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Account<'info, MarketStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<MutualCancel>, node_share_bps: u16) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    let client = &ctx.accounts.client;
+    let node = &ctx.accounts.node;
+    let vault = &ctx.accounts.vault;
+    let clock = Clock::get()?;
+
+    // Checks both signers are the job's parties and moves it to Stopped
+    let (node_share, refund) = job.mutual_cancel(
+        &client.key(),
+        &node.key(),
+        node_share_bps,
+        clock.unix_timestamp,
+    )?;
+    ctx.accounts.market_stats.record_cancelled()?;
+    ctx.accounts.market.release_escrow(node_share + refund);
+
+    // Free the node's slot
+    ctx.accounts.reputation.release_slot();
+
+    // Transfer lamports from vault (PDA) to node and client
+    **vault.to_account_info().try_borrow_mut_lamports()? -= node_share + refund;
+    **node.to_account_info().try_borrow_mut_lamports()? += node_share;
+    **client.to_account_info().try_borrow_mut_lamports()? += refund;
+
+    msg!("Job mutually cancelled: {}", job.key());
+    msg!("Node: {} received {} lamports", node.key(), node_share);
+    msg!("Client: {} refunded {} lamports", client.key(), refund);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use hypernode_nodes::state::Node;
+
+/// Maximum node accounts read per call
+/// Each one is deserialized, so this bounds the compute cost
+pub const MAX_PRUNE_BATCH: usize = 16;
+
+/// Evict flagged nodes from the market queue (permissionless)
+/// Candidate Node accounts are passed as remaining accounts together with
+/// their NodeReputation accounts (writable); a node is only removed when the
+/// registration its listings are bound to is owned by the Nodes Program and
+/// has is_flagged set. The evicted listings are released on the reputation
+#[derive(Accounts)]
+pub struct PruneQueue<'info> {
+    #[account(mut)]
+    pub market: Account<'info, MarketAccount>,
+
+    /// Anyone can prune (permissionless)
+    pub caller: Signer<'info>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, PruneQueue<'info>>) -> Result<()> {
+    require!(
+        ctx.remaining_accounts.len() <= MAX_PRUNE_BATCH,
+        MarketError::PruneBatchTooLarge
+    );
+
+    // Account::try_from checks the owner (Nodes Program or ours) and discriminator
+    let mut flagged = Vec::with_capacity(ctx.remaining_accounts.len());
+    let mut reputations = Vec::new();
+    for account_info in ctx.remaining_accounts.iter() {
+        if account_info.owner == &crate::ID {
+            if !reputations.iter().any(|r: &Account<NodeReputation>| r.key() == account_info.key()) {
+                reputations.push(Account::<NodeReputation>::try_from(account_info)?);
+            }
+            continue;
+        }
+        let node = Account::<Node>::try_from(account_info)?;
+        if node.is_flagged {
+            flagged.push((node.key(), node.authority));
+        }
+    }
+
+    // Only the registration a node listed with counts, not any other it holds
+    let flagged: Vec<Pubkey> = flagged
+        .into_iter()
+        .filter(|(node_account, authority)| {
+            reputations
+                .iter()
+                .any(|r| r.authority == *authority && r.node_account == *node_account)
+        })
+        .map(|(_, authority)| authority)
+        .collect();
+
+    let market = &mut ctx.accounts.market;
+
+    // Each queue entry of an evicted node was one of its listings
+    if market.queue_type == MarketAccount::QUEUE_TYPE_NODES {
+        for reputation in reputations.iter_mut() {
+            if !flagged.contains(&reputation.authority) {
+                continue;
+            }
+            let entries = market.queue.iter().filter(|key| **key == reputation.authority).count();
+            for _ in 0..entries {
+                reputation.release_listing();
+            }
+            reputation.exit(&crate::ID)?;
+        }
+    }
+
+    let evicted = market.evict_flagged_nodes(&flagged);
+
+    msg!("Evicted {} flagged node(s) from queue", evicted);
+    msg!("Queue length: {}", market.queue_len());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Remove a dangling job reference from the market queue (permissionless)
+/// The referenced account must be closed, no longer a valid JobAccount,
+/// or a job in a terminal state; queue_type is reset if the queue drains
+#[derive(Accounts)]
+#[instruction(entry: Pubkey)]
+pub struct PurgeDeadQueueEntry<'info> {
+    #[account(mut)]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: The account referenced by the queue entry; it may be closed
+    /// or corrupted, so it is only deserialized in the handler
+    #[account(constraint = job.key() == entry @ MarketError::JobNotInQueue)]
+    pub job: UncheckedAccount<'info>,
+
+    /// Anyone can purge (permissionless)
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<PurgeDeadQueueEntry>, entry: Pubkey) -> Result<()> {
+    // Closed accounts, foreign owners and bad data all count as dead
+    let job_info = ctx.accounts.job.to_account_info();
+    let job = if job_info.owner == &crate::ID {
+        let data = job_info.try_borrow_data()?;
+        JobAccount::try_deserialize(&mut &data[..]).ok()
+    } else {
+        None
+    };
+
+    let market_key = ctx.accounts.market.key();
+    let market = &mut ctx.accounts.market;
+    market.purge_dead_job_entry(entry, job.as_ref(), market_key)?;
+
+    msg!("Purged dead queue entry {}", entry);
+    msg!("Queue length: {}", market.queue_len());
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Client rates the node that ran a finished job
+/// The rating feeds the client satisfaction component of the node's reputation
+#[derive(Accounts)]
+pub struct RateJob<'info> {
+    #[account(
+        mut,
+        constraint = job.client == client.key() @ MarketError::Unauthorized
+    )]
+    pub job: Account<'info, JobAccount>,
+
+    /// Client who created the job
+    pub client: Signer<'info>,
+
+    /// Reputation of the node that ran the job
+    #[account(
+        mut,
+        seeds = [b"reputation", reputation.authority.as_ref()],
+        bump = reputation.bump,
+        constraint = job.node == Some(reputation.authority) @ MarketError::Unauthorized
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+}
+
+pub fn handler(ctx: Context<RateJob>, rating: u8) -> Result<()> {
+    let job = &mut ctx.accounts.job;
+    let reputation = &mut ctx.accounts.reputation;
+
+    let node = job.rate(rating)?;
+    reputation.record_rating(rating);
+
+    msg!("Job {} rated {} by client", job.key(), rating);
+    msg!("Node {} reputation: {} (tier {})", node, reputation.reputation_score, reputation.tier);
+
+    Ok(())
+}
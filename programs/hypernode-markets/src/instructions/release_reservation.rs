@@ -0,0 +1,58 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// End a node reservation and release its escrow
+/// Called by the client or the node. The node is paid for the elapsed share
+/// of the window and the rest is refunded to the client, who also gets the
+/// account rent back
+#[derive(Accounts)]
+pub struct ReleaseReservation<'info> {
+    #[account(
+        mut,
+        seeds = [NodeReservation::SEED, reservation.market.as_ref(), reservation.node.as_ref()],
+        bump = reservation.bump,
+        close = client
+    )]
+    pub reservation: Account<'info, NodeReservation>,
+
+    /// Client holding the reservation (receives the refund)
+    #[account(
+        mut,
+        address = reservation.client @ MarketError::Unauthorized
+    )]
+    pub client: SystemAccount<'info>,
+
+    /// Reserved node (receives its payout)
+    #[account(
+        mut,
+        address = reservation.node @ MarketError::Unauthorized
+    )]
+    pub node: SystemAccount<'info>,
+
+    /// Client or node
+    #[account(
+        constraint = caller.key() == reservation.client
+            || caller.key() == reservation.node @ MarketError::Unauthorized
+    )]
+    pub caller: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReleaseReservation>) -> Result<()> {
+    let reservation = &ctx.accounts.reservation;
+    let clock = Clock::get()?;
+
+    let (node_payout, refund) = reservation.settle(clock.unix_timestamp);
+
+    // Pay the node its share; the refund and rent go to the client on close
+    if node_payout > 0 {
+        **reservation.to_account_info().try_borrow_mut_lamports()? -= node_payout;
+        **ctx.accounts.node.to_account_info().try_borrow_mut_lamports()? += node_payout;
+    }
+
+    msg!("Reservation released: {}", reservation.key());
+    msg!("Node payout: {} lamports", node_payout);
+    msg!("Client refund: {} lamports", refund);
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Repair an inconsistent queue_type (market authority only)
+/// The queue contents are kept; only queue_type is corrected
+#[derive(Accounts)]
+pub struct RepairQueue<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<RepairQueue>, queue_type: u8) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+
+    // Only allowed while the invariant is actually broken
+    require!(
+        !market.queue_invariant_holds(),
+        MarketError::InvalidQueueType
+    );
+
+    let old_queue_type = market.queue_type;
+    market.repair_queue_type(queue_type)?;
+
+    msg!("Queue type repaired: {} -> {}", old_queue_type, market.queue_type);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::*;
+
+/// Client pre-pays to reserve a node's capacity for a time window
+/// Both client and node sign the terms. The escrow is held in the
+/// reservation account; the node leaves the open node queue and, while the
+/// window is open, only takes the client's jobs
+#[derive(Accounts)]
+pub struct ReserveNode<'info> {
+    #[account(mut)]
+    pub market: Account<'info, MarketAccount>,
+
+    #[account(
+        init,
+        payer = client,
+        space = NodeReservation::LEN,
+        seeds = [NodeReservation::SEED, market.key().as_ref(), node.key().as_ref()],
+        bump
+    )]
+    pub reservation: Account<'info, NodeReservation>,
+
+    /// Client reserving the node (pays the escrow)
+    #[account(mut)]
+    pub client: Signer<'info>,
+
+    /// Node being reserved
+    pub node: Signer<'info>,
+
+    /// Node's reputation
+    /// The listings the node gives up by leaving the open queue are released
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<ReserveNode>,
+    escrow: u64,
+    starts_at: i64,
+    duration: i64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    require!(
+        NodeReservation::is_valid_window(starts_at, duration, clock.unix_timestamp),
+        MarketError::InvalidReservation
+    );
+
+    // Escrow the reservation payment in the reservation account
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.client.to_account_info(),
+                to: ctx.accounts.reservation.to_account_info(),
+            },
+        ),
+        escrow,
+    )?;
+
+    let reservation = &mut ctx.accounts.reservation;
+    reservation.market = ctx.accounts.market.key();
+    reservation.client = ctx.accounts.client.key();
+    reservation.node = ctx.accounts.node.key();
+    reservation.escrow = escrow;
+    reservation.starts_at = starts_at;
+    reservation.ends_at = starts_at + duration;
+    reservation.bump = ctx.bumps.reservation;
+
+    // Reserved capacity isn't offered to open-market jobs
+    let market = &mut ctx.accounts.market;
+    let unlisted = market.unlist_node(reservation.node);
+    if unlisted > 0 {
+        for _ in 0..unlisted {
+            ctx.accounts.reputation.release_listing();
+        }
+        msg!("Node removed from the open queue, queue length: {}", market.queue_len());
+    }
+
+    msg!("Node reserved: {}", reservation.node);
+    msg!("Client: {}", reservation.client);
+    msg!("Window: {} to {}", reservation.starts_at, reservation.ends_at);
+    msg!("Escrow: {} lamports", escrow);
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Resolve a disputed job (market authority only)
+/// Reversed: the held rewards fee is refunded to the client
+/// Upheld: the fee is released by finalize_job once the window closes
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub job: Account<'info, JobAccount>,
+
+    #[account(
+        mut,
+        constraint = job.market == market.key() @ MarketError::MarketMismatch,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// Client account to receive the refunded fee
+    #[account(
+        mut,
+        address = job.client @ MarketError::Unauthorized
+    )]
+    pub client: SystemAccount<'info>,
+
+    /// Vault PDA holding the fee
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Market statistics (outcome and revenue counters)
+    #[account(
+        mut,
+        seeds = [MarketStats::SEED, market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Account<'info, MarketStats>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<ResolveDispute>, reverse: bool) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let job = &mut ctx.accounts.job;
+
+    let refund = job.resolve_dispute(reverse)?;
+    if reverse {
+        ctx.accounts.market_stats.record_failed()?;
+    }
+
+    if refund > 0 {
+        ctx.accounts.market.release_escrow(refund);
+        **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? -= refund;
+        **ctx.accounts.client.to_account_info().try_borrow_mut_lamports()? += refund;
+    }
+
+    msg!("Dispute resolved for job {}: {}", job.key(), if reverse { "reversed" } else { "upheld" });
+    msg!("Fee refunded to client: {} lamports", refund);
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Opt in or out of auto-relisting (node only)
+/// When set, finish_job puts the node back in the market queue
+#[derive(Accounts)]
+pub struct SetAutoRelist<'info> {
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Node owning the reputation account
+    pub node: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetAutoRelist>, auto_relist: bool) -> Result<()> {
+    let reputation = &mut ctx.accounts.reputation;
+    reputation.auto_relist = auto_relist;
+
+    msg!("Node {} auto-relist: {}", ctx.accounts.node.key(), auto_relist);
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the fee kept from the escrow when a client cancels a job (market authority only)
+/// Applies to jobs cancelled after the change
+#[derive(Accounts)]
+pub struct SetCancellationFee<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetCancellationFee>, cancellation_fee_bps: u16) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        MarketAccount::is_valid_cancellation_fee(cancellation_fee_bps),
+        MarketError::InvalidCancellationFee
+    );
+
+    let market = &mut ctx.accounts.market;
+    market.cancellation_fee_bps = cancellation_fee_bps;
+
+    msg!("Cancellation fee set to {} bps", cancellation_fee_bps);
+
+    Ok(())
+}
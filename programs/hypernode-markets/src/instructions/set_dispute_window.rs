@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set how long completed jobs can be disputed (market authority only)
+/// Applies to jobs finished after the change
+#[derive(Accounts)]
+pub struct SetDisputeWindow<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetDisputeWindow>, dispute_window: i64) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(dispute_window >= 0, MarketError::InvalidDisputeWindow);
+
+    let market = &mut ctx.accounts.market;
+    market.dispute_window = dispute_window;
+
+    msg!("Dispute window set to {} seconds", dispute_window);
+
+    Ok(())
+}
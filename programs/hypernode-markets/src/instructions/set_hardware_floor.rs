@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the minimum node hardware for the market (market authority only)
+/// Nodes already queued are re-checked when they take a job
+#[derive(Accounts)]
+pub struct SetHardwareFloor<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(
+    ctx: Context<SetHardwareFloor>,
+    min_cpu_cores: u16,
+    min_ram_gb: u16,
+    min_gpu_cores: u16,
+) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.min_cpu_cores = min_cpu_cores;
+    market.min_ram_gb = min_ram_gb;
+    market.min_gpu_cores = min_gpu_cores;
+
+    msg!(
+        "Hardware floor set: {} CPU cores, {} GB RAM, {} GPU cores",
+        min_cpu_cores,
+        min_ram_gb,
+        min_gpu_cores
+    );
+
+    Ok(())
+}
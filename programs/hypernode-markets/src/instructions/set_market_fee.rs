@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the market fee taken from each job payment (market authority only)
+/// Applies to jobs finished after the change
+#[derive(Accounts)]
+pub struct SetMarketFee<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetMarketFee>, market_fee_bps: u16) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        MarketAccount::is_valid_market_fee(market_fee_bps),
+        MarketError::InvalidMarketFee
+    );
+
+    let market = &mut ctx.accounts.market;
+    market.market_fee_bps = market_fee_bps;
+
+    msg!("Market fee set to {} bps", market_fee_bps);
+
+    Ok(())
+}
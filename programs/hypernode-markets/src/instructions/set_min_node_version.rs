@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the oldest node software version allowed in the market (market authority only)
+/// Nodes already queued are re-checked when they take a job
+#[derive(Accounts)]
+pub struct SetMinNodeVersion<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetMinNodeVersion>, min_node_version: u32) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let market = &mut ctx.accounts.market;
+    market.min_node_version = min_node_version;
+
+    msg!("Minimum node version set to {}", min_node_version);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Tune the response time EMA finish_job applies to node reputations
+/// (market authority only). Applies to jobs finished after the change
+#[derive(Accounts)]
+pub struct SetResponseEma<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetResponseEma>, alpha_bps: u16, decay_half_life: i64) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let response_ema = ResponseEma { alpha_bps, decay_half_life };
+    require!(response_ema.is_valid(), MarketError::InvalidResponseEma);
+
+    let market = &mut ctx.accounts.market;
+    market.response_ema_alpha_bps = alpha_bps;
+    market.response_ema_half_life = decay_half_life;
+
+    msg!("Response EMA alpha: {} bps, decay half-life: {} seconds", alpha_bps, decay_half_life);
+
+    Ok(())
+}
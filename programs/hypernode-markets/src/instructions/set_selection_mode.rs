@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set how jobs pick among queued nodes (market authority only)
+/// Non-FIFO modes need the SlotHashes sysvar passed to create_job
+#[derive(Accounts)]
+pub struct SetSelectionMode<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetSelectionMode>, selection_mode: u8) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        MarketAccount::is_valid_selection_mode(selection_mode),
+        MarketError::InvalidSelectionMode
+    );
+
+    let market = &mut ctx.accounts.market;
+    market.selection_mode = selection_mode;
+
+    msg!("Node selection mode set to {}", selection_mode);
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the stake minimum multiplier of each reputation tier (market authority only)
+/// Applies to nodes listing after the change
+#[derive(Accounts)]
+pub struct SetTierStakeBps<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SetTierStakeBps>, tier_stake_bps: [u16; REPUTATION_TIER_COUNT]) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        MarketAccount::is_valid_tier_stake_bps(&tier_stake_bps),
+        MarketError::InvalidTierStakeBps
+    );
+
+    let market = &mut ctx.accounts.market;
+    market.tier_stake_bps = tier_stake_bps;
+
+    msg!("Tier stake multipliers set to {:?} bps", tier_stake_bps);
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::*;
+
+/// Sweep leftover vault balance of a fully settled market (market authority only)
+/// Only once escrow_outstanding is zero, i.e. no job is queued, running or
+/// holding a rewards fee. What remains is rounding dust (finalize_job forwards
+/// released fees to the rewards pool); the vault keeps its rent-exempt minimum
+#[derive(Accounts)]
+pub struct SweepMarketDust<'info> {
+    #[account(has_one = authority @ MarketError::Unauthorized)]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+
+    /// Vault PDA holding the dust
+    #[account(
+        mut,
+        seeds = [b"vault", market.key().as_ref()],
+        bump = market.vault_bump
+    )]
+    pub vault: SystemAccount<'info>,
+
+    /// Account receiving the dust
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SweepMarketDust>) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let market = &ctx.accounts.market;
+    let vault = &ctx.accounts.vault;
+
+    // Reconcile against tracked escrow: nothing may be owed to clients, nodes or the rewards pool
+    let rent_exempt = Rent::get()?.minimum_balance(0);
+    let dust = market.sweepable_dust(vault.lamports(), rent_exempt)?;
+    require!(dust > 0, MarketError::NoDustToSweep);
+
+    // PDA signer seeds
+    let market_key = market.key();
+    let seeds = &[
+        b"vault",
+        market_key.as_ref(),
+        &[market.vault_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: vault.to_account_info(),
+                to: ctx.accounts.recipient.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        dust,
+    )?;
+
+    msg!("Swept {} lamports of dust to {}", dust, ctx.accounts.recipient.key());
+
+    Ok(())
+}
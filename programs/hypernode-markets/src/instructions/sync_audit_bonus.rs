@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+use hypernode_nodes::state::{Node, NodesConfig};
+use crate::state::*;
+use crate::errors::*;
+
+/// Bring a node's audit bonus in line with its Nodes Program registration
+/// (permissionless). Audited nodes get NodesConfig::audit_reputation_bonus on
+/// top of their reputation score; an audit revoked later takes it back.
+#[derive(Accounts)]
+pub struct SyncAuditBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"reputation", reputation.authority.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Node's registration in the Nodes Program
+    #[account(
+        constraint = node_account.authority == reputation.authority @ MarketError::Unauthorized
+    )]
+    pub node_account: Account<'info, Node>,
+
+    /// Nodes Program config (audit bonus)
+    #[account(
+        seeds = [b"nodes_config"],
+        bump = nodes_config.bump,
+        seeds::program = hypernode_nodes::ID
+    )]
+    pub nodes_config: Account<'info, NodesConfig>,
+}
+
+pub fn handler(ctx: Context<SyncAuditBonus>) -> Result<()> {
+    let reputation = &mut ctx.accounts.reputation;
+    reputation.sync_audit_bonus(&ctx.accounts.node_account, &ctx.accounts.nodes_config);
+
+    msg!("Node {} audit bonus: {}", reputation.authority, reputation.audit_bonus);
+    msg!("Reputation score: {} (tier {})", reputation.reputation_score, reputation.tier);
+
+    Ok(())
+}
@@ -1,9 +1,14 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use hypernode_nodes::program::HypernodeNodes;
+use hypernode_nodes::state::{Node, MARKETS_STATS_AUTHORITY_SEED};
 
 /// Mark job as timed out and refund client
-/// Can be called by anyone if job has exceeded timeout
+/// Can be called by anyone if job has exceeded timeout,
+/// or earlier if the assigned node's heartbeat is stale.
+/// A job with retries left is re-queued instead, keeping its escrow,
+/// and only refunded once its retry budget is spent
 #[derive(Accounts)]
 pub struct TimeoutJob<'info> {
     #[account(mut)]
@@ -30,6 +35,42 @@ pub struct TimeoutJob<'info> {
     )]
     pub vault: SystemAccount<'info>,
 
+    /// Assigned node's account from Nodes Program (the one it claimed the job with)
+    /// A stale heartbeat allows timing out before job.timeout, and the failure
+    /// is recorded on it via the update_stats CPI
+    /// CHECK: parsed as a Node in the handler; empty once the node deregistered
+    /// mid-job, and the job then only times out on job.timeout
+    #[account(
+        mut,
+        address = job.node_account @ MarketError::NodeAccountMismatch
+    )]
+    pub node_account: UncheckedAccount<'info>,
+
+    /// Assigned node's reputation
+    /// The job's slot is released on it
+    #[account(
+        mut,
+        constraint = job.node == Some(reputation.authority) @ MarketError::Unauthorized
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// CHECK: PDA signing update_stats; the Nodes Program only accepts this program's
+    #[account(
+        seeds = [MARKETS_STATS_AUTHORITY_SEED],
+        bump
+    )]
+    pub stats_authority: UncheckedAccount<'info>,
+
+    pub nodes_program: Program<'info, HypernodeNodes>,
+
+    /// Market statistics (outcome and revenue counters)
+    #[account(
+        mut,
+        seeds = [MarketStats::SEED, market.key().as_ref()],
+        bump = market_stats.bump
+    )]
+    pub market_stats: Account<'info, MarketStats>,
+
     /// Caller (anyone can call this)
     pub caller: Signer<'info>,
 
@@ -38,7 +79,7 @@ pub struct TimeoutJob<'info> {
 
 pub fn handler(ctx: Context<TimeoutJob>) -> Result<()> {
     let job = &mut ctx.accounts.job;
-    let market = &ctx.accounts.market;
+    let market = &mut ctx.accounts.market;
     let client = &ctx.accounts.client;
     let vault = &ctx.accounts.vault;
     let clock = Clock::get()?;
@@ -46,15 +87,53 @@ pub fn handler(ctx: Context<TimeoutJob>) -> Result<()> {
     // Verify job is running
     require!(job.is_running(), MarketError::JobNotRunning);
 
-    // Verify job has timed out
+    // Verify job has timed out (or its node is dead)
+    let node_account = &ctx.accounts.node_account;
+    let last_heartbeat = if node_account.data_is_empty() {
+        None
+    } else {
+        require!(node_account.owner == &hypernode_nodes::ID, MarketError::NodeAccountMismatch);
+        let data = node_account.try_borrow_data()?;
+        Some(Node::try_deserialize(&mut &data[..])?.last_heartbeat)
+    };
     require!(
-        job.check_timeout(clock.unix_timestamp),
+        job.can_timeout(clock.unix_timestamp, last_heartbeat),
         MarketError::JobNotTimedOut
     );
 
+    let failed_node = job.node;
+
+    // Free the node's slot
+    ctx.accounts.reputation.release_slot();
+
+    // Report the failure to the Nodes Program (nothing to record on once deregistered)
+    if last_heartbeat.is_some() {
+        let stats_seeds: &[&[u8]] = &[MARKETS_STATS_AUTHORITY_SEED, &[ctx.bumps.stats_authority]];
+        let stats_signer = &[stats_seeds];
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.nodes_program.to_account_info(),
+            hypernode_nodes::cpi::accounts::UpdateStats {
+                node: ctx.accounts.node_account.to_account_info(),
+                markets_authority: ctx.accounts.stats_authority.to_account_info(),
+            },
+            stats_signer,
+        );
+        hypernode_nodes::cpi::update_stats(cpi_ctx, false, 0)?;
+    }
+
+    // Retry budget left: back to the queue for another node, escrow kept
+    let job_key = job.key();
+    if market.requeue_failed_job(job, job_key)? {
+        msg!("Job re-queued: {}", job_key);
+        msg!("Failed node: {:?}", failed_node);
+        msg!("Retry {} of {}", job.retry_count, job.max_retries);
+        return Ok(());
+    }
+
     // Update job state
     job.state = JobState::TimedOut;
     job.time_end = clock.unix_timestamp;
+    ctx.accounts.market_stats.record_timed_out()?;
 
     // Refund client from vault
     // PDA signer seeds
@@ -66,13 +145,17 @@ pub fn handler(ctx: Context<TimeoutJob>) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
 
+    // Full refund: no rewards or market fee on a job that never completed
+    let refund = job.abort_refund();
+    market.release_escrow(refund);
+
     // Transfer lamports from vault (PDA) to client
-    **vault.to_account_info().try_borrow_mut_lamports()? -= job.price;
-    **client.to_account_info().try_borrow_mut_lamports()? += job.price;
+    **vault.to_account_info().try_borrow_mut_lamports()? -= refund;
+    **client.to_account_info().try_borrow_mut_lamports()? += refund;
 
-    msg!("Job timed out: {}", job.key());
+    msg!("Job timed out: {}", job_key);
     msg!("Node: {}", job.node.unwrap());
-    msg!("Refund: {} lamports", job.price);
+    msg!("Refund: {} lamports", refund);
     msg!("Duration: {} seconds", job.time_end - job.time_start);
 
     Ok(())
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Node leaves a market's node queue
+/// Every listing it holds in this market is released, freeing those slots
+/// for other markets or jobs-queue work
+#[derive(Accounts)]
+pub struct UnlistNode<'info> {
+    #[account(mut)]
+    pub market: Account<'info, MarketAccount>,
+
+    /// Node leaving the queue
+    pub node: Signer<'info>,
+
+    /// Node's reputation (counts its listings across markets)
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+}
+
+pub fn handler(ctx: Context<UnlistNode>) -> Result<()> {
+    let market = &mut ctx.accounts.market;
+    let node = ctx.accounts.node.key();
+
+    let unlisted = market.unlist_node(node);
+    require!(unlisted > 0, MarketError::NodeNotInQueue);
+
+    // Each queue entry was one of the node's listings
+    for _ in 0..unlisted {
+        ctx.accounts.reputation.release_listing();
+    }
+
+    msg!("Node {} unlisted ({} listing(s))", node, unlisted);
+    msg!("Queue length: {}", market.queue_len());
+    msg!("Active listings: {}", ctx.accounts.reputation.active_listings);
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::errors::*;
+
+/// Replace the market's authority with an m-of-n signer set (market authority only)
+/// The first call is approved by the current key authority, creates the
+/// threshold config and points market.authority at it; later calls need the
+/// config's own threshold of member signatures
+#[derive(Accounts)]
+pub struct UpdateAuthorities<'info> {
+    #[account(
+        mut,
+        has_one = authority @ MarketError::Unauthorized
+    )]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; a key authority signs, a threshold config is checked in the handler
+    pub authority: UncheckedAccount<'info>,
+
+    /// CHECK: threshold config PDA, created on first use and written in the handler
+    /// Seeds: ["market_authority", market.key()]
+    #[account(
+        mut,
+        seeds = [MarketAuthority::SEED, market.key().as_ref()],
+        bump
+    )]
+    pub authority_config: UncheckedAccount<'info>,
+
+    /// Pays rent for the threshold config
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<UpdateAuthorities>, signers: Vec<Pubkey>, threshold: u8) -> Result<()> {
+    let market_key = ctx.accounts.market.key();
+    require_market_admin(
+        &market_key,
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    require!(
+        MarketAuthority::is_valid_config(&signers, threshold),
+        MarketError::InvalidAuthorityConfig
+    );
+
+    let bump = ctx.bumps.authority_config;
+    let authority_config = &ctx.accounts.authority_config;
+
+    if authority_config.data_is_empty() {
+        // Not create_account: it fails if the address already holds lamports,
+        // which anyone could send to block the config. Fund up to rent
+        // exemption, then allocate and assign under the PDA's signature
+        let seeds = &[MarketAuthority::SEED, market_key.as_ref(), &[bump]];
+        let signer = &[&seeds[..]];
+        let system_program = ctx.accounts.system_program.to_account_info();
+
+        let shortfall = Rent::get()?
+            .minimum_balance(MarketAuthority::LEN)
+            .saturating_sub(authority_config.lamports());
+        if shortfall > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: authority_config.to_account_info(),
+                    },
+                ),
+                shortfall,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Allocate {
+                    account_to_allocate: authority_config.to_account_info(),
+                },
+                signer,
+            ),
+            MarketAuthority::LEN as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(
+                system_program,
+                system_program::Assign {
+                    account_to_assign: authority_config.to_account_info(),
+                },
+                signer,
+            ),
+            &crate::ID,
+        )?;
+    }
+
+    let config = MarketAuthority {
+        market: market_key,
+        threshold,
+        bump,
+        signers,
+    };
+    {
+        let mut data = authority_config.try_borrow_mut_data()?;
+        config.try_serialize(&mut &mut data[..])?;
+    }
+
+    let market = &mut ctx.accounts.market;
+    market.authority = authority_config.key();
+
+    msg!(
+        "Market authority set to {}-of-{} signer set {}",
+        config.threshold,
+        config.signers.len(),
+        market.authority
+    );
+
+    Ok(())
+}
@@ -37,6 +37,15 @@ pub fn handler(ctx: Context<UpdateReputation>) -> Result<()> {
     reputation.reputation_score = 1000; // Start with perfect score
     reputation.tier = 4; // Start as Diamond (will adjust after first job)
     reputation.total_revenue = 0;
+    reputation.recent_revenue = 0;
+    reputation.recent_window_start = clock.unix_timestamp;
+    reputation.active_slots = 0;
+    reputation.auto_relist = false;
+    reputation.active_listings = 0;
+    reputation.rating_count = 0;
+    reputation.rating_total = 0;
+    reputation.audit_bonus = 0; // sync_audit_bonus applies it for audited nodes
+    reputation.node_account = Pubkey::default(); // bound on first list_node / work_job
     reputation.bump = ctx.bumps.reputation;
 
     msg!("Reputation account initialized for node: {}", ctx.accounts.node.key());
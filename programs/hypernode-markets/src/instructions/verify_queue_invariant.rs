@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Check the dual-queue invariant (permissionless)
+/// Fails if queue_type is EMPTY with queued items, or JOBS/NODES with an empty queue
+#[derive(Accounts)]
+pub struct VerifyQueueInvariant<'info> {
+    pub market: Account<'info, MarketAccount>,
+}
+
+pub fn handler(ctx: Context<VerifyQueueInvariant>) -> Result<()> {
+    let market = &ctx.accounts.market;
+
+    msg!("Queue type: {}", market.queue_type);
+    msg!("Queue length: {}", market.queue_len());
+
+    require!(
+        market.queue_invariant_holds(),
+        MarketError::QueueInvariantViolated
+    );
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Withdraw market and cancellation fees collected by a threshold config (market authority only)
+/// A key authority receives fees directly; a threshold config PDA holds them
+/// until its signers move them out. The config keeps its rent-exempt balance
+#[derive(Accounts)]
+pub struct WithdrawAuthorityFees<'info> {
+    #[account(has_one = authority @ MarketError::Unauthorized)]
+    pub market: Account<'info, MarketAccount>,
+
+    /// CHECK: matched by has_one; must be the market's threshold config
+    #[account(
+        mut,
+        owner = crate::ID @ MarketError::Unauthorized
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    /// Account receiving the fees
+    #[account(mut)]
+    pub recipient: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<WithdrawAuthorityFees>, amount: u64) -> Result<()> {
+    require_market_admin(
+        &ctx.accounts.market.key(),
+        &ctx.accounts.authority,
+        ctx.remaining_accounts,
+    )?;
+
+    let authority = ctx.accounts.authority.to_account_info();
+    let rent_exempt = Rent::get()?.minimum_balance(authority.data_len());
+    let available = authority.lamports().saturating_sub(rent_exempt);
+    require!(amount <= available, MarketError::InsufficientFunds);
+
+    **authority.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.recipient.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    msg!("Withdrew {} lamports of authority fees to {}", amount, ctx.accounts.recipient.key());
+
+    Ok(())
+}
@@ -1,10 +1,17 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use hypernode_nodes::state::{Blacklist, Node};
 
 /// Node claims a job from the queue (dual queue matching)
 /// If jobs are queued: pop job and start work
-/// If no jobs: add node to queue and wait
+/// If nodes are queued: a listed node takes a job created while it waited,
+/// staying listed until its jobs and its listings in other markets book all
+/// of its slots (Node::max_slots).
+/// Only the node picked for the job at create_job can take it, until
+/// SELECTION_CLAIM_WINDOW passes; then any listed node can.
+/// A node reserved in this market only takes its reserving client's jobs,
+/// which are assigned to it directly
 #[derive(Accounts)]
 pub struct WorkJob<'info> {
     #[account(mut)]
@@ -20,13 +27,50 @@ pub struct WorkJob<'info> {
     /// Must have sufficient xHYPER stake (verified off-chain or via CPI to staking program)
     pub node: Signer<'info>,
 
+    /// Node's registration in the Nodes Program
+    /// Carries its flag, capabilities, version, hardware, slots and price floor;
+    /// must be the one the node's listings and jobs are bound to
+    #[account(
+        seeds = [b"node", node_account.node_id.as_bytes()],
+        bump = node_account.bump,
+        seeds::program = hypernode_nodes::ID,
+        constraint = node_account.authority == node.key() @ MarketError::Unauthorized
+    )]
+    pub node_account: Account<'info, Node>,
+
+    /// Node's reputation
+    /// Tracks active slots and listings across markets
+    #[account(
+        mut,
+        seeds = [b"reputation", node.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, NodeReputation>,
+
+    /// Global node blacklist (Nodes Program); banned authorities can't take jobs
+    #[account(
+        seeds = [b"blacklist"],
+        bump = blacklist.bump,
+        seeds::program = hypernode_nodes::ID,
+        constraint = !blacklist.is_banned(&node.key()) @ MarketError::NodeBlacklisted
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    /// CHECK: the node's reservation address in this market; empty when not reserved
+    #[account(
+        seeds = [NodeReservation::SEED, market.key().as_ref(), node.key().as_ref()],
+        bump
+    )]
+    pub reservation: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<WorkJob>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, WorkJob<'info>>) -> Result<()> {
     let market = &mut ctx.accounts.market;
     let job = &mut ctx.accounts.job;
     let node = &ctx.accounts.node;
+    let node_account = &ctx.accounts.node_account;
     let clock = Clock::get()?;
 
     // Verify job is in queued state
@@ -38,42 +82,91 @@ pub fn handler(ctx: Context<WorkJob>) -> Result<()> {
         MarketError::JobTimedOut
     );
 
+    // Verify node supports every capability the job requires
+    require!(
+        job.node_has_capabilities(node_account.capabilities),
+        MarketError::MissingCapabilities
+    );
+
+    // Nodes with a price floor are only matched to jobs that meet it
+    require!(
+        job.meets_node_min_price(node_account.min_job_price),
+        MarketError::BelowNodeMinPrice
+    );
+
+    // Outdated node software is never matched
+    require!(
+        market.admits_node_version(Some(node_account.version)),
+        MarketError::NodeVersionTooOld
+    );
+
+    // Nodes below the market's hardware floor are never matched
+    let hardware = NodeHardware::of(node_account);
+    require!(
+        market.admits_node_hardware(Some(hardware)),
+        MarketError::NodeHardwareBelowMinimum
+    );
+
+    // The job's input (if the client gave its size) must fit the node's RAM
+    require!(
+        job.fits_node(Some(hardware)),
+        MarketError::NodeCapacityExceeded
+    );
+
+    // Flagged nodes are never matched
+    require!(!node_account.is_flagged, MarketError::NodeNotEligible);
+
+    // Reserved nodes only work for the reserving client while the window is open
+    let reservation = NodeReservation::load(&ctx.accounts.reservation)?
+        .filter(|r| r.is_active(clock.unix_timestamp));
+    if let Some(reservation) = reservation.as_ref() {
+        require!(
+            reservation.admits_job(&job.client, clock.unix_timestamp),
+            MarketError::NodeReserved
+        );
+    }
+
     // TODO: Verify node has sufficient xHYPER stake
     // This would be a CPI call to hypernode-staking program
     // For now, we assume node is eligible
 
-    // Dual Queue Logic
-    match market.queue_type {
-        // Jobs are waiting - pop from queue and assign to node
-        MarketAccount::QUEUE_TYPE_JOBS => {
-            // Remove job from queue
-            let popped_job = market
-                .queue_pop()
-                .ok_or(MarketError::QueueEmpty)?;
-
-            // Verify popped job matches current job
-            require!(
-                popped_job == job.key(),
-                MarketError::MarketMismatch
-            );
-
-            // Update queue type if empty
-            if market.queue_is_empty() {
-                market.queue_type = MarketAccount::QUEUE_TYPE_EMPTY;
-            }
-
-            msg!("Job {} assigned to node {}", job.key(), node.key());
-        }
-        // No jobs waiting, or nodes waiting - shouldn't reach here in normal flow
-        _ => {
-            return Err(MarketError::InvalidQueueType.into());
-        }
+    // Every gate above ran against the registration the node is bound to
+    ctx.accounts.reputation.bind_node_account(node_account.key())?;
+
+    // Take one of the node's slots
+    // Matched through its listing here, the listing already holds it;
+    // otherwise the node's listings in other markets must leave one free
+    let max_slots = node_account.max_slots;
+    let node_full = if reservation.is_none() && market.queue_type == MarketAccount::QUEUE_TYPE_NODES {
+        ctx.accounts.reputation.take_listed_slot(max_slots)?
+    } else {
+        ctx.accounts.reputation.reserve_slot(max_slots)?
+    };
+
+    if reservation.is_some() {
+        // Reserved capacity: the reserving client's job goes straight to its node
+        let job_key = job.key();
+        market.assign_reserved_job(job, job_key, node.key(), clock.unix_timestamp)?;
+    } else if market.queue_type == MarketAccount::QUEUE_TYPE_NODES {
+        // Only the node selected for this job at creation may take it (until the window lapses)
+        require!(
+            job.admits_selected_node(&node.key(), clock.unix_timestamp),
+            MarketError::NodeNotSelected
+        );
+
+        // Dual Queue Logic: nodes are waiting - match a listed node
+        market.match_queued_node(job, node.key(), node_full, clock.unix_timestamp)?;
+    } else {
+        // Dual Queue Logic: jobs are waiting - dequeue and assign to node
+        // Re-checks the job is still queued so a just-cancelled job can't be claimed
+        let job_key = job.key();
+        market.claim_queued_job(job, job_key, node.key(), clock.unix_timestamp)?;
     }
 
-    // Assign job to node
-    job.node = Some(node.key());
-    job.state = JobState::Running;
-    job.time_start = clock.unix_timestamp;
+    // Heartbeat and stats are read from this registration from now on
+    job.node_account = node_account.key();
+
+    msg!("Job {} assigned to node {}", job.key(), node.key());
 
     msg!("Job started: {}", job.key());
     msg!("Node: {}", node.key());
@@ -5,6 +5,7 @@ pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{JobHints, NodeEarnings, NodeScorecard, REPUTATION_TIER_COUNT};
 
 declare_id!("67UE2LconF9QU5Vobsaf5sXnW9yUisebLj8VmgGWLSdb");
 
@@ -16,28 +17,44 @@ pub mod hypernode_markets {
     use super::*;
 
     /// Create a new GPU marketplace
+    /// `max_queue_size` caps how many jobs or nodes can wait (1..=MAX_QUEUE_SIZE)
     pub fn create_market(
         ctx: Context<CreateMarket>,
         job_price: u64,
         job_timeout: i64,
         node_xhyper_minimum: u128,
+        max_queue_size: u16,
     ) -> Result<()> {
-        instructions::create_market::handler(ctx, job_price, job_timeout, node_xhyper_minimum)
+        instructions::create_market::handler(ctx, job_price, job_timeout, node_xhyper_minimum, max_queue_size)
     }
 
     /// Create a new job and add to queue
-    pub fn create_job(
-        ctx: Context<CreateJob>,
+    pub fn create_job<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CreateJob<'info>>,
         job_id: Pubkey,
         ipfs_job: [u8; 32],
         min_vram: u8,
         gpu_type: u8,
+        required_capabilities: u64,
+        sla_deadline: i64,
+        max_retries: u8,
+        hints: JobHints,
     ) -> Result<()> {
-        instructions::create_job::handler(ctx, job_id, ipfs_job, min_vram, gpu_type)
+        instructions::create_job::handler(
+            ctx,
+            job_id,
+            ipfs_job,
+            min_vram,
+            gpu_type,
+            required_capabilities,
+            sla_deadline,
+            max_retries,
+            hints,
+        )
     }
 
     /// Node claims a job from the queue
-    pub fn work_job(ctx: Context<WorkJob>) -> Result<()> {
+    pub fn work_job<'info>(ctx: Context<'_, '_, 'info, 'info, WorkJob<'info>>) -> Result<()> {
         instructions::work_job::handler(ctx)
     }
 
@@ -54,7 +71,13 @@ pub mod hypernode_markets {
         instructions::list_node::handler(ctx)
     }
 
-    /// Mark job as timed out and refund client
+    /// Node leaves the queue, releasing its listings in this market
+    pub fn unlist_node(ctx: Context<UnlistNode>) -> Result<()> {
+        instructions::unlist_node::handler(ctx)
+    }
+
+    /// Mark job as timed out and refund client, or re-queue it while it has retries left
+    /// Early timeout allowed when the assigned node stopped heartbeating
     pub fn timeout_job(ctx: Context<TimeoutJob>) -> Result<()> {
         instructions::timeout_job::handler(ctx)
     }
@@ -68,4 +91,157 @@ pub mod hypernode_markets {
     pub fn update_reputation(ctx: Context<UpdateReputation>) -> Result<()> {
         instructions::update_reputation::handler(ctx)
     }
+
+    /// Node earnings view (lifetime and last 30 days)
+    pub fn get_earnings(ctx: Context<GetEarnings>) -> Result<NodeEarnings> {
+        instructions::get_earnings::handler(ctx)
+    }
+
+    /// Node scorecard view (reputation, uptime, liveness, flags and earnings)
+    pub fn get_node_scorecard(ctx: Context<GetNodeScorecard>) -> Result<NodeScorecard> {
+        instructions::get_node_scorecard::handler(ctx)
+    }
+
+    /// Evict flagged nodes from the queue (permissionless)
+    /// Node accounts (Nodes Program) are passed as remaining accounts
+    pub fn prune_queue<'info>(ctx: Context<'_, '_, 'info, 'info, PruneQueue<'info>>) -> Result<()> {
+        instructions::prune_queue::handler(ctx)
+    }
+
+    /// Check queue_type is consistent with queue contents (permissionless)
+    pub fn verify_queue_invariant(ctx: Context<VerifyQueueInvariant>) -> Result<()> {
+        instructions::verify_queue_invariant::handler(ctx)
+    }
+
+    /// Fix an inconsistent queue_type (market authority only)
+    pub fn repair_queue(ctx: Context<RepairQueue>, queue_type: u8) -> Result<()> {
+        instructions::repair_queue::handler(ctx, queue_type)
+    }
+
+    /// Drop a closed or finished job from the queue (permissionless)
+    pub fn purge_dead_queue_entry(ctx: Context<PurgeDeadQueueEntry>, entry: Pubkey) -> Result<()> {
+        instructions::purge_dead_queue_entry::handler(ctx, entry)
+    }
+
+    /// Client disputes a completed job within the dispute window
+    pub fn dispute_job(ctx: Context<DisputeJob>) -> Result<()> {
+        instructions::dispute_job::handler(ctx)
+    }
+
+    /// Resolve a dispute; reversing refunds the held rewards fee (market authority only)
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, reverse: bool) -> Result<()> {
+        instructions::resolve_dispute::handler(ctx, reverse)
+    }
+
+    /// Release a job's held rewards fee after the dispute window (permissionless)
+    pub fn finalize_job(ctx: Context<FinalizeJob>) -> Result<()> {
+        instructions::finalize_job::handler(ctx)
+    }
+
+    /// Client rates the node that ran a finished job (1-5, once per job)
+    pub fn rate_job(ctx: Context<RateJob>, rating: u8) -> Result<()> {
+        instructions::rate_job::handler(ctx, rating)
+    }
+
+    /// Apply (or remove) a node's audit reputation bonus from its Nodes Program registration (permissionless)
+    pub fn sync_audit_bonus(ctx: Context<SyncAuditBonus>) -> Result<()> {
+        instructions::sync_audit_bonus::handler(ctx)
+    }
+
+    /// Set the dispute window for completed jobs (market authority only)
+    pub fn set_dispute_window(ctx: Context<SetDisputeWindow>, dispute_window: i64) -> Result<()> {
+        instructions::set_dispute_window::handler(ctx, dispute_window)
+    }
+
+    /// Set the share of each job payment paid to the market authority (market authority only)
+    pub fn set_market_fee(ctx: Context<SetMarketFee>, market_fee_bps: u16) -> Result<()> {
+        instructions::set_market_fee::handler(ctx, market_fee_bps)
+    }
+
+    /// Set how jobs pick among queued nodes: FIFO, reputation-weighted or random (market authority only)
+    pub fn set_selection_mode(ctx: Context<SetSelectionMode>, selection_mode: u8) -> Result<()> {
+        instructions::set_selection_mode::handler(ctx, selection_mode)
+    }
+
+    /// Set the oldest node software version allowed to list or take jobs (market authority only)
+    pub fn set_min_node_version(ctx: Context<SetMinNodeVersion>, min_node_version: u32) -> Result<()> {
+        instructions::set_min_node_version::handler(ctx, min_node_version)
+    }
+
+    /// Opt in or out of being re-queued automatically after each finish_job (node only)
+    pub fn set_auto_relist(ctx: Context<SetAutoRelist>, auto_relist: bool) -> Result<()> {
+        instructions::set_auto_relist::handler(ctx, auto_relist)
+    }
+
+    /// Set the minimum CPU cores, RAM and GPU cores of nodes in the market (market authority only)
+    pub fn set_hardware_floor(
+        ctx: Context<SetHardwareFloor>,
+        min_cpu_cores: u16,
+        min_ram_gb: u16,
+        min_gpu_cores: u16,
+    ) -> Result<()> {
+        instructions::set_hardware_floor::handler(ctx, min_cpu_cores, min_ram_gb, min_gpu_cores)
+    }
+
+    /// Set the share of the escrow kept when a client cancels a job (market authority only)
+    pub fn set_cancellation_fee(ctx: Context<SetCancellationFee>, cancellation_fee_bps: u16) -> Result<()> {
+        instructions::set_cancellation_fee::handler(ctx, cancellation_fee_bps)
+    }
+
+    /// Set the response time EMA weight and decay applied at finish_job (market authority only)
+    pub fn set_response_ema(ctx: Context<SetResponseEma>, alpha_bps: u16, decay_half_life: i64) -> Result<()> {
+        instructions::set_response_ema::handler(ctx, alpha_bps, decay_half_life)
+    }
+
+    /// Hand market administration to an m-of-n signer set, or change it (market authority only)
+    pub fn update_authorities(
+        ctx: Context<UpdateAuthorities>,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::update_authorities::handler(ctx, signers, threshold)
+    }
+
+    /// Withdraw fees collected by a threshold config authority (market authority only)
+    pub fn withdraw_authority_fees(ctx: Context<WithdrawAuthorityFees>, amount: u64) -> Result<()> {
+        instructions::withdraw_authority_fees::handler(ctx, amount)
+    }
+
+    /// Scale the node stake minimum per reputation tier, Starter..Diamond (market authority only)
+    pub fn set_tier_stake_bps(
+        ctx: Context<SetTierStakeBps>,
+        tier_stake_bps: [u16; REPUTATION_TIER_COUNT],
+    ) -> Result<()> {
+        instructions::set_tier_stake_bps::handler(ctx, tier_stake_bps)
+    }
+
+    /// Abort a running job agreed by client and node, splitting the escrow (both sign)
+    pub fn mutual_cancel(ctx: Context<MutualCancel>, node_share_bps: u16) -> Result<()> {
+        instructions::mutual_cancel::handler(ctx, node_share_bps)
+    }
+
+    /// Reserve a node's capacity for a time window, pre-paying its escrow (client and node sign)
+    pub fn reserve_node(
+        ctx: Context<ReserveNode>,
+        escrow: u64,
+        starts_at: i64,
+        duration: i64,
+    ) -> Result<()> {
+        instructions::reserve_node::handler(ctx, escrow, starts_at, duration)
+    }
+
+    /// End a node reservation, paying the node for the elapsed window and refunding the rest (client or node)
+    pub fn release_reservation(ctx: Context<ReleaseReservation>) -> Result<()> {
+        instructions::release_reservation::handler(ctx)
+    }
+
+    /// Create a market's outcome and revenue statistics account (permissionless)
+    pub fn initialize_market_stats(ctx: Context<InitializeMarketStats>) -> Result<()> {
+        instructions::initialize_market_stats::handler(ctx)
+    }
+
+    /// Sweep a fully settled market's leftover vault balance (market authority only)
+    pub fn sweep_market_dust(ctx: Context<SweepMarketDust>) -> Result<()> {
+        instructions::sweep_market_dust::handler(ctx)
+    }
 }
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+
+/// Most signers a market's threshold config can hold
+pub const MAX_MARKET_SIGNERS: usize = 5;
+
+/// Threshold (m-of-n) authority for a market
+///
+/// update_authorities points `MarketAccount.authority` at this PDA; from then
+/// on administrative instructions need `threshold` distinct signers from
+/// `signers`, passed as signing remaining accounts.
+/// Seeds: ["market_authority", market.key()]
+#[account]
+pub struct MarketAuthority {
+    /// Market this config administers
+    pub market: Pubkey,
+
+    /// Threshold needed to approve an administrative action
+    pub threshold: u8,
+
+    /// PDA bump
+    pub bump: u8,
+
+    /// Members of the signer set
+    pub signers: Vec<Pubkey>,
+}
+
+impl MarketAuthority {
+    pub const SEED: &'static [u8] = b"market_authority";
+
+    pub const LEN: usize = 8 + 32 + 1 + 1 + 4 + 32 * MAX_MARKET_SIGNERS;
+
+    /// A signer set is valid with 1..=MAX_MARKET_SIGNERS distinct members and
+    /// a threshold between 1 and the number of members
+    pub fn is_valid_config(signers: &[Pubkey], threshold: u8) -> bool {
+        if signers.is_empty() || signers.len() > MAX_MARKET_SIGNERS {
+            return false;
+        }
+        if threshold == 0 || threshold as usize > signers.len() {
+            return false;
+        }
+        signers
+            .iter()
+            .enumerate()
+            .all(|(i, signer)| !signers[..i].contains(signer))
+    }
+
+    /// Number of distinct members among `approvers`
+    pub fn approvals(&self, approvers: &[Pubkey]) -> usize {
+        self.signers
+            .iter()
+            .filter(|member| approvers.contains(member))
+            .count()
+    }
+
+    /// Whether `approvers` reach the threshold
+    pub fn is_approved(&self, approvers: &[Pubkey]) -> bool {
+        self.approvals(approvers) >= self.threshold as usize
+    }
+}
+
+/// Check that an administrative action on `market` is authorized
+///
+/// `authority` must already be `market.authority` (enforced by `has_one`).
+/// A plain key authority has to sign. A threshold config authority is read
+/// here and needs its threshold of member signatures among `cosigners`.
+pub fn require_market_admin(
+    market: &Pubkey,
+    authority: &AccountInfo,
+    cosigners: &[AccountInfo],
+) -> Result<()> {
+    if authority.is_signer {
+        return Ok(());
+    }
+
+    require!(authority.owner == &crate::ID, MarketError::Unauthorized);
+
+    let config = {
+        let data = authority.try_borrow_data()?;
+        MarketAuthority::try_deserialize(&mut &data[..])?
+    };
+    require!(config.market == *market, MarketError::Unauthorized);
+
+    let approvers: Vec<Pubkey> = cosigners
+        .iter()
+        .filter(|account| account.is_signer)
+        .map(|account| account.key())
+        .collect();
+    require!(config.is_approved(&approvers), MarketError::ThresholdNotMet);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn two_of_three() -> (MarketAuthority, [Pubkey; 3]) {
+        let members = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let config = MarketAuthority {
+            market: Pubkey::new_unique(),
+            threshold: 2,
+            bump: 255,
+            signers: members.to_vec(),
+        };
+        (config, members)
+    }
+
+    #[test]
+    fn test_two_of_three_approves_parameter_change() {
+        let (config, members) = two_of_three();
+
+        assert!(config.is_approved(&[members[0], members[2]]));
+        assert!(config.is_approved(&members));
+    }
+
+    #[test]
+    fn test_single_signer_is_rejected() {
+        let (config, members) = two_of_three();
+        let outsider = Pubkey::new_unique();
+
+        assert!(!config.is_approved(&[members[1]]));
+        // Repeating a member or adding outsiders doesn't count twice
+        assert!(!config.is_approved(&[members[1], members[1]]));
+        assert!(!config.is_approved(&[members[1], outsider]));
+        assert!(!config.is_approved(&[]));
+    }
+
+    #[test]
+    fn test_signer_set_validation() {
+        let (_, members) = two_of_three();
+
+        assert!(MarketAuthority::is_valid_config(&members, 1));
+        assert!(MarketAuthority::is_valid_config(&members, 3));
+        assert!(!MarketAuthority::is_valid_config(&members, 0));
+        assert!(!MarketAuthority::is_valid_config(&members, 4));
+        assert!(!MarketAuthority::is_valid_config(&[], 1));
+        assert!(!MarketAuthority::is_valid_config(&[members[0], members[0]], 1));
+
+        let too_many: Vec<Pubkey> = (0..=MAX_MARKET_SIGNERS).map(|_| Pubkey::new_unique()).collect();
+        assert!(!MarketAuthority::is_valid_config(&too_many, 1));
+    }
+}
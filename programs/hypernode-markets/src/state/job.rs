@@ -1,4 +1,21 @@
 use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+use crate::state::market::{NodeHardware, SELECTION_CLAIM_WINDOW};
+
+/// Heartbeat staleness after which the assigned node is considered dead (seconds)
+/// Running jobs of dead nodes can be timed out before job.timeout elapses
+pub const NODE_LIVENESS_THRESHOLD: i64 = 10 * 60;
+
+/// Payment share a node forfeits for finishing right at the hard timeout
+/// when it missed the job's SLA deadline (scales linearly from the deadline)
+pub const MAX_SLA_PENALTY_BPS: u64 = 5000; // 50%
+
+/// Most times a job can be re-queued after its node fails it
+pub const MAX_JOB_RETRIES: u8 = 5;
+
+/// Range of the client's rating of the node that ran a job (rate_job)
+pub const MIN_JOB_RATING: u8 = 1;
+pub const MAX_JOB_RATING: u8 = 5;
 
 /// Job state enum
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
@@ -13,6 +30,34 @@ pub enum JobState {
     Stopped,
     /// Job timed out
     TimedOut,
+    /// Result submitted; the client can dispute it until the dispute window closes
+    PendingReview,
+    /// Client contested the result; waiting for the market authority
+    Disputed,
+}
+
+/// Optional scheduling hints a client gives at create_job
+/// Off-chain matchers use them to estimate resource needs; on-chain only
+/// the input size is checked, against the RAM of nodes taking the job
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct JobHints {
+    /// Expected run time in seconds
+    pub estimated_duration: Option<i64>,
+
+    /// Size of the job's input data in MB
+    pub input_size_mb: Option<u32>,
+
+    /// Identifier of the model the job runs (e.g. a hash of its name or weights)
+    pub model_id: Option<[u8; 32]>,
+}
+
+impl JobHints {
+    /// Check the hints are plausible for a job with `timeout`
+    /// An estimate must be positive and within the timeout, an input size non-zero
+    pub fn is_valid(&self, timeout: i64) -> bool {
+        self.estimated_duration.is_none_or(|d| d > 0 && d <= timeout)
+            && self.input_size_mb.is_none_or(|size| size > 0)
+    }
 }
 
 /// Job account - represents a GPU computation job
@@ -31,6 +76,10 @@ pub struct JobAccount {
     /// Node executing the job (None if queued)
     pub node: Option<Pubkey>,
 
+    /// Node's registration (Nodes Program PDA) the job was claimed with
+    /// (default while queued); its heartbeat and stats are the ones checked
+    pub node_account: Pubkey,
+
     /// IPFS CID of job definition (32 bytes)
     /// Contains: model, framework, operations, input data
     pub ipfs_job: [u8; 32],
@@ -62,13 +111,51 @@ pub struct JobAccount {
     /// GPU type requirement (0=Any, 1=NVIDIA, 2=AMD)
     pub gpu_type: u8,
 
+    /// Required node capabilities (hypernode-nodes CAPABILITY_* bitmask, 0 = none)
+    pub required_capabilities: u64,
+
+    /// Soft deadline in seconds after start (0 = no SLA), below the hard timeout
+    /// Finishing later costs the node a share of its payment
+    pub sla_deadline: i64,
+
+    /// Rewards fee held in escrow until the dispute window closes
+    pub held_rewards_fee: u64,
+
+    /// When the held fee can be released to the rewards pool (0 if none held)
+    pub fee_release_at: i64,
+
+    /// Queued node picked by the market's selection_mode at creation
+    /// (None if no node was waiting, or once the job is re-queued)
+    pub selected_node: Option<Pubkey>,
+
+    /// Times the job may be re-queued when its node fails it (0 = refund on first failure)
+    pub max_retries: u8,
+
+    /// Times the job has been re-queued so far
+    pub retry_count: u8,
+
+    /// Expected run time in seconds (scheduling hint, None if not given)
+    pub estimated_duration: Option<i64>,
+
+    /// Size of the job's input data in MB (scheduling hint, None if not given)
+    pub input_size_mb: Option<u32>,
+
+    /// Model the job runs (scheduling hint, None if not given)
+    pub model_id: Option<[u8; 32]>,
+
+    /// Client's rating of the node (MIN_JOB_RATING..=MAX_JOB_RATING, 0 = not rated)
+    pub rating: u8,
+
+    /// Whether the market authority reversed the result on dispute (found against the node)
+    pub dispute_reversed: bool,
+
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl JobAccount {
     /// Account size: 8 (discriminator) + size of fields
-    pub const LEN: usize = 8 + 32 + 32 + 32 + (1 + 32) + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + 1 + 1 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + (1 + 32) + 32 + 32 + 32 + 8 + 8 + 1 + 8 + 8 + 8 + 1 + 1 + 8 + 8 + 8 + 8 + (1 + 32) + 1 + 1 + (1 + 8) + (1 + 4) + (1 + 32) + 1 + 1 + 1;
 
     /// Job state helpers (inlined for performance)
     #[inline(always)]
@@ -86,11 +173,22 @@ impl JobAccount {
         self.state == JobState::Completed
     }
 
+    /// Result submitted but not yet final (PendingReview or Disputed)
+    #[inline(always)]
+    pub fn is_under_review(&self) -> bool {
+        matches!(self.state, JobState::PendingReview | JobState::Disputed)
+    }
+
+    /// No node work left: ended, or its result is under review
     #[inline(always)]
     pub fn is_finished(&self) -> bool {
         matches!(
             self.state,
-            JobState::Completed | JobState::Stopped | JobState::TimedOut
+            JobState::Completed
+                | JobState::Stopped
+                | JobState::TimedOut
+                | JobState::PendingReview
+                | JobState::Disputed
         )
     }
 
@@ -102,4 +200,617 @@ impl JobAccount {
         }
         current_time - self.time_start > self.timeout
     }
+
+    /// Check if the assigned node stopped sending heartbeats
+    #[inline]
+    pub fn is_node_dead(&self, current_time: i64, last_heartbeat: i64) -> bool {
+        if self.time_start == 0 {
+            return false;
+        }
+        current_time - last_heartbeat > NODE_LIVENESS_THRESHOLD
+    }
+
+    /// Check if job can be timed out
+    /// Either the hard timeout elapsed or the node's heartbeat went stale
+    #[inline]
+    pub fn can_timeout(&self, current_time: i64, last_heartbeat: Option<i64>) -> bool {
+        self.check_timeout(current_time)
+            || last_heartbeat.map_or(false, |hb| self.is_node_dead(current_time, hb))
+    }
+
+    /// Check `node` may take the job from the nodes queue
+    /// Only the node selected at creation can, until SELECTION_CLAIM_WINDOW
+    /// passes; then (or without a selection) any listed node can
+    pub fn admits_selected_node(&self, node: &Pubkey, current_time: i64) -> bool {
+        match self.selected_node {
+            Some(selected) => {
+                selected == *node || current_time - self.time_created > SELECTION_CLAIM_WINDOW
+            }
+            None => true,
+        }
+    }
+
+    /// Check an SLA deadline leaves room before the hard timeout (0 = none)
+    pub fn is_valid_sla_deadline(sla_deadline: i64, timeout: i64) -> bool {
+        sla_deadline == 0 || (sla_deadline > 0 && sla_deadline < timeout)
+    }
+
+    /// Penalty on `node_payment` for finishing at `finished_at`
+    /// Zero on or before the SLA deadline, then proportional to the lateness,
+    /// reaching MAX_SLA_PENALTY_BPS at the hard timeout
+    pub fn sla_penalty(&self, node_payment: u64, finished_at: i64) -> u64 {
+        if self.sla_deadline == 0 || self.time_start == 0 {
+            return 0;
+        }
+
+        let late = finished_at - self.time_start - self.sla_deadline;
+        if late <= 0 {
+            return 0;
+        }
+
+        let grace = (self.timeout - self.sla_deadline).max(1);
+        let late = late.min(grace);
+        (node_payment as u128 * MAX_SLA_PENALTY_BPS as u128 * late as u128
+            / (10_000 * grace as u128)) as u64
+    }
+
+    /// Check a retry budget is within MAX_JOB_RETRIES
+    pub fn is_valid_max_retries(max_retries: u8) -> bool {
+        max_retries <= MAX_JOB_RETRIES
+    }
+
+    /// Whether a failed run leaves the job another attempt
+    #[inline]
+    pub fn has_retries_left(&self) -> bool {
+        self.retry_count < self.max_retries
+    }
+
+    /// Take back a failed running job for another attempt: Running -> Queued
+    /// Uses one retry; the escrow stays in the vault for the next node.
+    /// The caller puts the job back in the market queue
+    pub fn retry(&mut self) -> Result<()> {
+        require!(self.is_running(), MarketError::JobNotRunning);
+        require!(self.has_retries_left(), MarketError::RetryBudgetExhausted);
+
+        self.retry_count += 1;
+        self.node = None;
+        self.node_account = Pubkey::default();
+        self.selected_node = None;
+        self.state = JobState::Queued;
+        self.time_start = 0;
+        self.time_end = 0;
+        Ok(())
+    }
+
+    /// Escrow released when the job ends without completing
+    /// (timeout_job, cancel_job)
+    /// Job fees are only charged on completed work: the rewards fee is split off
+    /// in finish_job, so an aborted job releases its full price and no share
+    /// of the escrow is left behind in the vault. cancel_job then takes the
+    /// market's cancellation fee out of it
+    pub fn abort_refund(&self) -> u64 {
+        self.price
+    }
+
+    /// Abort a running job agreed by both parties: Running -> Stopped
+    /// `client` and `node` are the signers; both must be the job's own.
+    /// Returns (node share, client refund) of the escrow, split by `node_share_bps`.
+    /// No fees are taken and nothing is reported against the node
+    pub fn mutual_cancel(
+        &mut self,
+        client: &Pubkey,
+        node: &Pubkey,
+        node_share_bps: u16,
+        current_time: i64,
+    ) -> Result<(u64, u64)> {
+        require!(self.is_running(), MarketError::JobNotRunning);
+        require!(
+            self.client == *client && self.node == Some(*node),
+            MarketError::Unauthorized
+        );
+        require!(node_share_bps <= 10_000, MarketError::InvalidNodeShare);
+
+        let escrow = self.abort_refund();
+        let node_share = (escrow as u128 * node_share_bps as u128 / 10_000) as u64;
+
+        self.state = JobState::Stopped;
+        self.time_end = current_time;
+        Ok((node_share, escrow - node_share))
+    }
+
+    /// Submit the result for review: Running -> PendingReview
+    /// The rewards fee is held in escrow until `release_at`
+    pub fn hold_rewards_fee(&mut self, fee: u64, release_at: i64) {
+        self.held_rewards_fee = fee;
+        self.fee_release_at = release_at;
+        self.state = JobState::PendingReview;
+    }
+
+    /// Client disputes a result under review while its fee is still held
+    /// PendingReview -> Disputed
+    pub fn dispute(&mut self, current_time: i64) -> Result<()> {
+        require!(self.state != JobState::Disputed, MarketError::JobDisputed);
+        require!(self.state == JobState::PendingReview, MarketError::JobNotPendingReview);
+        require!(self.held_rewards_fee > 0, MarketError::NoHeldFee);
+        require!(current_time < self.fee_release_at, MarketError::DisputeWindowClosed);
+
+        self.state = JobState::Disputed;
+        Ok(())
+    }
+
+    /// Resolve a dispute; returns the fee refunded to the client
+    /// Reversed jobs refund the held fee and are settled (Disputed -> Completed),
+    /// upheld ones go back to review and the fee is released by finalize_job
+    pub fn resolve_dispute(&mut self, reverse: bool) -> Result<u64> {
+        require!(self.state == JobState::Disputed, MarketError::JobNotDisputed);
+
+        if !reverse {
+            self.state = JobState::PendingReview;
+            return Ok(0);
+        }
+
+        let refund = self.held_rewards_fee;
+        self.held_rewards_fee = 0;
+        self.state = JobState::Completed;
+        self.dispute_reversed = true;
+        Ok(refund)
+    }
+
+    /// Release the held fee to the rewards pool once finalized
+    /// (dispute window over and no open dispute); returns the fee
+    /// PendingReview -> Completed
+    pub fn release_rewards_fee(&mut self, current_time: i64) -> Result<u64> {
+        require!(self.state != JobState::Disputed, MarketError::JobDisputed);
+        require!(self.state == JobState::PendingReview, MarketError::JobNotPendingReview);
+        require!(current_time >= self.fee_release_at, MarketError::DisputeWindowOpen);
+
+        let fee = self.held_rewards_fee;
+        self.held_rewards_fee = 0;
+        self.state = JobState::Completed;
+        Ok(fee)
+    }
+
+    /// Record the client's rating of the node once finish_job has run
+    /// (under review or completed); each job can be rated once.
+    /// Returns the rated node
+    pub fn rate(&mut self, rating: u8) -> Result<Pubkey> {
+        require!(
+            self.is_under_review() || self.is_completed(),
+            MarketError::JobNotCompleted
+        );
+        require!(self.rating == 0, MarketError::JobAlreadyRated);
+        require!(
+            (MIN_JOB_RATING..=MAX_JOB_RATING).contains(&rating),
+            MarketError::InvalidRating
+        );
+        let node = self.node.ok_or(MarketError::JobNotCompleted)?;
+
+        self.rating = rating;
+        Ok(node)
+    }
+
+    /// Check if a node's capabilities cover every required capability
+    #[inline]
+    pub fn node_has_capabilities(&self, node_capabilities: u64) -> bool {
+        node_capabilities & self.required_capabilities == self.required_capabilities
+    }
+
+    /// Check the job's price meets a node's floor (Node::min_job_price, 0 = any)
+    #[inline]
+    pub fn meets_node_min_price(&self, min_job_price: u64) -> bool {
+        self.price >= min_job_price
+    }
+
+    /// Record the client's scheduling hints
+    pub fn set_hints(&mut self, hints: JobHints) {
+        self.estimated_duration = hints.estimated_duration;
+        self.input_size_mb = hints.input_size_mb;
+        self.model_id = hints.model_id;
+    }
+
+    /// Scheduling hints recorded at creation
+    pub fn hints(&self) -> JobHints {
+        JobHints {
+            estimated_duration: self.estimated_duration,
+            input_size_mb: self.input_size_mb,
+            model_id: self.model_id,
+        }
+    }
+
+    /// Check the job's input fits a node's RAM
+    /// Jobs without an input size fit any node; with one, a node whose
+    /// hardware is unknown (no Nodes Program account) never fits
+    pub fn fits_node(&self, hardware: Option<NodeHardware>) -> bool {
+        match (self.input_size_mb, hardware) {
+            (Some(input_size_mb), Some(hardware)) => input_size_mb as u64 <= hardware.ram_gb as u64 * 1024,
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::market::DEFAULT_DISPUTE_WINDOW;
+
+    fn running_job(time_start: i64, timeout: i64) -> JobAccount {
+        JobAccount {
+            id: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            node: Some(Pubkey::new_unique()),
+            node_account: Pubkey::new_unique(),
+            ipfs_job: [1u8; 32],
+            ipfs_result: [0u8; 32],
+            price: 1_000_000,
+            timeout,
+            state: JobState::Running,
+            time_created: time_start,
+            time_start,
+            time_end: 0,
+            min_vram: 0,
+            gpu_type: 0,
+            required_capabilities: 0,
+            sla_deadline: 0,
+            held_rewards_fee: 0,
+            fee_release_at: 0,
+            selected_node: None,
+            max_retries: 0,
+            retry_count: 0,
+            estimated_duration: None,
+            input_size_mb: None,
+            model_id: None,
+            rating: 0,
+            dispute_reversed: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_early_timeout_dead_node() {
+        let job = running_job(1_000, 3_600);
+        let now = 1_000 + NODE_LIVENESS_THRESHOLD + 60;
+
+        // Hard timeout not reached, but last heartbeat was at job start
+        assert!(!job.check_timeout(now));
+        assert!(job.can_timeout(now, Some(1_000)));
+    }
+
+    #[test]
+    fn test_no_early_timeout_alive_node() {
+        let job = running_job(1_000, 3_600);
+        let now = 1_000 + NODE_LIVENESS_THRESHOLD + 60;
+
+        // Node heartbeat is fresh
+        assert!(!job.can_timeout(now, Some(now - 30)));
+
+        // Without node account only the normal timeout path applies
+        assert!(!job.can_timeout(now, None));
+        assert!(job.can_timeout(1_000 + 3_601, None));
+    }
+
+    #[test]
+    fn test_node_missing_capability_rejected() {
+        use hypernode_nodes::state::{CAPABILITY_CUDA_12, CAPABILITY_ONNX, CAPABILITY_PYTORCH};
+
+        let mut job = running_job(1_000, 3_600);
+        job.required_capabilities = CAPABILITY_PYTORCH | CAPABILITY_CUDA_12;
+
+        assert!(!job.node_has_capabilities(CAPABILITY_PYTORCH));
+        assert!(!job.node_has_capabilities(CAPABILITY_ONNX | CAPABILITY_CUDA_12));
+        assert!(job.node_has_capabilities(CAPABILITY_PYTORCH | CAPABILITY_CUDA_12));
+        assert!(job.node_has_capabilities(CAPABILITY_PYTORCH | CAPABILITY_CUDA_12 | CAPABILITY_ONNX));
+    }
+
+    #[test]
+    fn test_no_required_capabilities_matches_any_node() {
+        let job = running_job(1_000, 3_600);
+
+        assert!(job.node_has_capabilities(0));
+    }
+
+    #[test]
+    fn test_node_price_floor_skips_cheap_jobs() {
+        let mut cheap = running_job(1_000, 3_600);
+        cheap.price = 1_000_000;
+        let mut expensive = running_job(1_000, 3_600);
+        expensive.price = 50_000_000;
+        let floor = 10_000_000;
+
+        assert!(!cheap.meets_node_min_price(floor));
+        assert!(expensive.meets_node_min_price(floor));
+        expensive.price = floor;
+        assert!(expensive.meets_node_min_price(floor));
+
+        // Default floor accepts anything
+        assert!(cheap.meets_node_min_price(0));
+    }
+
+    #[test]
+    fn test_reversed_dispute_refunds_fee_to_client() {
+        let mut job = running_job(1_000, 3_600);
+        job.hold_rewards_fee(10_000, 2_000 + DEFAULT_DISPUTE_WINDOW);
+
+        // Fee can't reach stakers while the window is open
+        assert_eq!(
+            job.release_rewards_fee(2_000).err(),
+            Some(MarketError::DisputeWindowOpen.into())
+        );
+
+        job.dispute(2_100).unwrap();
+        assert!(job.dispute(2_200).is_err());
+
+        // Still held after the window while disputed
+        let after_window = 2_000 + DEFAULT_DISPUTE_WINDOW;
+        assert_eq!(
+            job.release_rewards_fee(after_window).err(),
+            Some(MarketError::JobDisputed.into())
+        );
+
+        // Reversed: the whole fee goes back to the client, nothing to stakers
+        assert_eq!(job.resolve_dispute(true).unwrap(), 10_000);
+        assert_eq!(job.held_rewards_fee, 0);
+        assert!(job.dispute_reversed);
+        assert!(job.release_rewards_fee(after_window).is_err());
+    }
+
+    #[test]
+    fn test_undisputed_fee_released_after_window() {
+        let mut job = running_job(1_000, 3_600);
+        job.hold_rewards_fee(10_000, 2_000 + DEFAULT_DISPUTE_WINDOW);
+
+        let after_window = 2_000 + DEFAULT_DISPUTE_WINDOW;
+        assert_eq!(
+            job.dispute(after_window).err(),
+            Some(MarketError::DisputeWindowClosed.into())
+        );
+        assert_eq!(job.release_rewards_fee(after_window).unwrap(), 10_000);
+
+        // Upheld dispute: fee stays with stakers
+        job.hold_rewards_fee(500, after_window + 100);
+        job.dispute(after_window).unwrap();
+        assert_eq!(job.resolve_dispute(false).unwrap(), 0);
+        assert!(!job.dispute_reversed);
+        assert_eq!(job.release_rewards_fee(after_window + 100).unwrap(), 500);
+    }
+
+    #[test]
+    fn test_on_sla_finish_gets_full_pay() {
+        let mut job = running_job(1_000, 3_600);
+        job.sla_deadline = 600;
+
+        assert_eq!(job.sla_penalty(990_000, 1_000 + 300), 0);
+        assert_eq!(job.sla_penalty(990_000, 1_000 + 600), 0);
+
+        // No SLA: never penalized
+        job.sla_deadline = 0;
+        assert_eq!(job.sla_penalty(990_000, 1_000 + 3_600), 0);
+    }
+
+    #[test]
+    fn test_late_finish_within_timeout_partially_penalized() {
+        let mut job = running_job(1_000, 3_600);
+        job.sla_deadline = 600;
+
+        // Halfway between the SLA and the hard timeout: half the max penalty (25%)
+        assert_eq!(job.sla_penalty(1_000_000, 1_000 + 2_100), 250_000);
+
+        // At the hard timeout: the max penalty
+        assert_eq!(job.sla_penalty(1_000_000, 1_000 + 3_600), 500_000);
+
+        // Slightly late costs slightly
+        let small = job.sla_penalty(1_000_000, 1_000 + 630);
+        assert!(small > 0 && small < 10_000);
+
+        assert!(JobAccount::is_valid_sla_deadline(600, 3_600));
+        assert!(!JobAccount::is_valid_sla_deadline(3_600, 3_600));
+        assert!(!JobAccount::is_valid_sla_deadline(-1, 3_600));
+    }
+
+    #[test]
+    fn test_nodes_program_accepts_our_stats_authority() {
+        use hypernode_nodes::state::{MARKETS_PROGRAM_ID, MARKETS_STATS_AUTHORITY_SEED};
+
+        // update_stats derives the signer PDA under this program's ID
+        assert_eq!(MARKETS_PROGRAM_ID, crate::ID);
+        let (expected, _) = Pubkey::find_program_address(&[MARKETS_STATS_AUTHORITY_SEED], &MARKETS_PROGRAM_ID);
+        let (ours, _) = Pubkey::find_program_address(&[MARKETS_STATS_AUTHORITY_SEED], &crate::ID);
+        assert_eq!(expected, ours);
+    }
+
+    #[test]
+    fn test_blacklisted_node_cannot_join_queue() {
+        use hypernode_nodes::state::Blacklist;
+
+        // list_node and work_job load the blacklist owned by the Nodes Program
+        assert_eq!(<Blacklist as anchor_lang::Owner>::owner(), hypernode_nodes::ID);
+
+        let banned = Pubkey::new_unique();
+        let blacklist = Blacklist { authorities: vec![banned], bump: 0 };
+        assert!(blacklist.is_banned(&banned));
+        assert!(!blacklist.is_banned(&Pubkey::new_unique()));
+    }
+
+
+    #[test]
+    fn test_review_state_transitions() {
+        let mut job = running_job(1_000, 3_600);
+        let release_at = 2_000 + DEFAULT_DISPUTE_WINDOW;
+
+        // finish_job: Running -> PendingReview
+        job.hold_rewards_fee(10_000, release_at);
+        assert!(job.state == JobState::PendingReview);
+        assert!(!job.is_running() && !job.is_completed());
+        assert!(job.is_finished() && job.is_under_review());
+
+        // dispute_job: PendingReview -> Disputed
+        job.dispute(2_100).unwrap();
+        assert!(job.state == JobState::Disputed);
+        assert!(job.is_finished() && job.is_under_review());
+        assert_eq!(job.dispute(2_200).err(), Some(MarketError::JobDisputed.into()));
+
+        // Upheld: back to PendingReview, then finalize_job: -> Completed
+        job.resolve_dispute(false).unwrap();
+        assert!(job.state == JobState::PendingReview);
+        assert_eq!(job.resolve_dispute(false).err(), Some(MarketError::JobNotDisputed.into()));
+        job.release_rewards_fee(release_at).unwrap();
+        assert!(job.is_completed() && !job.is_under_review());
+
+        // Settled jobs can't be disputed or finalized again
+        assert_eq!(job.dispute(2_300).err(), Some(MarketError::JobNotPendingReview.into()));
+        assert_eq!(
+            job.release_rewards_fee(release_at).err(),
+            Some(MarketError::JobNotPendingReview.into())
+        );
+
+        // Reversed: Disputed -> Completed
+        let mut job = running_job(1_000, 3_600);
+        job.hold_rewards_fee(10_000, release_at);
+        job.dispute(2_100).unwrap();
+        job.resolve_dispute(true).unwrap();
+        assert!(job.is_completed());
+
+        // A running job has no result to review
+        let mut job = running_job(1_000, 3_600);
+        assert_eq!(job.dispute(2_100).err(), Some(MarketError::JobNotPendingReview.into()));
+        assert!(!job.is_finished());
+    }
+
+    #[test]
+    fn test_job_rated_once_after_finish() {
+        let mut job = running_job(1_000, 3_600);
+
+        // Not before finish_job
+        assert_eq!(job.rate(5).err(), Some(MarketError::JobNotCompleted.into()));
+
+        // Under review: rated once, returns the node to credit
+        job.hold_rewards_fee(10_000, 2_000 + DEFAULT_DISPUTE_WINDOW);
+        assert_eq!(job.rate(0).err(), Some(MarketError::InvalidRating.into()));
+        assert_eq!(job.rate(MAX_JOB_RATING + 1).err(), Some(MarketError::InvalidRating.into()));
+        assert_eq!(job.rate(4).unwrap(), job.node.unwrap());
+        assert_eq!(job.rating, 4);
+
+        // Duplicate rating rejected, also once the job is completed
+        assert_eq!(job.rate(1).err(), Some(MarketError::JobAlreadyRated.into()));
+        job.release_rewards_fee(2_000 + DEFAULT_DISPUTE_WINDOW).unwrap();
+        assert_eq!(job.rate(1).err(), Some(MarketError::JobAlreadyRated.into()));
+        assert_eq!(job.rating, 4);
+
+        // Jobs that ended without a result can't be rated
+        let mut job = running_job(1_000, 3_600);
+        job.state = JobState::TimedOut;
+        assert_eq!(job.rate(1).err(), Some(MarketError::JobNotCompleted.into()));
+    }
+
+    #[test]
+    fn test_len_matches_serialized_size() {
+        let mut job = running_job(1_000, 3_600);
+        job.state = JobState::Disputed;
+        job.rating = MAX_JOB_RATING;
+        job.selected_node = Some(Pubkey::new_unique());
+        job.set_hints(JobHints {
+            estimated_duration: Some(600),
+            input_size_mb: Some(2_048),
+            model_id: Some([7u8; 32]),
+        });
+        let mut data = Vec::new();
+        job.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), JobAccount::LEN);
+    }
+
+    #[test]
+    fn test_mutual_cancel_splits_escrow_50_50() {
+        let mut job = running_job(1_000, 3_600);
+        let (client, node) = (job.client, job.node.unwrap());
+
+        let (node_share, refund) = job.mutual_cancel(&client, &node, 5_000, 1_500).unwrap();
+
+        assert_eq!(node_share, 500_000);
+        assert_eq!(refund, 500_000);
+        assert_eq!(node_share + refund, job.price);
+        assert!(job.state == JobState::Stopped);
+        assert_eq!(job.time_end, 1_500);
+
+        // Settled once only
+        assert!(job.mutual_cancel(&client, &node, 5_000, 1_600).is_err());
+    }
+
+    #[test]
+    fn test_mutual_cancel_needs_both_parties() {
+        let mut job = running_job(1_000, 3_600);
+        let (client, node) = (job.client, job.node.unwrap());
+
+        // One party signing for both roles
+        assert!(job.mutual_cancel(&client, &client, 5_000, 1_500).is_err());
+        assert!(job.mutual_cancel(&node, &node, 5_000, 1_500).is_err());
+
+        // A share over 100% is rejected too
+        assert!(job.mutual_cancel(&client, &node, 10_001, 1_500).is_err());
+
+        assert!(job.is_running());
+    }
+
+    #[test]
+    fn test_scheduling_hints_persist_through_submission() {
+        let hints = JobHints {
+            estimated_duration: Some(900),
+            input_size_mb: Some(4_096),
+            model_id: Some([3u8; 32]),
+        };
+        assert!(hints.is_valid(3_600));
+
+        // create_job records the hints; they survive the account round trip
+        // and a node claiming the job
+        let mut job = running_job(0, 3_600);
+        job.state = JobState::Queued;
+        job.node = None;
+        job.set_hints(hints);
+
+        let mut data = Vec::new();
+        job.try_serialize(&mut data).unwrap();
+        let mut stored = JobAccount::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(stored.hints(), hints);
+        assert_eq!(stored.estimated_duration, Some(900));
+        assert_eq!(stored.input_size_mb, Some(4_096));
+        assert_eq!(stored.model_id, Some([3u8; 32]));
+
+        stored.node = Some(Pubkey::new_unique());
+        stored.state = JobState::Running;
+        stored.time_start = 10;
+        assert_eq!(stored.hints(), hints);
+
+        // Hints are optional: none given, none stored
+        let mut plain = running_job(0, 3_600);
+        plain.set_hints(JobHints::default());
+        assert_eq!(plain.hints(), JobHints::default());
+        assert!(JobHints::default().is_valid(3_600));
+    }
+
+    #[test]
+    fn test_scheduling_hints_validated_and_checked_against_node_ram() {
+        // Estimates beyond the timeout (or non-positive) and empty inputs are rejected
+        let hint = |estimated_duration, input_size_mb| JobHints {
+            estimated_duration,
+            input_size_mb,
+            model_id: None,
+        };
+        assert!(hint(Some(3_600), None).is_valid(3_600));
+        assert!(!hint(Some(3_601), None).is_valid(3_600));
+        assert!(!hint(Some(0), None).is_valid(3_600));
+        assert!(!hint(None, Some(0)).is_valid(3_600));
+
+        // 4 GB of input needs a node with at least 4 GB of RAM
+        let mut job = running_job(0, 3_600);
+        job.set_hints(hint(None, Some(4_096)));
+        let node = |ram_gb| Some(NodeHardware { cpu_cores: 8, ram_gb, gpu_cores: 1 });
+        assert!(job.fits_node(node(4)));
+        assert!(!job.fits_node(node(3)));
+
+        // A sized job needs the node's hardware; unsized jobs fit anywhere
+        assert!(!job.fits_node(None));
+        job.set_hints(JobHints::default());
+        assert!(job.fits_node(node(0)));
+        assert!(job.fits_node(None));
+    }
 }
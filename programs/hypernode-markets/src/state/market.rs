@@ -1,4 +1,8 @@
 use anchor_lang::prelude::*;
+use super::job::{JobAccount, JobState};
+use super::reputation::ResponseEma;
+use crate::errors::MarketError;
+use hypernode_nodes::state::Node;
 
 /// HYPER Token Configuration
 /// Mint Address: 92s9qna3djkMncZzkacyNQ38UKnNXZFh4Jgqe3Cmpump
@@ -6,6 +10,74 @@ use anchor_lang::prelude::*;
 /// Decimals: 6
 pub const HYPER_DECIMALS: u8 = 6;
 
+/// Share of a node's payout withheld (refunded to the client) when the node
+/// no longer meets the market's stake minimum at finish_job
+pub const UNDERSTAKED_WITHHOLD_BPS: u64 = 5000; // 50%
+
+/// Share of each job payment sent to the rewards pool (1% per whitepaper)
+pub const REWARDS_FEE_BPS: u16 = 100;
+
+/// Highest cancellation fee a market can charge (bps of the escrow)
+pub const MAX_CANCELLATION_FEE_BPS: u16 = 1_000; // 10%
+
+/// Number of reputation tiers (Starter, Bronze, Silver, Gold, Diamond)
+pub const REPUTATION_TIER_COUNT: usize = 5;
+
+/// Highest stake multiplier a market can set for a reputation tier
+pub const MAX_TIER_STAKE_BPS: u16 = 30_000; // 3x
+
+/// Default window after finish_job during which the client can dispute (seconds)
+/// The rewards fee stays in escrow until it closes
+pub const DEFAULT_DISPUTE_WINDOW: i64 = 24 * 3600; // 24 hours
+
+/// Most queued nodes weighed in reputation-weighted selection
+/// Their NodeReputation accounts are read in work_job, so this bounds the
+/// accounts and compute it needs; nodes further back wait their turn
+pub const MAX_WEIGHTED_CANDIDATES: usize = 16;
+
+/// How long the node selected at create_job has to take the job, in seconds
+/// Past it any listed node may, so an offline pick can't strand the job
+pub const SELECTION_CLAIM_WINDOW: i64 = 300; // 5 minutes
+
+/// Mix the most recent slot hash with the job's sequence number in its
+/// market (`total_jobs` at creation) into selection entropy
+///
+/// `slot_hashes` is the raw SlotHashes sysvar: a u64 entry count followed
+/// by (slot, hash) entries, newest first. Returns None if it holds no entry.
+/// The client chooses neither input, so it can't grind the draw through
+/// its job ID; it can only pick the slot it submits in.
+///
+/// Not a real VRF: the leader that produced the slot can influence its hash,
+/// and anyone can predict the outcome once the slot is known. Good enough to
+/// spread jobs across honest nodes, not to resist a colluding validator.
+pub fn selection_entropy(slot_hashes: &[u8], sequence: u64) -> Option<u64> {
+    let hash = slot_hashes.get(16..48)?;
+    let mut entropy = sequence;
+    for hash_chunk in hash.chunks_exact(8) {
+        let hash_word = u64::from_le_bytes(hash_chunk.try_into().ok()?);
+        entropy = entropy.rotate_left(17) ^ hash_word;
+    }
+    Some(entropy)
+}
+
+/// Node hardware specs checked against a market's floor
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NodeHardware {
+    pub cpu_cores: u16,
+    pub ram_gb: u16,
+    pub gpu_cores: u16,
+}
+
+impl NodeHardware {
+    pub fn of(node: &Node) -> Self {
+        Self {
+            cpu_cores: node.cpu_cores,
+            ram_gb: node.ram_gb,
+            gpu_cores: node.gpu_cores,
+        }
+    }
+}
+
 /// Market account - manages GPU job marketplace
 /// Based on Nosana's MarketAccount pattern with dual queue system
 #[account]
@@ -37,19 +109,77 @@ pub struct MarketAccount {
     /// Vault PDA for escrow payments
     pub vault: Pubkey,
 
+    /// Dispute window after completion; the rewards fee is held until it closes
+    pub dispute_window: i64,
+
+    /// Share of each job payment paid to the market authority (0 = none)
+    pub market_fee_bps: u16,
+
+    /// How a job picks among queued nodes: 0=FIFO, 1=Reputation-weighted, 2=Random
+    pub selection_mode: u8,
+
+    /// Oldest node software (Node::version) allowed to list or take jobs (0 = any)
+    pub min_node_version: u32,
+
+    /// Hardware floor for nodes listing or taking jobs (0 = no minimum)
+    pub min_cpu_cores: u16,
+    pub min_ram_gb: u16,
+    pub min_gpu_cores: u16,
+
+    /// Share of the escrow kept by the market authority when a client cancels (0 = free)
+    pub cancellation_fee_bps: u16,
+
+    /// Stake minimum multiplier per reputation tier, Starter..Diamond
+    /// (bps of node_xhyper_minimum, 0 = no adjustment for that tier)
+    pub tier_stake_bps: [u16; REPUTATION_TIER_COUNT],
+
+    /// Lamports in the vault still owed on unsettled jobs: the price of queued
+    /// and running jobs, and rewards fees held through the dispute window
+    pub escrow_outstanding: u64,
+
+    /// Weight of the newest sample in node response time EMAs (bps)
+    pub response_ema_alpha_bps: u16,
+
+    /// Idle time after which a node's old response average keeps half its weight (0 = no decay)
+    pub response_ema_half_life: i64,
+
+    /// Most jobs or nodes this market's queue holds (set at create_market,
+    /// up to MAX_QUEUE_SIZE; the account is sized for it)
+    pub max_queue_size: u16,
+
     /// Queue of job/node pubkeys (dynamic vector)
     pub queue: Vec<Pubkey>,
 }
 
 impl MarketAccount {
-    /// Maximum queue size
+    /// Largest queue a market can be created with (account size limit)
     pub const MAX_QUEUE_SIZE: usize = 314;
 
+    /// Account size for a market whose queue holds `max_queue_size` entries
+    pub const fn space(max_queue_size: usize) -> usize {
+        8 + 32 + 8 + 8 + 16 + 1 + 1 + 8 + 8 + 32 + 8 + 2 + 1 + 4 + 2 + 2 + 2 + 2
+            + 2 * REPUTATION_TIER_COUNT
+            + 8 // escrow_outstanding
+            + 2 + 8 // response_ema_alpha_bps, response_ema_half_life
+            + 2 // max_queue_size
+            + 4 + 32 * max_queue_size // queue
+    }
+
+    /// Check a requested queue capacity fits the account limit
+    pub fn is_valid_max_queue_size(max_queue_size: u16) -> bool {
+        (1..=Self::MAX_QUEUE_SIZE).contains(&(max_queue_size as usize))
+    }
+
     /// Queue types
     pub const QUEUE_TYPE_EMPTY: u8 = 0;
     pub const QUEUE_TYPE_JOBS: u8 = 1;
     pub const QUEUE_TYPE_NODES: u8 = 2;
 
+    /// Node selection modes
+    pub const SELECTION_FIFO: u8 = 0;
+    pub const SELECTION_REPUTATION_WEIGHTED: u8 = 1;
+    pub const SELECTION_RANDOM: u8 = 2;
+
     /// Get current queue length
     #[inline(always)]
     pub fn queue_len(&self) -> usize {
@@ -59,8 +189,8 @@ impl MarketAccount {
     /// Add item to queue
     pub fn queue_push(&mut self, pubkey: Pubkey) -> Result<()> {
         require!(
-            self.queue.len() < Self::MAX_QUEUE_SIZE,
-            crate::errors::MarketError::QueueFull
+            self.queue.len() < self.max_queue_size as usize,
+            MarketError::QueueFull
         );
         self.queue.push(pubkey);
         Ok(())
@@ -90,4 +220,1263 @@ impl MarketAccount {
             false
         }
     }
+
+    /// Remove flagged nodes from the node queue
+    /// Returns the number of entries evicted
+    pub fn evict_flagged_nodes(&mut self, flagged: &[Pubkey]) -> usize {
+        if self.queue_type != Self::QUEUE_TYPE_NODES {
+            return 0;
+        }
+
+        let before = self.queue.len();
+        self.queue.retain(|key| !flagged.contains(key));
+        self.reset_queue_type_if_empty();
+        before - self.queue.len()
+    }
+
+    /// Take every listing of a node out of the node queue (unlist_node, reserve_node)
+    /// Returns the number of entries removed
+    pub fn unlist_node(&mut self, node: Pubkey) -> usize {
+        if self.queue_type != Self::QUEUE_TYPE_NODES {
+            return 0;
+        }
+
+        let before = self.queue.len();
+        self.queue.retain(|key| *key != node);
+        self.reset_queue_type_if_empty();
+        before - self.queue.len()
+    }
+
+    /// Check a selection mode is known
+    pub fn is_valid_selection_mode(selection_mode: u8) -> bool {
+        selection_mode <= Self::SELECTION_RANDOM
+    }
+
+    /// Number of queued nodes a job chooses between
+    pub fn selection_candidates(&self) -> usize {
+        match self.selection_mode {
+            Self::SELECTION_REPUTATION_WEIGHTED => self.queue.len().min(MAX_WEIGHTED_CANDIDATES),
+            _ => self.queue.len(),
+        }
+    }
+
+    /// Queued node a job is offered to
+    /// FIFO picks the longest-waiting node; Random draws uniformly from the queue
+    /// with `entropy`; Reputation-weighted draws from the first candidates in
+    /// proportion to `weights` (one per candidate, in queue order, each at least 1)
+    pub fn select_node(&self, entropy: u64, weights: &[u64]) -> Option<Pubkey> {
+        if self.queue_type != Self::QUEUE_TYPE_NODES || self.queue.is_empty() {
+            return None;
+        }
+
+        match self.selection_mode {
+            Self::SELECTION_RANDOM => {
+                let index = (entropy % self.queue.len() as u64) as usize;
+                Some(self.queue[index])
+            }
+            Self::SELECTION_REPUTATION_WEIGHTED => {
+                if weights.len() != self.selection_candidates() {
+                    return None;
+                }
+                let total: u64 = weights.iter().map(|w| (*w).max(1)).sum();
+                let mut pick = entropy % total;
+                for (node, weight) in self.queue.iter().zip(weights) {
+                    let weight = (*weight).max(1);
+                    if pick < weight {
+                        return Some(*node);
+                    }
+                    pick -= weight;
+                }
+                None
+            }
+            _ => self.queue.first().copied(),
+        }
+    }
+
+    /// Check a node's software version meets the market's minimum
+    /// Without the node's Nodes Program account the version is unknown,
+    /// which only passes when the market has no minimum
+    pub fn admits_node_version(&self, node_version: Option<u32>) -> bool {
+        self.min_node_version == 0
+            || node_version.is_some_and(|version| version >= self.min_node_version)
+    }
+
+    /// Whether the market sets any hardware minimum
+    pub fn has_hardware_floor(&self) -> bool {
+        self.min_cpu_cores > 0 || self.min_ram_gb > 0 || self.min_gpu_cores > 0
+    }
+
+    /// Check a node's hardware meets the market's floor
+    /// Without the node's Nodes Program account its specs are unknown,
+    /// which only passes when the market has no floor
+    pub fn admits_node_hardware(&self, hardware: Option<NodeHardware>) -> bool {
+        if !self.has_hardware_floor() {
+            return true;
+        }
+        hardware.is_some_and(|hardware| {
+            hardware.cpu_cores >= self.min_cpu_cores
+                && hardware.ram_gb >= self.min_ram_gb
+                && hardware.gpu_cores >= self.min_gpu_cores
+        })
+    }
+
+    /// Check a market fee leaves room for the rewards fee (total at most 100%)
+    pub fn is_valid_market_fee(market_fee_bps: u16) -> bool {
+        market_fee_bps as u32 + REWARDS_FEE_BPS as u32 <= 10_000
+    }
+
+    /// Split a job payment into (node payment, rewards fee, market fee)
+    pub fn split_job_payment(&self, price: u64) -> (u64, u64, u64) {
+        let rewards_fee = (price as u128 * REWARDS_FEE_BPS as u128 / 10_000) as u64;
+        let market_fee = (price as u128 * self.market_fee_bps as u128 / 10_000) as u64;
+        (price - rewards_fee - market_fee, rewards_fee, market_fee)
+    }
+
+    /// Response time EMA parameters finish_job applies to node reputations
+    pub fn response_ema(&self) -> ResponseEma {
+        ResponseEma {
+            alpha_bps: self.response_ema_alpha_bps,
+            decay_half_life: self.response_ema_half_life,
+        }
+    }
+
+    /// Check a cancellation fee is within MAX_CANCELLATION_FEE_BPS
+    pub fn is_valid_cancellation_fee(cancellation_fee_bps: u16) -> bool {
+        cancellation_fee_bps <= MAX_CANCELLATION_FEE_BPS
+    }
+
+    /// Record escrow paid into the vault for a job
+    pub fn hold_escrow(&mut self, amount: u64) -> Result<()> {
+        self.escrow_outstanding = self
+            .escrow_outstanding
+            .checked_add(amount)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record escrow paid out of the vault or released on settlement
+    /// Saturates so jobs created before escrow was tracked still settle
+    pub fn release_escrow(&mut self, amount: u64) {
+        self.escrow_outstanding = self.escrow_outstanding.saturating_sub(amount);
+    }
+
+    /// Vault balance left over once every job is settled (rounding leftovers;
+    /// finalize_job forwards released rewards fees out of the vault), above
+    /// the vault's rent-exempt minimum
+    /// Fails while any escrow is outstanding
+    pub fn sweepable_dust(&self, vault_balance: u64, rent_exempt: u64) -> Result<u64> {
+        require!(self.escrow_outstanding == 0, MarketError::EscrowOutstanding);
+        Ok(vault_balance.saturating_sub(rent_exempt))
+    }
+
+    /// Split a cancelled job's escrow into (client refund, cancellation fee)
+    pub fn split_cancellation(&self, escrow: u64) -> (u64, u64) {
+        let fee = (escrow as u128 * self.cancellation_fee_bps as u128 / 10_000) as u64;
+        (escrow - fee, fee)
+    }
+
+    /// Check every tier's stake multiplier is within MAX_TIER_STAKE_BPS
+    pub fn is_valid_tier_stake_bps(tier_stake_bps: &[u16; REPUTATION_TIER_COUNT]) -> bool {
+        tier_stake_bps.iter().all(|bps| *bps <= MAX_TIER_STAKE_BPS)
+    }
+
+    /// Stake minimum for a node of the given reputation tier
+    /// Nodes without a reputation account are treated as Starter
+    pub fn effective_stake_minimum(&self, tier: Option<u8>) -> u128 {
+        let tier = tier.unwrap_or(0) as usize;
+        let bps = self.tier_stake_bps[tier.min(REPUTATION_TIER_COUNT - 1)];
+        if bps == 0 {
+            return self.node_xhyper_minimum;
+        }
+        self.node_xhyper_minimum
+            .checked_mul(bps as u128)
+            .map_or(u128::MAX, |scaled| scaled / 10_000)
+    }
+
+    /// Check a node's stake still meets the minimum for its reputation tier
+    /// (the one list_node admitted it under). A market without a minimum (0)
+    /// doesn't require a stake at all
+    pub fn meets_stake_minimum(&self, tier: Option<u8>, stake_active: bool, xhyper: u128) -> bool {
+        let required = self.effective_stake_minimum(tier);
+        required == 0 || (stake_active && xhyper >= required)
+    }
+
+    /// Split a node payment into (paid to node, withheld for client)
+    /// Nodes that under-staked during execution forfeit UNDERSTAKED_WITHHOLD_BPS
+    pub fn apply_stake_policy(&self, node_payment: u64, meets_minimum: bool) -> (u64, u64) {
+        if meets_minimum {
+            return (node_payment, 0);
+        }
+
+        let withheld = (node_payment as u128 * UNDERSTAKED_WITHHOLD_BPS as u128 / 10_000) as u64;
+        (node_payment - withheld, withheld)
+    }
+
+    /// Dual-queue invariant: queue_type is EMPTY iff the queue is empty,
+    /// and is always one of the known queue types
+    pub fn queue_invariant_holds(&self) -> bool {
+        match self.queue_type {
+            Self::QUEUE_TYPE_EMPTY => self.queue_is_empty(),
+            Self::QUEUE_TYPE_JOBS | Self::QUEUE_TYPE_NODES => !self.queue_is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Restore the invariant (admin fix)
+    /// An empty queue is always EMPTY; a non-empty one takes `queue_type`
+    pub fn repair_queue_type(&mut self, queue_type: u8) -> Result<()> {
+        if self.queue_is_empty() {
+            self.queue_type = Self::QUEUE_TYPE_EMPTY;
+        } else {
+            require!(
+                queue_type == Self::QUEUE_TYPE_JOBS || queue_type == Self::QUEUE_TYPE_NODES,
+                MarketError::InvalidQueueType
+            );
+            self.queue_type = queue_type;
+        }
+        Ok(())
+    }
+
+    /// Reset queue type once the queue drains
+    fn reset_queue_type_if_empty(&mut self) {
+        if self.queue_is_empty() {
+            self.queue_type = Self::QUEUE_TYPE_EMPTY;
+        }
+    }
+
+    /// Put a node that just finished a job back in the node queue (auto-relist)
+    /// Returns false without queueing when jobs are waiting (the node should
+    /// claim one with work_job) or the node is still queued with free slots
+    pub fn relist_node(&mut self, node: Pubkey) -> Result<bool> {
+        if self.queue_type == Self::QUEUE_TYPE_JOBS || self.queue.contains(&node) {
+            return Ok(false);
+        }
+
+        self.queue_push(node)?;
+        self.queue_type = Self::QUEUE_TYPE_NODES;
+        self.total_nodes += 1;
+        Ok(true)
+    }
+
+    /// Remove a dangling job reference from the jobs queue
+    /// `job` is None when the referenced account is closed or no longer
+    /// deserializes; a live job is only purged once it is finished or
+    /// belongs to another market
+    pub fn purge_dead_job_entry(
+        &mut self,
+        entry: Pubkey,
+        job: Option<&JobAccount>,
+        market_key: Pubkey,
+    ) -> Result<()> {
+        require!(
+            self.queue_type == Self::QUEUE_TYPE_JOBS,
+            MarketError::InvalidQueueType
+        );
+
+        let dead = match job {
+            None => true,
+            Some(job) => job.is_finished() || job.market != market_key,
+        };
+        require!(dead, MarketError::QueueEntryLive);
+
+        require!(self.queue_remove(entry), MarketError::JobNotInQueue);
+        self.reset_queue_type_if_empty();
+
+        Ok(())
+    }
+
+    /// Cancel a queued job: verify state and dequeue in one step
+    /// Fails if the job was already claimed (or cancelled), so only one of
+    /// cancel_job / work_job can win for a given job. A job offered to a
+    /// waiting node (create_job, requeue_failed_job) was never pushed to the
+    /// queue; its selection is dropped instead
+    pub fn cancel_queued_job(
+        &mut self,
+        job: &mut JobAccount,
+        job_key: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(job.is_queued(), MarketError::JobNotQueued);
+
+        if self.queue_type == Self::QUEUE_TYPE_JOBS {
+            self.queue_remove(job_key);
+            self.reset_queue_type_if_empty();
+        }
+
+        job.selected_node = None;
+        job.state = JobState::Stopped;
+        job.time_end = current_time;
+        Ok(())
+    }
+
+    /// Put a job that just failed on its node back up for matching
+    /// Uses one of the job's retries. While nodes are waiting the job stays
+    /// out of the queue and the next work_job matches it, as in create_job;
+    /// otherwise it joins the back of the jobs queue.
+    /// Returns false, leaving the job as is, when its retry budget is spent
+    /// or the jobs queue is full (the caller then settles the failure)
+    pub fn requeue_failed_job(&mut self, job: &mut JobAccount, job_key: Pubkey) -> Result<bool> {
+        let nodes_waiting = self.queue_type == Self::QUEUE_TYPE_NODES;
+        if !job.has_retries_left()
+            || (!nodes_waiting && self.queue_len() >= self.max_queue_size as usize)
+        {
+            return Ok(false);
+        }
+
+        job.retry()?;
+        if !nodes_waiting {
+            self.queue_push(job_key)?;
+            self.queue_type = Self::QUEUE_TYPE_JOBS;
+        }
+        Ok(true)
+    }
+
+    /// Claim the job at the head of the jobs queue for a node
+    /// Re-checks job state so a just-cancelled job can never be claimed
+    pub fn claim_queued_job(
+        &mut self,
+        job: &mut JobAccount,
+        job_key: Pubkey,
+        node: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(job.is_queued(), MarketError::JobNotQueued);
+        require!(
+            self.queue_type == Self::QUEUE_TYPE_JOBS,
+            MarketError::InvalidQueueType
+        );
+        require!(!self.queue_is_empty(), MarketError::QueueEmpty);
+        require!(self.queue[0] == job_key, MarketError::JobNotInQueue);
+
+        self.queue_pop();
+        self.reset_queue_type_if_empty();
+
+        job.node = Some(node);
+        job.state = JobState::Running;
+        job.time_start = current_time;
+        Ok(())
+    }
+
+    /// Assign a job of the reserving client to its reserved node
+    /// Bypasses node selection; a job waiting in the jobs queue is taken
+    /// out of it wherever it is queued
+    pub fn assign_reserved_job(
+        &mut self,
+        job: &mut JobAccount,
+        job_key: Pubkey,
+        node: Pubkey,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(job.is_queued(), MarketError::JobNotQueued);
+
+        if self.queue_type == Self::QUEUE_TYPE_JOBS {
+            self.queue_remove(job_key);
+            self.reset_queue_type_if_empty();
+        }
+
+        job.node = Some(node);
+        job.state = JobState::Running;
+        job.time_start = current_time;
+        Ok(())
+    }
+
+    /// Match a job created while nodes were waiting to a queued node
+    /// Multi-slot nodes stay queued until `node_full` (all slots taken)
+    pub fn match_queued_node(
+        &mut self,
+        job: &mut JobAccount,
+        node: Pubkey,
+        node_full: bool,
+        current_time: i64,
+    ) -> Result<()> {
+        require!(job.is_queued(), MarketError::JobNotQueued);
+        require!(
+            self.queue_type == Self::QUEUE_TYPE_NODES,
+            MarketError::InvalidQueueType
+        );
+        require!(self.queue.contains(&node), MarketError::NodeNotInQueue);
+
+        if node_full {
+            self.queue_remove(node);
+            self.reset_queue_type_if_empty();
+        }
+
+        job.node = Some(node);
+        job.state = JobState::Running;
+        job.time_start = current_time;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::reputation::{
+        NodeReputation, DEFAULT_RESPONSE_EMA_ALPHA_BPS, DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE,
+    };
+    use crate::state::job::MAX_JOB_RETRIES;
+
+    fn market_with_job(job_key: Pubkey) -> MarketAccount {
+        MarketAccount {
+            authority: Pubkey::default(),
+            job_price: 1_000_000,
+            job_timeout: 3600,
+            node_xhyper_minimum: 0,
+            queue_type: MarketAccount::QUEUE_TYPE_JOBS,
+            vault_bump: 0,
+            total_jobs: 1,
+            total_nodes: 0,
+            vault: Pubkey::default(),
+            dispute_window: DEFAULT_DISPUTE_WINDOW,
+            market_fee_bps: 0,
+            selection_mode: MarketAccount::SELECTION_FIFO,
+            min_node_version: 0,
+            min_cpu_cores: 0,
+            min_ram_gb: 0,
+            min_gpu_cores: 0,
+            cancellation_fee_bps: 0,
+            tier_stake_bps: [0; REPUTATION_TIER_COUNT],
+            escrow_outstanding: 0,
+            response_ema_alpha_bps: DEFAULT_RESPONSE_EMA_ALPHA_BPS,
+            response_ema_half_life: DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE,
+            max_queue_size: MarketAccount::MAX_QUEUE_SIZE as u16,
+            queue: vec![job_key],
+        }
+    }
+
+    fn queued_job() -> JobAccount {
+        JobAccount {
+            id: Pubkey::new_unique(),
+            market: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            node: None,
+            node_account: Pubkey::default(),
+            ipfs_job: [1u8; 32],
+            ipfs_result: [0u8; 32],
+            price: 1_000_000,
+            timeout: 3600,
+            state: JobState::Queued,
+            time_created: 0,
+            time_start: 0,
+            time_end: 0,
+            min_vram: 0,
+            gpu_type: 0,
+            required_capabilities: 0,
+            sla_deadline: 0,
+            held_rewards_fee: 0,
+            fee_release_at: 0,
+            selected_node: None,
+            max_retries: 0,
+            retry_count: 0,
+            estimated_duration: None,
+            input_size_mb: None,
+            model_id: None,
+            rating: 0,
+            dispute_reversed: false,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_cancel_then_claim_only_cancel_wins() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        let mut job = queued_job();
+
+        assert!(market.cancel_queued_job(&mut job, job_key, 10).is_ok());
+        assert!(market.queue_is_empty());
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_EMPTY);
+
+        // Node tries to claim the just-cancelled job
+        assert!(market
+            .claim_queued_job(&mut job, job_key, Pubkey::new_unique(), 11)
+            .is_err());
+        assert!(job.state == JobState::Stopped);
+        assert!(job.node.is_none());
+    }
+
+    #[test]
+    fn test_purge_closed_job_reference() {
+        let market_key = Pubkey::new_unique();
+        let dead_key = Pubkey::new_unique();
+        let live_key = Pubkey::new_unique();
+        let mut market = market_with_job(dead_key);
+        market.queue.push(live_key);
+
+        let mut live_job = queued_job();
+        live_job.market = market_key;
+
+        // A live queued job stays
+        assert_eq!(
+            market.purge_dead_job_entry(live_key, Some(&live_job), market_key).err(),
+            Some(MarketError::QueueEntryLive.into())
+        );
+
+        // Closed account: removed, the rest of the queue is kept
+        assert!(market.purge_dead_job_entry(dead_key, None, market_key).is_ok());
+        assert_eq!(market.queue, vec![live_key]);
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_JOBS);
+
+        // Already purged
+        assert!(market.purge_dead_job_entry(dead_key, None, market_key).is_err());
+
+        // Finished job: removed and queue_type reset once the queue drains
+        live_job.state = JobState::Stopped;
+        assert!(market.purge_dead_job_entry(live_key, Some(&live_job), market_key).is_ok());
+        assert!(market.queue_is_empty());
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_EMPTY);
+    }
+
+    #[test]
+    fn test_claim_then_cancel_only_claim_wins() {
+        let job_key = Pubkey::new_unique();
+        let node = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        let mut job = queued_job();
+
+        assert!(market.claim_queued_job(&mut job, job_key, node, 10).is_ok());
+        assert!(market.queue_is_empty());
+
+        // Client tries to cancel (and get refunded) after the claim
+        assert!(market.cancel_queued_job(&mut job, job_key, 11).is_err());
+        assert!(job.state == JobState::Running);
+        assert_eq!(job.node, Some(node));
+    }
+
+    #[test]
+    fn test_cancel_twice_rejected() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        let mut job = queued_job();
+
+        assert!(market.cancel_queued_job(&mut job, job_key, 10).is_ok());
+        assert!(market.cancel_queued_job(&mut job, job_key, 11).is_err());
+    }
+
+    #[test]
+    fn test_cancel_job_offered_to_waiting_node() {
+        let node = Pubkey::new_unique();
+        let mut market = market_with_job(node);
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+
+        // Created while the node was waiting: offered to it, never pushed
+        let job_key = Pubkey::new_unique();
+        let mut job = queued_job();
+        job.selected_node = market.select_node(0, &[]);
+        assert_eq!(job.selected_node, Some(node));
+
+        assert!(market.cancel_queued_job(&mut job, job_key, 10).is_ok());
+        assert!(job.state == JobState::Stopped);
+        assert!(job.selected_node.is_none());
+
+        // The waiting node keeps its place in the queue
+        assert_eq!(market.queue, vec![node]);
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_NODES);
+    }
+
+    #[test]
+    fn test_flagged_node_evicted_and_never_matched() {
+        let healthy = Pubkey::new_unique();
+        let flagged = Pubkey::new_unique();
+        let mut market = market_with_job(flagged);
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        market.queue.push(healthy);
+
+        assert_eq!(market.evict_flagged_nodes(&[flagged]), 1);
+        assert!(!market.queue.contains(&flagged));
+
+        // Next match goes to the healthy node
+        assert_eq!(market.queue_pop(), Some(healthy));
+        assert_eq!(market.queue_pop(), None);
+    }
+
+    #[test]
+    fn test_evicting_last_node_empties_queue() {
+        let flagged = Pubkey::new_unique();
+        let mut market = market_with_job(flagged);
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+
+        assert_eq!(market.evict_flagged_nodes(&[flagged]), 1);
+        assert!(market.queue_is_empty());
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_EMPTY);
+    }
+
+    #[test]
+    fn test_evict_ignores_job_queue() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+
+        assert_eq!(market.evict_flagged_nodes(&[job_key]), 0);
+        assert_eq!(market.queue_len(), 1);
+    }
+
+    #[test]
+    fn test_inconsistent_queue_type_detected() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        assert!(market.queue_invariant_holds());
+
+        // NODES with an empty queue
+        market.queue.clear();
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        assert!(!market.queue_invariant_holds());
+
+        // EMPTY with queued items
+        market.queue.push(job_key);
+        market.queue_type = MarketAccount::QUEUE_TYPE_EMPTY;
+        assert!(!market.queue_invariant_holds());
+
+        // Unknown queue type
+        market.queue_type = 7;
+        assert!(!market.queue_invariant_holds());
+    }
+
+    #[test]
+    fn test_repair_restores_invariant() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+
+        market.queue_type = MarketAccount::QUEUE_TYPE_EMPTY;
+        assert!(market.repair_queue_type(MarketAccount::QUEUE_TYPE_EMPTY).is_err());
+        assert!(market.repair_queue_type(MarketAccount::QUEUE_TYPE_JOBS).is_ok());
+        assert!(market.queue_invariant_holds());
+
+        // Empty queue is always repaired to EMPTY
+        market.queue.clear();
+        assert!(market.repair_queue_type(MarketAccount::QUEUE_TYPE_NODES).is_ok());
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_EMPTY);
+    }
+
+    #[test]
+    fn test_node_unstaked_mid_job_has_payout_withheld() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.node_xhyper_minimum = 1_000;
+
+        // Still staked: paid in full
+        assert!(market.meets_stake_minimum(Some(2), true, 1_000));
+        assert_eq!(market.apply_stake_policy(990, true), (990, 0));
+
+        // Unstaked (stake inactive) or below minimum after being matched
+        assert!(!market.meets_stake_minimum(Some(2), false, 5_000));
+        assert!(!market.meets_stake_minimum(Some(2), true, 999));
+
+        let (paid, withheld) = market.apply_stake_policy(990, false);
+        assert_eq!(paid, 495);
+        assert_eq!(withheld, 495);
+        assert_eq!(paid + withheld, 990);
+
+        // No minimum: an unstaked node (or one without a stake account) is paid in full
+        market.node_xhyper_minimum = 0;
+        assert!(market.meets_stake_minimum(Some(2), false, 0));
+        assert_eq!(market.apply_stake_policy(990, true), (990, 0));
+    }
+
+    #[test]
+    fn test_stake_minimum_varies_by_reputation_tier() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.node_xhyper_minimum = 10_000;
+
+        // No multipliers set: flat minimum for every tier
+        assert_eq!(market.effective_stake_minimum(Some(4)), 10_000);
+        assert_eq!(market.effective_stake_minimum(None), 10_000);
+
+        // Starter pays a premium, Diamond gets a discount, Silver is flat
+        market.tier_stake_bps = [15_000, 12_000, 0, 8_000, 5_000];
+        assert!(MarketAccount::is_valid_tier_stake_bps(&market.tier_stake_bps));
+        assert_eq!(market.effective_stake_minimum(Some(0)), 15_000);
+        assert_eq!(market.effective_stake_minimum(Some(1)), 12_000);
+        assert_eq!(market.effective_stake_minimum(Some(2)), 10_000);
+        assert_eq!(market.effective_stake_minimum(Some(3)), 8_000);
+        assert_eq!(market.effective_stake_minimum(Some(4)), 5_000);
+
+        // Without a reputation account the node is treated as Starter
+        assert_eq!(market.effective_stake_minimum(None), 15_000);
+
+        // The finish_job re-check uses the same scaled minimum as admission
+        assert!(market.meets_stake_minimum(Some(4), true, 5_000));
+        assert!(!market.meets_stake_minimum(Some(0), true, 10_000));
+
+        assert!(!MarketAccount::is_valid_tier_stake_bps(&[0, 0, 0, 0, MAX_TIER_STAKE_BPS + 1]));
+    }
+
+    #[test]
+    fn test_market_fee_paid_to_authority() {
+        let mut market = market_with_job(Pubkey::new_unique());
+
+        // No market fee by default: only the 1% rewards fee
+        assert_eq!(market.split_job_payment(1_000_000), (990_000, 10_000, 0));
+
+        // 2.5% market fee on top of the rewards fee
+        assert!(MarketAccount::is_valid_market_fee(250));
+        market.market_fee_bps = 250;
+        let (node_payment, rewards_fee, market_fee) = market.split_job_payment(1_000_000);
+        assert_eq!(market_fee, 25_000);
+        assert_eq!(rewards_fee, 10_000);
+        assert_eq!(node_payment, 965_000);
+        assert_eq!(node_payment + rewards_fee + market_fee, 1_000_000);
+
+        // Fees together can't exceed 100%
+        assert!(MarketAccount::is_valid_market_fee(10_000 - REWARDS_FEE_BPS));
+        assert!(!MarketAccount::is_valid_market_fee(10_000 - REWARDS_FEE_BPS + 1));
+        market.market_fee_bps = 10_000 - REWARDS_FEE_BPS;
+        assert_eq!(market.split_job_payment(1_000_000).0, 0);
+    }
+
+    fn idle_reputation() -> NodeReputation {
+        NodeReputation {
+            authority: Pubkey::new_unique(),
+            total_jobs: 0,
+            failed_jobs: 0,
+            timeout_jobs: 0,
+            avg_response_time: 0,
+            total_uptime: 0,
+            last_active: 0,
+            reputation_score: 1000,
+            tier: 4,
+            total_revenue: 0,
+            recent_revenue: 0,
+            recent_window_start: 0,
+            active_slots: 0,
+            active_listings: 0,
+            auto_relist: false,
+            rating_count: 0,
+            rating_total: 0,
+            audit_bonus: 0,
+            node_account: Pubkey::default(),
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_two_slot_node_matched_twice_before_dequeue() {
+        let node = Pubkey::new_unique();
+        let mut market = market_with_job(node);
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        let mut reputation = idle_reputation();
+        let (mut job_a, mut job_b, mut job_c) = (queued_job(), queued_job(), queued_job());
+
+        // First match: one slot left, node stays queued
+        let full = reputation.reserve_slot(2).unwrap();
+        market.match_queued_node(&mut job_a, node, full, 10).unwrap();
+        assert_eq!(market.queue, vec![node]);
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_NODES);
+
+        // Second match fills the node: dequeued
+        let full = reputation.reserve_slot(2).unwrap();
+        market.match_queued_node(&mut job_b, node, full, 11).unwrap();
+        assert!(market.queue_is_empty());
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_EMPTY);
+        assert_eq!(job_a.node, Some(node));
+        assert_eq!(job_b.node, Some(node));
+        assert!(job_b.state == JobState::Running);
+
+        // No third job until a slot frees up
+        assert!(reputation.reserve_slot(2).is_err());
+        assert!(market.match_queued_node(&mut job_c, node, false, 12).is_err());
+        assert_eq!(reputation.active_slots, 2);
+
+        reputation.release_slot();
+        assert!(reputation.reserve_slot(2).unwrap());
+    }
+
+    /// list_node: the node's reputation records the listing, then the market queues it
+    fn list(market: &mut MarketAccount, reputation: &mut NodeReputation, max_slots: u8) -> Result<()> {
+        reputation.reserve_listing(max_slots)?;
+        market.queue_push(reputation.authority)?;
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_slot_node_not_double_booked_across_markets() {
+        let mut reputation = idle_reputation();
+        let node = reputation.authority;
+        let mut market_a = market_with_job(Pubkey::new_unique());
+        let mut market_b = market_with_job(Pubkey::new_unique());
+        market_a.queue.clear();
+        market_b.queue.clear();
+
+        // Listed in A, it can't also wait in B
+        list(&mut market_a, &mut reputation, 1).unwrap();
+        assert_eq!(
+            list(&mut market_b, &mut reputation, 1).unwrap_err(),
+            MarketError::NodeAlreadyBooked.into()
+        );
+        assert!(market_b.queue_is_empty());
+
+        // Nor claim a job waiting in B's jobs queue while A may match it
+        assert_eq!(
+            reputation.reserve_slot(1).unwrap_err(),
+            MarketError::NodeAlreadyBooked.into()
+        );
+
+        // A matches it: the listing becomes the job and the node leaves A's queue
+        let mut job = queued_job();
+        let full = reputation.take_listed_slot(1).unwrap();
+        assert!(full);
+        market_a.match_queued_node(&mut job, node, full, 10).unwrap();
+        assert!(market_a.queue_is_empty());
+        assert_eq!((reputation.active_slots, reputation.active_listings), (1, 0));
+
+        // Busy with A's job, still no room in B
+        assert!(list(&mut market_b, &mut reputation, 1).is_err());
+
+        // Once the job finishes it can wait in B
+        reputation.release_slot();
+        list(&mut market_b, &mut reputation, 1).unwrap();
+        assert_eq!(market_b.queue, vec![node]);
+    }
+
+    #[test]
+    fn test_two_slot_node_listed_in_two_markets() {
+        let mut reputation = idle_reputation();
+        let node = reputation.authority;
+        let mut market_a = market_with_job(Pubkey::new_unique());
+        let mut market_b = market_with_job(Pubkey::new_unique());
+        let mut market_c = market_with_job(Pubkey::new_unique());
+        for market in [&mut market_a, &mut market_b, &mut market_c] {
+            market.queue.clear();
+        }
+
+        // One slot offered in each of A and B, nothing left for C
+        list(&mut market_a, &mut reputation, 2).unwrap();
+        list(&mut market_b, &mut reputation, 2).unwrap();
+        assert!(list(&mut market_c, &mut reputation, 2).is_err());
+
+        // A match in A uses A's listing; the node stays waiting in B only
+        let mut job = queued_job();
+        let full = reputation.take_listed_slot(2).unwrap();
+        market_a.match_queued_node(&mut job, node, full, 10).unwrap();
+        assert!(market_a.queue_is_empty());
+        assert_eq!(market_b.queue, vec![node]);
+        assert_eq!(reputation.booked_slots(), 2);
+
+        // B matches the last free slot
+        let mut job = queued_job();
+        let full = reputation.take_listed_slot(2).unwrap();
+        market_b.match_queued_node(&mut job, node, full, 11).unwrap();
+        assert!(market_b.queue_is_empty());
+        assert_eq!((reputation.active_slots, reputation.active_listings), (2, 0));
+    }
+
+    #[test]
+    fn test_single_slot_node_dequeued_on_first_match() {
+        let node = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let mut market = market_with_job(node);
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        market.queue.push(other);
+        let mut reputation = idle_reputation();
+        let mut job = queued_job();
+
+        let full = reputation.reserve_slot(1).unwrap();
+        market.match_queued_node(&mut job, node, full, 10).unwrap();
+        assert_eq!(market.queue, vec![other]);
+
+        // A node that isn't queued can't be matched this way
+        let mut job = queued_job();
+        assert!(market.match_queued_node(&mut job, node, false, 11).is_err());
+    }
+
+    #[test]
+    fn test_timeout_refunds_full_price_without_fee() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        market.market_fee_bps = 250;
+
+        // finish_job: node payment and both fees account for the whole escrow
+        let (node_payment, rewards_fee, market_fee) = market.split_job_payment(1_000_000);
+        assert!(rewards_fee > 0);
+        assert_eq!(node_payment + rewards_fee + market_fee, 1_000_000);
+
+        // timeout_job: the whole escrow goes back, nothing is held for the rewards pool
+        let mut job = queued_job();
+        job.state = JobState::Running;
+        job.time_start = 1_000;
+        assert!(job.can_timeout(1_000 + 3_601, None));
+        job.state = JobState::TimedOut;
+        assert_eq!(job.abort_refund(), job.price);
+        assert_eq!(job.held_rewards_fee, 0);
+        assert!(job.release_rewards_fee(i64::MAX).is_err());
+
+        // cancel_job follows the same policy
+        let mut job = queued_job();
+        market.cancel_queued_job(&mut job, job_key, 10).unwrap();
+        assert_eq!(job.abort_refund(), job.price);
+        assert_eq!(job.held_rewards_fee, 0);
+    }
+
+    #[test]
+    fn test_job_retries_twice_then_refunds_on_third_failure() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        let mut job = queued_job();
+        job.max_retries = 2;
+        assert!(JobAccount::is_valid_max_retries(MAX_JOB_RETRIES));
+        assert!(!JobAccount::is_valid_max_retries(MAX_JOB_RETRIES + 1));
+
+        for attempt in 1..=2u8 {
+            // A node claims the job, then lets it time out
+            let node = Pubkey::new_unique();
+            market.claim_queued_job(&mut job, job_key, node, 1_000).unwrap();
+            job.node_account = Pubkey::new_unique();
+            assert!(job.can_timeout(1_000 + 3_601, None));
+
+            // timeout_job: back at the end of the jobs queue, escrow untouched
+            assert!(market.requeue_failed_job(&mut job, job_key).unwrap());
+            assert_eq!(job.retry_count, attempt);
+            assert!(job.is_queued());
+            assert!(job.node.is_none());
+            assert_eq!(job.node_account, Pubkey::default());
+            assert_eq!(job.time_start, 0);
+            assert_eq!(job.price, 1_000_000);
+            assert_eq!(market.queue, vec![job_key]);
+            assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_JOBS);
+        }
+
+        // Third failure: budget spent, the job is left for timeout_job to refund
+        market.claim_queued_job(&mut job, job_key, Pubkey::new_unique(), 9_000).unwrap();
+        assert!(!market.requeue_failed_job(&mut job, job_key).unwrap());
+        assert!(job.is_running());
+        assert!(market.queue_is_empty());
+        assert_eq!(
+            job.retry().unwrap_err(),
+            MarketError::RetryBudgetExhausted.into()
+        );
+        job.state = JobState::TimedOut;
+        assert_eq!(job.abort_refund(), 1_000_000);
+    }
+
+    #[test]
+    fn test_retried_job_waits_for_node_when_nodes_queued() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        let mut job = queued_job();
+        job.max_retries = 1;
+        market.claim_queued_job(&mut job, job_key, Pubkey::new_unique(), 1_000).unwrap();
+
+        // Nodes waiting: the job isn't queued, the next work_job matches it
+        let node = Pubkey::new_unique();
+        market.queue = vec![node];
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        assert!(market.requeue_failed_job(&mut job, job_key).unwrap());
+        assert_eq!(market.queue, vec![node]);
+        market.match_queued_node(&mut job, node, true, 2_000).unwrap();
+        assert_eq!(job.node, Some(node));
+
+        // A full jobs queue can't take a retry: settled as a failure instead
+        let mut job = queued_job();
+        job.max_retries = 1;
+        job.state = JobState::Running;
+        market.queue_type = MarketAccount::QUEUE_TYPE_JOBS;
+        market.max_queue_size = 1;
+        market.queue = vec![Pubkey::new_unique()];
+        assert!(!market.requeue_failed_job(&mut job, job_key).unwrap());
+        assert_eq!(job.retry_count, 0);
+        assert!(job.is_running());
+    }
+
+    fn node_queue(nodes: &[Pubkey], selection_mode: u8) -> MarketAccount {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.queue = nodes.to_vec();
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        market.selection_mode = selection_mode;
+        market
+    }
+
+    #[test]
+    fn test_fifo_selects_longest_waiting_node() {
+        let nodes: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let market = node_queue(&nodes, MarketAccount::SELECTION_FIFO);
+
+        for entropy in [0, 1, 7, u64::MAX] {
+            assert_eq!(market.select_node(entropy, &[]), Some(nodes[0]));
+        }
+    }
+
+    #[test]
+    fn test_random_selection_varies_with_entropy() {
+        let nodes: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let market = node_queue(&nodes, MarketAccount::SELECTION_RANDOM);
+
+        // Same entropy, same node
+        assert_eq!(market.select_node(5, &[]), market.select_node(5, &[]));
+
+        // Jobs created in different slots draw different nodes
+        let picked: Vec<Pubkey> = (0u8..32)
+            .map(|slot| {
+                let mut slot_hashes = vec![0u8; 48];
+                slot_hashes[0] = 1;
+                slot_hashes[16..48].copy_from_slice(&[slot.wrapping_mul(37).wrapping_add(11); 32]);
+                let entropy = selection_entropy(&slot_hashes, 7).unwrap();
+                market.select_node(entropy, &[]).unwrap()
+            })
+            .collect();
+        assert!(picked.iter().all(|node| nodes.contains(node)));
+        assert!(nodes.iter().filter(|node| picked.contains(node)).count() > 1);
+
+        // Within a slot the draw follows the market's job sequence, not the client
+        let mut slot_hashes = vec![0u8; 48];
+        slot_hashes[0] = 1;
+        slot_hashes[16..48].copy_from_slice(&[42; 32]);
+        assert_eq!(selection_entropy(&slot_hashes, 7), selection_entropy(&slot_hashes, 7));
+        assert_ne!(selection_entropy(&slot_hashes, 7), selection_entropy(&slot_hashes, 8));
+
+        // No slot hash, no entropy
+        assert!(selection_entropy(&[0u8; 8], 7).is_none());
+    }
+
+    #[test]
+    fn test_reputation_weighted_selection_favours_high_scores() {
+        let nodes: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let market = node_queue(&nodes, MarketAccount::SELECTION_REPUTATION_WEIGHTED);
+        let weights = [100, 0, 900];
+
+        let mut hits = [0u32; 3];
+        for entropy in 0..1_000u64 {
+            let node = market.select_node(entropy, &weights).unwrap();
+            hits[nodes.iter().position(|n| *n == node).unwrap()] += 1;
+        }
+        assert_eq!(hits, [100, 1, 899]);
+
+        // Weights must cover exactly the candidates
+        assert!(market.select_node(0, &weights[..2]).is_none());
+    }
+
+    #[test]
+    fn test_weighted_candidates_capped() {
+        let nodes: Vec<Pubkey> = (0..MAX_WEIGHTED_CANDIDATES + 4).map(|_| Pubkey::new_unique()).collect();
+        let market = node_queue(&nodes, MarketAccount::SELECTION_REPUTATION_WEIGHTED);
+        assert_eq!(market.selection_candidates(), MAX_WEIGHTED_CANDIDATES);
+
+        let weights = vec![1u64; MAX_WEIGHTED_CANDIDATES];
+        for entropy in 0..100 {
+            let node = market.select_node(entropy, &weights).unwrap();
+            assert!(nodes[..MAX_WEIGHTED_CANDIDATES].contains(&node));
+        }
+
+        assert!(MarketAccount::is_valid_selection_mode(MarketAccount::SELECTION_RANDOM));
+        assert!(!MarketAccount::is_valid_selection_mode(3));
+    }
+
+    #[test]
+    fn test_selected_node_claims_job_or_selection_lapses() {
+        let nodes: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let market = node_queue(&nodes, MarketAccount::SELECTION_RANDOM);
+        let mut job = queued_job();
+        job.time_created = 1_000;
+
+        // Stored at create_job: only the pick may take the job at first
+        job.selected_node = market.select_node(5, &[]);
+        let selected = job.selected_node.unwrap();
+        let other = *nodes.iter().find(|node| **node != selected).unwrap();
+        assert!(job.admits_selected_node(&selected, 1_000));
+        assert!(!job.admits_selected_node(&other, 1_000 + SELECTION_CLAIM_WINDOW));
+
+        // The pick went offline: any listed node takes the job once the window passes
+        assert!(job.admits_selected_node(&other, 1_000 + SELECTION_CLAIM_WINDOW + 1));
+
+        // No pick (queued behind other jobs, or re-queued): any listed node
+        job.selected_node = None;
+        assert!(job.admits_selected_node(&other, 1_000));
+    }
+
+    #[test]
+    fn test_outdated_node_version_rejected() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.min_node_version = 3;
+
+        assert!(!market.admits_node_version(Some(2)));
+        assert!(!market.admits_node_version(None));
+    }
+
+    #[test]
+    fn test_current_node_version_admitted() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        assert!(market.admits_node_version(None));
+
+        market.min_node_version = 3;
+        assert!(market.admits_node_version(Some(3)));
+        assert!(market.admits_node_version(Some(4)));
+    }
+
+    #[test]
+    fn test_auto_relisted_node_back_in_queue_after_finish() {
+        let node = Pubkey::new_unique();
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.queue.clear();
+        market.queue_type = MarketAccount::QUEUE_TYPE_NODES;
+        market.queue_push(node).unwrap();
+        let mut job = queued_job();
+        let mut reputation = idle_reputation();
+        reputation.auto_relist = true;
+
+        // Single-slot node takes the job and leaves the queue
+        let full = reputation.reserve_slot(1).unwrap();
+        market.match_queued_node(&mut job, node, full, 10).unwrap();
+        assert!(market.queue_is_empty());
+
+        // finish_job frees the slot and relists straight away
+        reputation.release_slot();
+        assert!(market.relist_node(node).unwrap());
+        assert_eq!(market.queue, vec![node]);
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_NODES);
+
+        // Already queued: not added twice
+        assert!(!market.relist_node(node).unwrap());
+        assert_eq!(market.queue_len(), 1);
+    }
+
+    #[test]
+    fn test_auto_relist_skipped_while_jobs_wait() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+
+        assert!(!market.relist_node(Pubkey::new_unique()).unwrap());
+        assert_eq!(market.queue, vec![job_key]);
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_JOBS);
+    }
+
+    fn hardware(cpu_cores: u16, ram_gb: u16, gpu_cores: u16) -> Option<NodeHardware> {
+        Some(NodeHardware { cpu_cores, ram_gb, gpu_cores })
+    }
+
+    #[test]
+    fn test_underpowered_node_rejected_by_hardware_floor() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.min_cpu_cores = 16;
+        market.min_ram_gb = 64;
+        market.min_gpu_cores = 1;
+
+        // Short on any one spec
+        assert!(!market.admits_node_hardware(hardware(8, 64, 1)));
+        assert!(!market.admits_node_hardware(hardware(16, 32, 1)));
+        assert!(!market.admits_node_hardware(hardware(16, 64, 0)));
+
+        // Specs unknown
+        assert!(!market.admits_node_hardware(None));
+
+        assert!(market.admits_node_hardware(hardware(16, 64, 1)));
+        assert!(market.admits_node_hardware(hardware(32, 128, 4)));
+    }
+
+    #[test]
+    fn test_market_without_hardware_floor_admits_any_node() {
+        let market = market_with_job(Pubkey::new_unique());
+
+        assert!(!market.has_hardware_floor());
+        assert!(market.admits_node_hardware(None));
+        assert!(market.admits_node_hardware(hardware(0, 0, 0)));
+    }
+
+    #[test]
+    fn test_cancel_refunds_escrow_minus_fee() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        let mut job = queued_job();
+        market.cancel_queued_job(&mut job, job_key, 10).unwrap();
+
+        // Free by default
+        assert_eq!(market.split_cancellation(job.abort_refund()), (job.price, 0));
+
+        // 2% fee to the market authority, the rest back to the client
+        market.cancellation_fee_bps = 200;
+        let (refund, fee) = market.split_cancellation(1_000_000);
+        assert_eq!(fee, 20_000);
+        assert_eq!(refund, 980_000);
+
+        assert!(MarketAccount::is_valid_cancellation_fee(MAX_CANCELLATION_FEE_BPS));
+        assert!(!MarketAccount::is_valid_cancellation_fee(MAX_CANCELLATION_FEE_BPS + 1));
+    }
+
+    #[test]
+    fn test_dust_swept_only_when_no_escrow_outstanding() {
+        let job_key = Pubkey::new_unique();
+        let mut market = market_with_job(job_key);
+        market.market_fee_bps = 250;
+        let rent_exempt = 890_880;
+        let mut vault = rent_exempt;
+
+        // create_job: two jobs escrowed
+        for _ in 0..2 {
+            market.hold_escrow(1_000_000).unwrap();
+            vault += 1_000_000;
+        }
+        assert_eq!(
+            market.sweepable_dust(vault, rent_exempt).unwrap_err(),
+            MarketError::EscrowOutstanding.into()
+        );
+
+        // finish_job: the node and market fee are paid, the rewards fee stays held
+        let (node_payment, rewards_fee, market_fee) = market.split_job_payment(1_000_000);
+        vault -= node_payment + market_fee;
+        market.release_escrow(1_000_000 - rewards_fee);
+        assert_eq!(market.escrow_outstanding, 1_000_000 + rewards_fee);
+
+        // cancel_job: the second job is refunded
+        let mut job = queued_job();
+        market.cancel_queued_job(&mut job, job_key, 10).unwrap();
+        let (refund, fee) = market.split_cancellation(job.abort_refund());
+        vault -= refund + fee;
+        market.release_escrow(refund + fee);
+
+        // Still held through the dispute window
+        assert!(market.sweepable_dust(vault, rent_exempt).is_err());
+
+        // finalize_job forwards the fee to the rewards pool: it never counts as dust
+        market.release_escrow(rewards_fee);
+        vault -= rewards_fee;
+        assert_eq!(market.escrow_outstanding, 0);
+        assert_eq!(market.sweepable_dust(vault, rent_exempt).unwrap(), 0);
+
+        // Only stray lamports above rent are dust
+        vault += 3;
+        assert_eq!(market.sweepable_dust(vault, rent_exempt).unwrap(), 3);
+        assert_eq!(market.sweepable_dust(rent_exempt - 1, rent_exempt).unwrap(), 0);
+
+        // Escrow tracked on overflow, and never below zero
+        market.escrow_outstanding = u64::MAX;
+        assert!(market.hold_escrow(1).is_err());
+        market.escrow_outstanding = 0;
+        market.release_escrow(5);
+        assert_eq!(market.escrow_outstanding, 0);
+    }
+
+    #[test]
+    fn test_small_capacity_market_rejects_overflow_job() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.max_queue_size = 2;
+
+        market.queue_push(Pubkey::new_unique()).unwrap();
+        assert_eq!(
+            market.queue_push(Pubkey::new_unique()).unwrap_err(),
+            MarketError::QueueFull.into()
+        );
+        assert_eq!(market.queue_len(), 2);
+
+        // The account is sized for exactly that many entries
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), MarketAccount::space(2));
+
+        assert!(!MarketAccount::is_valid_max_queue_size(0));
+        assert!(!MarketAccount::is_valid_max_queue_size(MarketAccount::MAX_QUEUE_SIZE as u16 + 1));
+    }
+
+    #[test]
+    fn test_large_capacity_market_accepts_more_jobs() {
+        let mut market = market_with_job(Pubkey::new_unique());
+        market.max_queue_size = 200;
+
+        for _ in 1..200 {
+            market.queue_push(Pubkey::new_unique()).unwrap();
+        }
+        assert_eq!(market.queue_len(), 200);
+        assert!(market.queue_push(Pubkey::new_unique()).is_err());
+
+        let mut data = Vec::new();
+        market.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), MarketAccount::space(200));
+        assert!(MarketAccount::space(MarketAccount::MAX_QUEUE_SIZE) <= 10_240);
+    }
 }
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+
+/// Per-market outcome and revenue counters for analytics
+///
+/// Updated on every settlement so indexers don't have to replay jobs:
+/// finish_job (completed), resolve_dispute (failed, when reversed),
+/// timeout_job (timed out), cancel_job and mutual_cancel (cancelled).
+/// Seeds: ["market_stats", market.key()]
+#[account]
+pub struct MarketStats {
+    /// Market the stats belong to
+    pub market: Pubkey,
+
+    /// Jobs finished by a node
+    pub completed_jobs: u64,
+
+    /// Finished jobs whose result was reversed on dispute
+    pub failed_jobs: u64,
+
+    /// Running jobs that timed out with no retries left
+    pub timed_out_jobs: u64,
+
+    /// Jobs cancelled by the client (queued) or by agreement (running)
+    pub cancelled_jobs: u64,
+
+    /// Total price of completed jobs (lamports)
+    pub total_volume: u64,
+
+    /// Sum of completed jobs' run time, start to finish (seconds)
+    pub total_job_duration: u64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl MarketStats {
+    pub const SEED: &'static [u8] = b"market_stats";
+
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 1;
+
+    /// Record a finished job, its price and how long it ran
+    pub fn record_completed(&mut self, price: u64, duration: i64) -> Result<()> {
+        self.completed_jobs = self.completed_jobs
+            .checked_add(1)
+            .ok_or(MarketError::MathOverflow)?;
+        self.total_volume = self.total_volume
+            .checked_add(price)
+            .ok_or(MarketError::MathOverflow)?;
+        self.total_job_duration = self.total_job_duration
+            .checked_add(duration.max(0) as u64)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a result reversed on dispute
+    pub fn record_failed(&mut self) -> Result<()> {
+        self.failed_jobs = self.failed_jobs
+            .checked_add(1)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a timed-out job
+    pub fn record_timed_out(&mut self) -> Result<()> {
+        self.timed_out_jobs = self.timed_out_jobs
+            .checked_add(1)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a cancelled job
+    pub fn record_cancelled(&mut self) -> Result<()> {
+        self.cancelled_jobs = self.cancelled_jobs
+            .checked_add(1)
+            .ok_or(MarketError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Average run time of completed jobs (seconds, 0 before the first)
+    pub fn average_job_duration(&self) -> u64 {
+        self.total_job_duration
+            .checked_div(self.completed_jobs)
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats() -> MarketStats {
+        MarketStats {
+            market: Pubkey::new_unique(),
+            completed_jobs: 0,
+            failed_jobs: 0,
+            timed_out_jobs: 0,
+            cancelled_jobs: 0,
+            total_volume: 0,
+            total_job_duration: 0,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_market_stats_accumulate_across_mixed_outcomes() {
+        let mut stats = stats();
+        assert_eq!(stats.average_job_duration(), 0);
+
+        // Three completions, one later reversed on dispute
+        stats.record_completed(1_000_000, 600).unwrap();
+        stats.record_completed(2_000_000, 1_200).unwrap();
+        stats.record_completed(1_500_000, 300).unwrap();
+        stats.record_failed().unwrap();
+
+        // A timeout and two cancellations add no volume or run time
+        stats.record_timed_out().unwrap();
+        stats.record_cancelled().unwrap();
+        stats.record_cancelled().unwrap();
+
+        assert_eq!(stats.completed_jobs, 3);
+        assert_eq!(stats.failed_jobs, 1);
+        assert_eq!(stats.timed_out_jobs, 1);
+        assert_eq!(stats.cancelled_jobs, 2);
+        assert_eq!(stats.total_volume, 4_500_000);
+        assert_eq!(stats.total_job_duration, 2_100);
+        assert_eq!(stats.average_job_duration(), 700);
+
+        let mut data = Vec::new();
+        stats.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), MarketStats::LEN);
+    }
+
+    #[test]
+    fn test_market_stats_overflow_rejected() {
+        let mut stats = stats();
+        stats.total_volume = u64::MAX;
+        assert_eq!(
+            stats.record_completed(1, 10).unwrap_err(),
+            MarketError::MathOverflow.into()
+        );
+
+        stats.timed_out_jobs = u64::MAX;
+        assert!(stats.record_timed_out().is_err());
+    }
+}
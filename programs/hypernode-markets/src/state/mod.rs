@@ -1,7 +1,13 @@
 pub mod job;
 pub mod market;
 pub mod reputation;
+pub mod authority;
+pub mod reservation;
+pub mod market_stats;
 
 pub use job::*;
 pub use market::*;
 pub use reputation::*;
+pub use authority::*;
+pub use reservation::*;
+pub use market_stats::*;
@@ -1,4 +1,131 @@
 use anchor_lang::prelude::*;
+use hypernode_nodes::state::{Country, Node, NodesConfig};
+use crate::errors::MarketError;
+
+/// Window for recent earnings (seconds)
+pub const EARNINGS_WINDOW: i64 = 30 * 86400; // 30 days
+
+/// Response time EMA smoothing factor (bps of the new sample's weight)
+/// Markets start with this; the authority can tune it (set_response_ema)
+pub const DEFAULT_RESPONSE_EMA_ALPHA_BPS: u16 = 2000; // 20%
+
+/// Idle time after which the old average keeps only half its usual weight
+/// (0 disables time decay)
+pub const DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE: i64 = 7 * 86400; // 7 days
+
+/// Response time EMA parameters of a market (MarketAccount::response_ema)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ResponseEma {
+    /// Weight of the newest sample (bps)
+    pub alpha_bps: u16,
+
+    /// Idle time halving the old average's weight (0 = no decay)
+    pub decay_half_life: i64,
+}
+
+impl Default for ResponseEma {
+    fn default() -> Self {
+        Self {
+            alpha_bps: DEFAULT_RESPONSE_EMA_ALPHA_BPS,
+            decay_half_life: DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE,
+        }
+    }
+}
+
+impl ResponseEma {
+    /// Alpha in 1..=10000 bps (0 would freeze the average), no negative half-life
+    pub fn is_valid(&self) -> bool {
+        (1..=10_000).contains(&self.alpha_bps) && self.decay_half_life >= 0
+    }
+}
+
+/// Exponential moving average with optional time decay
+///
+/// `alpha_bps` is the weight of the new sample. With a decay half-life the
+/// old average's weight shrinks as `half_life / (half_life + elapsed)`, so a
+/// node coming back after a long gap is judged mostly on recent jobs.
+/// Uses u128 intermediates so it can't overflow.
+pub fn ema_update(
+    previous: u64,
+    sample: u64,
+    alpha_bps: u16,
+    elapsed: i64,
+    decay_half_life: i64,
+) -> u64 {
+    if previous == 0 {
+        return sample;
+    }
+
+    let alpha = alpha_bps.min(10_000) as u128;
+    let mut keep = 10_000 - alpha;
+    if decay_half_life > 0 && elapsed > 0 {
+        keep = keep * decay_half_life as u128 / (decay_half_life as u128 + elapsed as u128);
+    }
+
+    let weighted = previous as u128 * keep + sample as u128 * (10_000 - keep);
+    (weighted / 10_000) as u64
+}
+
+/// Earnings summary returned by get_earnings
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct NodeEarnings {
+    /// Lifetime earnings (lamports)
+    pub lifetime: u64,
+
+    /// Earnings in the current window (lamports)
+    pub recent: u64,
+
+    /// Start of the current window
+    pub window_start: i64,
+}
+
+/// Consolidated node view returned by get_node_scorecard
+/// Reputation and earnings come from NodeReputation (this program), uptime,
+/// liveness and flags from the node's registration (Nodes Program)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, Debug)]
+pub struct NodeScorecard {
+    /// Node authority
+    pub authority: Pubkey,
+
+    /// Reputation score (0-1000)
+    pub reputation_score: u16,
+
+    /// Tier level (0-4: Starter, Bronze, Silver, Gold, Diamond)
+    pub tier: u8,
+
+    /// Completion rate (0-10000 basis points)
+    pub completion_rate: u16,
+
+    /// Jobs completed, failed and timed out
+    pub total_jobs: u64,
+    pub failed_jobs: u64,
+    pub timeout_jobs: u64,
+
+    /// Average response time (milliseconds)
+    pub avg_response_time: u64,
+
+    /// Total uptime (seconds) and uptime percentage (0-100)
+    pub total_uptime: u64,
+    pub uptime_percentage: u8,
+
+    /// Liveness score and heartbeat regularity (0-100)
+    pub liveness_score: u8,
+    pub heartbeat_regularity: u8,
+
+    /// Health check pass rate (0-100)
+    pub health_check_pass_rate: u8,
+
+    /// Last heartbeat timestamp
+    pub last_heartbeat: i64,
+
+    /// Flags
+    pub is_active: bool,
+    pub is_audited: bool,
+    pub is_flagged: bool,
+
+    /// Lifetime and recent earnings
+    pub earnings: NodeEarnings,
+}
 
 /// Node reputation account - tracks performance metrics
 #[account]
@@ -25,21 +152,52 @@ pub struct NodeReputation {
     pub last_active: i64,
 
     /// Reputation score (0-1000)
-    /// Calculated from: completion_rate, response_time, uptime
+    /// Calculated from: completion_rate, uptime, response_time, client ratings
     pub reputation_score: u16,
 
     /// Tier level (0-4: Starter, Bronze, Silver, Gold, Diamond)
     pub tier: u8,
 
-    /// Total revenue earned (for stats)
+    /// Total revenue earned (updated on each finish_job)
     pub total_revenue: u64,
 
+    /// Revenue earned since recent_window_start
+    pub recent_revenue: u64,
+
+    /// Start of the recent earnings window
+    pub recent_window_start: i64,
+
+    /// Jobs currently assigned to the node (bounded by Node::max_slots)
+    pub active_slots: u8,
+
+    /// Put the node back in the market queue when it finishes a job (opt-in)
+    pub auto_relist: bool,
+
+    /// Market node queues the node is waiting in
+    /// Reputation is per node, not per market, so together with active_slots
+    /// this bounds what the node has on offer across all markets
+    pub active_listings: u8,
+
+    /// Client ratings received (rate_job)
+    pub rating_count: u64,
+
+    /// Sum of client ratings received (1-5 each)
+    pub rating_total: u64,
+
+    /// Bonus added to the reputation score while the node is audited
+    /// (NodesConfig::audit_reputation_bonus, set by sync_audit_bonus)
+    pub audit_bonus: u16,
+
+    /// Registration (Nodes Program PDA) the node lists and works with
+    /// (default until its first listing or job)
+    pub node_account: Pubkey,
+
     /// PDA bump
     pub bump: u8,
 }
 
 impl NodeReputation {
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 1 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 2 + 1 + 8 + 8 + 8 + 1 + 1 + 1 + 8 + 8 + 2 + 32 + 1;
 
     /// Calculate completion rate (0-10000 basis points)
     pub fn completion_rate(&self) -> u16 {
@@ -57,10 +215,24 @@ impl NodeReputation {
         ((completed * 10000) / total) as u16
     }
 
+    /// Client satisfaction factor (0-1000) from the average rating
+    /// An average of 5 = 1000, 1 = 0
+    pub fn rating_factor(&self) -> u16 {
+        if self.rating_count == 0 {
+            return 1000; // Unrated nodes get benefit of doubt
+        }
+
+        let above_min = self.rating_total.saturating_sub(self.rating_count) as u128;
+        (above_min * 1000 / (4 * self.rating_count as u128)).min(1000) as u16
+    }
+
     /// Update reputation score based on metrics
-    /// Score = (completion_rate * 0.6) + (uptime_factor * 0.3) + (response_factor * 0.1)
+    /// Score = (completion_rate * 0.5) + (uptime_factor * 0.25)
+    ///       + (response_factor * 0.1) + (rating_factor * 0.15)
+    ///       + audit_bonus
     pub fn update_reputation_score(&mut self) {
-        let completion_rate = self.completion_rate();
+        // Completion factor: completion rate scaled from bps to 0-1000
+        let completion_factor = self.completion_rate() / 10;
 
         // Uptime factor: >99% = 1000, <90% = 0
         let uptime_factor = if self.total_uptime > 0 {
@@ -80,9 +252,11 @@ impl NodeReputation {
         };
 
         // Weighted calculation
-        let score = (completion_rate as u32 * 6 / 10)
-            + (uptime_factor as u32 * 3 / 10)
-            + (response_factor as u32 * 1 / 10);
+        let score = (completion_factor as u32 * 50 / 100)
+            + (uptime_factor as u32 * 25 / 100)
+            + (response_factor as u32 * 10 / 100)
+            + (self.rating_factor() as u32 * 15 / 100)
+            + self.audit_bonus as u32;
 
         self.reputation_score = score.min(1000) as u16;
 
@@ -102,18 +276,166 @@ impl NodeReputation {
         }
     }
 
-    /// Record successful job completion
-    pub fn record_success(&mut self, execution_time: u64, revenue: u64) {
-        self.total_jobs += 1;
-        self.total_revenue += revenue;
+    /// Record successful job completion (finish_job; the payout goes through record_earnings)
+    /// avg_response_time is an EMA with the market's parameters, decayed by
+    /// the time since last_active
+    pub fn record_success(&mut self, execution_time: u64, current_time: i64, ema: ResponseEma) {
+        self.total_jobs = self.total_jobs.saturating_add(1);
+
+        let elapsed = if self.last_active > 0 {
+            current_time.saturating_sub(self.last_active)
+        } else {
+            0
+        };
+        self.avg_response_time = ema_update(
+            self.avg_response_time,
+            execution_time,
+            ema.alpha_bps,
+            elapsed,
+            ema.decay_half_life,
+        );
+        self.last_active = current_time;
+
+        self.update_reputation_score();
+    }
 
-        // Update average response time (rolling average)
-        if self.avg_response_time == 0 {
-            self.avg_response_time = execution_time;
+    /// Set the audit bonus from the node's registration and rescore
+    /// `node` must be the registration of this reputation's authority
+    pub fn sync_audit_bonus(&mut self, node: &Node, nodes_config: &NodesConfig) {
+        self.audit_bonus = if node.is_audited {
+            nodes_config.audit_reputation_bonus
         } else {
-            self.avg_response_time = (self.avg_response_time * 9 + execution_time) / 10;
+            0
+        };
+        self.update_reputation_score();
+    }
+
+    /// Record a settlement payout in the earnings ledger
+    /// Starts a new recent window once the current one has elapsed
+    pub fn record_earnings(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        self.total_revenue = self
+            .total_revenue
+            .checked_add(amount)
+            .ok_or(MarketError::MathOverflow)?;
+
+        if current_time - self.recent_window_start >= EARNINGS_WINDOW {
+            self.recent_revenue = 0;
+            self.recent_window_start = current_time;
+        }
+        self.recent_revenue = self
+            .recent_revenue
+            .checked_add(amount)
+            .ok_or(MarketError::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Lifetime and recent earnings as of current_time
+    pub fn earnings(&self, current_time: i64) -> NodeEarnings {
+        let window_elapsed = current_time - self.recent_window_start >= EARNINGS_WINDOW;
+        NodeEarnings {
+            lifetime: self.total_revenue,
+            recent: if window_elapsed { 0 } else { self.recent_revenue },
+            window_start: self.recent_window_start,
+        }
+    }
+
+    /// Everything clients show about a node in one struct, as of current_time
+    /// `node` must be the registration of this reputation's authority
+    pub fn scorecard(&self, node: &Node, current_time: i64) -> NodeScorecard {
+        NodeScorecard {
+            authority: self.authority,
+            reputation_score: self.reputation_score,
+            tier: self.tier,
+            completion_rate: self.completion_rate(),
+            total_jobs: self.total_jobs,
+            failed_jobs: self.failed_jobs,
+            timeout_jobs: self.timeout_jobs,
+            avg_response_time: self.avg_response_time,
+            total_uptime: self.total_uptime,
+            uptime_percentage: node.uptime_percentage,
+            liveness_score: node.liveness_score,
+            heartbeat_regularity: node.heartbeat_regularity,
+            health_check_pass_rate: node.health_check_pass_rate,
+            last_heartbeat: node.last_heartbeat,
+            is_active: node.is_active,
+            is_audited: node.is_audited,
+            is_flagged: node.is_flagged,
+            earnings: self.earnings(current_time),
+        }
+    }
+
+    /// Slots taken by assigned jobs or offered in market queues
+    #[inline]
+    pub fn booked_slots(&self) -> u16 {
+        self.active_slots as u16 + self.active_listings as u16
+    }
+
+    /// Whether the node can take on one more job or listing
+    #[inline]
+    pub fn has_free_capacity(&self, max_slots: u8) -> bool {
+        self.booked_slots() < max_slots as u16
+    }
+
+    /// Take a slot for a job assigned outside the node's listings
+    /// (claimed from a jobs queue or through a reservation); listings in other
+    /// markets count against the capacity too.
+    /// Returns true once the node has no free slots left
+    pub fn reserve_slot(&mut self, max_slots: u8) -> Result<bool> {
+        require!(self.active_slots < max_slots, MarketError::NodeAtCapacity);
+        require!(self.has_free_capacity(max_slots), MarketError::NodeAlreadyBooked);
+        self.active_slots += 1;
+        Ok(self.active_slots >= max_slots)
+    }
+
+    /// Take a slot for a job matched to the node through one of its listings
+    /// The listing already holds the slot. Returns true when the node's
+    /// bookings now exceed its capacity (or no listing was recorded): that
+    /// listing then ends and the node must leave the matching market's queue
+    pub fn take_listed_slot(&mut self, max_slots: u8) -> Result<bool> {
+        require!(self.active_slots < max_slots, MarketError::NodeAtCapacity);
+        self.active_slots += 1;
+
+        if self.active_listings == 0 || self.booked_slots() > max_slots as u16 {
+            self.release_listing();
+            return Ok(true);
         }
+        Ok(false)
+    }
+
+    /// Record a listing in another market's node queue (list_node, auto-relist)
+    /// Rejected once jobs and listings already book every slot
+    pub fn reserve_listing(&mut self, max_slots: u8) -> Result<()> {
+        require!(self.has_free_capacity(max_slots), MarketError::NodeAlreadyBooked);
+        self.active_listings += 1;
+        Ok(())
+    }
+
+    /// Bind the node to the registration it lists and works with
+    /// An operator can hold several registrations; its listings and jobs all
+    /// go through one, which can only change while nothing is booked
+    pub fn bind_node_account(&mut self, node_account: Pubkey) -> Result<()> {
+        if self.node_account != node_account {
+            require!(self.booked_slots() == 0, MarketError::NodeAccountMismatch);
+            self.node_account = node_account;
+        }
+        Ok(())
+    }
+
+    /// Drop a listing when the node leaves a market's node queue
+    pub fn release_listing(&mut self) {
+        self.active_listings = self.active_listings.saturating_sub(1);
+    }
+
+    /// Free a slot when an assigned job finishes or times out
+    pub fn release_slot(&mut self) {
+        self.active_slots = self.active_slots.saturating_sub(1);
+    }
 
+    /// Record a client's rating of a finished job (1-5)
+    pub fn record_rating(&mut self, rating: u8) {
+        self.rating_count = self.rating_count.saturating_add(1);
+        self.rating_total = self.rating_total.saturating_add(rating as u64);
         self.update_reputation_score();
     }
 
@@ -160,4 +482,403 @@ impl NodeReputation {
             _ => 0,
         }
     }
+
+    /// Tier boost plus the geo-diversity boost for the node's country
+    /// (no geo boost without a nodes config or while it is disabled)
+    pub fn get_priority_boost_with_geo(
+        &self,
+        nodes_config: Option<&NodesConfig>,
+        country: &Country,
+    ) -> u8 {
+        let geo_boost = nodes_config
+            .map(|config| config.geo_priority_boost(country))
+            .unwrap_or(0);
+
+        self.get_priority_boost().saturating_add(geo_boost)
+    }
+
+    /// Weight in reputation-weighted node selection
+    /// The reputation score, raised 5% per point of priority boost (tier and
+    /// geo-diversity), so boosts tilt the draw without overriding reputation
+    pub fn selection_weight(&self, nodes_config: Option<&NodesConfig>, country: &Country) -> u64 {
+        let boost = self.get_priority_boost_with_geo(nodes_config, country) as u64;
+        self.reputation_score as u64 * (20 + boost) / 20
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_reputation() -> NodeReputation {
+        NodeReputation {
+            authority: Pubkey::new_unique(),
+            total_jobs: 0,
+            failed_jobs: 0,
+            timeout_jobs: 0,
+            avg_response_time: 0,
+            total_uptime: 0,
+            last_active: 0,
+            reputation_score: 1000,
+            tier: 4,
+            total_revenue: 0,
+            recent_revenue: 0,
+            recent_window_start: 0,
+            active_slots: 0,
+            auto_relist: false,
+            active_listings: 0,
+            rating_count: 0,
+            rating_total: 0,
+            audit_bonus: 0,
+            node_account: Pubkey::default(),
+            bump: 0,
+        }
+    }
+
+    fn registration(authority: Pubkey) -> Node {
+        use hypernode_nodes::state::Architecture;
+
+        Node {
+            authority,
+            node_id: "node-1".to_string(),
+            is_audited: true,
+            architecture: Architecture::Amd64,
+            country: Country::DE,
+            cpu_cores: 16,
+            gpu_cores: 1,
+            ram_gb: 64,
+            iops: 0,
+            storage_gb: 512,
+            capabilities: 0,
+            max_slots: 1,
+            min_job_price: 0,
+            jobs_completed: 1,
+            jobs_failed: 1,
+            total_earned: 2_000,
+            reputation_score: 500,
+            uptime_percentage: 97,
+            endpoint: String::new(),
+            version: 1,
+            registered_at: 0,
+            last_heartbeat: 950,
+            is_active: true,
+            last_metrics: None,
+            last_metrics_at: 0,
+            last_health_check: 900,
+            total_health_checks: 4,
+            passed_health_checks: 3,
+            failed_health_checks: 1,
+            health_check_pass_rate: 75,
+            heartbeat_regularity: 90,
+            liveness_score: 84,
+            gpu_fingerprint_hash: [0u8; 32],
+            last_challenge_ts: 0,
+            challenge_failures: 0,
+            challenge_successes: 0,
+            audit_failures: 0,
+            is_flagged: true,
+            reinstated_at: 0,
+            reinstatement_count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_geo_diversity_boosts_rare_country() {
+        use hypernode_nodes::state::COUNTRY_COUNT;
+
+        let reputation = new_reputation();
+        let mut config = NodesConfig {
+            authority: Pubkey::new_unique(),
+            geo_diversity_enabled: false,
+            country_node_counts: [0; COUNTRY_COUNT],
+            audit_reputation_bonus: 0,
+            health_checkers: Vec::new(),
+            bump: 0,
+        };
+        config.country_node_counts[Country::US.index()] = 200;
+        config.country_node_counts[Country::DE.index()] = 50;
+        config.country_node_counts[Country::BR.index()] = 2;
+
+        let base = reputation.get_priority_boost();
+
+        // Disabled (default) or no config: tier boost only
+        assert_eq!(reputation.get_priority_boost_with_geo(Some(&config), &Country::BR), base);
+        assert_eq!(reputation.get_priority_boost_with_geo(None, &Country::BR), base);
+
+        config.geo_diversity_enabled = true;
+        let rare = reputation.get_priority_boost_with_geo(Some(&config), &Country::BR);
+        let saturated = reputation.get_priority_boost_with_geo(Some(&config), &Country::US);
+        assert_eq!(saturated, base);
+        assert!(rare > saturated);
+
+        // Reputation-weighted matching draws the rare-country node more often
+        let rare_weight = reputation.selection_weight(Some(&config), &Country::BR);
+        let saturated_weight = reputation.selection_weight(Some(&config), &Country::US);
+        assert!(rare_weight > saturated_weight);
+        assert!(saturated_weight >= reputation.reputation_score as u64);
+    }
+
+    #[test]
+    fn test_earnings_accumulate_across_finished_jobs() {
+        let mut reputation = new_reputation();
+
+        reputation.record_earnings(990, 100).unwrap();
+        reputation.record_earnings(1_980, 200).unwrap();
+        reputation.record_earnings(495, 300).unwrap();
+
+        let earnings = reputation.earnings(400);
+        assert_eq!(earnings.lifetime, 3_465);
+        assert_eq!(earnings.recent, 3_465);
+    }
+
+    #[test]
+    fn test_recent_earnings_roll_over_window() {
+        let mut reputation = new_reputation();
+        reputation.record_earnings(1_000, 100).unwrap();
+
+        // Window elapsed: recent resets, lifetime keeps growing
+        assert_eq!(reputation.earnings(EARNINGS_WINDOW + 100).recent, 0);
+        reputation.record_earnings(500, EARNINGS_WINDOW + 100).unwrap();
+
+        let earnings = reputation.earnings(EARNINGS_WINDOW + 200);
+        assert_eq!(earnings.lifetime, 1_500);
+        assert_eq!(earnings.recent, 500);
+        assert_eq!(earnings.window_start, EARNINGS_WINDOW + 100);
+    }
+
+    #[test]
+    fn test_earnings_overflow_rejected() {
+        let mut reputation = new_reputation();
+        reputation.total_revenue = u64::MAX;
+
+        assert!(reputation.record_earnings(1, 100).is_err());
+        assert_eq!(reputation.total_revenue, u64::MAX);
+    }
+
+    /// Previous fixed 9:1 rolling average, for comparison
+    fn fixed_average(previous: u64, sample: u64) -> u64 {
+        if previous == 0 {
+            sample
+        } else {
+            (previous * 9 + sample) / 10
+        }
+    }
+
+    #[test]
+    fn test_ema_converges_faster_than_fixed_average() {
+        let (mut fixed, mut ema) = (1_000u64, 1_000u64);
+
+        // Burst of slow jobs
+        for _ in 0..5 {
+            fixed = fixed_average(fixed, 9_000);
+            ema = ema_update(ema, 9_000, DEFAULT_RESPONSE_EMA_ALPHA_BPS, 60, 0);
+        }
+        assert!(ema > fixed);
+        assert!(ema > 6_000);
+
+        // Back to fast jobs: the EMA recovers faster too
+        for _ in 0..5 {
+            fixed = fixed_average(fixed, 1_000);
+            ema = ema_update(ema, 1_000, DEFAULT_RESPONSE_EMA_ALPHA_BPS, 60, 0);
+        }
+        assert!(ema < fixed);
+    }
+
+    #[test]
+    fn test_ema_decay_weights_new_sample_after_long_gap() {
+        let short_gap = ema_update(1_000, 9_000, DEFAULT_RESPONSE_EMA_ALPHA_BPS, 60, DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE);
+        let long_gap = ema_update(
+            1_000,
+            9_000,
+            DEFAULT_RESPONSE_EMA_ALPHA_BPS,
+            DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE,
+            DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE,
+        );
+
+        assert!(long_gap > short_gap);
+        // One half-life: old weight 80% -> 40%
+        assert_eq!(long_gap, 5_800);
+    }
+
+    #[test]
+    fn test_ema_no_overflow_at_extremes() {
+        assert_eq!(ema_update(u64::MAX, u64::MAX, 10_000, i64::MAX, i64::MAX), u64::MAX);
+        assert_eq!(ema_update(0, 5, DEFAULT_RESPONSE_EMA_ALPHA_BPS, 0, 0), 5);
+
+        let mut reputation = new_reputation();
+        reputation.record_success(500, 100, ResponseEma::default());
+        reputation.record_success(1_500, 200, ResponseEma::default());
+        assert_eq!(reputation.avg_response_time, 700);
+        assert_eq!(reputation.total_jobs, 2);
+    }
+
+    #[test]
+    fn test_staking_reads_node_tier_from_reputation() {
+        use hypernode_staking::state::{read_node_tier, NODE_REPUTATION_DISCRIMINATOR};
+
+        assert_eq!(NodeReputation::DISCRIMINATOR, NODE_REPUTATION_DISCRIMINATOR);
+
+        let mut reputation = new_reputation();
+        reputation.total_jobs = 25;
+        reputation.tier = 3;
+        let mut data = Vec::new();
+        reputation.try_serialize(&mut data).unwrap();
+        assert_eq!(read_node_tier(&data, &reputation.authority), Some(3));
+    }
+
+    #[test]
+    fn test_scorecard_matches_reputation_and_node_accounts() {
+        let mut reputation = new_reputation();
+        reputation.record_success(1_500, 100, ResponseEma::default());
+        reputation.record_failure();
+        reputation.record_timeout();
+        reputation.update_uptime(1_000);
+        reputation.record_earnings(2_000, 1_000).unwrap();
+
+        let node = registration(reputation.authority);
+
+        let scorecard = reputation.scorecard(&node, 1_200);
+        assert_eq!(scorecard.authority, reputation.authority);
+        assert_eq!(scorecard.reputation_score, reputation.reputation_score);
+        assert_eq!(scorecard.tier, reputation.tier);
+        assert_eq!(scorecard.completion_rate, reputation.completion_rate());
+        assert_eq!(scorecard.completion_rate, 3_333);
+        assert_eq!(
+            (scorecard.total_jobs, scorecard.failed_jobs, scorecard.timeout_jobs),
+            (1, 1, 1)
+        );
+        assert_eq!(scorecard.avg_response_time, reputation.avg_response_time);
+        assert_eq!(scorecard.total_uptime, reputation.total_uptime);
+        assert_eq!(scorecard.uptime_percentage, node.uptime_percentage);
+        assert_eq!(scorecard.liveness_score, node.liveness_score);
+        assert_eq!(scorecard.heartbeat_regularity, node.heartbeat_regularity);
+        assert_eq!(scorecard.health_check_pass_rate, node.health_check_pass_rate);
+        assert_eq!(scorecard.last_heartbeat, node.last_heartbeat);
+        assert_eq!(
+            (scorecard.is_active, scorecard.is_audited, scorecard.is_flagged),
+            (true, true, true)
+        );
+        assert_eq!(scorecard.earnings, reputation.earnings(1_200));
+        assert_eq!((scorecard.earnings.lifetime, scorecard.earnings.recent), (2_000, 2_000));
+    }
+
+    #[test]
+    fn test_client_rating_updates_reputation() {
+        let mut reputation = new_reputation();
+        reputation.record_success(1_000, 100, ResponseEma::default());
+        assert_eq!(reputation.rating_factor(), 1000);
+        assert_eq!((reputation.reputation_score, reputation.tier), (1000, 4));
+
+        // One poor rating costs the full rating weight
+        reputation.record_rating(1);
+        assert_eq!((reputation.rating_count, reputation.rating_total), (1, 1));
+        assert_eq!(reputation.rating_factor(), 0);
+        assert_eq!((reputation.reputation_score, reputation.tier), (850, 3));
+
+        // Ratings are averaged: 1 and 5 put the node halfway
+        reputation.record_rating(5);
+        assert_eq!(reputation.rating_factor(), 500);
+        assert_eq!(reputation.reputation_score, 925);
+
+        // Top ratings win it back over time
+        for _ in 0..6 {
+            reputation.record_rating(5);
+        }
+        assert_eq!(reputation.rating_factor(), 875);
+        assert_eq!((reputation.reputation_score, reputation.tier), (981, 4));
+    }
+    #[test]
+    fn test_audited_node_outranks_unaudited_node() {
+        use hypernode_nodes::state::{COUNTRY_COUNT, DEFAULT_AUDIT_REPUTATION_BONUS};
+
+        let config = NodesConfig {
+            authority: Pubkey::new_unique(),
+            geo_diversity_enabled: false,
+            country_node_counts: [0; COUNTRY_COUNT],
+            audit_reputation_bonus: DEFAULT_AUDIT_REPUTATION_BONUS,
+            health_checkers: Vec::new(),
+            bump: 0,
+        };
+
+        // Same record: one success, one failure
+        let mut audited = new_reputation();
+        let mut unaudited = new_reputation();
+        for reputation in [&mut audited, &mut unaudited] {
+            reputation.record_success(1_000, 100, ResponseEma::default());
+            reputation.record_failure();
+        }
+        assert_eq!((unaudited.reputation_score, unaudited.tier), (750, 2));
+
+        let mut node = registration(audited.authority);
+        node.is_audited = true;
+        audited.sync_audit_bonus(&node, &config);
+        assert_eq!(audited.audit_bonus, DEFAULT_AUDIT_REPUTATION_BONUS);
+        assert_eq!((audited.reputation_score, audited.tier), (950, 4));
+
+        // The bonus persists through later updates
+        audited.record_timeout();
+        unaudited.record_timeout();
+        assert_eq!(
+            audited.reputation_score - unaudited.reputation_score,
+            DEFAULT_AUDIT_REPUTATION_BONUS
+        );
+
+        // Capped at 1000, and taken back once the audit is revoked
+        audited.record_rating(5);
+        audited.sync_audit_bonus(&node, &NodesConfig { audit_reputation_bonus: 500, ..config.clone() });
+        assert_eq!(audited.reputation_score, 1000);
+        node.is_audited = false;
+        audited.sync_audit_bonus(&node, &config);
+        unaudited.record_rating(5);
+        assert_eq!(audited.audit_bonus, 0);
+        assert_eq!(audited.reputation_score, unaudited.reputation_score);
+    }
+
+    #[test]
+    fn test_market_response_ema_params() {
+        let mut responsive = new_reputation();
+        let mut smoothed = new_reputation();
+        let instant = ResponseEma { alpha_bps: 10_000, decay_half_life: 0 };
+
+        responsive.record_success(1_000, 100, instant);
+        responsive.record_success(9_000, 200, instant);
+        smoothed.record_success(1_000, 100, ResponseEma::default());
+        smoothed.record_success(9_000, 200, ResponseEma::default());
+
+        // Full weight on the newest sample vs the default 20%
+        assert_eq!(responsive.avg_response_time, 9_000);
+        assert!(smoothed.avg_response_time < 3_000);
+        assert!(responsive.reputation_score < smoothed.reputation_score);
+
+        assert!(ResponseEma::default().is_valid());
+        assert!(instant.is_valid());
+        assert!(!ResponseEma { alpha_bps: 0, decay_half_life: 0 }.is_valid());
+        assert!(!ResponseEma { alpha_bps: 10_001, decay_half_life: 0 }.is_valid());
+        assert!(!ResponseEma { alpha_bps: 2_000, decay_half_life: -1 }.is_valid());
+    }
+
+    #[test]
+    fn test_node_bound_to_one_registration_while_booked() {
+        let mut reputation = new_reputation();
+        let listed = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+
+        // First listing binds the registration it was made with
+        reputation.bind_node_account(listed).unwrap();
+        reputation.reserve_listing(2).unwrap();
+        assert_eq!(reputation.node_account, listed);
+
+        // A second (e.g. unflagged) registration can't take work meanwhile
+        assert_eq!(
+            reputation.bind_node_account(other).unwrap_err(),
+            MarketError::NodeAccountMismatch.into()
+        );
+        assert!(reputation.bind_node_account(listed).is_ok());
+
+        // Once nothing is booked the node may switch registrations
+        reputation.release_listing();
+        reputation.bind_node_account(other).unwrap();
+        assert_eq!(reputation.node_account, other);
+    }
 }
@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+use crate::errors::MarketError;
+
+/// Longest window a node can be reserved for (seconds)
+pub const MAX_RESERVATION_DURATION: i64 = 30 * 86400; // 30 days
+
+/// Reserved capacity: a client pre-pays to hold a node for a time window
+///
+/// While active the node is kept out of the open node queue and only takes
+/// the reserving client's jobs. The escrow is held in this account and
+/// released by release_reservation.
+/// Seeds: ["reservation", market.key(), node.key()]
+#[account]
+pub struct NodeReservation {
+    /// Market the node is reserved in
+    pub market: Pubkey,
+
+    /// Client holding the reservation
+    pub client: Pubkey,
+
+    /// Reserved node authority
+    pub node: Pubkey,
+
+    /// Lamports escrowed for the node (on top of rent)
+    pub escrow: u64,
+
+    /// Start of the reserved window
+    pub starts_at: i64,
+
+    /// End of the reserved window (exclusive)
+    pub ends_at: i64,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl NodeReservation {
+    pub const SEED: &'static [u8] = b"reservation";
+
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 8 + 8 + 8 + 1;
+
+    /// A window must start now or later and last 1..=MAX_RESERVATION_DURATION
+    pub fn is_valid_window(starts_at: i64, duration: i64, current_time: i64) -> bool {
+        starts_at >= current_time && duration > 0 && duration <= MAX_RESERVATION_DURATION
+    }
+
+    /// Whether the node is held for the client at `current_time`
+    pub fn is_active(&self, current_time: i64) -> bool {
+        (self.starts_at..self.ends_at).contains(&current_time)
+    }
+
+    /// Whether the node may take a job from `client`
+    /// During the window only the reserving client's jobs are admitted
+    pub fn admits_job(&self, client: &Pubkey, current_time: i64) -> bool {
+        !self.is_active(current_time) || *client == self.client
+    }
+
+    /// Split the escrow on release into (node payout, client refund)
+    /// The node earns the share of the window that has elapsed; the
+    /// unused remainder goes back to the client
+    pub fn settle(&self, current_time: i64) -> (u64, u64) {
+        let duration = (self.ends_at - self.starts_at).max(1);
+        let elapsed = (current_time - self.starts_at).clamp(0, duration);
+        let node_payout = (self.escrow as u128 * elapsed as u128 / duration as u128) as u64;
+        (node_payout, self.escrow - node_payout)
+    }
+
+    /// Read the reservation at a (market, node) reservation address, if any
+    /// Instructions take the address unchecked so nodes without a
+    /// reservation can pass it; an empty account means none
+    pub fn load(account: &AccountInfo) -> Result<Option<Self>> {
+        if account.data_is_empty() {
+            return Ok(None);
+        }
+        require!(account.owner == &crate::ID, MarketError::InvalidReservation);
+
+        let data = account.try_borrow_data()?;
+        Ok(Some(Self::try_deserialize(&mut &data[..])?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::market::{MarketAccount, DEFAULT_DISPUTE_WINDOW, REPUTATION_TIER_COUNT};
+    use crate::state::reputation::{DEFAULT_RESPONSE_EMA_ALPHA_BPS, DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE};
+
+    fn reservation(starts_at: i64, ends_at: i64) -> NodeReservation {
+        NodeReservation {
+            market: Pubkey::new_unique(),
+            client: Pubkey::new_unique(),
+            node: Pubkey::new_unique(),
+            escrow: 1_000_000,
+            starts_at,
+            ends_at,
+            bump: 255,
+        }
+    }
+
+    #[test]
+    fn test_reserved_node_not_matched_to_open_market_jobs() {
+        let reservation = reservation(1_000, 2_000);
+        let other_client = Pubkey::new_unique();
+
+        // During the window only the reserving client's jobs are admitted
+        assert!(!reservation.admits_job(&other_client, 1_000));
+        assert!(!reservation.admits_job(&other_client, 1_999));
+        assert!(reservation.admits_job(&reservation.client, 1_500));
+
+        // Outside it the node is back on the open market
+        assert!(reservation.admits_job(&other_client, 999));
+        assert!(reservation.admits_job(&other_client, 2_000));
+
+        // Reserving takes a listed node out of the open node queue
+        let other_node = Pubkey::new_unique();
+        let mut market = MarketAccount {
+            authority: Pubkey::default(),
+            job_price: 1_000_000,
+            job_timeout: 3600,
+            node_xhyper_minimum: 0,
+            queue_type: MarketAccount::QUEUE_TYPE_NODES,
+            vault_bump: 0,
+            total_jobs: 0,
+            total_nodes: 2,
+            vault: Pubkey::default(),
+            dispute_window: DEFAULT_DISPUTE_WINDOW,
+            market_fee_bps: 0,
+            selection_mode: MarketAccount::SELECTION_FIFO,
+            min_node_version: 0,
+            min_cpu_cores: 0,
+            min_ram_gb: 0,
+            min_gpu_cores: 0,
+            cancellation_fee_bps: 0,
+            tier_stake_bps: [0; REPUTATION_TIER_COUNT],
+            escrow_outstanding: 0,
+            response_ema_alpha_bps: DEFAULT_RESPONSE_EMA_ALPHA_BPS,
+            response_ema_half_life: DEFAULT_RESPONSE_EMA_DECAY_HALF_LIFE,
+            max_queue_size: MarketAccount::MAX_QUEUE_SIZE as u16,
+            queue: vec![reservation.node, other_node, reservation.node],
+        };
+        assert_eq!(market.unlist_node(reservation.node), 2);
+        assert_eq!(market.select_node(0, &[]), Some(other_node));
+        assert_eq!(market.unlist_node(reservation.node), 0);
+
+        // The last node unlisting empties the queue
+        assert_eq!(market.unlist_node(other_node), 1);
+        assert_eq!(market.queue_type, MarketAccount::QUEUE_TYPE_EMPTY);
+    }
+
+    #[test]
+    fn test_reservation_escrow_released_pro_rata() {
+        let reservation = reservation(1_000, 2_000);
+
+        // Released before the window opens: full refund
+        assert_eq!(reservation.settle(500), (0, 1_000_000));
+
+        // Halfway through: node keeps half
+        assert_eq!(reservation.settle(1_500), (500_000, 500_000));
+
+        // After the window: all to the node
+        assert_eq!(reservation.settle(5_000), (1_000_000, 0));
+
+        assert!(NodeReservation::is_valid_window(1_000, 60, 1_000));
+        assert!(!NodeReservation::is_valid_window(999, 60, 1_000));
+        assert!(!NodeReservation::is_valid_window(1_000, 0, 1_000));
+        assert!(!NodeReservation::is_valid_window(1_000, MAX_RESERVATION_DURATION + 1, 1_000));
+    }
+}
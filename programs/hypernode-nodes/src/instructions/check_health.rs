@@ -5,26 +5,36 @@
  * - Verifies node specifications and performance
  * - Records health check results on-chain
  * - Enables automatic penalties for underperformance
+ * - Auto-flags nodes whose pass rate stays below threshold
  *
  * Architecture Principles:
- * - Trustless: Permissionless checks anyone can call
+ * - Trusted: Only checkers approved in NodesConfig report results
  * - Safe: Validates timestamp and prevents spam
  * - Clear: Simple pass/fail with timestamp
  */
 
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::instructions::register::NodeError;
 
 #[derive(Accounts)]
 pub struct CheckHealth<'info> {
     #[account(
         mut,
-        seeds = [b"node", node.authority.as_ref()],
+        seeds = [b"node", node.node_id.as_bytes()],
         bump = node.bump
     )]
     pub node: Account<'info, Node>,
 
-    /// Checker authority (can be anyone - permissionless)
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        constraint = config.is_health_checker(&checker.key()) @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Checker oracle approved by the trusted authority
     pub checker: Signer<'info>,
 }
 
@@ -43,23 +53,26 @@ pub fn check_health(
         ErrorCode::CheckTooFrequent
     );
 
-    // Update health check data
+    // Update health check data, pass rate and liveness score
     node.last_health_check = clock.unix_timestamp;
-    node.total_health_checks = node.total_health_checks.checked_add(1).unwrap();
-
-    if passed {
-        node.passed_health_checks = node.passed_health_checks.checked_add(1).unwrap();
-    } else {
-        node.failed_health_checks = node.failed_health_checks.checked_add(1).unwrap();
+    let newly_flagged = node.record_health_check(passed);
 
+    if !passed {
         // Reduce reputation on failed check
         const REPUTATION_PENALTY: u16 = 10;
         node.reputation_score = node.reputation_score.saturating_sub(REPUTATION_PENALTY);
     }
 
-    // Calculate health check pass rate
-    if node.total_health_checks > 0 {
-        node.health_check_pass_rate = ((node.passed_health_checks * 100) / node.total_health_checks) as u8;
+    // Auto-flag chronically failing nodes; they leave the country registry until reinstated
+    if newly_flagged {
+        ctx.accounts.config.record_deregistration(&node.country);
+        msg!("Node {} flagged: pass rate {}%", node.node_id, node.health_check_pass_rate);
+        emit!(NodeFlaggedEvent {
+            node: node.key(),
+            pass_rate: node.health_check_pass_rate,
+            total_health_checks: node.total_health_checks,
+            timestamp: clock.unix_timestamp,
+        });
     }
 
     msg!("Health check result: {} - {}", if passed { "PASSED" } else { "FAILED" }, message);
@@ -67,6 +80,15 @@ pub fn check_health(
     Ok(())
 }
 
+/// Event emitted when a node is auto-flagged for failing health checks
+#[event]
+pub struct NodeFlaggedEvent {
+    pub node: Pubkey,
+    pub pass_rate: u8,
+    pub total_health_checks: u64,
+    pub timestamp: i64,
+}
+
 #[error_code]
 pub enum ErrorCode {
     #[msg("Health check too frequent. Wait at least 5 minutes between checks.")]
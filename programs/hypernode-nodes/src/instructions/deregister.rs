@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Close a node's registration (node authority only)
+///
+/// The rent goes back to the authority and the node leaves its country's
+/// registry (flagged nodes already left it when they were flagged).
+/// Markets no longer match a node without its registration, so finish
+/// running jobs first.
+pub fn deregister(ctx: Context<Deregister>) -> Result<()> {
+    let node = &ctx.accounts.node;
+
+    if !node.is_flagged {
+        ctx.accounts.config.record_deregistration(&node.country);
+    }
+
+    msg!("Node deregistered: {}", node.node_id);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Deregister<'info> {
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"node", node.node_id.as_bytes()],
+        bump = node.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
@@ -3,12 +3,18 @@ use crate::state::*;
 use crate::instructions::register::NodeError;
 
 /// Update node heartbeat to show it's still active
-pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
+/// Optionally carries current metrics (GPU load, free VRAM, temperature)
+/// At most one heartbeat per MIN_HEARTBEAT_SPACING
+pub fn heartbeat(ctx: Context<Heartbeat>, metrics: Option<NodeMetrics>) -> Result<()> {
     let node = &mut ctx.accounts.node;
     let clock = Clock::get()?;
 
-    node.last_heartbeat = clock.unix_timestamp;
-    node.is_active = true;
+    // Also updates heartbeat regularity and the liveness score
+    node.record_heartbeat(clock.unix_timestamp)?;
+
+    if let Some(metrics) = metrics {
+        node.record_metrics(metrics, clock.unix_timestamp)?;
+    }
 
     Ok(())
 }
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Send one heartbeat for many nodes (operators running fleets)
+/// Node accounts are passed as writable remaining accounts (max 16);
+/// every node's authority must be the signer
+pub fn heartbeat_batch<'info>(
+    ctx: Context<'_, '_, 'info, 'info, HeartbeatBatch<'info>>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Account::try_from checks the owner and discriminator
+    let mut nodes = ctx
+        .remaining_accounts
+        .iter()
+        .map(Account::<Node>::try_from)
+        .collect::<Result<Vec<_>>>()?;
+
+    let updated = record_batch_heartbeat(
+        nodes.iter_mut().map(|node| &mut **node),
+        &ctx.accounts.authority.key(),
+        clock.unix_timestamp,
+    )?;
+
+    for node in nodes.iter() {
+        node.exit(&crate::ID)?;
+    }
+
+    msg!("Heartbeat recorded for {} node(s)", updated);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct HeartbeatBatch<'info> {
+    /// Operator owning every node in the batch
+    pub authority: Signer<'info>,
+}
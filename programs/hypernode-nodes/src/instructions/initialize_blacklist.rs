@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Create the global node blacklist (trusted authority only)
+pub fn initialize_blacklist(ctx: Context<InitializeBlacklist>) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+
+    blacklist.authorities = Vec::new();
+    blacklist.bump = ctx.bumps.blacklist;
+
+    msg!("Node blacklist initialized");
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeBlacklist<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = Blacklist::SPACE,
+        seeds = [b"blacklist"],
+        bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    #[account(
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::program::HypernodeNodes;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Initialize the nodes config
+/// Only the program's upgrade authority can initialize it (so the config
+/// can't be front-run); it becomes the trusted authority
+pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.authority = ctx.accounts.payer.key();
+    config.geo_diversity_enabled = false;
+    config.country_node_counts = [0; COUNTRY_COUNT];
+    config.audit_reputation_bonus = DEFAULT_AUDIT_REPUTATION_BONUS;
+    config.health_checkers = Vec::new();
+    config.bump = ctx.bumps.config;
+
+    msg!("Nodes config initialized. Authority: {}", config.authority);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = NodesConfig::SPACE,
+        seeds = [b"nodes_config"],
+        bump
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Program upgrade authority, becomes the trusted authority
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, HypernodeNodes>,
+
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(payer.key()) @ NodeError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
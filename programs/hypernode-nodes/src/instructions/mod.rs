@@ -1,9 +1,33 @@
 pub mod register;
 pub mod update;
 pub mod heartbeat;
+pub mod heartbeat_batch;
 pub mod check_health;
+pub mod initialize_config;
+pub mod reinstate_node;
+pub mod set_geo_diversity;
+pub mod set_min_job_price;
+pub mod update_stats;
+pub mod initialize_blacklist;
+pub mod set_blacklisted;
+pub mod set_audited;
+pub mod set_audit_bonus;
+pub mod set_health_checker;
+pub mod deregister;
 
 pub use register::*;
 pub use update::*;
 pub use heartbeat::*;
+pub use heartbeat_batch::*;
 pub use check_health::*;
+pub use initialize_config::*;
+pub use reinstate_node::*;
+pub use set_geo_diversity::*;
+pub use set_min_job_price::*;
+pub use update_stats::*;
+pub use initialize_blacklist::*;
+pub use set_blacklisted::*;
+pub use set_audited::*;
+pub use set_audit_bonus::*;
+pub use set_health_checker::*;
+pub use deregister::*;
@@ -14,14 +14,23 @@ pub fn register(
     storage_gb: u32,
     endpoint: String,
     gpu_fingerprint_hash: [u8; 32], // GPU hardware fingerprint
+    capabilities: u64, // CAPABILITY_* bitmask
+    max_slots: u8, // concurrent job slots
 ) -> Result<()> {
     let node = &mut ctx.accounts.node;
     let clock = Clock::get()?;
 
+    // Banned authorities can't come back under a new node_id
+    require!(
+        !ctx.accounts.blacklist.is_banned(&ctx.accounts.authority.key()),
+        NodeError::AuthorityBlacklisted
+    );
+
     // Validate all hardware specs are non-zero
     require!(cpu_cores > 0, NodeError::InvalidHardwareSpec);
     require!(ram_gb > 0, NodeError::InvalidHardwareSpec);
     require!(storage_gb > 0, NodeError::InvalidHardwareSpec);
+    require!(Node::is_valid_max_slots(max_slots), NodeError::InvalidMaxSlots);
 
     // Validate string lengths
     require!(
@@ -45,6 +54,9 @@ pub fn register(
     node.ram_gb = ram_gb;
     node.iops = iops;
     node.storage_gb = storage_gb;
+    node.capabilities = capabilities;
+    node.max_slots = max_slots;
+    node.min_job_price = 0;
 
     // Initialize stats
     node.jobs_completed = 0;
@@ -61,6 +73,8 @@ pub fn register(
     node.registered_at = clock.unix_timestamp;
     node.last_heartbeat = clock.unix_timestamp;
     node.is_active = true;
+    node.last_metrics = None;
+    node.last_metrics_at = 0;
 
     // Health check stats (initialized to zero)
     node.last_health_check = 0;
@@ -68,6 +82,8 @@ pub fn register(
     node.passed_health_checks = 0;
     node.failed_health_checks = 0;
     node.health_check_pass_rate = 0;
+    node.heartbeat_regularity = 100;
+    node.liveness_score = 100;
 
     // Anti-spoofing verification
     node.gpu_fingerprint_hash = gpu_fingerprint_hash;
@@ -76,9 +92,14 @@ pub fn register(
     node.challenge_successes = 0;
     node.audit_failures = 0;
     node.is_flagged = false;
+    node.reinstated_at = 0;
+    node.reinstatement_count = 0;
 
     node.bump = ctx.bumps.node;
 
+    // Per-country registry (geo-diversity boost)
+    ctx.accounts.config.record_registration(&node.country);
+
     msg!(
         "Node registered: {} | {} cores CPU, {} cores GPU, {}GB RAM",
         node.node_id,
@@ -102,6 +123,21 @@ pub struct Register<'info> {
     )]
     pub node: Account<'info, Node>,
 
+    /// Nodes config (per-country node counts)
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Global blacklist of banned node authorities
+    #[account(
+        seeds = [b"blacklist"],
+        bump = blacklist.bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
     #[account(mut)]
     pub authority: Signer<'info>,
 
@@ -124,4 +160,40 @@ pub enum NodeError {
 
     #[msg("Unauthorized")]
     Unauthorized,
+
+    #[msg("Node is not flagged")]
+    NodeNotFlagged,
+
+    #[msg("Node cannot reinstate itself")]
+    SelfReinstatement,
+
+    #[msg("Max slots must be between 1 and MAX_NODE_SLOTS")]
+    InvalidMaxSlots,
+
+    #[msg("Heartbeat batch must hold between 1 and MAX_HEARTBEAT_BATCH nodes")]
+    InvalidHeartbeatBatch,
+
+    #[msg("Math overflow")]
+    MathOverflow,
+
+    #[msg("Heartbeat metrics out of range")]
+    InvalidMetrics,
+
+    #[msg("Node authority is blacklisted")]
+    AuthorityBlacklisted,
+
+    #[msg("Blacklist is full")]
+    BlacklistFull,
+
+    #[msg("Node cannot audit itself")]
+    SelfAudit,
+
+    #[msg("Audit bonus exceeds MAX_AUDIT_REPUTATION_BONUS")]
+    InvalidAuditBonus,
+
+    #[msg("Health checker list is full")]
+    HealthCheckersFull,
+
+    #[msg("Heartbeat too frequent. Wait MIN_HEARTBEAT_SPACING between heartbeats.")]
+    HeartbeatTooFrequent,
 }
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Clear a node's flag once it has fixed its issues
+///
+/// Only the trusted authority in NodesConfig can reinstate,
+/// and never for a node it operates itself.
+/// The node counts in its country's registry again.
+pub fn reinstate_node(
+    ctx: Context<ReinstateNode>,
+    reset_challenge_failures: bool,
+) -> Result<()> {
+    let node = &mut ctx.accounts.node;
+    let clock = Clock::get()?;
+
+    node.reinstate(
+        &ctx.accounts.authority.key(),
+        clock.unix_timestamp,
+        reset_challenge_failures,
+    )?;
+    ctx.accounts.config.record_registration(&node.country);
+
+    msg!(
+        "Node {} reinstated (count: {})",
+        node.node_id,
+        node.reinstatement_count
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReinstateNode<'info> {
+    #[account(
+        mut,
+        seeds = [b"node", node.node_id.as_bytes()],
+        bump = node.bump
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    pub authority: Signer<'info>,
+}
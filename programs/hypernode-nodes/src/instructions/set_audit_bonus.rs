@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Set the reputation bonus markets grant audited nodes (trusted authority only)
+pub fn set_audit_bonus(ctx: Context<SetAuditBonus>, audit_reputation_bonus: u16) -> Result<()> {
+    require!(
+        NodesConfig::is_valid_audit_bonus(audit_reputation_bonus),
+        NodeError::InvalidAuditBonus
+    );
+
+    let config = &mut ctx.accounts.config;
+    config.audit_reputation_bonus = audit_reputation_bonus;
+
+    msg!("Audit reputation bonus set to {}", audit_reputation_bonus);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAuditBonus<'info> {
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Record (or revoke) a node's audit
+///
+/// Only the trusted authority in NodesConfig can audit, never for a node
+/// it operates itself. Markets reputation adds the configured audit bonus.
+pub fn set_audited(ctx: Context<SetAudited>, audited: bool) -> Result<()> {
+    let node = &mut ctx.accounts.node;
+
+    node.set_audited(&ctx.accounts.authority.key(), audited)?;
+
+    msg!("Node {} audited: {}", node.node_id, audited);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetAudited<'info> {
+    #[account(
+        mut,
+        seeds = [b"node", node.node_id.as_bytes()],
+        bump = node.bump
+    )]
+    pub node: Account<'info, Node>,
+
+    #[account(
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Ban or unban a node authority (trusted authority only)
+///
+/// Banned authorities can't register nodes or join market queues.
+/// Nodes they already registered keep their accounts.
+pub fn set_blacklisted(
+    ctx: Context<SetBlacklisted>,
+    node_authority: Pubkey,
+    banned: bool,
+) -> Result<()> {
+    let blacklist = &mut ctx.accounts.blacklist;
+
+    let changed = if banned {
+        blacklist.ban(node_authority).ok_or(NodeError::BlacklistFull)?
+    } else {
+        blacklist.unban(&node_authority)
+    };
+
+    msg!(
+        "Node authority {} {}{}",
+        node_authority,
+        if banned { "banned" } else { "unbanned" },
+        if changed { "" } else { " (unchanged)" }
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetBlacklisted<'info> {
+    #[account(
+        mut,
+        seeds = [b"blacklist"],
+        bump = blacklist.bump
+    )]
+    pub blacklist: Account<'info, Blacklist>,
+
+    #[account(
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    pub authority: Signer<'info>,
+}
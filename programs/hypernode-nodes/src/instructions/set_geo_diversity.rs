@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Turn the geo-diversity priority boost on or off (trusted authority only)
+pub fn set_geo_diversity(ctx: Context<SetGeoDiversity>, enabled: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.geo_diversity_enabled = enabled;
+
+    msg!("Geo-diversity boost {}", if enabled { "enabled" } else { "disabled" });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetGeoDiversity<'info> {
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Approve or revoke a health check oracle (trusted authority only)
+///
+/// Only approved checkers can report check_health results, which feed
+/// the pass rate, auto-flagging and the liveness score.
+pub fn set_health_checker(
+    ctx: Context<SetHealthChecker>,
+    checker: Pubkey,
+    approved: bool,
+) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    let changed = config
+        .set_health_checker(checker, approved)
+        .ok_or(NodeError::HealthCheckersFull)?;
+
+    msg!(
+        "Health checker {} {}{}",
+        checker,
+        if approved { "approved" } else { "revoked" },
+        if changed { "" } else { " (unchanged)" }
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetHealthChecker<'info> {
+    #[account(
+        mut,
+        seeds = [b"nodes_config"],
+        bump = config.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub config: Account<'info, NodesConfig>,
+
+    /// Trusted authority
+    pub authority: Signer<'info>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::instructions::register::NodeError;
+
+/// Set the node's job price floor
+/// Markets only assign the node jobs priced at or above it
+pub fn set_min_job_price(ctx: Context<SetMinJobPrice>, min_job_price: u64) -> Result<()> {
+    let node = &mut ctx.accounts.node;
+
+    node.min_job_price = min_job_price;
+
+    msg!("Node {} minimum job price set to {}", node.node_id, min_job_price);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetMinJobPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"node", node.node_id.as_bytes()],
+        bump = node.bump,
+        has_one = authority @ NodeError::Unauthorized
+    )]
+    pub node: Account<'info, Node>,
+
+    pub authority: Signer<'info>,
+}
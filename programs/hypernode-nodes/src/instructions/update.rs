@@ -11,6 +11,8 @@ pub fn update(
     iops: u32,
     storage_gb: u32,
     endpoint: String,
+    capabilities: u64,
+    max_slots: u8,
 ) -> Result<()> {
     let node = &mut ctx.accounts.node;
 
@@ -18,6 +20,7 @@ pub fn update(
     require!(cpu_cores > 0, NodeError::InvalidHardwareSpec);
     require!(ram_gb > 0, NodeError::InvalidHardwareSpec);
     require!(storage_gb > 0, NodeError::InvalidHardwareSpec);
+    require!(Node::is_valid_max_slots(max_slots), NodeError::InvalidMaxSlots);
     require!(
         endpoint.len() <= Node::MAX_ENDPOINT_LEN,
         NodeError::EndpointTooLong
@@ -29,6 +32,8 @@ pub fn update(
     node.ram_gb = ram_gb;
     node.iops = iops;
     node.storage_gb = storage_gb;
+    node.capabilities = capabilities;
+    node.max_slots = max_slots;
     node.endpoint = endpoint;
 
     msg!("Node {} hardware specs updated", node.node_id);
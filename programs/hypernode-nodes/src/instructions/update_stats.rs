@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Record a finished job on the node's stats
+/// Only callable by the Markets Program: `markets_authority` is its
+/// ["stats_authority"] PDA, signed from finish_job / timeout_job
+pub fn update_stats(ctx: Context<UpdateStats>, completed: bool, earned: u64) -> Result<()> {
+    let node = &mut ctx.accounts.node;
+
+    node.record_job_outcome(completed, earned)?;
+
+    msg!(
+        "Node {} stats: {} completed, {} failed, {} earned",
+        node.node_id,
+        node.jobs_completed,
+        node.jobs_failed,
+        node.total_earned
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateStats<'info> {
+    #[account(
+        mut,
+        seeds = [b"node", node.node_id.as_bytes()],
+        bump = node.bump
+    )]
+    pub node: Account<'info, Node>,
+
+    /// Markets Program stats authority PDA
+    #[account(
+        seeds = [MARKETS_STATS_AUTHORITY_SEED],
+        bump,
+        seeds::program = MARKETS_PROGRAM_ID
+    )]
+    pub markets_authority: Signer<'info>,
+}
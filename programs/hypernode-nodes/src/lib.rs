@@ -24,6 +24,9 @@ pub mod hypernode_nodes {
         iops: u32,
         storage_gb: u32,
         endpoint: String,
+        gpu_fingerprint_hash: [u8; 32],
+        capabilities: u64,
+        max_slots: u8,
     ) -> Result<()> {
         instructions::register::register(
             ctx,
@@ -36,6 +39,9 @@ pub mod hypernode_nodes {
             iops,
             storage_gb,
             endpoint,
+            gpu_fingerprint_hash,
+            capabilities,
+            max_slots,
         )
     }
 
@@ -48,6 +54,8 @@ pub mod hypernode_nodes {
         iops: u32,
         storage_gb: u32,
         endpoint: String,
+        capabilities: u64,
+        max_slots: u8,
     ) -> Result<()> {
         instructions::update::update(
             ctx,
@@ -57,15 +65,24 @@ pub mod hypernode_nodes {
             iops,
             storage_gb,
             endpoint,
+            capabilities,
+            max_slots,
         )
     }
 
-    /// Send heartbeat to mark node as active
-    pub fn heartbeat(ctx: Context<Heartbeat>) -> Result<()> {
-        instructions::heartbeat::heartbeat(ctx)
+    /// Send heartbeat to mark node as active, optionally with current metrics
+    pub fn heartbeat(ctx: Context<Heartbeat>, metrics: Option<NodeMetrics>) -> Result<()> {
+        instructions::heartbeat::heartbeat(ctx, metrics)
     }
 
-    /// Check node health (permissionless - Checker system)
+    /// Send heartbeats for several nodes of the same authority in one transaction
+    pub fn heartbeat_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, HeartbeatBatch<'info>>,
+    ) -> Result<()> {
+        instructions::heartbeat_batch::heartbeat_batch(ctx)
+    }
+
+    /// Check node health (approved checkers only - Checker system)
     pub fn check_health(
         ctx: Context<CheckHealth>,
         passed: bool,
@@ -73,4 +90,70 @@ pub mod hypernode_nodes {
     ) -> Result<()> {
         instructions::check_health::check_health(ctx, passed, message)
     }
+
+    /// Initialize nodes config (upgrade authority only; becomes the trusted authority)
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config::initialize_config(ctx)
+    }
+
+    /// Clear a flagged node (trusted authority only)
+    pub fn reinstate_node(
+        ctx: Context<ReinstateNode>,
+        reset_challenge_failures: bool,
+    ) -> Result<()> {
+        instructions::reinstate_node::reinstate_node(ctx, reset_challenge_failures)
+    }
+
+    /// Enable or disable the geo-diversity priority boost (trusted authority only)
+    pub fn set_geo_diversity(ctx: Context<SetGeoDiversity>, enabled: bool) -> Result<()> {
+        instructions::set_geo_diversity::set_geo_diversity(ctx, enabled)
+    }
+
+    /// Set the lowest job price the node accepts (0 = any price)
+    pub fn set_min_job_price(ctx: Context<SetMinJobPrice>, min_job_price: u64) -> Result<()> {
+        instructions::set_min_job_price::set_min_job_price(ctx, min_job_price)
+    }
+
+    /// Record a finished job on the node's stats (Markets Program CPI only)
+    pub fn update_stats(ctx: Context<UpdateStats>, completed: bool, earned: u64) -> Result<()> {
+        instructions::update_stats::update_stats(ctx, completed, earned)
+    }
+
+    /// Create the global node blacklist (trusted authority only)
+    pub fn initialize_blacklist(ctx: Context<InitializeBlacklist>) -> Result<()> {
+        instructions::initialize_blacklist::initialize_blacklist(ctx)
+    }
+
+    /// Ban or unban a node authority across registration and market queues (trusted authority only)
+    pub fn set_blacklisted(
+        ctx: Context<SetBlacklisted>,
+        node_authority: Pubkey,
+        banned: bool,
+    ) -> Result<()> {
+        instructions::set_blacklisted::set_blacklisted(ctx, node_authority, banned)
+    }
+
+    /// Record or revoke a node's audit (trusted authority only)
+    pub fn set_audited(ctx: Context<SetAudited>, audited: bool) -> Result<()> {
+        instructions::set_audited::set_audited(ctx, audited)
+    }
+
+    /// Set the reputation bonus markets grant audited nodes (trusted authority only)
+    pub fn set_audit_bonus(ctx: Context<SetAuditBonus>, audit_reputation_bonus: u16) -> Result<()> {
+        instructions::set_audit_bonus::set_audit_bonus(ctx, audit_reputation_bonus)
+    }
+
+    /// Approve or revoke a health check oracle (trusted authority only)
+    pub fn set_health_checker(
+        ctx: Context<SetHealthChecker>,
+        checker: Pubkey,
+        approved: bool,
+    ) -> Result<()> {
+        instructions::set_health_checker::set_health_checker(ctx, checker, approved)
+    }
+
+    /// Close a node's registration, returning its rent to the authority
+    pub fn deregister(ctx: Context<Deregister>) -> Result<()> {
+        instructions::deregister::deregister(ctx)
+    }
 }
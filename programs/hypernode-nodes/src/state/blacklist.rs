@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+
+/// Most node authorities the blacklist can hold
+pub const MAX_BLACKLIST_ENTRIES: usize = 128;
+
+/// Global blacklist of banned node authorities
+///
+/// Banned keys can't register nodes here, and the Markets and Jobs programs
+/// refuse to queue them. Managed by the NodesConfig authority.
+/// Seeds: ["blacklist"]
+#[account]
+pub struct Blacklist {
+    /// Banned node authorities
+    pub authorities: Vec<Pubkey>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl Blacklist {
+    pub const SPACE: usize = 8 + // discriminator
+        4 + 32 * MAX_BLACKLIST_ENTRIES + // authorities
+        1; // bump
+
+    /// Check whether a node authority is banned
+    pub fn is_banned(&self, authority: &Pubkey) -> bool {
+        self.authorities.contains(authority)
+    }
+
+    /// Ban a node authority; returns false if it already was
+    pub fn ban(&mut self, authority: Pubkey) -> Option<bool> {
+        if self.is_banned(&authority) {
+            return Some(false);
+        }
+        if self.authorities.len() >= MAX_BLACKLIST_ENTRIES {
+            return None;
+        }
+        self.authorities.push(authority);
+        Some(true)
+    }
+
+    /// Lift a ban; returns false if the authority wasn't banned
+    pub fn unban(&mut self, authority: &Pubkey) -> bool {
+        let before = self.authorities.len();
+        self.authorities.retain(|banned| banned != authority);
+        self.authorities.len() != before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blacklisted_authority_cannot_register() {
+        let mut blacklist = Blacklist { authorities: Vec::new(), bump: 0 };
+        let slashed = Pubkey::new_unique();
+        let honest = Pubkey::new_unique();
+
+        assert_eq!(blacklist.ban(slashed), Some(true));
+        assert_eq!(blacklist.ban(slashed), Some(false));
+
+        // register checks the signing authority, whatever node_id it picks
+        assert!(blacklist.is_banned(&slashed));
+        assert!(!blacklist.is_banned(&honest));
+
+        assert!(blacklist.unban(&slashed));
+        assert!(!blacklist.unban(&slashed));
+        assert!(!blacklist.is_banned(&slashed));
+    }
+
+    #[test]
+    fn test_blacklist_capacity() {
+        let mut blacklist = Blacklist { authorities: Vec::new(), bump: 0 };
+        for _ in 0..MAX_BLACKLIST_ENTRIES {
+            assert_eq!(blacklist.ban(Pubkey::new_unique()), Some(true));
+        }
+        assert_eq!(blacklist.ban(Pubkey::new_unique()), None);
+
+        let mut data = Vec::new();
+        blacklist.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), Blacklist::SPACE);
+
+        // The Jobs Program reads the blacklist raw by this discriminator
+        assert_eq!(data[..8], [131, 9, 212, 250, 58, 186, 247, 3]);
+    }
+}
@@ -0,0 +1,206 @@
+use anchor_lang::prelude::*;
+use super::node::{Country, COUNTRY_COUNT};
+
+/// Largest matching-priority boost for nodes in the scarcest regions
+pub const GEO_DIVERSITY_MAX_BOOST: u8 = 3;
+
+/// Reputation bonus for audited nodes unless the authority sets another
+pub const DEFAULT_AUDIT_REPUTATION_BONUS: u16 = 200;
+
+/// Highest reputation bonus the authority can grant for an audit
+pub const MAX_AUDIT_REPUTATION_BONUS: u16 = 500;
+
+/// Most health checkers the authority can approve
+pub const MAX_HEALTH_CHECKERS: usize = 16;
+
+/// Nodes program configuration
+/// Holds the trusted authority allowed to reinstate flagged nodes,
+/// the checkers allowed to report health checks,
+/// and the per-country node-count registry used for the geo-diversity boost
+#[account]
+pub struct NodesConfig {
+    /// Trusted authority (audits / reinstatements)
+    pub authority: Pubkey,
+
+    /// Whether nodes in under-represented countries get a priority boost (off by default)
+    pub geo_diversity_enabled: bool,
+
+    /// Registered nodes per country, indexed by Country::index()
+    pub country_node_counts: [u32; COUNTRY_COUNT],
+
+    /// Reputation bonus audited nodes get in markets NodeReputation
+    pub audit_reputation_bonus: u16,
+
+    /// Oracles allowed to submit health check results
+    pub health_checkers: Vec<Pubkey>,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl NodesConfig {
+    pub const SPACE: usize = 8 + // discriminator
+        32 + // authority
+        1 + // geo_diversity_enabled
+        4 * COUNTRY_COUNT + // country_node_counts
+        2 + // audit_reputation_bonus
+        4 + 32 * MAX_HEALTH_CHECKERS + // health_checkers
+        1; // bump
+
+    /// Check an audit bonus is within MAX_AUDIT_REPUTATION_BONUS
+    pub fn is_valid_audit_bonus(bonus: u16) -> bool {
+        bonus <= MAX_AUDIT_REPUTATION_BONUS
+    }
+
+    /// Check whether a key may submit health check results
+    pub fn is_health_checker(&self, checker: &Pubkey) -> bool {
+        self.health_checkers.contains(checker)
+    }
+
+    /// Approve or revoke a health checker; returns false if unchanged
+    /// None if approving would exceed MAX_HEALTH_CHECKERS
+    pub fn set_health_checker(&mut self, checker: Pubkey, approved: bool) -> Option<bool> {
+        if approved == self.is_health_checker(&checker) {
+            return Some(false);
+        }
+        if approved {
+            if self.health_checkers.len() >= MAX_HEALTH_CHECKERS {
+                return None;
+            }
+            self.health_checkers.push(checker);
+        } else {
+            self.health_checkers.retain(|approved| *approved != checker);
+        }
+        Some(true)
+    }
+
+    /// Count a newly registered (or reinstated) node in its country
+    pub fn record_registration(&mut self, country: &Country) {
+        let count = &mut self.country_node_counts[country.index()];
+        *count = count.saturating_add(1);
+    }
+
+    /// Stop counting a deregistered (or flagged) node in its country
+    pub fn record_deregistration(&mut self, country: &Country) {
+        let count = &mut self.country_node_counts[country.index()];
+        *count = count.saturating_sub(1);
+    }
+
+    /// Matching-priority boost for a node in `country`
+    /// Scales from 0 (at or above an even share of known-country nodes)
+    /// up to GEO_DIVERSITY_MAX_BOOST (no other nodes there). Always 0 when disabled
+    pub fn geo_priority_boost(&self, country: &Country) -> u8 {
+        if !self.geo_diversity_enabled || *country == Country::Unknown {
+            return 0;
+        }
+
+        let known = &self.country_node_counts[..Country::Unknown.index()];
+        let total: u64 = known.iter().map(|&c| c as u64).sum();
+        if total == 0 {
+            return 0;
+        }
+
+        // Compare count * regions against total to avoid rounding the even share
+        let regions = known.len() as u64;
+        let scaled_count = self.country_node_counts[country.index()] as u64 * regions;
+        if scaled_count >= total {
+            return 0;
+        }
+
+        let deficit = total - scaled_count;
+        ((GEO_DIVERSITY_MAX_BOOST as u64 * deficit + total / 2) / total) as u8
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_counts(us: u32, br: u32) -> NodesConfig {
+        let mut config = NodesConfig {
+            authority: Pubkey::new_unique(),
+            geo_diversity_enabled: false,
+            country_node_counts: [0; COUNTRY_COUNT],
+            audit_reputation_bonus: DEFAULT_AUDIT_REPUTATION_BONUS,
+            health_checkers: Vec::new(),
+            bump: 0,
+        };
+        config.country_node_counts[Country::US.index()] = us;
+        config.country_node_counts[Country::BR.index()] = br;
+        config
+    }
+
+    #[test]
+    fn test_rare_country_boosted_over_saturated_region() {
+        let mut config = config_with_counts(90, 1);
+
+        // Off by default
+        assert_eq!(config.geo_priority_boost(&Country::BR), 0);
+        assert_eq!(config.geo_priority_boost(&Country::US), 0);
+
+        config.geo_diversity_enabled = true;
+        assert_eq!(config.geo_priority_boost(&Country::US), 0);
+        assert!(config.geo_priority_boost(&Country::BR) > config.geo_priority_boost(&Country::US));
+        assert_eq!(config.geo_priority_boost(&Country::JP), GEO_DIVERSITY_MAX_BOOST);
+        assert_eq!(config.geo_priority_boost(&Country::Unknown), 0);
+    }
+
+    #[test]
+    fn test_registration_counts_towards_registry() {
+        let mut config = config_with_counts(0, 0);
+        config.geo_diversity_enabled = true;
+        assert_eq!(config.geo_priority_boost(&Country::DE), 0);
+
+        config.record_registration(&Country::DE);
+        config.record_registration(&Country::DE);
+        assert_eq!(config.country_node_counts[Country::DE.index()], 2);
+
+        // Only region with nodes: at or above its share, no boost
+        assert_eq!(config.geo_priority_boost(&Country::DE), 0);
+        assert_eq!(config.geo_priority_boost(&Country::FR), GEO_DIVERSITY_MAX_BOOST);
+    }
+
+    #[test]
+    fn test_deregistration_leaves_registry() {
+        let mut config = config_with_counts(0, 1);
+        config.geo_diversity_enabled = true;
+        config.record_registration(&Country::DE);
+
+        // The only BR node leaves: BR is empty again, DE alone holds the share
+        config.record_deregistration(&Country::BR);
+        assert_eq!(config.country_node_counts[Country::BR.index()], 0);
+        assert_eq!(config.geo_priority_boost(&Country::DE), 0);
+        assert_eq!(config.geo_priority_boost(&Country::BR), GEO_DIVERSITY_MAX_BOOST);
+
+        // Never below zero
+        config.record_deregistration(&Country::BR);
+        assert_eq!(config.country_node_counts[Country::BR.index()], 0);
+    }
+
+    #[test]
+    fn test_only_approved_checkers_report_health() {
+        let mut config = config_with_counts(0, 0);
+        let oracle = Pubkey::new_unique();
+        let anyone = Pubkey::new_unique();
+
+        assert!(!config.is_health_checker(&oracle));
+        assert_eq!(config.set_health_checker(oracle, true), Some(true));
+        assert_eq!(config.set_health_checker(oracle, true), Some(false));
+        assert!(config.is_health_checker(&oracle));
+        assert!(!config.is_health_checker(&anyone));
+
+        assert_eq!(config.set_health_checker(oracle, false), Some(true));
+        assert_eq!(config.set_health_checker(oracle, false), Some(false));
+        assert!(!config.is_health_checker(&oracle));
+
+        // Capacity
+        for _ in 0..MAX_HEALTH_CHECKERS {
+            assert_eq!(config.set_health_checker(Pubkey::new_unique(), true), Some(true));
+        }
+        assert_eq!(config.set_health_checker(anyone, true), None);
+
+        let mut data = Vec::new();
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), NodesConfig::SPACE);
+    }
+}
@@ -1,3 +1,7 @@
+pub mod config;
 pub mod node;
+pub mod blacklist;
 
+pub use config::*;
 pub use node::*;
+pub use blacklist::*;
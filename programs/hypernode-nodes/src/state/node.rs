@@ -1,4 +1,48 @@
 use anchor_lang::prelude::*;
+use crate::instructions::register::NodeError;
+
+/// Job-type capability bits advertised by nodes (Node::capabilities)
+/// Jobs list the bits they need in required_capabilities
+pub const CAPABILITY_PYTORCH: u64 = 1 << 0;
+pub const CAPABILITY_TENSORFLOW: u64 = 1 << 1;
+pub const CAPABILITY_ONNX: u64 = 1 << 2;
+pub const CAPABILITY_VLLM: u64 = 1 << 3;
+pub const CAPABILITY_CUDA_11: u64 = 1 << 4;
+pub const CAPABILITY_CUDA_12: u64 = 1 << 5;
+pub const CAPABILITY_ROCM: u64 = 1 << 6;
+
+/// Health check auto-flagging (Checker system)
+/// A node is flagged once its pass rate drops below the threshold,
+/// but only after enough checks for the rate to be meaningful
+pub const HEALTH_FLAG_PASS_RATE_THRESHOLD: u8 = 50; // percent
+pub const HEALTH_FLAG_MIN_SAMPLES: u64 = 10;
+
+/// Liveness scoring (independent of job outcomes)
+/// Heartbeats at or under the expected interval score 100; longer gaps score
+/// proportionally less and feed an EMA of heartbeat regularity
+pub const HEARTBEAT_INTERVAL: i64 = 300; // 5 minutes
+pub const LIVENESS_EMA_ALPHA: u8 = 25; // percent weight of the newest gap
+
+/// Shortest gap between two heartbeats of a node
+/// Stops a node that was down from spamming heartbeats to rebuild its
+/// regularity at once: recovering takes real time at a regular pace
+pub const MIN_HEARTBEAT_SPACING: i64 = HEARTBEAT_INTERVAL / 2;
+
+/// Maximum nodes updated by one heartbeat_batch call
+pub const MAX_HEARTBEAT_BATCH: usize = 16;
+
+/// Upper bounds on heartbeat telemetry (NodeMetrics)
+pub const MAX_GPU_TEMPERATURE_C: u8 = 120;
+pub const MAX_REPORTED_VRAM_GB: u16 = 1024;
+
+/// Markets program (referenced by ID: markets depends on this crate)
+pub const MARKETS_PROGRAM_ID: Pubkey = pubkey!("67UE2LconF9QU5Vobsaf5sXnW9yUisebLj8VmgGWLSdb");
+
+/// Seed of the Markets Program PDA that signs update_stats CPIs
+pub const MARKETS_STATS_AUTHORITY_SEED: &[u8] = b"stats_authority";
+
+/// Upper bound on concurrent job slots a node can advertise
+pub const MAX_NODE_SLOTS: u8 = 16;
 
 /// Node account storing hardware specs and stats
 #[account]
@@ -21,6 +65,15 @@ pub struct Node {
     pub iops: u32,
     pub storage_gb: u32,
 
+    /// Supported job types/frameworks (CAPABILITY_* bitmask)
+    pub capabilities: u64,
+
+    /// Jobs the node can run concurrently (1..=MAX_NODE_SLOTS)
+    pub max_slots: u8,
+
+    /// Lowest job price the node accepts (0 = any price)
+    pub min_job_price: u64,
+
     /// Performance stats
     pub jobs_completed: u64,
     pub jobs_failed: u64,
@@ -37,6 +90,10 @@ pub struct Node {
     pub last_heartbeat: i64,
     pub is_active: bool,
 
+    /// Latest self-reported telemetry (heartbeat), None until the first report
+    pub last_metrics: Option<NodeMetrics>,
+    pub last_metrics_at: i64,
+
     /// Health check stats (Checker system)
     pub last_health_check: i64,
     pub total_health_checks: u64,
@@ -44,6 +101,10 @@ pub struct Node {
     pub failed_health_checks: u64,
     pub health_check_pass_rate: u8, // 0-100
 
+    /// Liveness (heartbeat regularity + health check pass rate, no job outcomes)
+    pub heartbeat_regularity: u8, // 0-100, EMA of heartbeat gap scores
+    pub liveness_score: u8, // 0-100
+
     /// Anti-spoofing verification
     pub gpu_fingerprint_hash: [u8; 32], // Hash of GPU hardware details
     pub last_challenge_ts: i64,
@@ -52,6 +113,10 @@ pub struct Node {
     pub audit_failures: u32,
     pub is_flagged: bool, // Flagged for suspicious behavior
 
+    /// Reinstatement history (flag cleared by trusted authority)
+    pub reinstated_at: i64,
+    pub reinstatement_count: u32,
+
     /// PDA bump
     pub bump: u8,
 }
@@ -71,6 +136,9 @@ impl Node {
         2 + // ram_gb
         4 + // iops
         4 + // storage_gb
+        8 + // capabilities
+        1 + // max_slots
+        8 + // min_job_price
         8 + // jobs_completed
         8 + // jobs_failed
         8 + // total_earned
@@ -81,18 +149,210 @@ impl Node {
         8 + // registered_at
         8 + // last_heartbeat
         1 + // is_active
+        1 + NodeMetrics::SPACE + // last_metrics
+        8 + // last_metrics_at
         8 + // last_health_check
         8 + // total_health_checks
         8 + // passed_health_checks
         8 + // failed_health_checks
         1 + // health_check_pass_rate
+        1 + // heartbeat_regularity
+        1 + // liveness_score
         32 + // gpu_fingerprint_hash
         8 + // last_challenge_ts
         4 + // challenge_failures
         4 + // challenge_successes
         4 + // audit_failures
         1 + // is_flagged
+        8 + // reinstated_at
+        4 + // reinstatement_count
         1; // bump
+
+    /// Check an advertised slot count is within 1..=MAX_NODE_SLOTS
+    pub fn is_valid_max_slots(max_slots: u8) -> bool {
+        (1..=MAX_NODE_SLOTS).contains(&max_slots)
+    }
+
+    /// Record a health check result and recompute the pass rate
+    /// Returns true if this check newly flagged the node
+    pub fn record_health_check(&mut self, passed: bool) -> bool {
+        self.total_health_checks = self.total_health_checks.saturating_add(1);
+        if passed {
+            self.passed_health_checks = self.passed_health_checks.saturating_add(1);
+        } else {
+            self.failed_health_checks = self.failed_health_checks.saturating_add(1);
+        }
+
+        self.health_check_pass_rate =
+            ((self.passed_health_checks * 100) / self.total_health_checks) as u8;
+
+        self.update_liveness_score();
+
+        if !self.is_flagged && self.is_chronically_unhealthy() {
+            self.is_flagged = true;
+            return true;
+        }
+        false
+    }
+
+    /// Check a heartbeat at `now` is at least MIN_HEARTBEAT_SPACING after the last one
+    pub fn can_heartbeat(&self, now: i64) -> bool {
+        now.saturating_sub(self.last_heartbeat) >= MIN_HEARTBEAT_SPACING
+    }
+
+    /// Record a heartbeat at `now` and fold the gap since the last one
+    /// into heartbeat regularity
+    /// Rejected if it comes sooner than MIN_HEARTBEAT_SPACING after the last
+    pub fn record_heartbeat(&mut self, now: i64) -> Result<()> {
+        require!(self.can_heartbeat(now), NodeError::HeartbeatTooFrequent);
+
+        let gap = now.saturating_sub(self.last_heartbeat);
+        let gap_score = if gap <= HEARTBEAT_INTERVAL {
+            100
+        } else {
+            (HEARTBEAT_INTERVAL * 100 / gap) as u8
+        };
+
+        self.heartbeat_regularity = ((self.heartbeat_regularity as u16
+            * (100 - LIVENESS_EMA_ALPHA) as u16
+            + gap_score as u16 * LIVENESS_EMA_ALPHA as u16)
+            / 100) as u8;
+
+        self.last_heartbeat = now;
+        self.is_active = true;
+        self.update_liveness_score();
+        Ok(())
+    }
+
+    /// Store telemetry reported with a heartbeat at `now`
+    pub fn record_metrics(&mut self, metrics: NodeMetrics, now: i64) -> Result<()> {
+        require!(metrics.is_valid(), NodeError::InvalidMetrics);
+
+        self.last_metrics = Some(metrics);
+        self.last_metrics_at = now;
+        Ok(())
+    }
+
+    /// Recompute the liveness score: equal parts heartbeat regularity and
+    /// health check pass rate (regularity alone until the first check)
+    pub fn update_liveness_score(&mut self) {
+        self.liveness_score = if self.total_health_checks == 0 {
+            self.heartbeat_regularity
+        } else {
+            ((self.heartbeat_regularity as u16 + self.health_check_pass_rate as u16) / 2) as u8
+        };
+    }
+
+    /// Matching filter: node's liveness is at least `min_liveness`
+    pub fn meets_liveness(&self, min_liveness: u8) -> bool {
+        self.liveness_score >= min_liveness
+    }
+
+    /// Clear the flag on behalf of the trusted authority
+    /// The node's own authority can never reinstate it
+    pub fn reinstate(
+        &mut self,
+        reinstated_by: &Pubkey,
+        now: i64,
+        reset_challenge_failures: bool,
+    ) -> Result<()> {
+        require!(*reinstated_by != self.authority, NodeError::SelfReinstatement);
+        require!(self.is_flagged, NodeError::NodeNotFlagged);
+
+        self.is_flagged = false;
+        if reset_challenge_failures {
+            self.challenge_failures = 0;
+        }
+        self.reinstated_at = now;
+        self.reinstatement_count = self.reinstatement_count.saturating_add(1);
+        Ok(())
+    }
+
+    /// Mark the node audited (or not) on behalf of the trusted authority
+    /// Markets reputation grants audited nodes NodesConfig::audit_reputation_bonus
+    pub fn set_audited(&mut self, audited_by: &Pubkey, audited: bool) -> Result<()> {
+        require!(*audited_by != self.authority, NodeError::SelfAudit);
+        self.is_audited = audited;
+        Ok(())
+    }
+
+    /// Record a finished Markets Program job (reported by update_stats)
+    /// Completed jobs add the node's payout to total_earned
+    pub fn record_job_outcome(&mut self, completed: bool, earned: u64) -> Result<()> {
+        if completed {
+            self.jobs_completed = self.jobs_completed
+                .checked_add(1)
+                .ok_or(NodeError::MathOverflow)?;
+            self.total_earned = self.total_earned
+                .checked_add(earned)
+                .ok_or(NodeError::MathOverflow)?;
+        } else {
+            self.jobs_failed = self.jobs_failed
+                .checked_add(1)
+                .ok_or(NodeError::MathOverflow)?;
+        }
+        Ok(())
+    }
+
+    /// Pass rate below threshold over the minimum sample size
+    pub fn is_chronically_unhealthy(&self) -> bool {
+        self.total_health_checks >= HEALTH_FLAG_MIN_SAMPLES
+            && self.health_check_pass_rate < HEALTH_FLAG_PASS_RATE_THRESHOLD
+    }
+}
+
+/// Current load reported by a node with its heartbeat
+/// Lets matchers prefer nodes with spare capacity
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NodeMetrics {
+    /// GPU utilization (0-100%)
+    pub gpu_utilization: u8,
+
+    /// Free GPU memory (GB, up to MAX_REPORTED_VRAM_GB)
+    pub free_vram_gb: u16,
+
+    /// GPU temperature (°C, up to MAX_GPU_TEMPERATURE_C)
+    pub gpu_temperature_c: u8,
+}
+
+impl NodeMetrics {
+    pub const SPACE: usize = 1 + 2 + 1;
+
+    /// Check every reading is within range
+    pub fn is_valid(&self) -> bool {
+        self.gpu_utilization <= 100
+            && self.free_vram_gb <= MAX_REPORTED_VRAM_GB
+            && self.gpu_temperature_c <= MAX_GPU_TEMPERATURE_C
+    }
+}
+
+/// Record a heartbeat for every node in a batch owned by `authority`
+/// Nothing is updated unless every node belongs to the authority and is
+/// due a heartbeat (MIN_HEARTBEAT_SPACING)
+/// Returns the number of nodes updated
+pub fn record_batch_heartbeat<'a>(
+    nodes: impl IntoIterator<Item = &'a mut Node>,
+    authority: &Pubkey,
+    now: i64,
+) -> Result<usize> {
+    let mut nodes: Vec<&mut Node> = nodes.into_iter().collect();
+    require!(
+        !nodes.is_empty() && nodes.len() <= MAX_HEARTBEAT_BATCH,
+        NodeError::InvalidHeartbeatBatch
+    );
+    require!(
+        nodes.iter().all(|node| node.authority == *authority),
+        NodeError::Unauthorized
+    );
+    require!(
+        nodes.iter().all(|node| node.can_heartbeat(now)),
+        NodeError::HeartbeatTooFrequent
+    );
+
+    for node in nodes.iter_mut() {
+        node.record_heartbeat(now)?;
+    }
+    Ok(nodes.len())
 }
 
 /// Hardware architecture types
@@ -126,3 +386,314 @@ pub enum Country {
     IN, // India
     Unknown,
 }
+
+/// Number of Country variants (size of the per-country registry)
+pub const COUNTRY_COUNT: usize = 11;
+
+impl Country {
+    /// Position in NodesConfig::country_node_counts
+    pub fn index(&self) -> usize {
+        match self {
+            Country::US => 0,
+            Country::BR => 1,
+            Country::DE => 2,
+            Country::JP => 3,
+            Country::CN => 4,
+            Country::GB => 5,
+            Country::FR => 6,
+            Country::CA => 7,
+            Country::AU => 8,
+            Country::IN => 9,
+            Country::Unknown => 10,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_node() -> Node {
+        Node {
+            authority: Pubkey::new_unique(),
+            node_id: "node-1".to_string(),
+            is_audited: false,
+            architecture: Architecture::Amd64,
+            country: Country::US,
+            cpu_cores: 16,
+            gpu_cores: 1,
+            ram_gb: 64,
+            iops: 0,
+            storage_gb: 512,
+            capabilities: 0,
+            max_slots: 1,
+            min_job_price: 0,
+            jobs_completed: 0,
+            jobs_failed: 0,
+            total_earned: 0,
+            reputation_score: 500,
+            uptime_percentage: 100,
+            endpoint: String::new(),
+            version: 1,
+            registered_at: 0,
+            last_heartbeat: 0,
+            is_active: true,
+            last_metrics: None,
+            last_metrics_at: 0,
+            last_health_check: 0,
+            total_health_checks: 0,
+            passed_health_checks: 0,
+            failed_health_checks: 0,
+            health_check_pass_rate: 0,
+            heartbeat_regularity: 100,
+            liveness_score: 100,
+            gpu_fingerprint_hash: [0u8; 32],
+            last_challenge_ts: 0,
+            challenge_failures: 0,
+            challenge_successes: 0,
+            audit_failures: 0,
+            is_flagged: false,
+            reinstated_at: 0,
+            reinstatement_count: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_node_below_pass_rate_gets_flagged() {
+        let mut node = new_node();
+
+        // 4 passes, then failures drive the rate below 50%
+        for _ in 0..4 {
+            assert!(!node.record_health_check(true));
+        }
+        let mut flagged_at = None;
+        for i in 0..8 {
+            if node.record_health_check(false) {
+                flagged_at = Some(i);
+            }
+        }
+
+        assert!(node.is_flagged);
+        assert_eq!(node.total_health_checks, 12);
+        assert_eq!(node.health_check_pass_rate, 33);
+        // Flagged exactly once, at the first check with enough samples (10th)
+        assert_eq!(flagged_at, Some(5));
+    }
+
+    #[test]
+    fn test_no_flag_below_min_samples() {
+        let mut node = new_node();
+
+        for _ in 0..(HEALTH_FLAG_MIN_SAMPLES - 1) {
+            assert!(!node.record_health_check(false));
+        }
+        assert_eq!(node.health_check_pass_rate, 0);
+        assert!(!node.is_flagged);
+    }
+
+    #[test]
+    fn test_authority_reinstates_flagged_node() {
+        let mut node = new_node();
+        let resolver = Pubkey::new_unique();
+        node.is_flagged = true;
+        node.challenge_failures = 3;
+
+        assert!(node.reinstate(&resolver, 1_000, true).is_ok());
+        assert!(!node.is_flagged);
+        assert_eq!(node.challenge_failures, 0);
+        assert_eq!(node.reinstated_at, 1_000);
+        assert_eq!(node.reinstatement_count, 1);
+
+        // Not flagged anymore
+        assert!(node.reinstate(&resolver, 2_000, false).is_err());
+    }
+
+    #[test]
+    fn test_node_cannot_reinstate_itself() {
+        let mut node = new_node();
+        node.is_flagged = true;
+        let own_authority = node.authority;
+
+        assert!(node.reinstate(&own_authority, 1_000, true).is_err());
+        assert!(node.is_flagged);
+        assert_eq!(node.reinstatement_count, 0);
+    }
+
+    #[test]
+    fn test_set_audited_by_trusted_authority_only() {
+        use crate::state::config::{NodesConfig, MAX_AUDIT_REPUTATION_BONUS};
+
+        let auditor = Pubkey::new_unique();
+        let mut node = new_node();
+
+        node.set_audited(&auditor, true).unwrap();
+        assert!(node.is_audited);
+        node.set_audited(&auditor, false).unwrap();
+        assert!(!node.is_audited);
+
+        // A node can't audit itself
+        let own_authority = node.authority;
+        assert!(node.set_audited(&own_authority, true).is_err());
+        assert!(!node.is_audited);
+        assert!(!NodesConfig::is_valid_audit_bonus(MAX_AUDIT_REPUTATION_BONUS + 1));
+    }
+
+    #[test]
+    fn test_max_slots_bounds() {
+        assert!(!Node::is_valid_max_slots(0));
+        assert!(Node::is_valid_max_slots(1));
+        assert!(Node::is_valid_max_slots(MAX_NODE_SLOTS));
+        assert!(!Node::is_valid_max_slots(MAX_NODE_SLOTS + 1));
+    }
+
+    #[test]
+    fn test_liveness_drops_on_heartbeat_gaps_and_recovers() {
+        let mut node = new_node();
+        let mut now = 0;
+
+        // Regular heartbeats keep liveness at 100
+        for _ in 0..5 {
+            now += HEARTBEAT_INTERVAL;
+            node.record_heartbeat(now).unwrap();
+        }
+        assert_eq!(node.liveness_score, 100);
+
+        // Two long outages
+        now += 10 * HEARTBEAT_INTERVAL;
+        node.record_heartbeat(now).unwrap();
+        let after_one_gap = node.liveness_score;
+        assert!(after_one_gap < 100);
+        now += 10 * HEARTBEAT_INTERVAL;
+        node.record_heartbeat(now).unwrap();
+        assert!(node.liveness_score < after_one_gap);
+        let dropped = node.liveness_score;
+
+        // Back to regular heartbeats: liveness recovers
+        for _ in 0..20 {
+            now += HEARTBEAT_INTERVAL;
+            node.record_heartbeat(now).unwrap();
+        }
+        assert!(node.liveness_score > dropped);
+        assert!(node.meets_liveness(90));
+
+        // Failing health checks pull liveness down; job stats don't matter
+        node.jobs_failed = 100;
+        node.record_health_check(false);
+        assert_eq!(node.liveness_score, node.heartbeat_regularity / 2);
+        assert!(!node.meets_liveness(90));
+    }
+
+    #[test]
+    fn test_heartbeat_spam_cannot_restore_liveness() {
+        let mut node = new_node();
+        let mut now = 0;
+
+        // A long outage
+        now += 20 * HEARTBEAT_INTERVAL;
+        node.record_heartbeat(now).unwrap();
+        let dropped = node.liveness_score;
+        assert!(dropped < 90);
+
+        // A burst right after is rejected and changes nothing
+        for _ in 0..20 {
+            assert_eq!(
+                node.record_heartbeat(now + 1).err(),
+                Some(NodeError::HeartbeatTooFrequent.into())
+            );
+        }
+        assert_eq!((node.liveness_score, node.last_heartbeat), (dropped, now));
+
+        // Recovery needs heartbeats spread over time
+        now += MIN_HEARTBEAT_SPACING;
+        node.record_heartbeat(now).unwrap();
+        assert!(node.liveness_score > dropped);
+        assert!(!node.meets_liveness(90));
+    }
+
+    #[test]
+    fn test_heartbeat_five_nodes_in_one_batch() {
+        let operator = Pubkey::new_unique();
+        let mut fleet: Vec<Node> = (0..5)
+            .map(|_| {
+                let mut node = new_node();
+                node.authority = operator;
+                node.is_active = false;
+                node
+            })
+            .collect();
+
+        assert_eq!(record_batch_heartbeat(fleet.iter_mut(), &operator, 1_000).unwrap(), 5);
+        assert!(fleet.iter().all(|node| node.last_heartbeat == 1_000 && node.is_active));
+
+        // One foreign node rejects the whole batch
+        fleet[2].authority = Pubkey::new_unique();
+        assert_eq!(
+            record_batch_heartbeat(fleet.iter_mut(), &operator, 2_000).err(),
+            Some(NodeError::Unauthorized.into())
+        );
+        assert!(fleet.iter().all(|node| node.last_heartbeat == 1_000));
+
+        // So does one node heartbeating too soon
+        fleet[2].authority = operator;
+        fleet[4].last_heartbeat = 2_000 - MIN_HEARTBEAT_SPACING + 1;
+        assert_eq!(
+            record_batch_heartbeat(fleet.iter_mut(), &operator, 2_000).err(),
+            Some(NodeError::HeartbeatTooFrequent.into())
+        );
+        assert!(fleet[..4].iter().all(|node| node.last_heartbeat == 1_000));
+
+        assert!(record_batch_heartbeat(Vec::<&mut Node>::new(), &operator, 2_000).is_err());
+    }
+
+    #[test]
+    fn test_record_job_outcome() {
+        let mut node = new_node();
+
+        node.record_job_outcome(true, 990_000).unwrap();
+        node.record_job_outcome(true, 10_000).unwrap();
+        assert_eq!(node.jobs_completed, 2);
+        assert_eq!(node.total_earned, 1_000_000);
+        assert_eq!(node.jobs_failed, 0);
+
+        // Failures count but never add earnings
+        node.record_job_outcome(false, 500).unwrap();
+        assert_eq!(node.jobs_failed, 1);
+        assert_eq!(node.total_earned, 1_000_000);
+
+        node.total_earned = u64::MAX;
+        assert!(node.record_job_outcome(true, 1).is_err());
+    }
+
+    fn metrics(gpu_utilization: u8, free_vram_gb: u16, gpu_temperature_c: u8) -> NodeMetrics {
+        NodeMetrics { gpu_utilization, free_vram_gb, gpu_temperature_c }
+    }
+
+    #[test]
+    fn test_heartbeat_with_metrics_stores_them() {
+        let mut node = new_node();
+
+        node.record_heartbeat(1_000).unwrap();
+        node.record_metrics(metrics(85, 12, 71), 1_000).unwrap();
+        assert_eq!(node.last_metrics, Some(metrics(85, 12, 71)));
+        assert_eq!(node.last_metrics_at, 1_000);
+
+        // Out-of-range readings are rejected and the last report kept
+        assert!(node.record_metrics(metrics(101, 12, 71), 1_100).is_err());
+        assert!(node.record_metrics(metrics(85, MAX_REPORTED_VRAM_GB + 1, 71), 1_100).is_err());
+        assert!(node.record_metrics(metrics(85, 12, MAX_GPU_TEMPERATURE_C + 1), 1_100).is_err());
+        assert_eq!(node.last_metrics, Some(metrics(85, 12, 71)));
+        assert_eq!(node.last_metrics_at, 1_000);
+    }
+
+    #[test]
+    fn test_heartbeat_without_metrics_still_recorded() {
+        let mut node = new_node();
+
+        node.record_heartbeat(1_000).unwrap();
+        assert_eq!(node.last_heartbeat, 1_000);
+        assert!(node.is_active);
+        assert_eq!(node.last_metrics, None);
+        assert_eq!(node.last_metrics_at, 0);
+    }
+}
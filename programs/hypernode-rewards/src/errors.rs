@@ -19,4 +19,52 @@ pub enum RewardsError {
 
     #[msg("Calculation overflow")]
     CalculationOverflow,
+
+    #[msg("Claim cooldown has not elapsed")]
+    ClaimCooldown,
+
+    #[msg("Invalid beneficiary")]
+    InvalidBeneficiary,
+
+    #[msg("Treasury fee must be at most 10000 bps")]
+    InvalidFeeSplit,
+
+    #[msg("Treasury account does not match rewards config")]
+    InvalidTreasury,
+
+    #[msg("Rewards vault cannot cover outstanding rewards")]
+    InsolventRewardPool,
+
+    #[msg("Stake account data could not be read")]
+    InvalidStakeAccount,
+
+    #[msg("Claim is below the minimum claim amount; let rewards accumulate")]
+    ClaimBelowMinimum,
+
+    #[msg("Current reward epoch has not ended")]
+    EpochNotEnded,
+
+    #[msg("Token account does not hold the reward mint")]
+    InvalidRewardMint,
+
+    #[msg("Operator stake or token account missing or not the delegated operator's")]
+    InvalidOperatorAccount,
+
+    #[msg("Delegated stake rewards are claimed with claim_rewards or crank_claim")]
+    DelegatedStakeCannotCompound,
+
+    #[msg("Join delay must be between 0 and 7 days")]
+    InvalidJoinDelay,
+
+    #[msg("Stake is queued for the current epoch; advance it before leaving epoch mode")]
+    EpochStakeQueued,
+
+    #[msg("Epoch record missing or not the epoch the stake was queued in")]
+    InvalidEpochRecord,
+
+    #[msg("Stake's epoch was finalized; record its entry with sync_rewards first")]
+    StakeAwaitingJoin,
+
+    #[msg("Staker has not allowed crank claims")]
+    CrankNotAllowed,
 }
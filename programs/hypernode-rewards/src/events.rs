@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+/// Event emitted when rewards are added to the pool
+#[event]
+pub struct RewardsAddedEvent {
+    pub amount: u64,
+    pub new_rate: u128,
+    pub total_xhyper: u128,
+    pub timestamp: i64,
+}
+
+/// Event emitted when a stake (un)registration moves the rate
+#[event]
+pub struct RateChangedEvent {
+    pub old_rate: u128,
+    pub new_rate: u128,
+    pub total_xhyper: u128,
+    pub timestamp: i64,
+}
+
+/// Event emitted when an epoch is finalized and its rewards distributed
+#[event]
+pub struct EpochAdvancedEvent {
+    pub epoch: u64,
+    pub rewards: u64,
+    pub new_rate: u128,
+    pub total_xhyper: u128,
+    pub timestamp: i64,
+}
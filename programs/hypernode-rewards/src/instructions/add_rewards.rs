@@ -5,6 +5,8 @@ use crate::errors::*;
 
 /// Add rewards to the pool
 /// Called when job completes - percentage of payment goes to rewards
+/// The treasury share from RewardsConfig is split off first
+/// In epoch mode the staker share waits in the current epoch until advance_epoch
 #[derive(Accounts)]
 pub struct AddRewards<'info> {
     #[account(
@@ -14,6 +16,13 @@ pub struct AddRewards<'info> {
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
 
+    /// Rewards config (staker/treasury split)
+    #[account(
+        seeds = [b"rewards_config"],
+        bump = rewards_config.bump
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
     /// Source token account (usually from Markets Program)
     #[account(mut)]
     pub source_token_account: Account<'info, TokenAccount>,
@@ -26,6 +35,13 @@ pub struct AddRewards<'info> {
     )]
     pub rewards_vault: Account<'info, TokenAccount>,
 
+    /// Protocol treasury (treasury share)
+    #[account(
+        mut,
+        constraint = treasury_token_account.key() == rewards_config.treasury @ RewardsError::InvalidTreasury
+    )]
+    pub treasury_token_account: Account<'info, TokenAccount>,
+
     /// Authority sending rewards (usually Markets Program)
     pub authority: Signer<'info>,
 
@@ -34,30 +50,52 @@ pub struct AddRewards<'info> {
 
 pub fn handler(ctx: Context<AddRewards>, amount: u64) -> Result<()> {
     let reflection = &mut ctx.accounts.reflection_account;
+    let clock = Clock::get()?;
 
     // Validate amount
     require!(amount > 0, RewardsError::InvalidAmount);
 
-    // Transfer tokens to rewards vault
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.source_token_account.to_account_info(),
-        to: ctx.accounts.rewards_vault.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
-    };
+    let (staker_amount, treasury_amount) = ctx.accounts.rewards_config.split_fee(amount);
+
+    // Transfer treasury share
+    if treasury_amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source_token_account.to_account_info(),
+            to: ctx.accounts.treasury_token_account.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+        );
 
-    let cpi_ctx = CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-    );
+        token::transfer(cpi_ctx, treasury_amount)?;
+    }
 
-    token::transfer(cpi_ctx, amount)?;
+    // Transfer staker share to rewards vault and update reflection accounting
+    if staker_amount > 0 {
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.source_token_account.to_account_info(),
+            to: ctx.accounts.rewards_vault.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+        };
 
-    // Update reflection accounting
-    reflection.add_rewards(amount);
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+        );
 
-    msg!("Rewards added: {}", amount);
+        token::transfer(cpi_ctx, staker_amount)?;
+
+        reflection.accrue_rewards(staker_amount);
+    }
+
+    msg!("Rewards added: {} (stakers: {}, treasury: {})", amount, staker_amount, treasury_amount);
     msg!("New rate: {}", reflection.rate);
     msg!("Total rewards: {}", reflection.total_rewards_distributed);
 
+    emit!(reflection.rewards_added_event(staker_amount, clock.unix_timestamp));
+
     Ok(())
 }
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Finalize the current reward epoch (permissionless, epoch mode only)
+/// Its accumulated rewards become claimable by the stakes in the pool when it
+/// started, stakes queued during it join the pool, and the next epoch starts.
+/// The epoch's accounting is kept in an EpochRecord
+#[derive(Accounts)]
+pub struct AdvanceEpoch<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = EpochRecord::LEN,
+        seeds = [b"epoch", reflection_account.current_epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub epoch_record: Account<'info, EpochRecord>,
+
+    /// Pays for the epoch record
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AdvanceEpoch>) -> Result<()> {
+    let reflection = &mut ctx.accounts.reflection_account;
+    let record = &mut ctx.accounts.epoch_record;
+    let clock = Clock::get()?;
+
+    require!(
+        reflection.can_advance_epoch(clock.unix_timestamp),
+        RewardsError::EpochNotEnded
+    );
+
+    let epoch = reflection.current_epoch;
+    let distributed = reflection.advance_epoch(record, clock.unix_timestamp);
+    record.bump = ctx.bumps.epoch_record;
+
+    msg!("Epoch {} finalized: {} rewards distributed", epoch, distributed);
+    msg!("Shared by: {} xHYPER, joined after: {} xHYPER", record.staked_xhyper, record.joined_xhyper);
+    msg!("New rate: {}", reflection.rate);
+
+    emit!(reflection.epoch_advanced_event(epoch, distributed, clock.unix_timestamp));
+
+    Ok(())
+}
@@ -0,0 +1,231 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::instructions::claim_rewards::{claimable_now, settle_claim};
+
+/// Claim accumulated rewards and restake them
+/// The claim (plus any loyalty bonus) is added to the staker's active stake
+/// through the Staking Program, and the extra xHYPER is registered here.
+/// Without an active stake, or when rewards go to a beneficiary, the claim
+/// is paid out like `claim_rewards`
+/// Delegated stakes owe their operator a commission and claim through
+/// `claim_rewards` or `crank_claim` instead
+#[derive(Accounts)]
+pub struct ClaimAndCompound<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_rewards", user_rewards_account.authority.as_ref()],
+        bump = user_rewards_account.bump,
+        constraint = user_rewards_account.can_be_claimed_by(&authority.key()) @ RewardsError::Unauthorized
+    )]
+    pub user_rewards_account: Account<'info, UserRewardsAccount>,
+
+    /// User's authority or beneficiary
+    pub authority: Signer<'info>,
+
+    /// Recipient's token account (used when the claim can't be compounded)
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user_rewards_account.reward_recipient() @ RewardsError::Unauthorized,
+        constraint = reflection_account.is_reward_mint(&user_token_account.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Rewards vault
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump,
+        constraint = reflection_account.is_reward_mint(&rewards_vault.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+
+    /// Staker's stake account (Staking Program), topped up with the claim
+    /// Without it the claim is paid out instead
+    /// CHECK: Owner and PDA are checked; the Staking Program validates it again
+    #[account(
+        mut,
+        seeds = [b"stake", user_rewards_account.authority.as_ref()],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID,
+        owner = STAKING_PROGRAM_ID
+    )]
+    pub stake_account: Option<UncheckedAccount<'info>>,
+
+    /// Staking vault
+    /// CHECK: PDA checked here, account validated by the Staking Program
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID
+    )]
+    pub staking_vault: UncheckedAccount<'info>,
+
+    /// Staking global statistics
+    /// CHECK: PDA checked here, account validated by the Staking Program
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID
+    )]
+    pub staking_global_stats: UncheckedAccount<'info>,
+
+    /// Staking config (multiplier curve for the compounded tokens)
+    /// CHECK: PDA checked here, account validated by the Staking Program
+    #[account(
+        seeds = [b"staking_config"],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID
+    )]
+    pub staking_config: UncheckedAccount<'info>,
+
+    /// Staker's NodeReputation PDA (Markets Program), for the node tier boost
+    /// on the compounded tokens
+    /// CHECK: PDA checked and parsed by the Staking Program
+    pub node_reputation: UncheckedAccount<'info>,
+
+    /// Vault authority PDA
+    /// CHECK: PDA signer
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    /// CHECK: Staking Program
+    #[account(address = STAKING_PROGRAM_ID)]
+    pub staking_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<ClaimAndCompound>) -> Result<()> {
+    let reflection = &mut ctx.accounts.reflection_account;
+    let user_rewards = &mut ctx.accounts.user_rewards_account;
+    let clock = Clock::get()?;
+
+    require!(
+        user_rewards.operator.is_none(),
+        RewardsError::DelegatedStakeCannotCompound
+    );
+
+    // Same checks as claim_rewards
+    let (claimable, rounding_remainder) = claimable_now(
+        reflection,
+        user_rewards,
+        ctx.accounts.rewards_vault.amount,
+        clock.unix_timestamp,
+    )?;
+
+    // Stake times, if the stake account was passed and is readable
+    let stake_times = match &ctx.accounts.stake_account {
+        Some(stake_account) => read_stake_times(&stake_account.try_borrow_data()?),
+        None => None,
+    };
+
+    // Loyalty bonus, paid from vault surplus only (same as claim_rewards)
+    let bonus = stake_times
+        .map(|(time_stake, time_unstake)| {
+            loyalty_bonus(claimable, time_stake, time_unstake, clock.unix_timestamp)
+        })
+        .unwrap_or(0)
+        .min(reflection.surplus(ctx.accounts.rewards_vault.amount));
+    let amount = claimable + bonus;
+
+    // Rewards owed to a beneficiary are never restaked into the staker's position
+    let compound = user_rewards.beneficiary.is_none()
+        && matches!(stake_times, Some((_, time_unstake)) if time_unstake == 0);
+
+    // Global invariant: the vault must cover all outstanding rewards, not just this claim
+    reflection.record_claim(claimable, ctx.accounts.rewards_vault.amount)?;
+
+    let vault_authority_bump = ctx.bumps.vault_authority;
+    let seeds: &[&[u8]] = &[b"vault_authority", &[vault_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    // Update user rewards account before any xHYPER is added
+    settle_claim(reflection, user_rewards, claimable, rounding_remainder);
+
+    if let (true, Some(stake_account)) = (compound, &ctx.accounts.stake_account) {
+        let xhyper_before = read_stake_xhyper(&stake_account.try_borrow_data()?)
+            .ok_or(RewardsError::InvalidStakeAccount)?;
+
+        // Top up the stake: rewards vault -> staking vault, signed by our vault authority
+        let staking_config = ctx.accounts.staking_config.to_account_info();
+
+        let ix = Instruction {
+            program_id: STAKING_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(stake_account.key(), false),
+                AccountMeta::new(ctx.accounts.staking_vault.key(), false),
+                AccountMeta::new(ctx.accounts.staking_global_stats.key(), false),
+                AccountMeta::new_readonly(staking_config.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.node_reputation.key(), false),
+                AccountMeta::new(ctx.accounts.rewards_vault.key(), false),
+                AccountMeta::new_readonly(ctx.accounts.vault_authority.key(), true),
+                AccountMeta::new_readonly(ctx.accounts.token_program.key(), false),
+            ],
+            data: compound_stake_ix_data(amount),
+        };
+
+        invoke_signed(
+            &ix,
+            &[
+                stake_account.to_account_info(),
+                ctx.accounts.staking_vault.to_account_info(),
+                ctx.accounts.staking_global_stats.to_account_info(),
+                staking_config,
+                ctx.accounts.node_reputation.to_account_info(),
+                ctx.accounts.rewards_vault.to_account_info(),
+                ctx.accounts.vault_authority.to_account_info(),
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.staking_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+
+        // Register the added xHYPER so the compounded tokens earn rewards too
+        let xhyper_after = read_stake_xhyper(&stake_account.try_borrow_data()?)
+            .ok_or(RewardsError::InvalidStakeAccount)?;
+        let added_xhyper = xhyper_after.saturating_sub(xhyper_before);
+        user_rewards.add_xhyper(reflection, added_xhyper);
+
+        msg!("Rewards compounded: {}", claimable);
+        msg!("Loyalty bonus: {}", bonus);
+        msg!("xHYPER added: {}", added_xhyper);
+    } else {
+        // Nothing to compound into: pay out as a normal claim
+        let cpi_accounts = Transfer {
+            from: ctx.accounts.rewards_vault.to_account_info(),
+            to: ctx.accounts.user_token_account.to_account_info(),
+            authority: ctx.accounts.vault_authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+
+        token::transfer(cpi_ctx, amount)?;
+
+        msg!("No active stake, rewards claimed: {}", claimable);
+        msg!("Loyalty bonus: {}", bonus);
+    }
+
+    msg!("Total claimed: {}", user_rewards.total_claimed);
+
+    Ok(())
+}
@@ -5,9 +5,13 @@ use crate::errors::*;
 
 /// Claim accumulated rewards
 /// Rewards are proportional to xHYPER held
+/// Paid to the stake's beneficiary if one is set
+/// Stakes held continuously for 90+ days get a loyalty bonus
+/// Delegated stakes pay their operator's commission out of the base claim
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
     #[account(
+        mut,
         seeds = [b"reflection"],
         bump = reflection_account.bump
     )]
@@ -15,19 +19,20 @@ pub struct ClaimRewards<'info> {
 
     #[account(
         mut,
-        seeds = [b"user_rewards", authority.key().as_ref()],
+        seeds = [b"user_rewards", user_rewards_account.authority.as_ref()],
         bump = user_rewards_account.bump,
-        constraint = user_rewards_account.authority == authority.key() @ RewardsError::Unauthorized
+        constraint = user_rewards_account.can_be_claimed_by(&authority.key()) @ RewardsError::Unauthorized
     )]
     pub user_rewards_account: Account<'info, UserRewardsAccount>,
 
-    /// User's authority
+    /// User's authority or beneficiary
     pub authority: Signer<'info>,
 
-    /// User's token account (destination for rewards)
+    /// Recipient's token account (destination for rewards)
     #[account(
         mut,
-        constraint = user_token_account.owner == authority.key() @ RewardsError::Unauthorized
+        constraint = user_token_account.owner == user_rewards_account.reward_recipient() @ RewardsError::Unauthorized,
+        constraint = reflection_account.is_reward_mint(&user_token_account.mint) @ RewardsError::InvalidRewardMint
     )]
     pub user_token_account: Account<'info, TokenAccount>,
 
@@ -35,10 +40,33 @@ pub struct ClaimRewards<'info> {
     #[account(
         mut,
         seeds = [b"rewards_vault"],
-        bump
+        bump,
+        constraint = reflection_account.is_reward_mint(&rewards_vault.mint) @ RewardsError::InvalidRewardMint
     )]
     pub rewards_vault: Account<'info, TokenAccount>,
 
+    /// Staker's stake account (Staking Program), read for the loyalty bonus
+    /// A closed (withdrawn) stake earns no bonus
+    /// CHECK: PDA is checked; owner and data are checked in stake_loyalty_bonus
+    #[account(
+        seeds = [b"stake", user_rewards_account.authority.as_ref()],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// Operator's stake account (Staking Program), read for the commission rate
+    /// Required when the stake is delegated
+    /// CHECK: PDA and owner are checked in operator_commission_bps
+    pub operator_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// Operator's token account (destination for the commission)
+    #[account(
+        mut,
+        constraint = reflection_account.is_reward_mint(&operator_token_account.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub operator_token_account: Option<Account<'info, TokenAccount>>,
+
     /// Vault authority PDA
     /// CHECK: PDA signer
     #[account(
@@ -50,46 +78,181 @@ pub struct ClaimRewards<'info> {
     pub token_program: Program<'info, Token>,
 }
 
-pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
-    let reflection = &ctx.accounts.reflection_account;
-    let user_rewards = &mut ctx.accounts.user_rewards_account;
+/// Accounts a claim payout moves tokens between
+/// Shared by claim_rewards and crank_claim
+pub(crate) struct ClaimAccounts<'a, 'info> {
+    pub rewards_vault: &'a Account<'info, TokenAccount>,
+    pub user_token_account: &'a Account<'info, TokenAccount>,
+    pub stake_account: &'a UncheckedAccount<'info>,
+    pub operator_stake_account: Option<&'a UncheckedAccount<'info>>,
+    pub operator_token_account: Option<&'a Account<'info, TokenAccount>>,
+    /// Set by a crank taking its fee
+    pub cranker_token_account: Option<&'a Account<'info, TokenAccount>>,
+    pub vault_authority: &'a AccountInfo<'info>,
+    pub vault_authority_bump: u8,
+    pub token_program: &'a Program<'info, Token>,
+}
+
+/// What a claim paid out
+pub(crate) struct ClaimPaid {
+    pub claimed: u64,
+    pub to_recipient: u64,
+    pub bonus: u64,
+    pub commission: u64,
+    pub crank_fee: u64,
+}
+
+/// Check the user can claim now against a vault holding `vault_balance`
+/// Returns (claimable, rounding remainder); shared by every claim path
+pub(crate) fn claimable_now(
+    reflection: &ReflectionAccount,
+    user_rewards: &UserRewardsAccount,
+    vault_balance: u64,
+    current_time: i64,
+) -> Result<(u64, u128)> {
+    // Enforce minimum interval between claims (anti dust-claim spam)
+    require!(
+        user_rewards.can_claim(current_time, reflection.claim_cooldown),
+        RewardsError::ClaimCooldown
+    );
 
-    // Calculate claimable rewards
-    let claimable = user_rewards.calculate_claimable(reflection.rate);
+    let claimable = user_rewards.total_claimable(reflection.rate, current_time);
+    let rounding_remainder = user_rewards.claimable_remainder(reflection.rate);
 
     // Validate there are rewards to claim
     require!(claimable > 0, RewardsError::NoRewardsToClaim);
 
+    // Small balances keep accruing instead of producing dust transfers
+    require!(
+        reflection.meets_min_claim(claimable),
+        RewardsError::ClaimBelowMinimum
+    );
+
     // Validate vault has sufficient balance
     require!(
-        ctx.accounts.rewards_vault.amount >= claimable,
+        vault_balance >= claimable,
         RewardsError::InsufficientVaultBalance
     );
 
-    // Transfer rewards from vault to user
-    let vault_authority_bump = ctx.bumps.vault_authority;
-    let seeds: &[&[u8]] = &[b"vault_authority", &[vault_authority_bump]];
-    let signer_seeds = &[seeds];
+    Ok((claimable, rounding_remainder))
+}
+
+/// Record a paid claim on the user and return its rounding to the pool
+/// once it adds up to whole tokens
+pub(crate) fn settle_claim(
+    reflection: &mut ReflectionAccount,
+    user_rewards: &mut UserRewardsAccount,
+    claimable: u64,
+    rounding_remainder: u128,
+) {
+    user_rewards.update_after_claim(claimable, reflection.rate);
+
+    let recovered = reflection.record_rounding_dust(rounding_remainder);
+    if recovered > 0 {
+        msg!("Rounding dust redistributed: {}", recovered);
+    }
+}
 
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.rewards_vault.to_account_info(),
-        to: ctx.accounts.user_token_account.to_account_info(),
-        authority: ctx.accounts.vault_authority.to_account_info(),
+/// Pay out a user's claim: loyalty bonus, operator commission and crank fee included
+pub(crate) fn pay_claim<'info>(
+    reflection: &mut ReflectionAccount,
+    user_rewards: &mut UserRewardsAccount,
+    accounts: ClaimAccounts<'_, 'info>,
+    current_time: i64,
+) -> Result<ClaimPaid> {
+    let vault_balance = accounts.rewards_vault.amount;
+    let (claimable, rounding_remainder) =
+        claimable_now(reflection, user_rewards, vault_balance, current_time)?;
+
+    // Loyalty bonus for long-term stakers, applied only here so accrual stays O(1)
+    // Paid from vault surplus, never from rewards owed to other stakers
+    let bonus = stake_loyalty_bonus(accounts.stake_account, claimable, current_time)?
+        .min(reflection.surplus(vault_balance));
+
+    // Delegated stake: the operator's commission comes off the base claim,
+    // capped at the rate recorded with the delegation
+    let commission = match user_rewards.operator {
+        Some(operator) => {
+            let operator_stake = accounts.operator_stake_account
+                .ok_or(RewardsError::InvalidOperatorAccount)?;
+            user_rewards.operator_commission(claimable, operator_commission_bps(operator_stake, &operator)?)
+        }
+        None => 0,
     };
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds,
-    );
+    // Global invariant: the vault must cover all outstanding rewards, not just this claim
+    reflection.record_claim(claimable, vault_balance)?;
 
-    token::transfer(cpi_ctx, claimable)?;
+    let (to_recipient, crank_fee) =
+        split_crank_claim(claimable - commission, accounts.cranker_token_account.is_some());
 
-    // Update user rewards account
-    user_rewards.update_after_claim(claimable, reflection.rate);
+    let seeds: &[&[u8]] = &[b"vault_authority", &[accounts.vault_authority_bump]];
+    let signer_seeds = &[seeds];
+    let vault_transfer = |to: AccountInfo<'info>, amount: u64| -> Result<()> {
+        let cpi_accounts = Transfer {
+            from: accounts.rewards_vault.to_account_info(),
+            to,
+            authority: accounts.vault_authority.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            cpi_accounts,
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, amount)
+    };
+
+    // Rewards go to the recipient, the loyalty bonus in full
+    vault_transfer(accounts.user_token_account.to_account_info(), to_recipient + bonus)?;
+
+    if commission > 0 {
+        let operator_token_account = accounts.operator_token_account
+            .filter(|account| user_rewards.operator == Some(account.owner))
+            .ok_or(RewardsError::InvalidOperatorAccount)?;
+        vault_transfer(operator_token_account.to_account_info(), commission)?;
+    }
+
+    if let Some(cranker_token_account) = accounts.cranker_token_account {
+        if crank_fee > 0 {
+            vault_transfer(cranker_token_account.to_account_info(), crank_fee)?;
+        }
+    }
+
+    settle_claim(reflection, user_rewards, claimable, rounding_remainder);
+
+    Ok(ClaimPaid {
+        claimed: claimable,
+        to_recipient,
+        bonus,
+        commission,
+        crank_fee,
+    })
+}
+
+pub fn handler(ctx: Context<ClaimRewards>) -> Result<()> {
+    let clock = Clock::get()?;
+    let accounts = ClaimAccounts {
+        rewards_vault: &ctx.accounts.rewards_vault,
+        user_token_account: &ctx.accounts.user_token_account,
+        stake_account: &ctx.accounts.stake_account,
+        operator_stake_account: ctx.accounts.operator_stake_account.as_ref(),
+        operator_token_account: ctx.accounts.operator_token_account.as_ref(),
+        cranker_token_account: None,
+        vault_authority: &ctx.accounts.vault_authority,
+        vault_authority_bump: ctx.bumps.vault_authority,
+        token_program: &ctx.accounts.token_program,
+    };
+    let paid = pay_claim(
+        &mut ctx.accounts.reflection_account,
+        &mut ctx.accounts.user_rewards_account,
+        accounts,
+        clock.unix_timestamp,
+    )?;
 
-    msg!("Rewards claimed: {}", claimable);
-    msg!("Total claimed: {}", user_rewards.total_claimed);
+    msg!("Rewards claimed: {}", paid.claimed);
+    msg!("Loyalty bonus: {}", paid.bonus);
+    msg!("Operator commission: {}", paid.commission);
+    msg!("Total claimed: {}", ctx.accounts.user_rewards_account.total_claimed);
 
     Ok(())
 }
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::instructions::claim_rewards::{pay_claim, ClaimAccounts};
+use crate::state::*;
+use crate::errors::*;
+
+/// Claim a user's rewards on their behalf (permissionless crank)
+/// Only for stakers who opted in with set_crank_allowed
+/// Rewards always go to the stake's reward recipient, never to the cranker
+/// The cranker may take CRANK_FEE_BPS of the claim by passing its token account
+/// Delegated stakes pay their operator's commission first, as in claim_rewards
+#[derive(Accounts)]
+pub struct CrankClaim<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_rewards", user_rewards_account.authority.as_ref()],
+        bump = user_rewards_account.bump,
+        constraint = user_rewards_account.allow_crank @ RewardsError::CrankNotAllowed
+    )]
+    pub user_rewards_account: Account<'info, UserRewardsAccount>,
+
+    /// Anyone cranking the claim
+    pub cranker: Signer<'info>,
+
+    /// Recipient's token account (destination for rewards)
+    #[account(
+        mut,
+        constraint = user_token_account.owner == user_rewards_account.reward_recipient() @ RewardsError::Unauthorized,
+        constraint = reflection_account.is_reward_mint(&user_token_account.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Cranker's token account for the crank fee (optional)
+    #[account(
+        mut,
+        constraint = cranker_token_account.owner == cranker.key() @ RewardsError::Unauthorized,
+        constraint = reflection_account.is_reward_mint(&cranker_token_account.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub cranker_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards vault
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump,
+        constraint = reflection_account.is_reward_mint(&rewards_vault.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+
+    /// Staker's stake account (Staking Program), read for the loyalty bonus
+    /// Required so a cranker can't strip the bonus; a closed stake earns none
+    /// CHECK: PDA is checked; owner and data are checked in stake_loyalty_bonus
+    #[account(
+        seeds = [b"stake", user_rewards_account.authority.as_ref()],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// Operator's stake account (Staking Program), read for the commission rate
+    /// Required when the stake is delegated
+    /// CHECK: PDA and owner are checked in operator_commission_bps
+    pub operator_stake_account: Option<UncheckedAccount<'info>>,
+
+    /// Operator's token account (destination for the commission)
+    #[account(
+        mut,
+        constraint = reflection_account.is_reward_mint(&operator_token_account.mint) @ RewardsError::InvalidRewardMint
+    )]
+    pub operator_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Vault authority PDA
+    /// CHECK: PDA signer
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CrankClaim>) -> Result<()> {
+    let clock = Clock::get()?;
+
+    // Same rules as a claim by the user; the crank fee comes out of the claim
+    let accounts = ClaimAccounts {
+        rewards_vault: &ctx.accounts.rewards_vault,
+        user_token_account: &ctx.accounts.user_token_account,
+        stake_account: &ctx.accounts.stake_account,
+        operator_stake_account: ctx.accounts.operator_stake_account.as_ref(),
+        operator_token_account: ctx.accounts.operator_token_account.as_ref(),
+        cranker_token_account: ctx.accounts.cranker_token_account.as_ref(),
+        vault_authority: &ctx.accounts.vault_authority,
+        vault_authority_bump: ctx.bumps.vault_authority,
+        token_program: &ctx.accounts.token_program,
+    };
+    let paid = pay_claim(
+        &mut ctx.accounts.reflection_account,
+        &mut ctx.accounts.user_rewards_account,
+        accounts,
+        clock.unix_timestamp,
+    )?;
+
+    msg!(
+        "Rewards cranked for {}: {}",
+        ctx.accounts.user_rewards_account.reward_recipient(),
+        paid.to_recipient
+    );
+    msg!("Loyalty bonus: {}", paid.bonus);
+    msg!("Operator commission: {}", paid.commission);
+    msg!("Crank fee to {}: {}", ctx.accounts.cranker.key(), paid.crank_fee);
+
+    Ok(())
+}
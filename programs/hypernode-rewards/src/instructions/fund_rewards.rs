@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+/// Top up the rewards pool outside job settlement (reward campaigns)
+/// The whole amount goes to stakers: no treasury share is split off
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        constraint = reflection_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Funding token account (treasury or sponsor tokens, owned by the authority)
+    #[account(
+        mut,
+        constraint = source_token_account.owner == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    /// Rewards vault (holds accumulated rewards)
+    #[account(
+        mut,
+        seeds = [b"rewards_vault"],
+        bump
+    )]
+    pub rewards_vault: Account<'info, TokenAccount>,
+
+    /// Reflection authority
+    pub authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+    let reflection = &mut ctx.accounts.reflection_account;
+    let clock = Clock::get()?;
+
+    // Validate amount
+    require!(amount > 0, RewardsError::InvalidAmount);
+
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.source_token_account.to_account_info(),
+        to: ctx.accounts.rewards_vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    );
+
+    token::transfer(cpi_ctx, amount)?;
+
+    // Same reflection accounting as job-fee rewards
+    reflection.accrue_rewards(amount);
+
+    msg!("Rewards pool funded: {}", amount);
+    msg!("New rate: {}", reflection.rate);
+    msg!("Total rewards: {}", reflection.total_rewards_distributed);
+
+    emit!(reflection.rewards_added_event(amount, clock.unix_timestamp));
+
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::Mint;
 use crate::state::*;
 
 /// Initialize reflection account for rewards distribution
@@ -13,6 +14,9 @@ pub struct Initialize<'info> {
     )]
     pub reflection_account: Account<'info, ReflectionAccount>,
 
+    /// Token rewards are paid in (HYPER)
+    pub reward_mint: Account<'info, Mint>,
+
     /// Authority that can add rewards
     #[account(mut)]
     pub authority: Signer<'info>,
@@ -28,10 +32,22 @@ pub fn handler(ctx: Context<Initialize>) -> Result<()> {
     reflection.total_reflection = 0;
     reflection.total_xhyper = 0;
     reflection.total_rewards_distributed = 0;
+    reflection.total_outstanding_rewards = 0;
+    reflection.claim_cooldown = DEFAULT_CLAIM_COOLDOWN;
+    reflection.min_claim_amount = DEFAULT_MIN_CLAIM_AMOUNT;
+    reflection.epoch_duration = 0;
+    reflection.current_epoch = 0;
+    reflection.epoch_started_at = 0;
+    reflection.epoch_rewards = 0;
+    reflection.rounding_dust = 0;
+    reflection.dust_redistributed = 0;
+    reflection.reward_mint = ctx.accounts.reward_mint.key();
+    reflection.join_delay = 0;
     reflection.bump = ctx.bumps.reflection_account;
 
     msg!("Reflection account initialized");
     msg!("Authority: {}", reflection.authority);
+    msg!("Reward mint: {}", reflection.reward_mint);
 
     Ok(())
 }
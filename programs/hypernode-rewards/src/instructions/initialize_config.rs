@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::state::*;
+use crate::errors::*;
+
+/// Initialize the rewards config
+/// Sets the treasury and the share of added rewards it receives
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = RewardsConfig::LEN,
+        seeds = [b"rewards_config"],
+        bump
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    #[account(
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        constraint = reflection_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Protocol treasury token account
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// Reflection authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>, treasury_fee_bps: u16) -> Result<()> {
+    require!(
+        RewardsConfig::is_valid_fee_bps(treasury_fee_bps),
+        RewardsError::InvalidFeeSplit
+    );
+
+    let config = &mut ctx.accounts.rewards_config;
+    config.authority = ctx.accounts.authority.key();
+    config.treasury = ctx.accounts.treasury.key();
+    config.treasury_fee_bps = treasury_fee_bps;
+    config.bump = ctx.bumps.rewards_config;
+
+    msg!("Rewards config initialized");
+    msg!("Treasury: {}", config.treasury);
+    msg!("Treasury fee: {} bps", treasury_fee_bps);
+
+    Ok(())
+}
@@ -3,9 +3,33 @@ pub mod register_stake;
 pub mod unregister_stake;
 pub mod add_rewards;
 pub mod claim_rewards;
+pub mod claim_and_compound;
+pub mod set_claim_cooldown;
+pub mod set_join_delay;
+pub mod set_min_claim_amount;
+pub mod initialize_config;
+pub mod set_fee_split;
+pub mod sync_rewards;
+pub mod fund_rewards;
+pub mod crank_claim;
+pub mod set_epoch_duration;
+pub mod advance_epoch;
+pub mod set_crank_allowed;
 
 pub use initialize::*;
 pub use register_stake::*;
 pub use unregister_stake::*;
 pub use add_rewards::*;
 pub use claim_rewards::*;
+pub use claim_and_compound::*;
+pub use set_claim_cooldown::*;
+pub use set_join_delay::*;
+pub use set_min_claim_amount::*;
+pub use initialize_config::*;
+pub use set_fee_split::*;
+pub use sync_rewards::*;
+pub use fund_rewards::*;
+pub use crank_claim::*;
+pub use set_epoch_duration::*;
+pub use advance_epoch::*;
+pub use set_crank_allowed::*;
@@ -4,6 +4,7 @@ use crate::errors::*;
 
 /// Register user's stake in rewards system
 /// Called after user stakes in Staking Program
+/// xHYPER and beneficiary are read from the stake account, never taken from the caller
 #[derive(Accounts)]
 pub struct RegisterStake<'info> {
     #[account(
@@ -27,35 +28,71 @@ pub struct RegisterStake<'info> {
     pub authority: Signer<'info>,
 
     /// User's stake account from Staking Program
-    /// CHECK: We read this account to get xHYPER balance
-    pub stake_account: AccountInfo<'info>,
+    /// CHECK: Owner and PDA are checked; xHYPER and beneficiary are read from its data
+    #[account(
+        seeds = [b"stake", authority.key().as_ref()],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID,
+        owner = STAKING_PROGRAM_ID
+    )]
+    pub stake_account: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<RegisterStake>, xhyper: u128) -> Result<()> {
+pub fn handler(ctx: Context<RegisterStake>) -> Result<()> {
     let reflection = &mut ctx.accounts.reflection_account;
     let user_rewards = &mut ctx.accounts.user_rewards_account;
     let clock = Clock::get()?;
 
+    let (xhyper, beneficiary) = {
+        let data = ctx.accounts.stake_account.try_borrow_data()?;
+        (read_stake_xhyper(&data), read_stake_beneficiary(&data))
+    };
+    let xhyper = xhyper.ok_or(RewardsError::InvalidStakeAccount)?;
+    let beneficiary = beneficiary.ok_or(RewardsError::InvalidStakeAccount)?;
+
     // Validate xHYPER amount
     require!(xhyper > 0, RewardsError::InvalidXhyperAmount);
 
-    // Register in reflection system
-    let initial_reflection = reflection.add_staker(xhyper);
+    // Validate beneficiary separately from the signing authority
+    if let Some(beneficiary) = beneficiary {
+        require!(
+            beneficiary != Pubkey::default() && beneficiary != ctx.accounts.authority.key(),
+            RewardsError::InvalidBeneficiary
+        );
+    }
+
+    // Register in reflection system (held out during the join delay, queued
+    // until the epoch ends in epoch mode)
+    let old_rate = reflection.rate;
+    let initial_reflection = user_rewards.register(reflection, xhyper, clock.unix_timestamp);
 
     // Initialize user rewards account
     user_rewards.authority = ctx.accounts.authority.key();
-    user_rewards.initial_reflection = initial_reflection;
-    user_rewards.xhyper = xhyper;
     user_rewards.total_claimed = 0;
     user_rewards.last_claim = clock.unix_timestamp;
+    user_rewards.beneficiary = beneficiary;
+    user_rewards.pending_rewards = 0;
+    user_rewards.operator = None;
+    user_rewards.allow_crank = false;
+    user_rewards.commission_bps = 0;
     user_rewards.bump = ctx.bumps.user_rewards_account;
 
     msg!("User registered in rewards system");
     msg!("xHYPER: {}", xhyper);
     msg!("Initial reflection: {}", initial_reflection);
     msg!("Current rate: {}", reflection.rate);
+    if user_rewards.in_join_delay {
+        msg!("Joins the pool from: {}", user_rewards.eligible_at);
+    }
+    if let Some(epoch) = user_rewards.queued_epoch {
+        msg!("Queued: joins the pool when epoch {} is finalized", epoch);
+    }
+
+    if let Some(event) = reflection.rate_changed_event(old_rate, clock.unix_timestamp) {
+        emit!(event);
+    }
 
     Ok(())
 }
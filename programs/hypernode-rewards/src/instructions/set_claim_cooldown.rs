@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the minimum interval between claims
+/// Setting zero disables the cooldown
+#[derive(Accounts)]
+pub struct SetClaimCooldown<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        constraint = reflection_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Reflection authority
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetClaimCooldown>, claim_cooldown: i64) -> Result<()> {
+    require!(claim_cooldown >= 0, RewardsError::InvalidAmount);
+
+    let reflection = &mut ctx.accounts.reflection_account;
+    reflection.claim_cooldown = claim_cooldown;
+
+    msg!("Claim cooldown updated: {} seconds", claim_cooldown);
+
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Opt in to (or out of) crank_claim for the caller's stake
+/// Cranked claims pay the cranker a small fee out of the claim
+#[derive(Accounts)]
+pub struct SetCrankAllowed<'info> {
+    #[account(
+        mut,
+        seeds = [b"user_rewards", authority.key().as_ref()],
+        bump = user_rewards_account.bump,
+        constraint = user_rewards_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub user_rewards_account: Account<'info, UserRewardsAccount>,
+
+    /// Staker
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetCrankAllowed>, allowed: bool) -> Result<()> {
+    ctx.accounts.user_rewards_account.allow_crank = allowed;
+
+    msg!("Crank claims allowed: {}", allowed);
+
+    Ok(())
+}
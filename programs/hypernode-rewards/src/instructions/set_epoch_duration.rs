@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Switch between continuous and epoch-based distribution
+/// A nonzero duration turns on epoch mode; zero goes back to continuous and
+/// distributes the current epoch's budget right away (only once no stake is
+/// queued for it)
+#[derive(Accounts)]
+pub struct SetEpochDuration<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        constraint = reflection_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Reflection authority
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetEpochDuration>, epoch_duration: i64) -> Result<()> {
+    require!(epoch_duration >= 0, RewardsError::InvalidAmount);
+
+    let reflection = &mut ctx.accounts.reflection_account;
+    let clock = Clock::get()?;
+
+    reflection.set_epoch_duration(epoch_duration, clock.unix_timestamp)?;
+
+    msg!("Epoch duration updated: {} seconds", epoch_duration);
+
+    Ok(())
+}
@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the share of added rewards sent to the treasury
+#[derive(Accounts)]
+pub struct SetFeeSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"rewards_config"],
+        bump = rewards_config.bump,
+        constraint = rewards_config.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub rewards_config: Account<'info, RewardsConfig>,
+
+    /// Config authority
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetFeeSplit>, treasury_fee_bps: u16) -> Result<()> {
+    require!(
+        RewardsConfig::is_valid_fee_bps(treasury_fee_bps),
+        RewardsError::InvalidFeeSplit
+    );
+
+    let config = &mut ctx.accounts.rewards_config;
+    config.treasury_fee_bps = treasury_fee_bps;
+
+    msg!("Treasury fee updated: {} bps", treasury_fee_bps);
+
+    Ok(())
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the join delay for newly registered stakes
+/// Setting zero disables it; stakes already registered keep their eligible_at
+#[derive(Accounts)]
+pub struct SetJoinDelay<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        constraint = reflection_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Reflection authority
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetJoinDelay>, join_delay: i64) -> Result<()> {
+    require!(
+        ReflectionAccount::is_valid_join_delay(join_delay),
+        RewardsError::InvalidJoinDelay
+    );
+
+    let reflection = &mut ctx.accounts.reflection_account;
+    reflection.join_delay = join_delay;
+
+    msg!("Join delay updated: {} seconds", join_delay);
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the smallest claim accepted
+/// Smaller balances keep accruing until they reach it
+#[derive(Accounts)]
+pub struct SetMinClaimAmount<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump,
+        constraint = reflection_account.authority == authority.key() @ RewardsError::Unauthorized
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    /// Reflection authority
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetMinClaimAmount>, min_claim_amount: u64) -> Result<()> {
+    require!(min_claim_amount > 0, RewardsError::InvalidAmount);
+
+    let reflection = &mut ctx.accounts.reflection_account;
+    reflection.min_claim_amount = min_claim_amount;
+
+    msg!("Minimum claim amount updated: {}", min_claim_amount);
+
+    Ok(())
+}
@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Re-checkpoint a user's rewards at their stake's current xHYPER
+/// Call after any xHYPER change (top-up, extend). Rewards accrued so far are
+/// kept as pending and the new xHYPER only earns from now on.
+/// Also records the node operator the stake is delegated to (with the
+/// commission rate they charge at that point), the entry into
+/// the pool of a stake queued for an epoch that has since been finalized, and
+/// admits a stake whose join delay has passed.
+/// Permissionless: the xHYPER is read from the Staking Program's stake account
+#[derive(Accounts)]
+pub struct SyncRewards<'info> {
+    #[account(
+        mut,
+        seeds = [b"reflection"],
+        bump = reflection_account.bump
+    )]
+    pub reflection_account: Account<'info, ReflectionAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"user_rewards", user_rewards_account.authority.as_ref()],
+        bump = user_rewards_account.bump
+    )]
+    pub user_rewards_account: Account<'info, UserRewardsAccount>,
+
+    /// User's stake account from Staking Program
+    /// CHECK: Owner and PDA are checked; xHYPER is read from its data
+    #[account(
+        seeds = [b"stake", user_rewards_account.authority.as_ref()],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID,
+        owner = STAKING_PROGRAM_ID
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+
+    /// Record of the epoch the stake was queued in
+    /// Required once that epoch is finalized, until the stake's entry is recorded
+    #[account(
+        seeds = [b"epoch", epoch_record.epoch.to_le_bytes().as_ref()],
+        bump = epoch_record.bump
+    )]
+    pub epoch_record: Option<Account<'info, EpochRecord>>,
+
+    /// Operator's stake account (Staking Program), read for the commission rate
+    /// Required when the stake was delegated to a new operator since the last sync
+    /// CHECK: PDA and owner are checked in operator_commission_bps
+    pub operator_stake_account: Option<UncheckedAccount<'info>>,
+}
+
+pub fn handler(ctx: Context<SyncRewards>) -> Result<()> {
+    let reflection = &mut ctx.accounts.reflection_account;
+    let user_rewards = &mut ctx.accounts.user_rewards_account;
+    let clock = Clock::get()?;
+
+    let (xhyper, delegation) = {
+        let data = ctx.accounts.stake_account.try_borrow_data()?;
+        (read_stake_xhyper(&data), read_stake_delegation(&data))
+    };
+    let xhyper = xhyper.ok_or(RewardsError::InvalidStakeAccount)?;
+    let old_rate = reflection.rate;
+
+    // A queued stake whose epoch was finalized joined the pool at that epoch's join rate
+    if user_rewards.awaits_join(reflection.current_epoch) {
+        let record = ctx.accounts.epoch_record.as_ref().ok_or(RewardsError::InvalidEpochRecord)?;
+        user_rewards.join_pool(record)?;
+        msg!("Joined the pool after epoch {}", record.epoch);
+    }
+
+    // Claims from now on pay the operator's commission, at most at today's rate
+    if let Some((operator, _)) = delegation {
+        if operator != user_rewards.operator {
+            let commission_bps = match operator {
+                Some(operator) => {
+                    let operator_stake = ctx.accounts.operator_stake_account
+                        .as_ref()
+                        .ok_or(RewardsError::InvalidOperatorAccount)?;
+                    operator_commission_bps(operator_stake, &operator)?
+                }
+                None => 0,
+            };
+            user_rewards.record_delegation(operator, commission_bps);
+            msg!("Delegated to operator: {:?}", operator);
+            msg!("Commission: {} bps", commission_bps);
+        }
+    }
+
+    if xhyper == user_rewards.xhyper {
+        msg!("xHYPER unchanged: {}", xhyper);
+    } else {
+        let old_xhyper = user_rewards.xhyper;
+        let settled = user_rewards.sync_xhyper(reflection, xhyper);
+
+        msg!("Rewards synced");
+        msg!("xHYPER: {} -> {}", old_xhyper, xhyper);
+        msg!("Rewards settled: {}", settled);
+        msg!("Pending rewards: {}", user_rewards.pending_rewards);
+    }
+
+    // A stake whose join delay has passed enters the pool from now on, at its current xHYPER
+    if user_rewards.end_join_delay(reflection, clock.unix_timestamp) {
+        msg!("Join delay over: stake entered the pool");
+    }
+
+    if let Some(event) = reflection.rate_changed_event(old_rate, clock.unix_timestamp) {
+        emit!(event);
+    }
+
+    Ok(())
+}
@@ -3,7 +3,8 @@ use crate::state::*;
 use crate::errors::*;
 
 /// Unregister user's stake from rewards system
-/// Called when user unstakes in Staking Program
+/// Called when user unstakes in Staking Program, or by the Staking Program
+/// (signing as the stake account) when the position moves to a new owner
 #[derive(Accounts)]
 pub struct UnregisterStake<'info> {
     #[account(
@@ -23,24 +24,41 @@ pub struct UnregisterStake<'info> {
     pub user_rewards_account: Account<'info, UserRewardsAccount>,
 
     /// User's authority (receives rent refund)
+    /// CHECK: Must sign unless stake_account does
     #[account(mut)]
-    pub authority: Signer<'info>,
+    pub authority: UncheckedAccount<'info>,
+
+    /// User's stake account (Staking Program), signing for the authority
+    /// when the Staking Program transfers the position
+    #[account(
+        seeds = [b"stake", authority.key().as_ref()],
+        bump,
+        seeds::program = STAKING_PROGRAM_ID
+    )]
+    pub stake_account: Option<Signer<'info>>,
 }
 
 pub fn handler(ctx: Context<UnregisterStake>) -> Result<()> {
     let reflection = &mut ctx.accounts.reflection_account;
     let user_rewards = &ctx.accounts.user_rewards_account;
-
-    // Remove from reflection system
-    reflection.remove_staker(
-        user_rewards.xhyper,
-        user_rewards.initial_reflection,
+    let clock = Clock::get()?;
+    require!(
+        ctx.accounts.authority.is_signer || ctx.accounts.stake_account.is_some(),
+        RewardsError::Unauthorized
     );
 
+    // Remove from reflection system (or from the current epoch's queue)
+    let old_rate = reflection.rate;
+    user_rewards.unregister(reflection)?;
+
     msg!("User unregistered from rewards system");
     msg!("xHYPER removed: {}", user_rewards.xhyper);
     msg!("Total claimed: {}", user_rewards.total_claimed);
     msg!("Account closed");
 
+    if let Some(event) = reflection.rate_changed_event(old_rate, clock.unix_timestamp) {
+        emit!(event);
+    }
+
     Ok(())
 }
@@ -1,11 +1,12 @@
 use anchor_lang::prelude::*;
 
 pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
 use instructions::*;
-pub use state::{ReflectionAccount, UserRewardsAccount};
+pub use state::{EpochRecord, ReflectionAccount, RewardsConfig, UserRewardsAccount};
 
 declare_id!("EqBzwuXKmDZbAMf2WTogQhzABsrG6dYbbKXW1adsLhbb");
 
@@ -22,8 +23,10 @@ pub mod hypernode_rewards {
     }
 
     /// Register user's stake in rewards system
-    pub fn register_stake(ctx: Context<RegisterStake>, xhyper: u128) -> Result<()> {
-        instructions::register_stake::handler(ctx, xhyper)
+    /// xHYPER and the optional beneficiary (who receives the rewards instead
+    /// of the staker) are read from the stake account
+    pub fn register_stake(ctx: Context<RegisterStake>) -> Result<()> {
+        instructions::register_stake::handler(ctx)
     }
 
     /// Unregister user's stake (on unstake)
@@ -40,4 +43,66 @@ pub mod hypernode_rewards {
     pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
         instructions::claim_rewards::handler(ctx)
     }
+
+    /// Claim rewards and restake them into the caller's active stake
+    /// Falls back to a normal claim when there is no active stake to top up
+    pub fn claim_and_compound(ctx: Context<ClaimAndCompound>) -> Result<()> {
+        instructions::claim_and_compound::handler(ctx)
+    }
+
+    /// Update minimum interval between claims (0 disables)
+    pub fn set_claim_cooldown(ctx: Context<SetClaimCooldown>, claim_cooldown: i64) -> Result<()> {
+        instructions::set_claim_cooldown::handler(ctx, claim_cooldown)
+    }
+
+    /// Update how long newly registered stakes wait before their rewards are claimable (0 disables)
+    /// Applies to stakes registered from now on
+    pub fn set_join_delay(ctx: Context<SetJoinDelay>, join_delay: i64) -> Result<()> {
+        instructions::set_join_delay::handler(ctx, join_delay)
+    }
+
+    /// Update the smallest claim accepted (1 accepts any nonzero claim)
+    pub fn set_min_claim_amount(ctx: Context<SetMinClaimAmount>, min_claim_amount: u64) -> Result<()> {
+        instructions::set_min_claim_amount::handler(ctx, min_claim_amount)
+    }
+
+    /// Initialize the rewards config (staker/treasury fee split)
+    pub fn initialize_config(ctx: Context<InitializeConfig>, treasury_fee_bps: u16) -> Result<()> {
+        instructions::initialize_config::handler(ctx, treasury_fee_bps)
+    }
+
+    /// Update the share of added rewards sent to the treasury
+    pub fn set_fee_split(ctx: Context<SetFeeSplit>, treasury_fee_bps: u16) -> Result<()> {
+        instructions::set_fee_split::handler(ctx, treasury_fee_bps)
+    }
+
+    /// Re-checkpoint a user's rewards after their stake's xHYPER changed (permissionless)
+    pub fn sync_rewards(ctx: Context<SyncRewards>) -> Result<()> {
+        instructions::sync_rewards::handler(ctx)
+    }
+
+    /// Top up the rewards pool from the authority (reward campaigns, no treasury share)
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        instructions::fund_rewards::handler(ctx, amount)
+    }
+
+    /// Claim a user's rewards into their own token account (permissionless crank, optional small fee)
+    pub fn crank_claim(ctx: Context<CrankClaim>) -> Result<()> {
+        instructions::crank_claim::handler(ctx)
+    }
+
+    /// Switch between continuous distribution (0) and epochs of the given length
+    pub fn set_epoch_duration(ctx: Context<SetEpochDuration>, epoch_duration: i64) -> Result<()> {
+        instructions::set_epoch_duration::handler(ctx, epoch_duration)
+    }
+
+    /// Finalize the current reward epoch, distribute its rewards and record it (permissionless)
+    pub fn advance_epoch(ctx: Context<AdvanceEpoch>) -> Result<()> {
+        instructions::advance_epoch::handler(ctx)
+    }
+
+    /// Opt the caller's stake in to (or out of) crank_claim
+    pub fn set_crank_allowed(ctx: Context<SetCrankAllowed>, allowed: bool) -> Result<()> {
+        instructions::set_crank_allowed::handler(ctx, allowed)
+    }
 }
@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::errors::RewardsError;
+use crate::events::{EpochAdvancedEvent, RateChangedEvent, RewardsAddedEvent};
 
 /// HYPER Token Configuration
 /// Mint Address: 92s9qna3djkMncZzkacyNQ38UKnNXZFh4Jgqe3Cmpump
@@ -9,6 +11,159 @@ pub const HYPER_DECIMALS: u8 = 6;
 /// Precision for reflection calculations
 pub const REFLECTION_PRECISION: u128 = 1_000_000_000_000_000_000; // 10^18
 
+/// Default minimum interval between claims (seconds)
+pub const DEFAULT_CLAIM_COOLDOWN: i64 = 3600; // 1 hour
+
+/// Default smallest claim accepted (any nonzero amount)
+pub const DEFAULT_MIN_CLAIM_AMOUNT: u64 = 1;
+
+/// Longest join delay the authority can set (seconds)
+pub const MAX_JOIN_DELAY: i64 = 7 * 86400; // 1 week
+
+/// Basis points denominator for the fee split
+pub const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Staking Program (owner of the stake accounts read for loyalty)
+pub const STAKING_PROGRAM_ID: Pubkey = pubkey!("3fw9eQN1KHarGcYVETvF7FDt2BYGuDPMjuhoE45RJnTJ");
+
+/// Continuous staking time after which the loyalty bonus applies
+pub const LOYALTY_PERIOD: i64 = 90 * 86400; // 90 days
+
+/// Loyalty bonus on claims for long-term stakers (bps of the claim)
+pub const LOYALTY_BONUS_BPS: u16 = 1_000; // +10%
+
+/// Share of a cranked claim paid to the cranker (bps of the claim)
+pub const CRANK_FEE_BPS: u16 = 10; // 0.1%
+
+/// Split a claim made by crank_claim into (paid to the recipient, paid to the cranker)
+/// The cranker is only paid when it asks for the fee
+pub fn split_crank_claim(claimable: u64, pay_cranker: bool) -> (u64, u64) {
+    if !pay_cranker {
+        return (claimable, 0);
+    }
+    let fee = (claimable as u128 * CRANK_FEE_BPS as u128 / BPS_DENOMINATOR as u128) as u64;
+    (claimable - fee, fee)
+}
+
+/// Offsets into a Staking Program StakeAccount
+/// (discriminator 8 + authority 32 + xhyper 16 + amount 8)
+const STAKE_XHYPER_OFFSET: usize = 40;
+const STAKE_TIME_STAKE_OFFSET: usize = 64;
+const STAKE_TIME_UNSTAKE_OFFSET: usize = 72;
+/// First variable-size field (beneficiary), after duration
+const STAKE_BENEFICIARY_OFFSET: usize = 88;
+
+/// Read (time_stake, time_unstake) from StakeAccount data
+/// Rewards can't depend on the Staking crate (it depends on us), so the
+/// fixed-size prefix of the account is read directly
+pub fn read_stake_times(data: &[u8]) -> Option<(i64, i64)> {
+    let read_i64 = |offset: usize| {
+        data.get(offset..offset + 8)
+            .map(|bytes| i64::from_le_bytes(bytes.try_into().unwrap()))
+    };
+    Some((read_i64(STAKE_TIME_STAKE_OFFSET)?, read_i64(STAKE_TIME_UNSTAKE_OFFSET)?))
+}
+
+/// Read the xHYPER balance from StakeAccount data
+pub fn read_stake_xhyper(data: &[u8]) -> Option<u128> {
+    data.get(STAKE_XHYPER_OFFSET..STAKE_XHYPER_OFFSET + 16)
+        .map(|bytes| u128::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// Read an Option<Pubkey> (1 or 33 bytes) at `offset`
+/// Returns the value and the offset just past it
+fn read_stake_option(data: &[u8], offset: usize) -> Option<(Option<Pubkey>, usize)> {
+    match *data.get(offset)? {
+        0 => Some((None, offset + 1)),
+        1 => {
+            let key = Pubkey::try_from(data.get(offset + 1..offset + 33)?).ok()?;
+            Some((Some(key), offset + 33))
+        }
+        _ => None,
+    }
+}
+
+/// Read the beneficiary from StakeAccount data
+pub fn read_stake_beneficiary(data: &[u8]) -> Option<Option<Pubkey>> {
+    read_stake_option(data, STAKE_BENEFICIARY_OFFSET).map(|(beneficiary, _)| beneficiary)
+}
+
+/// Read (operator, commission_bps) from StakeAccount data
+/// Past the fixed prefix come beneficiary and pending_authority (Option<Pubkey>,
+/// 1 or 33 bytes each), then operator (Option<Pubkey>) and commission_bps (u16)
+pub fn read_stake_delegation(data: &[u8]) -> Option<(Option<Pubkey>, u16)> {
+    let (_, offset) = read_stake_option(data, STAKE_BENEFICIARY_OFFSET)?;
+    let (_, offset) = read_stake_option(data, offset)?;
+    let (operator, offset) = read_stake_option(data, offset)?;
+    let commission_bps = u16::from_le_bytes(data.get(offset..offset + 2)?.try_into().ok()?);
+    Some((operator, commission_bps))
+}
+
+/// Split a delegator's claim into (operator commission, delegator share)
+pub fn split_commission(claimable: u64, commission_bps: u16) -> (u64, u64) {
+    let commission_bps = commission_bps.min(BPS_DENOMINATOR);
+    let commission = (claimable as u128 * commission_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+    (commission, claimable - commission)
+}
+
+/// Commission rate currently set by `operator`
+/// `operator_stake` must be the operator's stake PDA; the rate is read from
+/// it, and a closed operator stake charges nothing
+pub fn operator_commission_bps(operator_stake: &AccountInfo, operator: &Pubkey) -> Result<u16> {
+    let (expected, _) = Pubkey::find_program_address(&[b"stake", operator.as_ref()], &STAKING_PROGRAM_ID);
+    require!(operator_stake.key() == expected, RewardsError::InvalidOperatorAccount);
+    if operator_stake.owner != &STAKING_PROGRAM_ID || operator_stake.data_is_empty() {
+        return Ok(0);
+    }
+
+    let (_, commission_bps) = read_stake_delegation(&operator_stake.try_borrow_data()?)
+        .ok_or(RewardsError::InvalidStakeAccount)?;
+    Ok(commission_bps)
+}
+
+/// Anchor discriminator of the Staking Program's `compound_stake`
+/// (first 8 bytes of sha256("global:compound_stake"))
+pub const COMPOUND_STAKE_DISCRIMINATOR: [u8; 8] = [4, 38, 99, 75, 63, 118, 173, 119];
+
+/// Instruction data for `compound_stake(amount)`, built by hand for the same reason
+pub fn compound_stake_ix_data(amount: u64) -> Vec<u8> {
+    let mut data = COMPOUND_STAKE_DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+/// Loyalty bonus on top of a `base` claim
+/// Applies once the stake has been held continuously for LOYALTY_PERIOD;
+/// a stake that started unstaking (time_unstake != 0) gets nothing
+pub fn loyalty_bonus(base: u64, time_stake: i64, time_unstake: i64, current_time: i64) -> u64 {
+    if time_stake <= 0 || time_unstake != 0 {
+        return 0;
+    }
+    if current_time.saturating_sub(time_stake) < LOYALTY_PERIOD {
+        return 0;
+    }
+
+    (base as u128 * LOYALTY_BONUS_BPS as u128 / BPS_DENOMINATOR as u128) as u64
+}
+
+/// Loyalty bonus on a `base` claim, read from the staker's stake PDA
+/// A closed (withdrawn) stake or unreadable data earns none
+pub fn stake_loyalty_bonus(stake_account: &AccountInfo, base: u64, current_time: i64) -> Result<u64> {
+    if stake_account.owner != &STAKING_PROGRAM_ID || stake_account.data_is_empty() {
+        return Ok(0);
+    }
+
+    Ok(read_stake_times(&stake_account.try_borrow_data()?)
+        .map(|(time_stake, time_unstake)| loyalty_bonus(base, time_stake, time_unstake, current_time))
+        .unwrap_or(0))
+}
+
+/// Compute a * b / denom without overflowing on the intermediate product
+/// Splits `a` (the larger factor) into quotient and remainder of `denom`
+fn mul_div(a: u128, b: u128, denom: u128) -> u128 {
+    (a / denom) * b + ((a % denom) * b) / denom
+}
+
 /// Reflection account - tracks global rewards distribution
 /// Uses reflection algorithm for O(1) reward distribution
 #[account]
@@ -30,12 +185,97 @@ pub struct ReflectionAccount {
     /// Total rewards distributed (for stats)
     pub total_rewards_distributed: u64,
 
+    /// Rewards deposited for stakers and not yet claimed
+    /// The rewards vault must always hold at least this much
+    pub total_outstanding_rewards: u64,
+
+    /// Minimum seconds between claims per user (0 = disabled)
+    pub claim_cooldown: i64,
+
+    /// Smallest claim accepted; smaller balances keep accruing
+    pub min_claim_amount: u64,
+
+    /// Epoch length in seconds (0 = continuous distribution)
+    /// In epoch mode added rewards wait in the current epoch and reach
+    /// stakers when advance_epoch finalizes it
+    pub epoch_duration: i64,
+
+    /// Index of the epoch currently accumulating rewards
+    pub current_epoch: u64,
+
+    /// When the current epoch started
+    pub epoch_started_at: i64,
+
+    /// Rewards accumulated in the current epoch, not yet claimable
+    pub epoch_rewards: u64,
+
+    /// Fractions of a token dropped by claim rounding, scaled by REFLECTION_PRECISION
+    /// Whole tokens are redistributed to stakers as soon as they accumulate
+    pub rounding_dust: u128,
+
+    /// Rounding dust redistributed so far (for stats)
+    pub dust_redistributed: u64,
+
+    /// Token rewards are paid in; the rewards vault and claim destinations must hold it
+    pub reward_mint: Pubkey,
+
+    /// Seconds a newly registered stake waits before its rewards count toward claims
+    /// (0 = disabled). Deters joining right before a large distribution to snipe it
+    pub join_delay: i64,
+
+    /// xHYPER registered during the current epoch (epoch mode)
+    /// It joins the pool once the epoch is finalized, so it shares none of that epoch's budget
+    pub queued_xhyper: u128,
+
     /// Bump seed for PDA
     pub bump: u8,
 }
 
 impl ReflectionAccount {
-    pub const LEN: usize = 8 + 32 + 16 + 16 + 16 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 16 + 16 + 16 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 16 + 8 + 32 + 8 + 16 + 1;
+
+    /// Check a join delay is within 0..=MAX_JOIN_DELAY
+    pub fn is_valid_join_delay(join_delay: i64) -> bool {
+        (0..=MAX_JOIN_DELAY).contains(&join_delay)
+    }
+
+    /// rate = total_reflection * PRECISION / total_xhyper
+    fn compute_rate(total_reflection: u128, total_xhyper: u128) -> u128 {
+        mul_div(total_reflection, REFLECTION_PRECISION, total_xhyper)
+    }
+
+    /// Reflection points for `xhyper` joining the pool at `rate`
+    fn points_at(rate: u128, xhyper: u128) -> u128 {
+        if rate > 0 {
+            mul_div(rate, xhyper, REFLECTION_PRECISION)
+        } else {
+            xhyper * REFLECTION_PRECISION
+        }
+    }
+
+    /// Event for a rewards deposit (reflects the post-update rate)
+    pub fn rewards_added_event(&self, amount: u64, timestamp: i64) -> RewardsAddedEvent {
+        RewardsAddedEvent {
+            amount,
+            new_rate: self.rate,
+            total_xhyper: self.total_xhyper,
+            timestamp,
+        }
+    }
+
+    /// Event for a stake (un)registration, only if the rate moved
+    pub fn rate_changed_event(&self, old_rate: u128, timestamp: i64) -> Option<RateChangedEvent> {
+        if self.rate == old_rate {
+            return None;
+        }
+
+        Some(RateChangedEvent {
+            old_rate,
+            new_rate: self.rate,
+            total_xhyper: self.total_xhyper,
+            timestamp,
+        })
+    }
 
     /// Add rewards to the pool
     /// This is called when job completes and node receives payment
@@ -46,21 +286,162 @@ impl ReflectionAccount {
 
         // Recalculate rate (decreases with more rewards)
         if self.total_xhyper > 0 {
-            self.rate = (self.total_reflection * REFLECTION_PRECISION) / self.total_xhyper;
+            self.rate = Self::compute_rate(self.total_reflection, self.total_xhyper);
         }
 
         self.total_rewards_distributed += reward_amount;
+        self.total_outstanding_rewards = self.total_outstanding_rewards.saturating_add(reward_amount);
+    }
+
+    /// Whether rewards are distributed in discrete epochs
+    pub fn is_epoch_mode(&self) -> bool {
+        self.epoch_duration > 0
+    }
+
+    /// Take in rewards already transferred to the vault
+    /// Continuous mode distributes them right away; epoch mode adds them to
+    /// the current epoch's budget
+    pub fn accrue_rewards(&mut self, reward_amount: u64) {
+        if self.is_epoch_mode() {
+            self.epoch_rewards = self.epoch_rewards.saturating_add(reward_amount);
+        } else {
+            self.add_rewards(reward_amount);
+        }
+    }
+
+    /// Check the current epoch has run its full duration
+    pub fn can_advance_epoch(&self, current_time: i64) -> bool {
+        self.is_epoch_mode()
+            && current_time >= self.epoch_started_at.saturating_add(self.epoch_duration)
+    }
+
+    /// Finalize the current epoch into `record` and start the next one
+    /// The epoch's budget is distributed pro rata to the pool as it stood when
+    /// the epoch started; stake queued during the epoch joins right after, at
+    /// the post-distribution rate.
+    /// Returns the rewards distributed
+    pub fn advance_epoch(&mut self, record: &mut EpochRecord, current_time: i64) -> u64 {
+        let epoch_rewards = self.epoch_rewards;
+        record.epoch = self.current_epoch;
+        record.started_at = self.epoch_started_at;
+        record.finalized_at = current_time;
+        record.rewards = epoch_rewards;
+        record.staked_xhyper = self.total_xhyper;
+
+        if epoch_rewards > 0 {
+            self.add_rewards(epoch_rewards);
+        }
+
+        record.join_rate = self.rate;
+        record.joined_xhyper = self.queued_xhyper;
+        if self.queued_xhyper > 0 {
+            self.add_staker(self.queued_xhyper);
+            self.queued_xhyper = 0;
+        }
+
+        self.epoch_rewards = 0;
+        self.current_epoch += 1;
+        self.epoch_started_at = current_time;
+
+        epoch_rewards
+    }
+
+    /// Switch between continuous (0) and epoch distribution
+    /// Leaving epoch mode distributes the unfinalized budget right away; it is
+    /// rejected while stake is queued for the current epoch, which only joins
+    /// the pool through advance_epoch.
+    /// Entering it (or changing the length) keeps the current epoch and restarts its clock
+    pub fn set_epoch_duration(&mut self, epoch_duration: i64, current_time: i64) -> Result<()> {
+        require!(
+            epoch_duration > 0 || self.queued_xhyper == 0,
+            RewardsError::EpochStakeQueued
+        );
+
+        if epoch_duration == 0 && self.epoch_rewards > 0 {
+            let epoch_rewards = self.epoch_rewards;
+            self.epoch_rewards = 0;
+            self.add_rewards(epoch_rewards);
+        }
+
+        self.epoch_duration = epoch_duration;
+        self.epoch_started_at = current_time;
+        Ok(())
+    }
+
+    /// Event for a finalized epoch (reflects the post-update rate)
+    pub fn epoch_advanced_event(&self, epoch: u64, rewards: u64, timestamp: i64) -> EpochAdvancedEvent {
+        EpochAdvancedEvent {
+            epoch,
+            rewards,
+            new_rate: self.rate,
+            total_xhyper: self.total_xhyper,
+            timestamp,
+        }
+    }
+
+    /// Record the fraction of a token a claim rounded away
+    /// Once a whole token has accumulated it goes back into the pool: the
+    /// rate moves as for new rewards, but the tokens are already counted in
+    /// total_outstanding_rewards, so nothing new is owed.
+    /// Returns the tokens redistributed
+    pub fn record_rounding_dust(&mut self, remainder: u128) -> u64 {
+        self.rounding_dust = self.rounding_dust.saturating_add(remainder);
+
+        let whole = (self.rounding_dust / REFLECTION_PRECISION) as u64;
+        if whole == 0 {
+            return 0;
+        }
+        self.rounding_dust %= REFLECTION_PRECISION;
+
+        self.total_xhyper += whole as u128;
+        if self.total_xhyper > 0 {
+            self.rate = Self::compute_rate(self.total_reflection, self.total_xhyper);
+        }
+        self.dust_redistributed = self.dust_redistributed.saturating_add(whole);
+
+        whole
+    }
+
+    /// Check the vault covers every outstanding claim
+    pub fn is_solvent(&self, vault_balance: u64) -> bool {
+        vault_balance >= self.total_outstanding_rewards
+    }
+
+    /// Check a token account's mint is the reward mint
+    pub fn is_reward_mint(&self, mint: &Pubkey) -> bool {
+        *mint == self.reward_mint
+    }
+
+    /// Check a claim is large enough to pay out (anti dust-transfer)
+    pub fn meets_min_claim(&self, amount: u64) -> bool {
+        amount >= self.min_claim_amount
+    }
+
+    /// Vault balance beyond what is owed to stakers
+    /// Loyalty bonuses are only paid out of this, so they never eat into other claims
+    pub fn surplus(&self, vault_balance: u64) -> u64 {
+        vault_balance
+            .saturating_sub(self.total_outstanding_rewards)
+            .saturating_sub(self.epoch_rewards)
+    }
+
+    /// Record a claim paid out of a vault holding `vault_balance`
+    /// Rejects the claim if the pool is insolvent or it exceeds what is outstanding,
+    /// so early claimers can't drain rewards owed to others
+    pub fn record_claim(&mut self, amount: u64, vault_balance: u64) -> Result<()> {
+        require!(self.is_solvent(vault_balance), RewardsError::InsolventRewardPool);
+        self.total_outstanding_rewards = self
+            .total_outstanding_rewards
+            .checked_sub(amount)
+            .ok_or(RewardsError::InsolventRewardPool)?;
+        Ok(())
     }
 
     /// Register a new staker in reflection system
     /// Returns reflection points for this stake
     pub fn add_staker(&mut self, xhyper: u128) -> u128 {
         // Calculate reflection points for this xHYPER amount
-        let reflection = if self.rate > 0 {
-            (xhyper * self.rate) / REFLECTION_PRECISION
-        } else {
-            xhyper * REFLECTION_PRECISION
-        };
+        let reflection = Self::points_at(self.rate, xhyper);
 
         // Add to totals
         self.total_reflection += reflection;
@@ -68,12 +449,22 @@ impl ReflectionAccount {
 
         // Recalculate rate
         if self.total_xhyper > 0 {
-            self.rate = (self.total_reflection * REFLECTION_PRECISION) / self.total_xhyper;
+            self.rate = Self::compute_rate(self.total_reflection, self.total_xhyper);
         }
 
         reflection
     }
 
+    /// Queue xHYPER registered mid-epoch; it joins the pool at the next advance_epoch
+    pub fn queue_staker(&mut self, xhyper: u128) {
+        self.queued_xhyper = self.queued_xhyper.saturating_add(xhyper);
+    }
+
+    /// Take queued xHYPER back out of the current epoch's queue
+    pub fn dequeue_staker(&mut self, xhyper: u128) {
+        self.queued_xhyper = self.queued_xhyper.saturating_sub(xhyper);
+    }
+
     /// Remove staker from reflection system (on unstake)
     pub fn remove_staker(&mut self, xhyper: u128, reflection: u128) {
         if self.total_xhyper >= xhyper {
@@ -86,7 +477,7 @@ impl ReflectionAccount {
 
         // Recalculate rate
         if self.total_xhyper > 0 {
-            self.rate = (self.total_reflection * REFLECTION_PRECISION) / self.total_xhyper;
+            self.rate = Self::compute_rate(self.total_reflection, self.total_xhyper);
         }
     }
 }
@@ -109,17 +500,161 @@ pub struct UserRewardsAccount {
     /// Last claim timestamp
     pub last_claim: i64,
 
+    /// Optional beneficiary (from the stake) that receives and may claim rewards
+    pub beneficiary: Option<Pubkey>,
+
+    /// Rewards accrued before the last xHYPER change, not yet claimed
+    pub pending_rewards: u64,
+
+    /// Node operator the stake is delegated to (from the stake, recorded by sync_rewards)
+    /// Takes its commission on every claim
+    pub operator: Option<Pubkey>,
+
+    /// When the stake may enter the pool and start accruing
+    /// (registration time plus the join delay in force then)
+    pub eligible_at: i64,
+
+    /// Epoch the stake was registered in, while it waits to join the pool (epoch mode)
+    /// It earns nothing until that epoch is finalized and sync_rewards records its entry
+    pub queued_epoch: Option<u64>,
+
+    /// Held out of the pool until eligible_at, when sync_rewards admits it
+    /// Distributions during the delay go entirely to the stakes already in the pool
+    pub in_join_delay: bool,
+
+    /// Whether the staker lets anyone crank_claim for them (set by set_crank_allowed)
+    pub allow_crank: bool,
+
+    /// Operator's commission rate when the delegation was recorded
+    /// Claims never charge more, so a later raise can't take accrued rewards
+    pub commission_bps: u16,
+
     /// Bump seed
     pub bump: u8,
 }
 
 impl UserRewardsAccount {
-    pub const LEN: usize = 8 + 32 + 16 + 16 + 8 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 16 + 16 + 8 + 8 + (1 + 32) + 8 + (1 + 32) + 8 + (1 + 8) + 1 + 1 + 2 + 1;
+
+    /// Register a new stake's xHYPER at `current_time`
+    /// With a join delay the stake stays out of the pool until end_join_delay;
+    /// otherwise it enters right away (see enter_pool).
+    /// Returns the reflection points (0 while waiting or queued)
+    pub fn register(&mut self, reflection: &mut ReflectionAccount, xhyper: u128, current_time: i64) -> u128 {
+        self.xhyper = xhyper;
+        self.initial_reflection = 0;
+        self.queued_epoch = None;
+        self.eligible_at = current_time.saturating_add(reflection.join_delay);
+        self.in_join_delay = reflection.join_delay > 0;
+        if !self.in_join_delay {
+            self.enter_pool(reflection);
+        }
+        self.initial_reflection
+    }
+
+    /// Add the stake's xHYPER to the pool
+    /// Continuous mode adds it right away; epoch mode queues it until the
+    /// current epoch is finalized, so entering just before advance_epoch
+    /// captures nothing of that epoch
+    fn enter_pool(&mut self, reflection: &mut ReflectionAccount) {
+        if reflection.is_epoch_mode() {
+            reflection.queue_staker(self.xhyper);
+            self.queued_epoch = Some(reflection.current_epoch);
+        } else {
+            self.initial_reflection = reflection.add_staker(self.xhyper);
+        }
+    }
+
+    /// Admit a stake whose join delay has passed at `current_time` into the pool
+    /// It accrues from now on; nothing distributed during the delay is owed to it.
+    /// Returns whether it was admitted
+    pub fn end_join_delay(&mut self, reflection: &mut ReflectionAccount, current_time: i64) -> bool {
+        if !self.in_join_delay || !self.is_eligible(current_time) {
+            return false;
+        }
+        self.in_join_delay = false;
+        self.enter_pool(reflection);
+        true
+    }
+
+    /// Whether the stake is in the pool and accruing
+    fn in_pool(&self) -> bool {
+        !self.in_join_delay && self.queued_epoch.is_none()
+    }
+
+    /// Whether the stake's epoch was finalized but its entry into the pool isn't recorded yet
+    pub fn awaits_join(&self, current_epoch: u64) -> bool {
+        self.queued_epoch.is_some_and(|epoch| epoch < current_epoch)
+    }
+
+    /// Record the stake's entry into the pool from its queued epoch's record
+    pub fn join_pool(&mut self, record: &EpochRecord) -> Result<()> {
+        require!(self.queued_epoch == Some(record.epoch), RewardsError::InvalidEpochRecord);
+
+        self.initial_reflection = ReflectionAccount::points_at(record.join_rate, self.xhyper);
+        self.queued_epoch = None;
+        Ok(())
+    }
+
+    /// Take the stake out of the rewards system (on unstake)
+    /// A stake still in its join delay was never in the pool; one still queued
+    /// leaves the current epoch's queue; one whose epoch was finalized must
+    /// have its entry recorded by sync_rewards first
+    pub fn unregister(&self, reflection: &mut ReflectionAccount) -> Result<()> {
+        if self.in_join_delay {
+            return Ok(());
+        }
+        match self.queued_epoch {
+            Some(epoch) if epoch == reflection.current_epoch => reflection.dequeue_staker(self.xhyper),
+            Some(_) => return err!(RewardsError::StakeAwaitingJoin),
+            None => reflection.remove_staker(self.xhyper, self.initial_reflection),
+        }
+        Ok(())
+    }
+
+    /// Account whose token account receives claimed rewards
+    pub fn reward_recipient(&self) -> Pubkey {
+        self.beneficiary.unwrap_or(self.authority)
+    }
+
+    /// Check if a signer may claim (authority or beneficiary)
+    pub fn can_be_claimed_by(&self, signer: &Pubkey) -> bool {
+        *signer == self.authority || self.beneficiary == Some(*signer)
+    }
+
+    /// Record the operator the stake is delegated to and the commission rate
+    /// the operator charged at that point (0 when undelegated)
+    pub fn record_delegation(&mut self, operator: Option<Pubkey>, commission_bps: u16) {
+        self.operator = operator;
+        self.commission_bps = if operator.is_some() { commission_bps } else { 0 };
+    }
+
+    /// Commission owed to the operator on `claimable`
+    /// Charged at the operator's current rate, but never above the rate
+    /// recorded with the delegation
+    pub fn operator_commission(&self, claimable: u64, current_bps: u16) -> u64 {
+        split_commission(claimable, current_bps.min(self.commission_bps)).0
+    }
+
+    /// Whether the join delay has passed at `current_time`
+    pub fn is_eligible(&self, current_time: i64) -> bool {
+        current_time >= self.eligible_at
+    }
 
     /// Calculate claimable rewards
+    /// Nothing while the stake is still in its join delay: it is out of the
+    /// pool, so nothing accrues until it is admitted
+    pub fn calculate_claimable(&self, current_rate: u128, current_time: i64) -> u64 {
+        if !self.is_eligible(current_time) {
+            return 0;
+        }
+        self.accrued_rewards(current_rate)
+    }
+
+    /// Rewards accrued on the current xHYPER since the stake entered the pool
     /// rewards = (current_reflection / rate) - initial_xhyper
-    pub fn calculate_claimable(&self, current_rate: u128) -> u64 {
-        if current_rate == 0 || self.xhyper == 0 {
+    fn accrued_rewards(&self, current_rate: u128) -> u64 {
+        if current_rate == 0 || self.xhyper == 0 || !self.in_pool() {
             return 0;
         }
 
@@ -148,30 +683,203 @@ impl UserRewardsAccount {
         }
     }
 
+    /// Fraction of a token accrued rewards round down to, scaled by REFLECTION_PRECISION
+    pub fn claimable_remainder(&self, current_rate: u128) -> u128 {
+        if current_rate == 0 || self.xhyper == 0 || !self.in_pool() {
+            return 0;
+        }
+
+        let current_reflection_value = match self.xhyper
+            .checked_mul(current_rate)
+            .and_then(|v| v.checked_div(REFLECTION_PRECISION))
+        {
+            Some(v) => v,
+            None => return 0,
+        };
+        if current_reflection_value <= self.initial_reflection {
+            return 0;
+        }
+
+        (current_reflection_value - self.initial_reflection)
+            .checked_mul(REFLECTION_PRECISION)
+            .map(|scaled| mul_div(scaled % current_rate, REFLECTION_PRECISION, current_rate))
+            .unwrap_or(0)
+    }
+
+    /// Everything the user can claim: rewards settled at xHYPER changes
+    /// plus what accrued on the current xHYPER since then
+    /// (nothing until the join delay has passed)
+    pub fn total_claimable(&self, current_rate: u128, current_time: i64) -> u64 {
+        if !self.is_eligible(current_time) {
+            return 0;
+        }
+        self.pending_rewards.saturating_add(self.accrued_rewards(current_rate))
+    }
+
+    /// Re-checkpoint after the stake's xHYPER changed (top-up, extend, ...)
+    /// Rewards accrued on the old xHYPER are settled into pending_rewards first,
+    /// then the stake is re-registered at the new xHYPER, so the change neither
+    /// loses accrued rewards nor earns retroactively on the added xHYPER.
+    /// A stake still in its join delay or queued for the current epoch just
+    /// changes the xHYPER it will enter with.
+    /// Returns the rewards settled
+    pub fn sync_xhyper(&mut self, reflection: &mut ReflectionAccount, new_xhyper: u128) -> u64 {
+        if self.in_join_delay {
+            self.xhyper = new_xhyper;
+            return 0;
+        }
+        if self.queued_epoch.is_some() {
+            reflection.dequeue_staker(self.xhyper);
+            reflection.queue_staker(new_xhyper);
+            self.xhyper = new_xhyper;
+            return 0;
+        }
+
+        let settled = self.accrued_rewards(reflection.rate);
+        let remainder = self.claimable_remainder(reflection.rate);
+        self.pending_rewards = self.pending_rewards.saturating_add(settled);
+
+        reflection.remove_staker(self.xhyper, self.initial_reflection);
+        self.initial_reflection = reflection.add_staker(new_xhyper);
+        self.xhyper = new_xhyper;
+        reflection.record_rounding_dust(remainder);
+
+        settled
+    }
+
+    /// Check if the claim cooldown has elapsed since last claim
+    /// A cooldown of zero disables the check
+    pub fn can_claim(&self, current_time: i64, cooldown: i64) -> bool {
+        cooldown == 0 || current_time >= self.last_claim.saturating_add(cooldown)
+    }
+
     /// Update after claiming rewards
     pub fn update_after_claim(&mut self, claimed_amount: u64, current_rate: u128) {
         self.total_claimed += claimed_amount;
         self.last_claim = Clock::get().unwrap().unix_timestamp;
+        self.pending_rewards = 0;
 
         // Reset reflection to current value
         self.initial_reflection = (self.xhyper * current_rate) / REFLECTION_PRECISION;
     }
+
+    /// Register xHYPER added to the stake (compounded rewards)
+    /// Call right after a claim: nothing is owed, so reflection resets to the new rate
+    pub fn add_xhyper(&mut self, reflection: &mut ReflectionAccount, xhyper: u128) {
+        reflection.add_staker(xhyper);
+        self.xhyper += xhyper;
+        self.initial_reflection = (self.xhyper * reflection.rate) / REFLECTION_PRECISION;
+    }
+}
+
+/// Finalized reward epoch, written by advance_epoch
+#[account]
+pub struct EpochRecord {
+    /// Epoch index
+    pub epoch: u64,
+
+    /// When the epoch started
+    pub started_at: i64,
+
+    /// When advance_epoch finalized it
+    pub finalized_at: i64,
+
+    /// Budget distributed at finalization
+    pub rewards: u64,
+
+    /// Pool xHYPER that shared the budget (stakes from before the epoch plus earlier rewards)
+    pub staked_xhyper: u128,
+
+    /// xHYPER registered during the epoch, which joined the pool after the distribution
+    pub joined_xhyper: u128,
+
+    /// Rate the joining stakes entered the pool at
+    pub join_rate: u128,
+
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl EpochRecord {
+    pub const LEN: usize = 8 + 8 + 8 + 8 + 8 + 16 + 16 + 16 + 1;
+}
+
+/// Rewards configuration - split of collected fees between stakers and treasury
+#[account]
+pub struct RewardsConfig {
+    /// Authority that can change the split (reflection authority)
+    pub authority: Pubkey,
+
+    /// Treasury token account receiving the protocol share
+    pub treasury: Pubkey,
+
+    /// Share of each add_rewards sent to the treasury (bps), rest goes to stakers
+    pub treasury_fee_bps: u16,
+
+    /// Bump seed
+    pub bump: u8,
+}
+
+impl RewardsConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 2 + 1;
+
+    /// Treasury share must not exceed 100%
+    pub fn is_valid_fee_bps(treasury_fee_bps: u16) -> bool {
+        treasury_fee_bps <= BPS_DENOMINATOR
+    }
+
+    /// Split an amount into (stakers, treasury)
+    /// Rounding dust stays with stakers
+    pub fn split_fee(&self, amount: u64) -> (u64, u64) {
+        let treasury =
+            (amount as u128 * self.treasury_fee_bps as u128 / BPS_DENOMINATOR as u128) as u64;
+        (amount - treasury, treasury)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_reflection_distribution() {
-        let mut reflection = ReflectionAccount {
+    fn reflection_account() -> ReflectionAccount {
+        ReflectionAccount {
             authority: Pubkey::default(),
             rate: 0,
             total_reflection: 0,
             total_xhyper: 0,
             total_rewards_distributed: 0,
+            total_outstanding_rewards: 0,
+            claim_cooldown: DEFAULT_CLAIM_COOLDOWN,
+            min_claim_amount: DEFAULT_MIN_CLAIM_AMOUNT,
+            epoch_duration: 0,
+            current_epoch: 0,
+            epoch_started_at: 0,
+            epoch_rewards: 0,
+            rounding_dust: 0,
+            dust_redistributed: 0,
+            reward_mint: Pubkey::default(),
+            join_delay: 0,
+            queued_xhyper: 0,
             bump: 0,
-        };
+        }
+    }
+
+    fn epoch_record() -> EpochRecord {
+        EpochRecord {
+            epoch: 0,
+            started_at: 0,
+            finalized_at: 0,
+            rewards: 0,
+            staked_xhyper: 0,
+            joined_xhyper: 0,
+            join_rate: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_reflection_distribution() {
+        let mut reflection = reflection_account();
 
         // User 1 stakes with 1000 xHYPER
         let user1_reflection = reflection.add_staker(1000);
@@ -193,4 +901,635 @@ mod tests {
         // Both users should be able to claim proportional rewards
         // (exact calculations would need more precision testing)
     }
+
+    fn user_claimed_at(last_claim: i64) -> UserRewardsAccount {
+        UserRewardsAccount {
+            authority: Pubkey::default(),
+            initial_reflection: 0,
+            xhyper: 1000,
+            total_claimed: 0,
+            last_claim,
+            beneficiary: None,
+            pending_rewards: 0,
+            operator: None,
+            eligible_at: 0,
+            queued_epoch: None,
+            in_join_delay: false,
+            allow_crank: false,
+            commission_bps: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_claim_within_cooldown_rejected() {
+        let user = user_claimed_at(1_000);
+
+        assert!(!user.can_claim(1_000, DEFAULT_CLAIM_COOLDOWN));
+        assert!(!user.can_claim(1_000 + DEFAULT_CLAIM_COOLDOWN - 1, DEFAULT_CLAIM_COOLDOWN));
+    }
+
+    #[test]
+    fn test_claim_after_cooldown_accepted() {
+        let user = user_claimed_at(1_000);
+
+        assert!(user.can_claim(1_000 + DEFAULT_CLAIM_COOLDOWN, DEFAULT_CLAIM_COOLDOWN));
+    }
+
+    #[test]
+    fn test_claim_below_minimum_rejected() {
+        let mut reflection = ReflectionAccount { rate: REFLECTION_PRECISION, ..reflection_account() };
+
+        // Default keeps accepting any nonzero claim
+        assert!(reflection.meets_min_claim(1));
+
+        reflection.min_claim_amount = 5_000;
+        let user = user_claimed_at(0);
+        let claimable = user.calculate_claimable(reflection.rate, 0);
+        assert_eq!(claimable, 1_000);
+        assert!(!reflection.meets_min_claim(claimable));
+
+        // Accrued past the threshold
+        let mut whale = user_claimed_at(0);
+        whale.xhyper = 6_000;
+        assert!(reflection.meets_min_claim(whale.calculate_claimable(reflection.rate, 0)));
+        assert!(reflection.meets_min_claim(5_000));
+    }
+
+    #[test]
+    fn test_zero_cooldown_disables_check() {
+        let user = user_claimed_at(1_000);
+
+        assert!(user.can_claim(1_000, 0));
+    }
+
+    #[test]
+    fn test_rewards_added_event_has_post_update_rate() {
+        let mut reflection = reflection_account();
+
+        let old_rate = reflection.rate;
+        reflection.add_staker(1000);
+        let event = reflection.rate_changed_event(old_rate, 10).unwrap();
+        assert_eq!(event.old_rate, 0);
+        assert_eq!(event.new_rate, reflection.rate);
+        assert_eq!(event.total_xhyper, 1000);
+
+        let rate_before = reflection.rate;
+        reflection.add_rewards(100);
+        let event = reflection.rewards_added_event(100, 20);
+        assert_eq!(event.amount, 100);
+        assert_eq!(event.new_rate, reflection.rate);
+        assert!(event.new_rate < rate_before);
+        assert_eq!(event.total_xhyper, 1100);
+        assert_eq!(event.timestamp, 20);
+
+        // No event when the rate doesn't move
+        assert!(reflection.rate_changed_event(reflection.rate, 30).is_none());
+    }
+
+    #[test]
+    fn test_rewards_paid_to_beneficiary() {
+        let staker = Pubkey::new_unique();
+        let beneficiary = Pubkey::new_unique();
+
+        let mut user = user_claimed_at(0);
+        user.authority = staker;
+        user.beneficiary = Some(beneficiary);
+
+        // Rewards accrue on the stake but are paid to the beneficiary
+        assert_eq!(user.reward_recipient(), beneficiary);
+        assert!(user.can_be_claimed_by(&beneficiary));
+        assert!(user.can_be_claimed_by(&staker));
+        assert!(!user.can_be_claimed_by(&Pubkey::new_unique()));
+
+        // Without a beneficiary the staker is the recipient
+        user.beneficiary = None;
+        assert_eq!(user.reward_recipient(), staker);
+        assert!(!user.can_be_claimed_by(&beneficiary));
+    }
+
+    #[test]
+    fn test_cranked_claim_lands_in_user_wallet() {
+        let staker = Pubkey::new_unique();
+        let cranker = Pubkey::new_unique();
+
+        let mut user = user_claimed_at(0);
+        user.authority = staker;
+
+        // A third party can't claim for itself, but the crank pays the staker
+        assert!(!user.can_be_claimed_by(&cranker));
+        assert_eq!(user.reward_recipient(), staker);
+
+        // Nobody cranks a stake that hasn't opted in
+        assert!(!user.allow_crank);
+
+        // The cranker's tip comes out of the claim, the rest reaches the user
+        let (to_user, to_cranker) = split_crank_claim(100_000, true);
+        assert_eq!(to_cranker, 100);
+        assert_eq!(to_user, 99_900);
+
+        // Without a cranker token account the user gets everything
+        assert_eq!(split_crank_claim(100_000, false), (100_000, 0));
+
+        // Dust claims round the tip down to nothing
+        assert_eq!(split_crank_claim(999, true), (999, 0));
+    }
+
+    #[test]
+    fn test_fee_split_between_stakers_and_treasury() {
+        let config = RewardsConfig {
+            authority: Pubkey::default(),
+            treasury: Pubkey::new_unique(),
+            treasury_fee_bps: 2_000, // 20%
+            bump: 0,
+        };
+        let mut reflection = reflection_account();
+        reflection.add_staker(1_000);
+        let rate_before = reflection.rate;
+
+        let (stakers, treasury) = config.split_fee(1_001);
+        assert_eq!(treasury, 200);
+        assert_eq!(stakers, 801);
+
+        // Only the staker portion moves the rate
+        reflection.add_rewards(stakers);
+        assert_ne!(reflection.rate, rate_before);
+        assert_eq!(reflection.total_rewards_distributed, 801);
+    }
+
+    #[test]
+    fn test_fee_split_validation() {
+        assert!(RewardsConfig::is_valid_fee_bps(0));
+        assert!(RewardsConfig::is_valid_fee_bps(BPS_DENOMINATOR));
+        assert!(!RewardsConfig::is_valid_fee_bps(BPS_DENOMINATOR + 1));
+    }
+
+    #[test]
+    fn test_long_tenured_staker_earns_loyalty_bonus() {
+        let mut reflection = reflection_account();
+        reflection.add_rewards(2_000);
+
+        // Identical xHYPER and reflection: identical base claims
+        let veteran = user_claimed_at(0);
+        let newcomer = user_claimed_at(0);
+        let rate = REFLECTION_PRECISION;
+        let base = veteran.calculate_claimable(rate, 0);
+        assert_eq!(base, newcomer.calculate_claimable(rate, 0));
+        assert_eq!(base, 1_000);
+
+        let now = 200 * 86400;
+        let veteran_bonus = loyalty_bonus(base, 1, 0, now);
+        let newcomer_bonus = loyalty_bonus(base, now - 86400, 0, now);
+        assert_eq!(veteran_bonus, base / 10);
+        assert_eq!(newcomer_bonus, 0);
+
+        // Exactly at the threshold counts; an unstaking stake never does
+        assert_eq!(loyalty_bonus(base, now - LOYALTY_PERIOD, 0, now), base / 10);
+        assert_eq!(loyalty_bonus(base, 1, now - 10, now), 0);
+
+        // Bonus only comes out of vault surplus
+        assert_eq!(reflection.surplus(2_000), 0);
+        assert_eq!(reflection.surplus(2_050), 50);
+    }
+
+    #[test]
+    fn test_read_stake_fields_from_stake_data() {
+        let mut data = vec![0u8; 155];
+        data[64..72].copy_from_slice(&1_234i64.to_le_bytes());
+        data[72..80].copy_from_slice(&5_678i64.to_le_bytes());
+
+        assert_eq!(read_stake_times(&data), Some((1_234, 5_678)));
+        assert_eq!(read_stake_times(&data[..70]), None);
+
+        data[40..56].copy_from_slice(&4_000u128.to_le_bytes());
+        assert_eq!(read_stake_xhyper(&data), Some(4_000));
+        assert_eq!(read_stake_xhyper(&data[..50]), None);
+    }
+
+    /// StakeAccount data as the Staking Program lays it out
+    fn stake_data(beneficiary: Option<Pubkey>, operator: Option<Pubkey>, commission_bps: u16) -> Vec<u8> {
+        let mut data = vec![0u8; STAKE_BENEFICIARY_OFFSET];
+        for key in [beneficiary, None, operator] {
+            match key {
+                Some(key) => {
+                    data.push(1);
+                    data.extend_from_slice(key.as_ref());
+                }
+                None => data.push(0),
+            }
+        }
+        data.extend_from_slice(&commission_bps.to_le_bytes());
+        data.push(255); // bump
+        data
+    }
+
+    #[test]
+    fn test_operator_receives_commission_on_delegator_rewards() {
+        let operator = Pubkey::new_unique();
+
+        // Delegator's stake names the operator; the operator's own stake sets 15%
+        let delegator_stake = stake_data(Some(Pubkey::new_unique()), Some(operator), 0);
+        let operator_stake = stake_data(None, None, 1_500);
+        assert_eq!(read_stake_delegation(&delegator_stake), Some((Some(operator), 0)));
+        let (_, commission_bps) = read_stake_delegation(&operator_stake).unwrap();
+        assert_eq!(commission_bps, 1_500);
+
+        // register_stake takes the beneficiary from the stake too
+        let beneficiary = Pubkey::new_unique();
+        assert_eq!(read_stake_beneficiary(&stake_data(Some(beneficiary), None, 0)), Some(Some(beneficiary)));
+        assert_eq!(read_stake_beneficiary(&operator_stake), Some(None));
+
+        // sync_rewards records the delegation at the operator's current rate, then a claim is split
+        let mut user = user_claimed_at(0);
+        user.record_delegation(read_stake_delegation(&delegator_stake).unwrap().0, commission_bps);
+        assert_eq!((user.operator, user.commission_bps), (Some(operator), 1_500));
+
+        let (commission, to_delegator) = split_commission(1_000_000, commission_bps);
+        assert_eq!(commission, 150_000);
+        assert_eq!(to_delegator, 850_000);
+        assert_eq!(user.operator_commission(1_000_000, commission_bps), commission);
+
+        // Raising the rate afterwards doesn't reach accrued rewards; lowering it does
+        assert_eq!(user.operator_commission(1_000_000, BPS_DENOMINATOR), 150_000);
+        assert_eq!(user.operator_commission(1_000_000, 500), 50_000);
+
+        // Undelegating clears the recorded rate
+        user.record_delegation(None, 1_500);
+        assert_eq!((user.operator, user.commission_bps), (None, 0));
+
+        // No commission, or all of it
+        assert_eq!(split_commission(1_000, 0), (0, 1_000));
+        assert_eq!(split_commission(1_000, BPS_DENOMINATOR), (1_000, 0));
+
+        // Truncated or malformed data
+        assert_eq!(read_stake_delegation(&operator_stake[..STAKE_BENEFICIARY_OFFSET + 3]), None);
+        let mut bad = operator_stake.clone();
+        bad[STAKE_BENEFICIARY_OFFSET] = 2;
+        assert_eq!(read_stake_delegation(&bad), None);
+
+        let mut data = Vec::new();
+        user.beneficiary = Some(Pubkey::new_unique());
+        user.record_delegation(Some(operator), 1_500);
+        user.queued_epoch = Some(1);
+        user.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), UserRewardsAccount::LEN);
+    }
+
+    #[test]
+    fn test_compounded_rewards_registered_as_xhyper() {
+        let mut reflection = ReflectionAccount {
+            rate: REFLECTION_PRECISION,
+            total_reflection: 1_000,
+            total_xhyper: 1_000,
+            total_outstanding_rewards: 1_000,
+            ..reflection_account()
+        };
+        let mut user = user_claimed_at(0);
+        let claimable = user.calculate_claimable(reflection.rate, 0);
+        assert_eq!(claimable, 1_000);
+        reflection.record_claim(claimable, 1_000).unwrap();
+
+        // As update_after_claim leaves it (minus the Clock stamp)
+        user.total_claimed += claimable;
+        user.initial_reflection = (user.xhyper * reflection.rate) / REFLECTION_PRECISION;
+
+        // Reward restaked at 1x: xHYPER grows by the reward amount
+        user.add_xhyper(&mut reflection, claimable as u128);
+        assert_eq!(user.xhyper, 2_000);
+        assert_eq!(reflection.total_xhyper, 2_000);
+        assert_eq!(reflection.rate, REFLECTION_PRECISION);
+
+        // Nothing left to claim right after compounding
+        assert_eq!(user.calculate_claimable(reflection.rate, 0), 0);
+        assert_eq!(reflection.total_outstanding_rewards, 0);
+    }
+
+    #[test]
+    fn test_claims_beyond_solvency_blocked() {
+        let mut reflection = reflection_account();
+        reflection.add_staker(1000);
+        reflection.add_rewards(300);
+        assert_eq!(reflection.total_outstanding_rewards, 300);
+
+        // Fully funded vault: claims go through and reduce the outstanding total
+        let mut vault = 300;
+        reflection.record_claim(100, vault).unwrap();
+        vault -= 100;
+        assert_eq!(reflection.total_outstanding_rewards, 200);
+        assert!(reflection.is_solvent(vault));
+
+        // Vault under-funded (e.g. drained elsewhere): every claim is blocked
+        let underfunded = 150;
+        assert!(!reflection.is_solvent(underfunded));
+        assert!(reflection.record_claim(50, underfunded).is_err());
+        assert_eq!(reflection.total_outstanding_rewards, 200);
+
+        // A claim larger than what is outstanding is blocked even if the vault is flush
+        assert!(reflection.record_claim(201, 1_000).is_err());
+        reflection.record_claim(200, vault).unwrap();
+        assert_eq!(reflection.total_outstanding_rewards, 0);
+    }
+
+    #[test]
+    fn test_top_up_mid_cycle_keeps_accrued_rewards() {
+        let mut reflection = ReflectionAccount {
+            rate: REFLECTION_PRECISION,
+            total_xhyper: 1_000,
+            total_outstanding_rewards: 1_000,
+            ..reflection_account()
+        };
+        let mut user = user_claimed_at(0);
+        assert_eq!(user.total_claimable(reflection.rate, 0), 1_000);
+
+        // Stake topped up from 1_000 to 3_000 xHYPER mid-cycle
+        let settled = user.sync_xhyper(&mut reflection, 3_000);
+        assert_eq!(settled, 1_000);
+        assert_eq!(user.xhyper, 3_000);
+        assert_eq!(user.pending_rewards, 1_000);
+
+        // Accrued rewards survive, the added xHYPER earns nothing retroactively
+        assert_eq!(user.calculate_claimable(reflection.rate, 0), 0);
+        assert_eq!(user.total_claimable(reflection.rate, 0), 1_000);
+
+        // Syncing again without a change settles nothing new
+        assert_eq!(user.sync_xhyper(&mut reflection, 3_000), 0);
+        assert_eq!(user.total_claimable(reflection.rate, 0), 1_000);
+    }
+
+    #[test]
+    fn test_manual_top_up_credits_every_staker() {
+        let mut reflection = reflection_account();
+        let alice = reflection.add_staker(1_000);
+        let bob = reflection.add_staker(3_000);
+
+        // Each staker's share of the pool: reflection points at the current rate
+        let value = |reflection: &ReflectionAccount, points: u128| {
+            points * reflection.total_xhyper / reflection.total_reflection
+        };
+        assert_eq!(value(&reflection, alice), 1_000);
+        assert_eq!(value(&reflection, bob), 3_000);
+
+        let rate_before = reflection.rate;
+        reflection.add_rewards(400);
+
+        // Same accounting as job-fee rewards: the rate moves, the vault owes the top-up
+        assert!(reflection.rate != rate_before);
+        assert_eq!(reflection.total_rewards_distributed, 400);
+        assert_eq!(reflection.total_outstanding_rewards, 400);
+        assert!(reflection.is_solvent(400));
+
+        // Every staker is credited pro rata
+        assert_eq!(value(&reflection, alice), 1_100);
+        assert_eq!(value(&reflection, bob), 3_300);
+    }
+
+    #[test]
+    fn test_rewards_distributed_per_epoch() {
+        let mut reflection = reflection_account();
+        reflection.set_epoch_duration(86_400, 0).unwrap();
+        assert!(reflection.is_epoch_mode());
+
+        let value = |reflection: &ReflectionAccount, points: u128| {
+            points * reflection.total_xhyper / reflection.total_reflection
+        };
+
+        // Epoch 0: only alice is staked; rewards wait until the epoch closes
+        let alice = reflection.add_staker(1_000);
+        reflection.accrue_rewards(600);
+        reflection.accrue_rewards(400);
+        assert_eq!(reflection.epoch_rewards, 1_000);
+        assert_eq!(reflection.total_outstanding_rewards, 0);
+        assert_eq!(value(&reflection, alice), 1_000);
+
+        // The epoch budget isn't surplus that loyalty bonuses could spend
+        assert_eq!(reflection.surplus(1_000), 0);
+
+        assert!(!reflection.can_advance_epoch(86_399));
+        assert!(reflection.can_advance_epoch(86_400));
+        let mut record0 = epoch_record();
+        assert_eq!(reflection.advance_epoch(&mut record0, 86_400), 1_000);
+        assert_eq!(reflection.current_epoch, 1);
+        assert_eq!(reflection.epoch_rewards, 0);
+        assert_eq!(reflection.total_outstanding_rewards, 1_000);
+        assert_eq!(value(&reflection, alice), 2_000);
+
+        // The finalized epoch keeps its own accounting
+        assert_eq!(record0.epoch, 0);
+        assert_eq!((record0.started_at, record0.finalized_at), (0, 86_400));
+        assert_eq!(record0.rewards, 1_000);
+        assert_eq!(record0.staked_xhyper, 1_000);
+        assert_eq!(record0.joined_xhyper, 0);
+
+        // Epoch 1: bob registers; he is queued, so the budget is still alice's
+        let mut bob = user_claimed_at(86_400);
+        assert_eq!(bob.register(&mut reflection, 1_000, 86_400), 0);
+        assert_eq!(bob.queued_epoch, Some(1));
+        assert_eq!(reflection.queued_xhyper, 1_000);
+        reflection.accrue_rewards(300);
+        assert!(!reflection.can_advance_epoch(86_400 + 86_399));
+
+        let event_epoch = reflection.current_epoch;
+        let mut record1 = epoch_record();
+        assert_eq!(reflection.advance_epoch(&mut record1, 2 * 86_400), 300);
+        let event = reflection.epoch_advanced_event(event_epoch, 300, 2 * 86_400);
+        assert_eq!(event.epoch, 1);
+        assert_eq!(event.new_rate, reflection.rate);
+
+        assert_eq!(value(&reflection, alice), 2_300);
+        assert_eq!(record1.staked_xhyper, 2_000);
+        assert_eq!(record1.joined_xhyper, 1_000);
+        assert_eq!(reflection.queued_xhyper, 0);
+
+        // Bob joined the pool after the distribution; his entry is recorded from epoch 1's record
+        assert!(bob.awaits_join(reflection.current_epoch));
+        assert_eq!(bob.total_claimable(reflection.rate, i64::MAX), 0);
+        assert!(bob.join_pool(&record0).is_err());
+        bob.join_pool(&record1).unwrap();
+        assert!(!bob.awaits_join(reflection.current_epoch));
+        // Worth his 1_000 xHYPER, less a unit rounded off his reflection points
+        assert_eq!(value(&reflection, bob.initial_reflection), 999);
+
+        // Epoch 2: the budget is shared by both
+        reflection.accrue_rewards(330);
+        let mut record2 = epoch_record();
+        assert_eq!(reflection.advance_epoch(&mut record2, 3 * 86_400), 330);
+        assert_eq!(record2.staked_xhyper, 3_300);
+
+        assert_eq!(value(&reflection, alice), 2_530);
+        assert_eq!(value(&reflection, bob.initial_reflection), 1_099);
+        assert_eq!(reflection.total_rewards_distributed, 1_630);
+        assert_eq!(reflection.total_outstanding_rewards, 1_630);
+    }
+
+    #[test]
+    fn test_stake_registered_before_epoch_end_misses_that_epoch() {
+        let mut reflection = reflection_account();
+        let mut veteran = user_claimed_at(0);
+        veteran.register(&mut reflection, 1_000, 0);
+        assert_eq!(veteran.queued_epoch, None);
+        reflection.set_epoch_duration(86_400, 0).unwrap();
+
+        let value = |reflection: &ReflectionAccount, points: u128| {
+            points * reflection.total_xhyper / reflection.total_reflection
+        };
+
+        // A large stake registered a second before the advance captures nothing
+        reflection.accrue_rewards(1_000);
+        let mut sniper = user_claimed_at(86_399);
+        sniper.register(&mut reflection, 9_000, 86_399);
+        let mut record = epoch_record();
+        reflection.advance_epoch(&mut record, 86_400);
+        assert_eq!(value(&reflection, veteran.initial_reflection), 2_000);
+
+        // It can't leave before its entry is recorded, then leaves with only its own stake
+        assert!(sniper.unregister(&mut reflection).is_err());
+        sniper.join_pool(&record).unwrap();
+        assert_eq!(value(&reflection, sniper.initial_reflection), 9_000);
+        sniper.unregister(&mut reflection).unwrap();
+        assert_eq!(value(&reflection, veteran.initial_reflection), 2_000);
+
+        // A stake queued for the current epoch can top up or leave the queue
+        let mut late = user_claimed_at(90_000);
+        late.register(&mut reflection, 500, 90_000);
+        assert_eq!(late.sync_xhyper(&mut reflection, 700), 0);
+        assert_eq!(reflection.queued_xhyper, 700);
+
+        // Leaving epoch mode waits until nothing is queued
+        assert!(reflection.set_epoch_duration(0, 90_000).is_err());
+        late.unregister(&mut reflection).unwrap();
+        assert_eq!(reflection.queued_xhyper, 0);
+        reflection.set_epoch_duration(0, 90_000).unwrap();
+        assert!(!reflection.is_epoch_mode());
+    }
+
+    #[test]
+    fn test_continuous_mode_unaffected_by_epochs() {
+        let mut reflection = reflection_account();
+        reflection.add_staker(1_000);
+
+        // Continuous: rewards are distributed as they arrive
+        reflection.accrue_rewards(100);
+        assert_eq!(reflection.total_outstanding_rewards, 100);
+        assert!(!reflection.can_advance_epoch(i64::MAX));
+
+        // Leaving epoch mode distributes the unfinalized budget
+        reflection.set_epoch_duration(3_600, 0).unwrap();
+        reflection.accrue_rewards(50);
+        assert_eq!(reflection.total_outstanding_rewards, 100);
+        reflection.set_epoch_duration(0, 10).unwrap();
+        assert_eq!(reflection.epoch_rewards, 0);
+        assert_eq!(reflection.total_outstanding_rewards, 150);
+    }
+
+    #[test]
+    fn test_rounding_dust_recovered_across_many_claims() {
+        // rate = 7/3 * PRECISION: claims never convert back to whole tokens
+        let mut reflection = ReflectionAccount {
+            total_reflection: 7_000,
+            total_xhyper: 3_000,
+            total_outstanding_rewards: 10_000,
+            ..reflection_account()
+        };
+        reflection.rate = ReflectionAccount::compute_rate(7_000, 3_000);
+
+        let mut paid: u64 = 0;
+        let mut exact: u128 = 0; // scaled by REFLECTION_PRECISION
+        for _ in 0..100 {
+            let mut user = user_claimed_at(0);
+            user.xhyper = 10;
+
+            let claimable = user.calculate_claimable(reflection.rate, 0);
+            let remainder = user.claimable_remainder(reflection.rate);
+            assert!(remainder > 0 && remainder < REFLECTION_PRECISION);
+
+            paid += claimable;
+            exact += claimable as u128 * REFLECTION_PRECISION + remainder;
+            reflection.record_rounding_dust(remainder);
+        }
+
+        // Every dropped fraction is either back in the pool or still tracked
+        assert!(reflection.dust_redistributed > 0);
+        assert!(reflection.rounding_dust < REFLECTION_PRECISION);
+        assert_eq!(
+            paid as u128 * REFLECTION_PRECISION
+                + reflection.dust_redistributed as u128 * REFLECTION_PRECISION
+                + reflection.rounding_dust,
+            exact
+        );
+
+        // Redistributed dust went to stakers through the rate, without new debt
+        assert_eq!(reflection.total_xhyper, 3_000 + reflection.dust_redistributed as u128);
+        assert_eq!(reflection.total_outstanding_rewards, 10_000);
+    }
+
+    #[test]
+    fn test_wrong_mint_user_account_rejected() {
+        let reward_mint = Pubkey::new_unique();
+        let reflection = ReflectionAccount { reward_mint, ..reflection_account() };
+
+        // A destination in some other token can't receive a claim
+        assert!(!reflection.is_reward_mint(&Pubkey::new_unique()));
+        assert!(!reflection.is_reward_mint(&Pubkey::default()));
+        assert!(reflection.is_reward_mint(&reward_mint));
+
+        let mut data = Vec::new();
+        reflection.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), ReflectionAccount::LEN);
+    }
+
+    #[test]
+    fn test_stake_registered_before_distribution_waits_join_delay() {
+        let join_delay = 86_400;
+        let mut reflection = reflection_account();
+        let value = |reflection: &ReflectionAccount, points: u128| {
+            points * reflection.total_xhyper / reflection.total_reflection
+        };
+
+        let mut veteran = user_claimed_at(0);
+        veteran.register(&mut reflection, 1_000, 0);
+        assert!(!veteran.in_join_delay);
+
+        // Registered at 1_000, moments before a distribution lands
+        reflection.join_delay = join_delay;
+        let mut sniper = user_claimed_at(1_000);
+        assert_eq!(sniper.register(&mut reflection, 9_000, 1_000), 0);
+        assert!(sniper.in_join_delay);
+        assert_eq!(sniper.eligible_at, 1_000 + join_delay);
+        assert_eq!(reflection.total_xhyper, 1_000);
+
+        // The whole distribution goes to the stake already in the pool
+        reflection.add_rewards(1_000);
+        assert_eq!(value(&reflection, veteran.initial_reflection), 2_000);
+        assert_eq!(sniper.total_claimable(reflection.rate, 1_005), 0);
+        assert_eq!(sniper.total_claimable(reflection.rate, sniper.eligible_at), 0);
+
+        // Topping up during the delay only changes what it will enter with
+        assert_eq!(sniper.sync_xhyper(&mut reflection, 10_000), 0);
+        assert_eq!(sniper.xhyper, 10_000);
+        assert_eq!(reflection.total_xhyper, 2_000);
+
+        // Admitted once the delay has passed, it accrues only from then on
+        assert!(!sniper.end_join_delay(&mut reflection, sniper.eligible_at - 1));
+        assert!(sniper.end_join_delay(&mut reflection, sniper.eligible_at));
+        assert!(!sniper.end_join_delay(&mut reflection, sniper.eligible_at));
+        assert_eq!(value(&reflection, sniper.initial_reflection), 10_000);
+        assert_eq!(value(&reflection, veteran.initial_reflection), 2_000);
+
+        reflection.add_rewards(1_200);
+        assert_eq!(value(&reflection, veteran.initial_reflection), 2_200);
+        assert_eq!(value(&reflection, sniper.initial_reflection), 11_000);
+
+        // A stake leaving during its delay was never in the pool
+        let mut quitter = user_claimed_at(2_000);
+        quitter.register(&mut reflection, 5_000, 2_000);
+        let total_xhyper = reflection.total_xhyper;
+        quitter.unregister(&mut reflection).unwrap();
+        assert_eq!(reflection.total_xhyper, total_xhyper);
+
+        // 0 disables the delay; at most a week
+        assert!(ReflectionAccount::is_valid_join_delay(0));
+        assert!(ReflectionAccount::is_valid_join_delay(MAX_JOIN_DELAY));
+        assert!(!ReflectionAccount::is_valid_join_delay(MAX_JOIN_DELAY + 1));
+        assert!(!ReflectionAccount::is_valid_join_delay(-1));
+    }
 }
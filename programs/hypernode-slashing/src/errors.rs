@@ -31,4 +31,37 @@ pub enum SlashingError {
 
     #[msg("Node has no active stake")]
     NoActiveStake,
+
+    #[msg("Invalid slashing configuration")]
+    InvalidConfig,
+
+    #[msg("Destination account for a slash share is missing")]
+    MissingSlashDestination,
+
+    #[msg("Report must cite a failed job or the node's registration")]
+    MissingEvidence,
+
+    #[msg("Cited evidence does not belong to the reported node")]
+    EvidenceMismatch,
+
+    #[msg("Cited evidence does not show misbehavior")]
+    EvidenceNotIncriminating,
+
+    #[msg("Appeal or appeal voting window has closed")]
+    AppealWindowClosed,
+
+    #[msg("Appeal voting is still open")]
+    AppealVotingActive,
+
+    #[msg("Reporter stake below the minimum and no bond posted")]
+    ReporterStakeTooLow,
+
+    #[msg("Reporter account is required to refund the bond")]
+    MissingReporter,
+
+    #[msg("Validator stake below the minimum")]
+    ValidatorStakeTooLow,
+
+    #[msg("Treasury must be the configured HYPER treasury account")]
+    InvalidTreasury,
 }
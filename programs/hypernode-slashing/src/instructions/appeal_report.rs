@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Node appeals a confirmed report before its appeal window closes
+/// Opens appeal voting; the slash can't execute until the appeal is resolved
+#[derive(Accounts)]
+pub struct AppealReport<'info> {
+    #[account(mut)]
+    pub fraud_report: Account<'info, FraudReport>,
+
+    /// Slashing config (appeal voting period)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Reported node
+    pub node: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<AppealReport>) -> Result<()> {
+    let fraud_report = &mut ctx.accounts.fraud_report;
+    let clock = Clock::get()?;
+
+    fraud_report.appeal(&ctx.accounts.node.key(), clock.unix_timestamp, &ctx.accounts.config)?;
+
+    msg!("Fraud report appealed by node {}", fraud_report.node);
+    msg!("Appeal voting ends: {}", fraud_report.appeal_vote_ends);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use hypernode_staking::program::HypernodeStaking;
+use hypernode_staking::StakeAccount;
+
+/// Confirm a fraud report and propose a slash amount
+/// Validators must hold an active stake of at least min_validator_xhyper; the report's slash amount is the
+/// median of all proposals, so no single validator sets it
+#[derive(Accounts)]
+pub struct ConfirmReport<'info> {
+    #[account(mut)]
+    pub fraud_report: Account<'info, FraudReport>,
+
+    /// Slashing config (validator threshold, minimum validator stake)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Validator's stake account
+    #[account(
+        seeds = [b"stake", validator.key().as_ref()],
+        bump,
+        seeds::program = staking_program.key(),
+        constraint = validator_stake.is_active() @ SlashingError::NoActiveStake,
+        constraint = config.is_eligible_validator(validator_stake.xhyper) @ SlashingError::ValidatorStakeTooLow
+    )]
+    pub validator_stake: Account<'info, StakeAccount>,
+
+    /// Validator confirming the report
+    pub validator: Signer<'info>,
+
+    /// Staking program
+    pub staking_program: Program<'info, HypernodeStaking>,
+}
+
+pub fn handler(ctx: Context<ConfirmReport>, proposed_amount: u64) -> Result<()> {
+    let fraud_report = &mut ctx.accounts.fraud_report;
+
+    fraud_report.add_confirmation(
+        ctx.accounts.validator.key(),
+        proposed_amount,
+        &ctx.accounts.config,
+    )?;
+
+    msg!("Fraud report confirmed by {}", ctx.accounts.validator.key());
+    msg!("Proposed slash: {}", proposed_amount);
+    msg!(
+        "Validators: {}/{}",
+        fraud_report.validator_count,
+        ctx.accounts.config.required_validators(fraud_report.slash_amount)
+    );
+    msg!("Median slash: {}", fraud_report.slash_amount);
+
+    if fraud_report.status == ReportStatus::Confirmed {
+        msg!("Report confirmed, slash executable after {}", fraud_report.appeal_ends);
+    }
+
+    Ok(())
+}
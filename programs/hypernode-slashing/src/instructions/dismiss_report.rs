@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Dismiss a baseless fraud report
+/// Callable by the resolver (or governance) while the report is not executed.
+/// The report account is closed; rent and any bond return to the reporter unless penalized.
+#[derive(Accounts)]
+pub struct DismissReport<'info> {
+    #[account(
+        mut,
+        constraint = fraud_report.status != ReportStatus::Executed @ SlashingError::AlreadyExecuted,
+        constraint = fraud_report.can_dismiss() @ SlashingError::ReportNotActive
+    )]
+    pub fraud_report: Account<'info, FraudReport>,
+
+    /// Slashing config (resolver)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Original reporter (receives rent and bond back)
+    #[account(
+        mut,
+        constraint = reporter.key() == fraud_report.reporter @ SlashingError::Unauthorized
+    )]
+    pub reporter: SystemAccount<'info>,
+
+    /// Resolver dismissing the report
+    #[account(
+        mut,
+        constraint = config.is_resolver(&resolver.key()) @ SlashingError::Unauthorized
+    )]
+    pub resolver: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<DismissReport>,
+    penalize_reporter: bool,
+) -> Result<()> {
+    let fraud_report = &mut ctx.accounts.fraud_report;
+
+    let forfeited_bond = fraud_report.dismiss(penalize_reporter)?;
+
+    msg!("Fraud report dismissed");
+    msg!("Node: {}", fraud_report.node);
+    msg!("Reporter: {}", fraud_report.reporter);
+
+    // Frivolous reporters forfeit the report rent and bond
+    let rent_destination = if penalize_reporter {
+        msg!("Reporter penalized: rent and {} lamports bond forfeited", forfeited_bond);
+        ctx.accounts.resolver.to_account_info()
+    } else {
+        ctx.accounts.reporter.to_account_info()
+    };
+
+    fraud_report.close(rent_destination)?;
+
+    Ok(())
+}
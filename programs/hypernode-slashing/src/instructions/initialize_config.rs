@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::TokenAccount;
+use crate::program::HypernodeSlashing;
+use crate::state::*;
+use crate::errors::*;
+
+/// Initialize slashing config with default parameters
+/// Parameters can only be changed afterwards through governance
+/// Only the program's upgrade authority can initialize it (so the config
+/// can't be front-run); it becomes the report resolver
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = SlashingConfig::LEN,
+        seeds = [b"slashing_config"],
+        bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// HYPER token account receiving the treasury share of slashes
+    /// (changed afterwards with update_treasury)
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// Program upgrade authority, becomes the report resolver
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, HypernodeSlashing>,
+
+    #[account(
+        constraint = SlashingConfig::is_upgrade_authority(&program_data, &payer.key()) @ SlashingError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    config.max_slash_percentage = MAX_SLASH_PERCENTAGE;
+    config.appeal_period = APPEAL_PERIOD;
+    config.min_evidence_validators = MIN_EVIDENCE_VALIDATORS;
+    config.validator_tiers = DEFAULT_VALIDATOR_TIERS;
+    config.resolver = ctx.accounts.payer.key();
+    config.slash_splits = DEFAULT_SLASH_SPLITS;
+    config.appeal_voting_period = DEFAULT_APPEAL_VOTING_PERIOD;
+    config.appeal_quorum = DEFAULT_APPEAL_QUORUM;
+    config.min_reporter_xhyper = DEFAULT_MIN_REPORTER_XHYPER;
+    config.reporter_bond = DEFAULT_REPORTER_BOND;
+    config.min_validator_xhyper = DEFAULT_MIN_VALIDATOR_XHYPER;
+    config.reporter_penalty_bps = DEFAULT_REPORTER_PENALTY_BPS;
+    config.hyper_per_sol = DEFAULT_HYPER_PER_SOL;
+    config.treasury = ctx.accounts.treasury.key();
+    config.bump = ctx.bumps.config;
+
+    msg!("Slashing config initialized");
+    msg!("Max slash: {} bps", config.max_slash_percentage);
+    msg!("Appeal period: {} seconds", config.appeal_period);
+    msg!("Min validators: {}", config.min_evidence_validators);
+    msg!(
+        "Appeal voting: {} seconds, quorum {}, reporter penalty {} bps",
+        config.appeal_voting_period,
+        config.appeal_quorum,
+        config.reporter_penalty_bps
+    );
+    msg!("Reporter minimum: {} xHYPER or {} lamports bond", config.min_reporter_xhyper, config.reporter_bond);
+    msg!("Validator minimum: {} xHYPER", config.min_validator_xhyper);
+    msg!("Restitution: {} HYPER per SOL", config.hyper_per_sol);
+    msg!("Resolver: {}", config.resolver);
+    msg!("Treasury: {}", config.treasury);
+
+    Ok(())
+}
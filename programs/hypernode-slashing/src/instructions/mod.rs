@@ -1,5 +1,33 @@
+pub mod initialize_config;
+pub mod update_config;
+pub mod update_slash_split;
 pub mod report_fraud;
+pub mod confirm_report;
 pub mod slash_node;
+pub mod dismiss_report;
+pub mod update_validator_tiers;
+pub mod appeal_report;
+pub mod vote_appeal;
+pub mod resolve_appeal;
+pub mod update_appeal_voting;
+pub mod update_reporter_requirements;
+pub mod update_validator_stake;
+pub mod update_restitution_rate;
+pub mod update_treasury;
 
+pub use initialize_config::*;
+pub use update_config::*;
+pub use update_slash_split::*;
 pub use report_fraud::*;
+pub use confirm_report::*;
 pub use slash_node::*;
+pub use dismiss_report::*;
+pub use update_validator_tiers::*;
+pub use appeal_report::*;
+pub use vote_appeal::*;
+pub use resolve_appeal::*;
+pub use update_appeal_voting::*;
+pub use update_reporter_requirements::*;
+pub use update_validator_stake::*;
+pub use update_restitution_rate::*;
+pub use update_treasury::*;
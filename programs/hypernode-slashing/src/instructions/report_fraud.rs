@@ -1,25 +1,60 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program;
 use crate::state::*;
 use crate::errors::*;
+use hypernode_markets::state::JobAccount;
+use hypernode_nodes::state::Node;
+use hypernode_staking::StakeAccount;
 
 /// Report fraudulent behavior by a node
+/// The report must cite on-chain evidence: a job assigned to the node that timed out or whose
+/// result was reversed on dispute, or the node's registration with a heartbeat older than
+/// DOWNTIME_REPORT_THRESHOLD.
+/// A cited job links the report to its client, who receives restitution when slashed.
+/// The reporter needs min_reporter_xhyper staked, or posts reporter_bond with the report.
+/// The report is keyed on the cited evidence, so a job can't be reported twice under different CIDs
 #[derive(Accounts)]
-#[instruction(evidence_cid: [u8; 32])]
 pub struct ReportFraud<'info> {
     #[account(
         init,
         payer = reporter,
         space = FraudReport::LEN,
-        seeds = [b"fraud_report", node.key().as_ref(), evidence_cid.as_ref()],
+        seeds = [
+            b"fraud_report",
+            node.key().as_ref(),
+            evidence_account(&failed_job, &node_account).as_ref(),
+            evidence_nonce(&failed_job, &node_account).as_ref()
+        ],
         bump
     )]
     pub fraud_report: Account<'info, FraudReport>,
 
+    /// Slashing config (appeal period, reporter requirements)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
     /// Node being reported
     /// CHECK: This is the node being accused
     pub node: AccountInfo<'info>,
 
-    /// Reporter (must pay for account creation)
+    /// Timed-out or dispute-reversed job assigned to the node (Markets Program), cited as evidence
+    pub failed_job: Option<Account<'info, JobAccount>>,
+
+    /// Node's registration (Nodes Program), cited for a stale heartbeat
+    pub node_account: Option<Account<'info, Node>>,
+
+    /// Reporter's stake account (Staking Program), for the minimum reporter stake
+    #[account(
+        seeds = [b"stake", reporter.key().as_ref()],
+        bump,
+        seeds::program = hypernode_staking::ID
+    )]
+    pub reporter_stake: Option<Account<'info, StakeAccount>>,
+
+    /// Reporter (must pay for account creation and any bond)
     #[account(mut)]
     pub reporter: Signer<'info>,
 
@@ -29,26 +64,89 @@ pub struct ReportFraud<'info> {
 pub fn handler(
     ctx: Context<ReportFraud>,
     evidence_cid: [u8; 32],
+    post_bond: bool,
 ) -> Result<()> {
     let fraud_report = &mut ctx.accounts.fraud_report;
     let clock = Clock::get()?;
+    let node = ctx.accounts.node.key();
+
+    // Verify the cited on-chain evidence (the CID alone proves nothing)
+    let (evidence, evidence_account) = cited_evidence(&ctx.accounts.failed_job, &ctx.accounts.node_account)
+        .ok_or(SlashingError::MissingEvidence)?;
+    let fraud_type = evidence.verify(&node, clock.unix_timestamp)?;
+
+    // Skin in the game: enough xHYPER staked (zero once unstaking), or a bond
+    let reporter_xhyper = ctx.accounts.reporter_stake.as_ref().map_or(0, |stake| stake.xhyper);
+    let bond = ctx.accounts.config.reporter_bond_due(reporter_xhyper, post_bond)?;
 
     // Initialize fraud report
-    fraud_report.node = ctx.accounts.node.key();
+    fraud_report.node = node;
     fraud_report.reporter = ctx.accounts.reporter.key();
     fraud_report.evidence_cid = evidence_cid;
-    fraud_report.fraud_type = FraudType::InvalidResults; // Default, can be updated
+    fraud_report.fraud_type = fraud_type;
     fraud_report.time_reported = clock.unix_timestamp;
     fraud_report.validator_count = 0;
     fraud_report.validators = Vec::new();
+    fraud_report.proposed_amounts = Vec::new();
     fraud_report.status = ReportStatus::Pending;
-    fraud_report.slash_amount = 0; // To be set by validators
+    fraud_report.slash_amount = 0; // Median of validator proposals
+    fraud_report.appeal_ends = clock.unix_timestamp + ctx.accounts.config.appeal_period;
+    fraud_report.evidence_account = evidence_account;
+    fraud_report.affected_client = ctx.accounts.failed_job.as_ref().map(|job| job.client);
+    fraud_report.client_loss = ctx.accounts.failed_job
+        .as_ref()
+        .map_or(0, |job| ctx.accounts.config.lamports_to_hyper(job.price));
+    fraud_report.appeal_vote_ends = 0;
+    fraud_report.appeal_voters = Vec::new();
+    fraud_report.uphold_votes = 0;
+    fraud_report.overturn_votes = 0;
+    fraud_report.bond = bond;
     fraud_report.bump = ctx.bumps.fraud_report;
 
+    // The bond is held in the report account until it is resolved
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.reporter.to_account_info(),
+                    to: fraud_report.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+
     msg!("Fraud report created");
     msg!("Node: {}", fraud_report.node);
     msg!("Reporter: {}", fraud_report.reporter);
     msg!("Evidence IPFS: {:?}", evidence_cid);
+    msg!("Evidence account: {} ({:?})", evidence_account, fraud_report.fraud_type);
+    msg!("Appeal ends: {}", fraud_report.appeal_ends);
+    if bond > 0 {
+        msg!("Reporter bond: {} lamports", bond);
+    }
 
     Ok(())
 }
+
+/// Evidence the report cites: a failed job, else the node's registration
+fn cited_evidence(
+    failed_job: &Option<Account<JobAccount>>,
+    node_account: &Option<Account<Node>>,
+) -> Option<(ReportEvidence, Pubkey)> {
+    match (failed_job, node_account) {
+        (Some(job), _) => Some((ReportEvidence::from(&**job), job.key())),
+        (None, Some(node_account)) => Some((ReportEvidence::from(&**node_account), node_account.key())),
+        (None, None) => None,
+    }
+}
+
+/// Report PDA seeds for the cited evidence (unused when none is cited: the handler rejects it)
+fn evidence_account(failed_job: &Option<Account<JobAccount>>, node_account: &Option<Account<Node>>) -> Pubkey {
+    cited_evidence(failed_job, node_account).map_or(Pubkey::default(), |(_, account)| account)
+}
+
+fn evidence_nonce(failed_job: &Option<Account<JobAccount>>, node_account: &Option<Account<Node>>) -> [u8; 8] {
+    cited_evidence(failed_job, node_account).map_or([0u8; 8], |(evidence, _)| evidence.report_nonce())
+}
@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use hypernode_staking::program::HypernodeStaking;
+use hypernode_staking::{GlobalStakingStats, SlashPayouts, StakeAccount};
+use hypernode_staking::cpi::accounts::ApplySlash;
+use hypernode_staking::state::SLASH_AUTHORITY_SEED;
+
+/// Resolve an appeal once its voting window has ended (permissionless)
+/// Upheld: the report returns to Confirmed and slash_node can execute it.
+/// Overturned: the report is dismissed and closed, the reporter forfeits
+/// its rent and any bond to the node, and reporter_penalty_bps of its stake
+/// is slashed
+#[derive(Accounts)]
+pub struct ResolveAppeal<'info> {
+    #[account(mut)]
+    pub fraud_report: Account<'info, FraudReport>,
+
+    /// Slashing config (appeal quorum, reporter penalty)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Reported node (receives the report rent and bond if the slash is overturned)
+    #[account(
+        mut,
+        constraint = node.key() == fraud_report.node @ SlashingError::Unauthorized
+    )]
+    pub node: SystemAccount<'info>,
+
+    /// Reporter's stake account (penalized if the slash is overturned)
+    /// CHECK: PDA checked by seeds; only read if it is still a stake account
+    #[account(
+        mut,
+        seeds = [b"stake", fraud_report.reporter.as_ref()],
+        bump,
+        seeds::program = staking_program.key()
+    )]
+    pub reporter_stake: UncheckedAccount<'info>,
+
+    /// Staking global stats (the penalty leaves total_staked)
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+        seeds::program = staking_program.key()
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Slashing authority PDA (signs the staking program's apply_slash)
+    /// CHECK: PDA signer
+    #[account(
+        seeds = [SLASH_AUTHORITY_SEED],
+        bump
+    )]
+    pub slash_authority: UncheckedAccount<'info>,
+
+    /// Caller (anyone can call this)
+    pub caller: Signer<'info>,
+
+    /// Staking program
+    pub staking_program: Program<'info, HypernodeStaking>,
+}
+
+pub fn handler(ctx: Context<ResolveAppeal>) -> Result<()> {
+    let fraud_report = &mut ctx.accounts.fraud_report;
+    let clock = Clock::get()?;
+
+    let overturned = fraud_report.resolve_appeal(clock.unix_timestamp, &ctx.accounts.config)?;
+
+    msg!("Appeal resolved for node {}", fraud_report.node);
+    msg!("Uphold: {} | Overturn: {}", fraud_report.uphold_votes, fraud_report.overturn_votes);
+
+    if overturned {
+        // Slash the reporter's stake, if it still has one (a bonded reporter may not)
+        let reporter_stake = ctx.accounts.reporter_stake.to_account_info();
+        let penalty = if reporter_stake.owner == &hypernode_staking::ID && !reporter_stake.data_is_empty() {
+            let stake = StakeAccount::try_deserialize(&mut &reporter_stake.try_borrow_data()?[..])?;
+            ctx.accounts.config.reporter_penalty(stake.amount)
+        } else {
+            0
+        };
+
+        if penalty > 0 {
+            let slash_authority_seeds: &[&[u8]] = &[SLASH_AUTHORITY_SEED, &[ctx.bumps.slash_authority]];
+            let cpi_accounts = ApplySlash {
+                stake_account: reporter_stake,
+                global_stats: ctx.accounts.global_stats.to_account_info(),
+                slash_authority: ctx.accounts.slash_authority.to_account_info(),
+                vault: None,
+                vault_authority: None,
+                treasury: None,
+                client_token_account: None,
+                reflection_account: None,
+                rewards_vault: None,
+                rewards_config: None,
+                rewards_treasury: None,
+                rewards_program: None,
+                token_program: None,
+            };
+            // No payouts: the penalty stays in the staking vault
+            hypernode_staking::cpi::apply_slash(
+                CpiContext::new_with_signer(
+                    ctx.accounts.staking_program.to_account_info(),
+                    cpi_accounts,
+                    &[slash_authority_seeds],
+                ),
+                penalty,
+                SlashPayouts::default(),
+            )?;
+        }
+
+        msg!("Slash overturned: report dismissed, reporter rent and bond forfeited to the node");
+        msg!("Reporter stake penalty: {}", penalty);
+        fraud_report.close(ctx.accounts.node.to_account_info())?;
+    } else {
+        msg!("Slash upheld: executable after {}", fraud_report.appeal_ends);
+    }
+
+    Ok(())
+}
@@ -1,20 +1,32 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Token, TokenAccount};
 use crate::state::*;
 use crate::errors::*;
 use hypernode_staking::program::HypernodeStaking;
-use hypernode_staking::StakeAccount;
+use hypernode_staking::{GlobalStakingStats, SlashPayouts, StakeAccount};
+use hypernode_staking::cpi::accounts::ApplySlash;
+use hypernode_staking::state::SLASH_AUTHORITY_SEED;
+use hypernode_rewards::program::HypernodeRewards;
+use hypernode_rewards::{ReflectionAccount, RewardsConfig};
 
 /// Execute slash on confirmed fraud report
+/// The amount is the median of the confirming validators' proposals
 #[derive(Accounts)]
 pub struct SlashNode<'info> {
     #[account(
         mut,
         constraint = fraud_report.status == ReportStatus::Confirmed @ SlashingError::ReportNotActive,
-        constraint = fraud_report.is_confirmed() @ SlashingError::InsufficientValidators
+        constraint = fraud_report.is_confirmed(&config) @ SlashingError::InsufficientValidators
     )]
     pub fraud_report: Account<'info, FraudReport>,
 
+    /// Slashing config (max slash, validator threshold, slash routing)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
     #[account(
         init,
         payer = executor,
@@ -33,7 +45,24 @@ pub struct SlashNode<'info> {
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
-    /// Staking vault (holds staked tokens)
+    /// Staking global stats (slashed amount leaves total_staked)
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump,
+        seeds::program = staking_program.key()
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Slashing authority PDA (signs the staking program's apply_slash)
+    /// CHECK: PDA signer
+    #[account(
+        seeds = [SLASH_AUTHORITY_SEED],
+        bump
+    )]
+    pub slash_authority: UncheckedAccount<'info>,
+
+    /// Staking vault (holds staked tokens; apply_slash pays the shares out of it)
     #[account(
         mut,
         seeds = [b"vault"],
@@ -42,10 +71,56 @@ pub struct SlashNode<'info> {
     )]
     pub staking_vault: Account<'info, TokenAccount>,
 
-    /// Treasury to receive slashed funds
-    #[account(mut)]
+    /// Staking vault authority PDA (the staking program signs with it)
+    /// CHECK: PDA of the staking program
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+        seeds::program = staking_program.key()
+    )]
+    pub staking_vault_authority: UncheckedAccount<'info>,
+
+    /// Treasury to receive slashed funds (the config's, in the staked token)
+    #[account(
+        mut,
+        address = config.treasury @ SlashingError::InvalidTreasury,
+        constraint = treasury.mint == staking_vault.mint @ SlashingError::InvalidTreasury
+    )]
     pub treasury: Account<'info, TokenAccount>,
 
+    /// Token account of the cited job's client, or the reporter's when no job
+    /// was cited (client compensation share)
+    #[account(
+        mut,
+        constraint = client_token_account.owner == fraud_report.restitution_recipient() @ SlashingError::Unauthorized
+    )]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards Program reflection account (rewards share)
+    #[account(mut)]
+    pub reflection_account: Option<Account<'info, ReflectionAccount>>,
+
+    /// Rewards Program vault (rewards share)
+    #[account(mut)]
+    pub rewards_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards Program config (staker/treasury fee split)
+    pub rewards_config: Option<Account<'info, RewardsConfig>>,
+
+    /// Rewards Program treasury (treasury share of the rewards portion)
+    #[account(mut)]
+    pub rewards_treasury: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards program
+    pub rewards_program: Option<Program<'info, HypernodeRewards>>,
+
+    /// Original reporter (bond refund), required if the report holds a bond
+    #[account(
+        mut,
+        constraint = reporter.key() == fraud_report.reporter @ SlashingError::Unauthorized
+    )]
+    pub reporter: Option<SystemAccount<'info>>,
+
     /// Executor (authority that can execute slashes)
     #[account(mut)]
     pub executor: Signer<'info>,
@@ -57,10 +132,7 @@ pub struct SlashNode<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<SlashNode>,
-    slash_amount: u64,
-) -> Result<()> {
+pub fn handler(ctx: Context<SlashNode>) -> Result<()> {
     let fraud_report = &mut ctx.accounts.fraud_report;
     let stake_account = &ctx.accounts.stake_account;
     let clock = Clock::get()?;
@@ -71,12 +143,8 @@ pub fn handler(
         SlashingError::AppealPeriodActive
     );
 
-    // Verify slash amount is within limits
-    let max_slash = (stake_account.amount as u128 * MAX_SLASH_PERCENTAGE as u128) / 10000;
-    require!(
-        slash_amount <= max_slash as u64,
-        SlashingError::SlashAmountTooHigh
-    );
+    // Slash the validators' consensus (median) amount, capped at the max slash
+    let slash_amount = fraud_report.capped_slash_amount(stake_account.amount, &ctx.accounts.config);
 
     // Verify node has sufficient stake
     require!(
@@ -84,30 +152,83 @@ pub fn handler(
         SlashingError::InsufficientStake
     );
 
-    // Transfer slashed tokens from staking vault to treasury
-    // Use PDA signer from staking program
-    let staking_program_key = ctx.accounts.staking_program.key();
-    let vault_bump = ctx.bumps.staking_vault;
-    let vault_seeds: &[&[u8]] = &[b"vault", &[vault_bump]];
-    let signer_seeds = &[vault_seeds];
-
-    let cpi_accounts = Transfer {
-        from: ctx.accounts.staking_vault.to_account_info(),
-        to: ctx.accounts.treasury.to_account_info(),
-        authority: ctx.accounts.staking_vault.to_account_info(),
-    };
+    // Route the slash according to the fraud type
+    let (client_share, treasury_share, rewards_share) = ctx
+        .accounts
+        .config
+        .slash_split(&fraud_report.fraud_type)
+        .distribute(slash_amount);
 
-    let cpi_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        cpi_accounts,
-        signer_seeds,
-    );
+    // Restitution to a job's client is capped at what they paid
+    let (client_share, excess) = fraud_report.restitution(client_share);
+    let treasury_share = treasury_share + excess;
 
-    // Update fraud report status BEFORE external call (reentrancy protection)
+    // Update fraud report status BEFORE external calls (reentrancy protection)
     fraud_report.status = ReportStatus::Executed;
-
-    // Execute token transfer after state update
-    token::transfer(cpi_ctx, slash_amount)?;
+    let bond = fraud_report.release_bond();
+
+    // The report held up: its bond goes back to the reporter
+    if bond > 0 {
+        let reporter = ctx
+            .accounts
+            .reporter
+            .as_ref()
+            .ok_or(SlashingError::MissingReporter)?;
+        **fraud_report.to_account_info().try_borrow_mut_lamports()? -= bond;
+        **reporter.to_account_info().try_borrow_mut_lamports()? += bond;
+    }
+
+    // Rewards share needs the Rewards Program accounts; check before slashing
+    if rewards_share > 0 {
+        require!(
+            ctx.accounts.reflection_account.is_some()
+                && ctx.accounts.rewards_vault.is_some()
+                && ctx.accounts.rewards_config.is_some()
+                && ctx.accounts.rewards_treasury.is_some()
+                && ctx.accounts.rewards_program.is_some(),
+            SlashingError::MissingSlashDestination
+        );
+    }
+    if client_share > 0 {
+        require!(
+            ctx.accounts.client_token_account.is_some(),
+            SlashingError::MissingSlashDestination
+        );
+    }
+
+    // Reduce the node's stake so a later withdraw returns only the remainder
+    // (a remainder below the staking minimum is unbonded and withdrawable at once).
+    // The staking program pays the shares out of its vault, which only its
+    // vault authority can sign for
+    let slash_authority_seeds: &[&[u8]] = &[SLASH_AUTHORITY_SEED, &[ctx.bumps.slash_authority]];
+    let cpi_accounts = ApplySlash {
+        stake_account: ctx.accounts.stake_account.to_account_info(),
+        global_stats: ctx.accounts.global_stats.to_account_info(),
+        slash_authority: ctx.accounts.slash_authority.to_account_info(),
+        vault: Some(ctx.accounts.staking_vault.to_account_info()),
+        vault_authority: Some(ctx.accounts.staking_vault_authority.to_account_info()),
+        treasury: Some(ctx.accounts.treasury.to_account_info()),
+        client_token_account: ctx.accounts.client_token_account.as_ref().map(|a| a.to_account_info()),
+        reflection_account: ctx.accounts.reflection_account.as_ref().map(|a| a.to_account_info()),
+        rewards_vault: ctx.accounts.rewards_vault.as_ref().map(|a| a.to_account_info()),
+        rewards_config: ctx.accounts.rewards_config.as_ref().map(|a| a.to_account_info()),
+        rewards_treasury: ctx.accounts.rewards_treasury.as_ref().map(|a| a.to_account_info()),
+        rewards_program: ctx.accounts.rewards_program.as_ref().map(|p| p.to_account_info()),
+        token_program: Some(ctx.accounts.token_program.to_account_info()),
+    };
+    hypernode_staking::cpi::apply_slash(
+        CpiContext::new_with_signer(
+            ctx.accounts.staking_program.to_account_info(),
+            cpi_accounts,
+            &[slash_authority_seeds],
+        ),
+        slash_amount,
+        SlashPayouts {
+            client: client_share,
+            treasury: treasury_share,
+            rewards: rewards_share,
+        },
+    )?;
 
     // Create slash record
     let slash_record = &mut ctx.accounts.slash_record;
@@ -122,6 +243,7 @@ pub fn handler(
     msg!("Node: {}", fraud_report.node);
     msg!("Amount slashed: {} tokens", slash_amount);
     msg!("Slash type: {:?}", fraud_report.fraud_type);
+    msg!("Client: {} | Treasury: {} | Rewards: {}", client_share, treasury_share, rewards_share);
 
     Ok(())
 }
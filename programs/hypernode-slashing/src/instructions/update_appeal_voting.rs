@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the appeal voting period, quorum and reporter penalty
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateAppealVoting<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateAppealVoting>,
+    appeal_voting_period: i64,
+    appeal_quorum: u8,
+    reporter_penalty_bps: u16,
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateAppealVoting {
+        appeal_voting_period,
+        appeal_quorum,
+        reporter_penalty_bps,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    let config = &mut ctx.accounts.config;
+
+    // Verify caller is the governance program
+    require!(
+        SlashingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        SlashingError::Unauthorized
+    );
+
+    require!(
+        SlashingConfig::validate_appeal_voting(appeal_voting_period, appeal_quorum)
+            && SlashingConfig::validate_reporter_penalty(reporter_penalty_bps),
+        SlashingError::InvalidConfig
+    );
+
+    config.appeal_voting_period = appeal_voting_period;
+    config.appeal_quorum = appeal_quorum;
+    config.reporter_penalty_bps = reporter_penalty_bps;
+
+    msg!("Appeal voting updated by proposal {}", ctx.accounts.proposal.id);
+    msg!("Voting period: {} seconds", appeal_voting_period);
+    msg!("Quorum: {}", appeal_quorum);
+    msg!("Reporter penalty: {} bps", reporter_penalty_bps);
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update slashing parameters
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateConfig>,
+    max_slash_percentage: u16,
+    appeal_period: i64,
+    min_evidence_validators: u8,
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateConfig {
+        max_slash_percentage,
+        appeal_period,
+        min_evidence_validators,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    // Only the governance authority, within the allowed ranges
+    ctx.accounts.config.apply_update(
+        &ctx.accounts.governance_authority.key(),
+        max_slash_percentage,
+        appeal_period,
+        min_evidence_validators,
+    )?;
+
+    msg!("Slashing config updated by proposal {}", ctx.accounts.proposal.id);
+    msg!("Max slash: {} bps", max_slash_percentage);
+    msg!("Appeal period: {} seconds", appeal_period);
+    msg!("Min validators: {}", min_evidence_validators);
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the minimum reporter stake and the reporter bond
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateReporterRequirements<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateReporterRequirements>,
+    min_reporter_xhyper: u128,
+    reporter_bond: u64,
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateReporterRequirements {
+        min_reporter_xhyper,
+        reporter_bond,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    let config = &mut ctx.accounts.config;
+
+    // Verify caller is the governance program
+    require!(
+        SlashingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        SlashingError::Unauthorized
+    );
+
+    require!(
+        SlashingConfig::validate_reporter_requirements(reporter_bond),
+        SlashingError::InvalidConfig
+    );
+
+    config.min_reporter_xhyper = min_reporter_xhyper;
+    config.reporter_bond = reporter_bond;
+
+    msg!("Reporter requirements updated by proposal {}", ctx.accounts.proposal.id);
+    msg!("Min reporter xHYPER: {}", min_reporter_xhyper);
+    msg!("Reporter bond: {} lamports", reporter_bond);
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the HYPER restitution paid per SOL of a job's price
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateRestitutionRate<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateRestitutionRate>,
+    hyper_per_sol: u64,
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateRestitutionRate {
+        hyper_per_sol,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    let config = &mut ctx.accounts.config;
+
+    // Verify caller is the governance program
+    require!(
+        SlashingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        SlashingError::Unauthorized
+    );
+
+    require!(hyper_per_sol > 0, SlashingError::InvalidConfig);
+
+    config.hyper_per_sol = hyper_per_sol;
+
+    msg!("Restitution rate updated by proposal {}", ctx.accounts.proposal.id);
+    msg!("HYPER per SOL: {}", hyper_per_sol);
+
+    Ok(())
+}
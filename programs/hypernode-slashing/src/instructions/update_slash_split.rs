@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update slash routing for one fraud type
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateSlashSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateSlashSplit>,
+    fraud_type: FraudType,
+    split: SlashSplit,
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateSlashSplit {
+        fraud_type: fraud_type.clone(),
+        split,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    let config = &mut ctx.accounts.config;
+
+    // Verify caller is the governance program
+    require!(
+        SlashingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        SlashingError::Unauthorized
+    );
+
+    require!(split.is_valid(), SlashingError::InvalidConfig);
+
+    config.slash_splits[fraud_type.index()] = split;
+
+    msg!("Slash split updated by proposal {}", ctx.accounts.proposal.id);
+    msg!("Fraud type: {:?}", fraud_type);
+    msg!(
+        "Client: {} bps | Treasury: {} bps | Rewards: {} bps",
+        split.client_bps,
+        split.treasury_bps,
+        split.rewards_bps
+    );
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the token account receiving the treasury share of slashes
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateTreasury>,
+    treasury: Pubkey,
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateTreasury {
+        treasury,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    let config = &mut ctx.accounts.config;
+
+    // Verify caller is the governance program
+    require!(
+        SlashingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        SlashingError::Unauthorized
+    );
+
+    // slash_node checks the account's mint against the staking vault
+    require!(treasury != Pubkey::default(), SlashingError::InvalidTreasury);
+
+    config.treasury = treasury;
+
+    msg!("Slashing treasury updated by proposal {}", ctx.accounts.proposal.id);
+    msg!("Treasury: {}", treasury);
+
+    Ok(())
+}
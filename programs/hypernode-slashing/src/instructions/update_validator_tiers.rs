@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_lang::InstructionData;
+use hypernode_governance::state::{Proposal, ProposalStatus};
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the validators required by slash size
+/// Only callable via CPI from the governance program while executing a passed proposal
+#[derive(Accounts)]
+pub struct UpdateValidatorTiers<'info> {
+    #[account(
+        mut,
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Proposal being executed (owned by the governance program)
+    #[account(
+        constraint = proposal.status == ProposalStatus::Executed @ SlashingError::Unauthorized
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateValidatorTiers>,
+    validator_tiers: [ValidatorTier; VALIDATOR_TIER_COUNT],
+) -> Result<()> {
+    // Verify the executed proposal carries exactly this update
+    let data = crate::instruction::UpdateValidatorTiers {
+        validator_tiers,
+    }
+    .data();
+    SlashingConfig::verify_governance_update(&ctx.accounts.proposal, &ctx.accounts.config.key(), &data)?;
+
+    let config = &mut ctx.accounts.config;
+
+    // Verify caller is the governance program
+    require!(
+        SlashingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        SlashingError::Unauthorized
+    );
+
+    require!(
+        SlashingConfig::validate_tiers(&validator_tiers),
+        SlashingError::InvalidConfig
+    );
+
+    config.validator_tiers = validator_tiers;
+
+    msg!("Validator tiers updated by proposal {}", ctx.accounts.proposal.id);
+    for tier in validator_tiers.iter().filter(|tier| tier.validators > 0) {
+        msg!("Slash >= {}: {} validators", tier.min_slash_amount, tier.validators);
+    }
+
+    Ok(())
+}
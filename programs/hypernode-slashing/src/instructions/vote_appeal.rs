@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use hypernode_staking::program::HypernodeStaking;
+use hypernode_staking::StakeAccount;
+
+/// Vote to uphold or overturn an appealed slash
+/// Validators must hold an active stake of at least min_validator_xhyper and
+/// must not have confirmed the report
+#[derive(Accounts)]
+pub struct VoteAppeal<'info> {
+    #[account(mut)]
+    pub fraud_report: Account<'info, FraudReport>,
+
+    /// Slashing config (minimum validator stake)
+    #[account(
+        seeds = [b"slashing_config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, SlashingConfig>,
+
+    /// Validator's stake account
+    #[account(
+        seeds = [b"stake", validator.key().as_ref()],
+        bump,
+        seeds::program = staking_program.key(),
+        constraint = validator_stake.is_active() @ SlashingError::NoActiveStake,
+        constraint = config.is_eligible_validator(validator_stake.xhyper) @ SlashingError::ValidatorStakeTooLow
+    )]
+    pub validator_stake: Account<'info, StakeAccount>,
+
+    /// Validator voting on the appeal
+    pub validator: Signer<'info>,
+
+    /// Staking program
+    pub staking_program: Program<'info, HypernodeStaking>,
+}
+
+pub fn handler(ctx: Context<VoteAppeal>, uphold: bool) -> Result<()> {
+    let fraud_report = &mut ctx.accounts.fraud_report;
+    let clock = Clock::get()?;
+
+    fraud_report.vote_appeal(ctx.accounts.validator.key(), uphold, clock.unix_timestamp)?;
+
+    msg!(
+        "Appeal vote by {}: {}",
+        ctx.accounts.validator.key(),
+        if uphold { "uphold" } else { "overturn" }
+    );
+    msg!("Uphold: {} | Overturn: {}", fraud_report.uphold_votes, fraud_report.overturn_votes);
+
+    Ok(())
+}
@@ -5,6 +5,7 @@ pub mod instructions;
 pub mod state;
 
 use instructions::*;
+use state::{FraudType, SlashSplit, ValidatorTier, VALIDATOR_TIER_COUNT};
 
 declare_id!("83rLt9YBCTkaAX6vLUuEAQE7QdhofvQWUhjybXVr7nCL");
 
@@ -14,11 +15,117 @@ declare_id!("83rLt9YBCTkaAX6vLUuEAQE7QdhofvQWUhjybXVr7nCL");
 pub mod hypernode_slashing {
     use super::*;
 
-    pub fn report_fraud(ctx: Context<ReportFraud>, evidence_cid: [u8; 32]) -> Result<()> {
-        instructions::report_fraud::handler(ctx, evidence_cid)
+    /// Initialize slashing config with default parameters (upgrade authority only; becomes the resolver)
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config::handler(ctx)
     }
 
-    pub fn slash_node(ctx: Context<SlashNode>, slash_amount: u64) -> Result<()> {
-        instructions::slash_node::handler(ctx, slash_amount)
+    /// Update slashing parameters (governance CPI only)
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        max_slash_percentage: u16,
+        appeal_period: i64,
+        min_evidence_validators: u8,
+    ) -> Result<()> {
+        instructions::update_config::handler(ctx, max_slash_percentage, appeal_period, min_evidence_validators)
+    }
+
+    /// Update slash routing for a fraud type (governance CPI only)
+    pub fn update_slash_split(
+        ctx: Context<UpdateSlashSplit>,
+        fraud_type: FraudType,
+        split: SlashSplit,
+    ) -> Result<()> {
+        instructions::update_slash_split::handler(ctx, fraud_type, split)
+    }
+
+    /// Report a node, citing on-chain evidence
+    /// Reporters below min_reporter_xhyper must set post_bond to post reporter_bond
+    pub fn report_fraud(
+        ctx: Context<ReportFraud>,
+        evidence_cid: [u8; 32],
+        post_bond: bool,
+    ) -> Result<()> {
+        instructions::report_fraud::handler(ctx, evidence_cid, post_bond)
+    }
+
+    /// Confirm a report as a staked validator, proposing a slash amount
+    pub fn confirm_report(ctx: Context<ConfirmReport>, proposed_amount: u64) -> Result<()> {
+        instructions::confirm_report::handler(ctx, proposed_amount)
+    }
+
+    /// Execute a confirmed report, slashing the validators' median amount
+    pub fn slash_node(ctx: Context<SlashNode>) -> Result<()> {
+        instructions::slash_node::handler(ctx)
+    }
+
+    /// Dismiss a baseless report and close it
+    /// penalize_reporter forfeits the report rent and bond to the resolver
+    pub fn dismiss_report(ctx: Context<DismissReport>, penalize_reporter: bool) -> Result<()> {
+        instructions::dismiss_report::handler(ctx, penalize_reporter)
+    }
+
+    /// Update the validators required by slash size (governance CPI only)
+    pub fn update_validator_tiers(
+        ctx: Context<UpdateValidatorTiers>,
+        validator_tiers: [ValidatorTier; VALIDATOR_TIER_COUNT],
+    ) -> Result<()> {
+        instructions::update_validator_tiers::handler(ctx, validator_tiers)
+    }
+
+    /// Appeal a confirmed report before its appeal window closes (reported node only)
+    pub fn appeal_report(ctx: Context<AppealReport>) -> Result<()> {
+        instructions::appeal_report::handler(ctx)
+    }
+
+    /// Vote to uphold or overturn an appealed slash as a staked validator
+    pub fn vote_appeal(ctx: Context<VoteAppeal>, uphold: bool) -> Result<()> {
+        instructions::vote_appeal::handler(ctx, uphold)
+    }
+
+    /// Resolve an appeal after its voting window (permissionless)
+    /// Upheld reports return to Confirmed; overturned ones are dismissed and closed
+    pub fn resolve_appeal(ctx: Context<ResolveAppeal>) -> Result<()> {
+        instructions::resolve_appeal::handler(ctx)
+    }
+
+    /// Update the appeal voting period, quorum and reporter penalty (governance CPI only)
+    pub fn update_appeal_voting(
+        ctx: Context<UpdateAppealVoting>,
+        appeal_voting_period: i64,
+        appeal_quorum: u8,
+        reporter_penalty_bps: u16,
+    ) -> Result<()> {
+        instructions::update_appeal_voting::handler(ctx, appeal_voting_period, appeal_quorum, reporter_penalty_bps)
+    }
+
+    /// Update the minimum reporter stake and the bond alternative (governance CPI only)
+    pub fn update_reporter_requirements(
+        ctx: Context<UpdateReporterRequirements>,
+        min_reporter_xhyper: u128,
+        reporter_bond: u64,
+    ) -> Result<()> {
+        instructions::update_reporter_requirements::handler(ctx, min_reporter_xhyper, reporter_bond)
+    }
+
+    /// Update the minimum validator stake (governance CPI only)
+    pub fn update_validator_stake(
+        ctx: Context<UpdateValidatorStake>,
+        min_validator_xhyper: u128,
+    ) -> Result<()> {
+        instructions::update_validator_stake::handler(ctx, min_validator_xhyper)
+    }
+
+    /// Update the HYPER restitution per SOL of a job's price (governance CPI only)
+    pub fn update_restitution_rate(
+        ctx: Context<UpdateRestitutionRate>,
+        hyper_per_sol: u64,
+    ) -> Result<()> {
+        instructions::update_restitution_rate::handler(ctx, hyper_per_sol)
+    }
+
+    /// Update the token account receiving the treasury share of slashes (governance CPI only)
+    pub fn update_treasury(ctx: Context<UpdateTreasury>, treasury: Pubkey) -> Result<()> {
+        instructions::update_treasury::handler(ctx, treasury)
     }
 }
@@ -1,13 +1,319 @@
 use anchor_lang::prelude::*;
+use hypernode_governance::state::{Proposal, GOVERNANCE_AUTHORITY_SEED};
+use hypernode_markets::state::{JobAccount, JobState};
+use hypernode_nodes::state::Node;
+use crate::errors::SlashingError;
 
 /// HYPER Token Configuration
 pub const HYPER_DECIMALS: u8 = 6;
 
-/// Slashing configuration
+/// Default slashing configuration (initial values of SlashingConfig)
 pub const MAX_SLASH_PERCENTAGE: u16 = 5000; // 50% max slash
 pub const APPEAL_PERIOD: i64 = 7 * 86400; // 7 days to appeal
 pub const MIN_EVIDENCE_VALIDATORS: u8 = 3; // Min validators to confirm fraud
 
+/// Maximum validators that can confirm a single report
+pub const MAX_REPORT_VALIDATORS: u8 = 10;
+
+/// Default appeal vote: how long validators can vote once a node appeals,
+/// and the votes needed for the appeal to be decided by them
+pub const DEFAULT_APPEAL_VOTING_PERIOD: i64 = 3 * 86400; // 3 days
+pub const DEFAULT_APPEAL_QUORUM: u8 = MIN_EVIDENCE_VALIDATORS;
+
+/// Default reporter requirements: reporters need this much xHYPER staked,
+/// or else post a refundable bond (lamports) with the report
+pub const DEFAULT_MIN_REPORTER_XHYPER: u128 = 100 * 10u128.pow(HYPER_DECIMALS as u32); // 100 xHYPER
+pub const DEFAULT_REPORTER_BOND: u64 = 100_000_000; // 0.1 SOL
+
+/// Default xHYPER a validator must have staked to confirm reports
+pub const DEFAULT_MIN_VALIDATOR_XHYPER: u128 = 1_000 * 10u128.pow(HYPER_DECIMALS as u32); // 1,000 xHYPER
+
+/// Default share of the reporter's stake slashed when an appeal overturns its report
+pub const DEFAULT_REPORTER_PENALTY_BPS: u16 = 1000; // 10%
+
+/// Largest reporter penalty governance can set
+pub const MAX_REPORTER_PENALTY_BPS: u16 = 5000; // 50%
+
+/// Default HYPER (base units) restitution per SOL a client paid
+/// Job prices are in lamports while slashes are in HYPER; governance keeps
+/// this in line with the market rate
+pub const DEFAULT_HYPER_PER_SOL: u64 = 1_000 * 10u64.pow(HYPER_DECIMALS as u32); // 1,000 HYPER
+
+/// Largest reporter bond governance can set (lamports)
+pub const MAX_REPORTER_BOND: u64 = 100_000_000_000; // 100 SOL
+
+/// Heartbeat gap after which a node can be reported for downtime
+pub const DOWNTIME_REPORT_THRESHOLD: i64 = 48 * 3600; // 48 hours
+
+/// Number of slash-size tiers for the validator threshold
+pub const VALIDATOR_TIER_COUNT: usize = 3;
+
+/// Validators required by slash size: a slash of at least `min_slash_amount`
+/// needs `validators` confirmations (the largest matching tier applies)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct ValidatorTier {
+    /// Smallest slash (HYPER base units) this tier applies to
+    pub min_slash_amount: u64,
+
+    /// Confirmations required (0 disables the tier)
+    pub validators: u8,
+}
+
+impl ValidatorTier {
+    pub const LEN: usize = 8 + 1;
+
+    pub const fn new(min_slash_amount: u64, validators: u8) -> Self {
+        Self { min_slash_amount, validators }
+    }
+}
+
+/// Default validator thresholds: 2 below 1,000 HYPER, 3 up to 10,000 HYPER, 5 above
+pub const DEFAULT_VALIDATOR_TIERS: [ValidatorTier; VALIDATOR_TIER_COUNT] = [
+    ValidatorTier::new(0, 2),
+    ValidatorTier::new(1_000 * 10u64.pow(HYPER_DECIMALS as u32), MIN_EVIDENCE_VALIDATORS),
+    ValidatorTier::new(10_000 * 10u64.pow(HYPER_DECIMALS as u32), 5),
+];
+
+/// Number of FraudType variants (size of the slash split table)
+pub const FRAUD_TYPE_COUNT: usize = 5;
+
+/// Where a slash goes, in basis points (must sum to 10000)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Debug)]
+pub struct SlashSplit {
+    /// Share compensating the defrauded client (the reporter)
+    pub client_bps: u16,
+
+    /// Share sent to the protocol treasury
+    pub treasury_bps: u16,
+
+    /// Share distributed to stakers through the Rewards Program
+    pub rewards_bps: u16,
+}
+
+impl SlashSplit {
+    pub const LEN: usize = 2 + 2 + 2;
+
+    pub const fn new(client_bps: u16, treasury_bps: u16, rewards_bps: u16) -> Self {
+        Self { client_bps, treasury_bps, rewards_bps }
+    }
+
+    /// Check the shares add up to 100%
+    pub fn is_valid(&self) -> bool {
+        self.client_bps as u32 + self.treasury_bps as u32 + self.rewards_bps as u32 == 10000
+    }
+
+    /// Split an amount into (client, treasury, rewards)
+    /// Rounding dust goes to the treasury
+    pub fn distribute(&self, amount: u64) -> (u64, u64, u64) {
+        let client = (amount as u128 * self.client_bps as u128 / 10000) as u64;
+        let rewards = (amount as u128 * self.rewards_bps as u128 / 10000) as u64;
+        let treasury = amount - client - rewards;
+        (client, treasury, rewards)
+    }
+}
+
+/// Default slash routing, indexed by FraudType
+pub const DEFAULT_SLASH_SPLITS: [SlashSplit; FRAUD_TYPE_COUNT] = [
+    SlashSplit::new(5000, 2500, 2500), // InvalidResults: compensate the client
+    SlashSplit::new(0, 10000, 0),      // ProlongedDowntime
+    SlashSplit::new(3000, 7000, 0),    // RepeatedFailures
+    SlashSplit::new(5000, 5000, 0),    // DoubleSpend
+    SlashSplit::new(0, 10000, 0),      // Other
+];
+
+/// Slashing configuration - governance-controlled parameters
+#[account]
+pub struct SlashingConfig {
+    /// Maximum slash in basis points of the node's stake
+    pub max_slash_percentage: u16,
+
+    /// Appeal window after a report is filed (seconds)
+    pub appeal_period: i64,
+
+    /// Validators required to confirm a report when no validator tier applies
+    pub min_evidence_validators: u8,
+
+    /// Validators required by slash size (ascending min_slash_amount)
+    pub validator_tiers: [ValidatorTier; VALIDATOR_TIER_COUNT],
+
+    /// Authority allowed to dismiss baseless reports (besides governance)
+    pub resolver: Pubkey,
+
+    /// Slash destination table, indexed by FraudType
+    pub slash_splits: [SlashSplit; FRAUD_TYPE_COUNT],
+
+    /// How long validators can vote on an appeal (seconds)
+    pub appeal_voting_period: i64,
+
+    /// Votes needed before an appeal can overturn a slash
+    pub appeal_quorum: u8,
+
+    /// xHYPER a reporter must have staked to report without a bond (0 = anyone)
+    pub min_reporter_xhyper: u128,
+
+    /// Bond (lamports) an under-staked reporter posts instead (0 = no bond option)
+    pub reporter_bond: u64,
+
+    /// xHYPER a validator must have staked to confirm reports
+    pub min_validator_xhyper: u128,
+
+    /// Share of the reporter's stake slashed when an appeal overturns its report (bps)
+    pub reporter_penalty_bps: u16,
+
+    /// HYPER (base units) of restitution per SOL a job's client paid
+    pub hyper_per_sol: u64,
+
+    /// HYPER token account receiving the treasury share of slashes
+    pub treasury: Pubkey,
+
+    /// PDA bump
+    pub bump: u8,
+}
+
+impl SlashingConfig {
+    pub const LEN: usize = 8 + 2 + 8 + 1 + ValidatorTier::LEN * VALIDATOR_TIER_COUNT + 32
+        + SlashSplit::LEN * FRAUD_TYPE_COUNT + 8 + 1 + 16 + 8 + 16 + 2 + 8 + 32 + 1;
+
+    /// Check `payer` is the program's upgrade authority (initialize_config)
+    pub fn is_upgrade_authority(program_data: &ProgramData, payer: &Pubkey) -> bool {
+        program_data.upgrade_authority_address == Some(*payer)
+    }
+
+    /// Governance authority PDA allowed to update the config
+    pub fn governance_authority() -> Pubkey {
+        Pubkey::find_program_address(&[GOVERNANCE_AUTHORITY_SEED], &hypernode_governance::ID).0
+    }
+
+    /// Check if a signer is the governance authority PDA
+    pub fn is_governance_authority(key: &Pubkey) -> bool {
+        *key == Self::governance_authority()
+    }
+
+    /// Check the executed proposal authorizes this update
+    /// It must be a ParameterUpdate aimed at this program and config, carrying
+    /// exactly this instruction's data, so one passed proposal can't be
+    /// replayed for some other change
+    pub fn verify_governance_update(proposal: &Proposal, config: &Pubkey, instruction_data: &[u8]) -> Result<()> {
+        require!(
+            proposal.authorizes(&crate::ID, config, instruction_data),
+            SlashingError::Unauthorized
+        );
+        Ok(())
+    }
+
+    /// Check if a signer may resolve (dismiss) reports
+    pub fn is_resolver(&self, key: &Pubkey) -> bool {
+        *key == self.resolver || Self::is_governance_authority(key)
+    }
+
+    /// Slash routing for a fraud type
+    pub fn slash_split(&self, fraud_type: &FraudType) -> SlashSplit {
+        self.slash_splits[fraud_type.index()]
+    }
+
+    /// Validators required to confirm a slash of `slash_amount`
+    /// The largest enabled tier the amount reaches, else min_evidence_validators
+    pub fn required_validators(&self, slash_amount: u64) -> u8 {
+        self.validator_tiers
+            .iter()
+            .rev()
+            .find(|tier| tier.validators > 0 && slash_amount >= tier.min_slash_amount)
+            .map_or(self.min_evidence_validators, |tier| tier.validators)
+    }
+
+    /// Validate a validator tier table
+    /// Enabled tiers need 1..=MAX_REPORT_VALIDATORS validators and strictly
+    /// ascending min_slash_amount
+    pub fn validate_tiers(tiers: &[ValidatorTier]) -> bool {
+        let enabled: Vec<&ValidatorTier> = tiers.iter().filter(|tier| tier.validators > 0).collect();
+        enabled.iter().all(|tier| tier.validators <= MAX_REPORT_VALIDATORS)
+            && enabled.windows(2).all(|pair| pair[0].min_slash_amount < pair[1].min_slash_amount)
+    }
+
+    /// Validate appeal voting parameters
+    /// A positive voting period and 1..=MAX_REPORT_VALIDATORS votes
+    pub fn validate_appeal_voting(appeal_voting_period: i64, appeal_quorum: u8) -> bool {
+        appeal_voting_period > 0
+            && appeal_quorum > 0
+            && appeal_quorum <= MAX_REPORT_VALIDATORS
+    }
+
+    /// Validate the reporter penalty (at most MAX_REPORTER_PENALTY_BPS)
+    pub fn validate_reporter_penalty(reporter_penalty_bps: u16) -> bool {
+        reporter_penalty_bps <= MAX_REPORTER_PENALTY_BPS
+    }
+
+    /// Stake slashed from a reporter whose report an appeal overturned
+    pub fn reporter_penalty(&self, reporter_stake: u64) -> u64 {
+        (reporter_stake as u128 * self.reporter_penalty_bps as u128 / 10000) as u64
+    }
+
+    /// Convert a job price (lamports) to HYPER base units, for restitution caps
+    pub fn lamports_to_hyper(&self, lamports: u64) -> u64 {
+        (lamports as u128 * self.hyper_per_sol as u128 / 1_000_000_000).min(u64::MAX as u128) as u64
+    }
+
+    /// Check a validator's stake is large enough to confirm reports or vote on appeals
+    /// Keeps dust stakes from adding confirmations or appeal votes
+    pub fn is_eligible_validator(&self, xhyper: u128) -> bool {
+        xhyper >= self.min_validator_xhyper
+    }
+
+    /// Validate reporter requirements (bond at most MAX_REPORTER_BOND)
+    pub fn validate_reporter_requirements(reporter_bond: u64) -> bool {
+        reporter_bond <= MAX_REPORTER_BOND
+    }
+
+    /// Bond a reporter with `reporter_xhyper` staked must post
+    /// Nothing once they hold min_reporter_xhyper; otherwise reporter_bond,
+    /// if they chose to post it and the config allows bonds
+    pub fn reporter_bond_due(&self, reporter_xhyper: u128, post_bond: bool) -> Result<u64> {
+        if reporter_xhyper >= self.min_reporter_xhyper {
+            return Ok(0);
+        }
+        require!(
+            post_bond && self.reporter_bond > 0,
+            SlashingError::ReporterStakeTooLow
+        );
+        Ok(self.reporter_bond)
+    }
+
+    /// Validate parameter ranges
+    pub fn validate(
+        max_slash_percentage: u16,
+        appeal_period: i64,
+        min_evidence_validators: u8,
+    ) -> bool {
+        max_slash_percentage > 0
+            && max_slash_percentage <= 10000
+            && appeal_period >= 0
+            && min_evidence_validators > 0
+            && min_evidence_validators <= MAX_REPORT_VALIDATORS
+    }
+
+    /// Apply a governance update of the core parameters
+    /// The signer must be the governance authority PDA, which only the
+    /// governance program signs for (when executing a passed ParameterUpdate)
+    pub fn apply_update(
+        &mut self,
+        signer: &Pubkey,
+        max_slash_percentage: u16,
+        appeal_period: i64,
+        min_evidence_validators: u8,
+    ) -> Result<()> {
+        require!(Self::is_governance_authority(signer), SlashingError::Unauthorized);
+        require!(
+            Self::validate(max_slash_percentage, appeal_period, min_evidence_validators),
+            SlashingError::InvalidConfig
+        );
+
+        self.max_slash_percentage = max_slash_percentage;
+        self.appeal_period = appeal_period;
+        self.min_evidence_validators = min_evidence_validators;
+        Ok(())
+    }
+}
+
 /// Fraud report - tracks reported malicious behavior
 #[account]
 pub struct FraudReport {
@@ -32,28 +338,294 @@ pub struct FraudReport {
     /// Validators who confirmed (max 10)
     pub validators: Vec<Pubkey>,
 
+    /// Slash amount proposed by each validator (parallel to `validators`)
+    pub proposed_amounts: Vec<u64>,
+
     /// Status
     pub status: ReportStatus,
 
-    /// Proposed slash amount
+    /// Consensus slash amount (median of validator proposals)
     pub slash_amount: u64,
 
+    /// End of appeal window (set from SlashingConfig at report time)
+    pub appeal_ends: i64,
+
+    /// On-chain account cited as evidence (failed job or node registration)
+    pub evidence_account: Pubkey,
+
+    /// Client who paid for the cited job (None for node-registration evidence)
+    pub affected_client: Option<Pubkey>,
+
+    /// What the affected client paid for the job, converted to HYPER at
+    /// hyper_per_sol when reported (caps their restitution)
+    pub client_loss: u64,
+
+    /// End of appeal voting (0 until the node appeals)
+    pub appeal_vote_ends: i64,
+
+    /// Validators who voted on the appeal (max 10)
+    pub appeal_voters: Vec<Pubkey>,
+
+    /// Appeal votes to uphold the slash
+    pub uphold_votes: u8,
+
+    /// Appeal votes to overturn the slash
+    pub overturn_votes: u8,
+
+    /// Bond posted by an under-staked reporter (lamports held on top of rent)
+    /// Refunded with the rent unless the report is found frivolous
+    pub bond: u64,
+
     /// PDA bump
     pub bump: u8,
 }
 
 impl FraudReport {
-    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 8 + 1 + (4 + 32 * 10) + 1 + 8 + 1;
+    pub const LEN: usize = 8 + 32 + 32 + 32 + 1 + 8 + 1 + (4 + 32 * 10) + (4 + 8 * 10) + 1 + 8 + 8 + 32 + (1 + 32) + 8 + 8
+        + (4 + 32 * 10) + 1 + 1 + 8 + 1;
 
     /// Check if appeal period has passed
     pub fn can_execute_slash(&self, current_time: i64) -> bool {
         self.status == ReportStatus::Confirmed
-            && current_time >= self.time_reported + APPEAL_PERIOD
+            && current_time >= self.appeal_ends
+    }
+
+    /// Check if enough validators confirmed for the proposed slash
+    /// Larger slashes need more confirmations (SlashingConfig::validator_tiers)
+    pub fn is_confirmed(&self, config: &SlashingConfig) -> bool {
+        self.validator_count >= config.required_validators(self.slash_amount)
+    }
+
+    /// Record a validator's confirmation and proposed slash amount
+    /// The report's slash_amount becomes the median proposal and the report is
+    /// Confirmed once enough validators have weighed in
+    pub fn add_confirmation(
+        &mut self,
+        validator: Pubkey,
+        proposed_amount: u64,
+        config: &SlashingConfig,
+    ) -> Result<()> {
+        require!(
+            matches!(self.status, ReportStatus::Pending | ReportStatus::Confirmed),
+            SlashingError::ReportNotActive
+        );
+        require!(
+            validator != self.node && validator != self.reporter,
+            SlashingError::Unauthorized
+        );
+        require!(
+            !self.validators.contains(&validator),
+            SlashingError::ValidatorAlreadyConfirmed
+        );
+        require!(
+            self.validators.len() < MAX_REPORT_VALIDATORS as usize,
+            SlashingError::MaxValidatorsReached
+        );
+
+        self.validators.push(validator);
+        self.proposed_amounts.push(proposed_amount);
+        self.validator_count = self.validators.len() as u8;
+        self.slash_amount = self.median_proposed_amount();
+
+        // A higher median can raise the threshold back above the confirmations so far
+        self.status = if self.is_confirmed(config) {
+            ReportStatus::Confirmed
+        } else {
+            ReportStatus::Pending
+        };
+
+        Ok(())
+    }
+
+    /// Median of the validators' proposals (mean of the middle two for an even count)
+    pub fn median_proposed_amount(&self) -> u64 {
+        let mut amounts = self.proposed_amounts.clone();
+        amounts.sort_unstable();
+
+        let len = amounts.len();
+        if len == 0 {
+            return 0;
+        }
+        if len % 2 == 1 {
+            amounts[len / 2]
+        } else {
+            ((amounts[len / 2 - 1] as u128 + amounts[len / 2] as u128) / 2) as u64
+        }
+    }
+
+    /// Amount slash_node takes: the consensus amount capped at max_slash_percentage of `stake`
+    pub fn capped_slash_amount(&self, stake: u64, config: &SlashingConfig) -> u64 {
+        let max_slash = (stake as u128 * config.max_slash_percentage as u128 / 10000) as u64;
+        self.slash_amount.min(max_slash)
+    }
+
+    /// Who receives the client share of a slash
+    /// The client of the cited job when there is one, otherwise the reporter
+    pub fn restitution_recipient(&self) -> Pubkey {
+        self.affected_client.unwrap_or(self.reporter)
+    }
+
+    /// Split the client share of a slash into (restitution, excess)
+    /// Restitution to a job's client is capped at what they paid; the excess
+    /// goes to the treasury instead
+    pub fn restitution(&self, client_share: u64) -> (u64, u64) {
+        if self.affected_client.is_none() {
+            return (client_share, 0);
+        }
+        let restitution = client_share.min(self.client_loss);
+        (restitution, client_share - restitution)
+    }
+
+    /// Node contests a confirmed slash before the appeal window closes
+    /// Confirmed -> Appealed; validators then vote for appeal_voting_period.
+    /// A report can only be appealed once
+    pub fn appeal(&mut self, node: &Pubkey, current_time: i64, config: &SlashingConfig) -> Result<()> {
+        require!(*node == self.node, SlashingError::Unauthorized);
+        require!(self.status == ReportStatus::Confirmed, SlashingError::ReportNotActive);
+        require!(
+            self.appeal_vote_ends == 0 && current_time < self.appeal_ends,
+            SlashingError::AppealWindowClosed
+        );
+
+        self.status = ReportStatus::Appealed;
+        self.appeal_vote_ends = current_time.saturating_add(config.appeal_voting_period);
+        Ok(())
+    }
+
+    /// Record a validator's appeal vote
+    /// The node, the reporter and the validators who confirmed the report
+    /// can't vote, so the appeal is reviewed by someone else
+    pub fn vote_appeal(&mut self, validator: Pubkey, uphold: bool, current_time: i64) -> Result<()> {
+        require!(self.status == ReportStatus::Appealed, SlashingError::ReportNotActive);
+        require!(current_time < self.appeal_vote_ends, SlashingError::AppealWindowClosed);
+        require!(
+            validator != self.node
+                && validator != self.reporter
+                && !self.validators.contains(&validator),
+            SlashingError::Unauthorized
+        );
+        require!(
+            !self.appeal_voters.contains(&validator),
+            SlashingError::ValidatorAlreadyConfirmed
+        );
+        require!(
+            self.appeal_voters.len() < MAX_REPORT_VALIDATORS as usize,
+            SlashingError::MaxValidatorsReached
+        );
+
+        self.appeal_voters.push(validator);
+        if uphold {
+            self.uphold_votes += 1;
+        } else {
+            self.overturn_votes += 1;
+        }
+        Ok(())
+    }
+
+    /// Whether the votes overturn the slash
+    /// Needs the quorum and a strict majority to overturn; otherwise the
+    /// slash stands
+    pub fn is_overturned(&self, config: &SlashingConfig) -> bool {
+        self.appeal_voters.len() >= config.appeal_quorum as usize
+            && self.overturn_votes > self.uphold_votes
+    }
+
+    /// Close appeal voting once its window has ended
+    /// Upheld: back to Confirmed, so slash_node can execute after appeal_ends.
+    /// Overturned: Dismissed. Returns whether the slash was overturned
+    pub fn resolve_appeal(&mut self, current_time: i64, config: &SlashingConfig) -> Result<bool> {
+        require!(self.status == ReportStatus::Appealed, SlashingError::ReportNotActive);
+        require!(current_time >= self.appeal_vote_ends, SlashingError::AppealVotingActive);
+
+        let overturned = self.is_overturned(config);
+        self.status = if overturned {
+            ReportStatus::Dismissed
+        } else {
+            ReportStatus::Confirmed
+        };
+        Ok(overturned)
+    }
+
+    /// Dismiss the report (it is closed afterwards)
+    /// Returns the bond a frivolous report forfeits; otherwise none, and the
+    /// bond goes back to the reporter with the rent
+    pub fn dismiss(&mut self, frivolous: bool) -> Result<u64> {
+        require!(self.can_dismiss(), SlashingError::ReportNotActive);
+        self.status = ReportStatus::Dismissed;
+        Ok(if frivolous { self.bond } else { 0 })
+    }
+
+    /// Release the bond of a report that held up (slash executed)
+    /// Returns the amount to refund to the reporter
+    pub fn release_bond(&mut self) -> u64 {
+        std::mem::take(&mut self.bond)
+    }
+
+    /// Check if report can still be dismissed (not executed or dismissed)
+    pub fn can_dismiss(&self) -> bool {
+        matches!(
+            self.status,
+            ReportStatus::Pending | ReportStatus::Confirmed | ReportStatus::Appealed
+        )
+    }
+}
+
+/// Verifiable on-chain fact a fraud report must cite
+pub enum ReportEvidence {
+    /// A Markets Program job assigned to the node
+    /// Timed out, or its result reversed on dispute (invalid results)
+    FailedJob { node: Option<Pubkey>, state: JobState, reversed: bool },
+
+    /// The node's registration in the Nodes Program
+    StaleHeartbeat { authority: Pubkey, last_heartbeat: i64 },
+}
+
+impl From<&JobAccount> for ReportEvidence {
+    fn from(job: &JobAccount) -> Self {
+        Self::FailedJob { node: job.node, state: job.state.clone(), reversed: job.dispute_reversed }
     }
+}
 
-    /// Check if enough validators confirmed
-    pub fn is_confirmed(&self) -> bool {
-        self.validator_count >= MIN_EVIDENCE_VALIDATORS
+impl From<&Node> for ReportEvidence {
+    fn from(node: &Node) -> Self {
+        Self::StaleHeartbeat { authority: node.authority, last_heartbeat: node.last_heartbeat }
+    }
+}
+
+impl ReportEvidence {
+    /// Check the evidence is about `node` and actually shows misbehavior
+    /// Returns the fraud type it supports
+    pub fn verify(&self, node: &Pubkey, current_time: i64) -> Result<FraudType> {
+        match self {
+            Self::FailedJob { node: job_node, state, reversed } => {
+                require!(*job_node == Some(*node), SlashingError::EvidenceMismatch);
+                match state {
+                    JobState::TimedOut => Ok(FraudType::RepeatedFailures),
+                    // Only a dispute the market authority resolved against the node;
+                    // an open dispute is just the client's claim
+                    JobState::Completed if *reversed => Ok(FraudType::InvalidResults),
+                    _ => Err(SlashingError::EvidenceNotIncriminating.into()),
+                }
+            }
+            Self::StaleHeartbeat { authority, last_heartbeat } => {
+                require!(authority == node, SlashingError::EvidenceMismatch);
+                require!(
+                    current_time.saturating_sub(*last_heartbeat) > DOWNTIME_REPORT_THRESHOLD,
+                    SlashingError::EvidenceNotIncriminating
+                );
+                Ok(FraudType::ProlongedDowntime)
+            }
+        }
+    }
+
+    /// Seed telling apart reports on the same evidence account
+    /// Reports are keyed on (evidence account, this), so a job can be reported
+    /// once and a registration once per heartbeat it went stale at
+    pub fn report_nonce(&self) -> [u8; 8] {
+        match self {
+            Self::FailedJob { .. } => [0u8; 8],
+            Self::StaleHeartbeat { last_heartbeat, .. } => last_heartbeat.to_le_bytes(),
+        }
     }
 }
 
@@ -102,6 +674,19 @@ pub enum FraudType {
     Other,
 }
 
+impl FraudType {
+    /// Position in SlashingConfig::slash_splits
+    pub fn index(&self) -> usize {
+        match self {
+            FraudType::InvalidResults => 0,
+            FraudType::ProlongedDowntime => 1,
+            FraudType::RepeatedFailures => 2,
+            FraudType::DoubleSpend => 3,
+            FraudType::Other => 4,
+        }
+    }
+}
+
 /// Report status
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq)]
 pub enum ReportStatus {
@@ -120,3 +705,646 @@ pub enum ReportStatus {
     /// Dismissed (insufficient evidence)
     Dismissed,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::InstructionData;
+    use hypernode_governance::state::{ProposalStatus, ProposalType};
+
+    fn default_config() -> SlashingConfig {
+        SlashingConfig {
+            max_slash_percentage: MAX_SLASH_PERCENTAGE,
+            appeal_period: APPEAL_PERIOD,
+            min_evidence_validators: MIN_EVIDENCE_VALIDATORS,
+            validator_tiers: DEFAULT_VALIDATOR_TIERS,
+            resolver: Pubkey::new_unique(),
+            slash_splits: DEFAULT_SLASH_SPLITS,
+            appeal_voting_period: DEFAULT_APPEAL_VOTING_PERIOD,
+            appeal_quorum: DEFAULT_APPEAL_QUORUM,
+            min_reporter_xhyper: DEFAULT_MIN_REPORTER_XHYPER,
+            reporter_bond: DEFAULT_REPORTER_BOND,
+            min_validator_xhyper: DEFAULT_MIN_VALIDATOR_XHYPER,
+            reporter_penalty_bps: DEFAULT_REPORTER_PENALTY_BPS,
+            hyper_per_sol: DEFAULT_HYPER_PER_SOL,
+            treasury: Pubkey::new_unique(),
+            bump: 0,
+        }
+    }
+
+    fn report_with_status(status: ReportStatus) -> FraudReport {
+        FraudReport {
+            node: Pubkey::new_unique(),
+            reporter: Pubkey::new_unique(),
+            evidence_cid: [1u8; 32],
+            fraud_type: FraudType::InvalidResults,
+            time_reported: 0,
+            validator_count: 0,
+            validators: Vec::new(),
+            proposed_amounts: Vec::new(),
+            status,
+            slash_amount: 0,
+            appeal_ends: APPEAL_PERIOD,
+            evidence_account: Pubkey::new_unique(),
+            affected_client: None,
+            client_loss: 0,
+            appeal_vote_ends: 0,
+            appeal_voters: Vec::new(),
+            uphold_votes: 0,
+            overturn_votes: 0,
+            bond: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_only_upgrade_authority_initializes_config() {
+        let upgrade_authority = Pubkey::new_unique();
+        let program_data = ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(upgrade_authority),
+        };
+
+        // Deployer initializes; anyone racing it to become resolver is rejected
+        assert!(SlashingConfig::is_upgrade_authority(&program_data, &upgrade_authority));
+        assert!(!SlashingConfig::is_upgrade_authority(&program_data, &Pubkey::new_unique()));
+
+        // Immutable program: nobody can initialize
+        let frozen = ProgramData { slot: 0, upgrade_authority_address: None };
+        assert!(!SlashingConfig::is_upgrade_authority(&frozen, &upgrade_authority));
+    }
+
+    #[test]
+    fn test_governance_update_changes_appeal_period() {
+        let mut config = default_config();
+        let governance = SlashingConfig::governance_authority();
+
+        // Anyone but the governance authority is rejected, leaving the config as is
+        assert_eq!(
+            config.apply_update(&Pubkey::new_unique(), MAX_SLASH_PERCENTAGE, 86400, MIN_EVIDENCE_VALIDATORS)
+                .unwrap_err(),
+            SlashingError::Unauthorized.into()
+        );
+        assert_eq!(config.appeal_period, APPEAL_PERIOD);
+
+        // Governance shortens the appeal period to 1 day
+        config.apply_update(&governance, MAX_SLASH_PERCENTAGE, 86400, MIN_EVIDENCE_VALIDATORS).unwrap();
+        assert_eq!(config.appeal_period, 86400);
+
+        // Out-of-range parameters are rejected even for governance
+        assert_eq!(
+            config.apply_update(&governance, 10001, APPEAL_PERIOD, MIN_EVIDENCE_VALIDATORS).unwrap_err(),
+            SlashingError::InvalidConfig.into()
+        );
+        assert_eq!(config.max_slash_percentage, MAX_SLASH_PERCENTAGE);
+    }
+
+    fn executed_update(config: Pubkey, execution_data: Vec<u8>) -> Proposal {
+        Proposal {
+            id: 1,
+            proposer: Pubkey::new_unique(),
+            title: "Update slashing".to_string(),
+            description_cid: [0u8; 32],
+            proposal_type: ProposalType::ParameterUpdate,
+            execution_data,
+            time_created: 0,
+            time_voting_ends: 0,
+            time_executable: 0,
+            total_voting_power: 100,
+            votes_for: 60,
+            votes_against: 0,
+            voter_count: 1,
+            status: ProposalStatus::Executed,
+            deposit: 0,
+            target_program: crate::ID,
+            target_accounts: vec![config, SlashingConfig::governance_authority()],
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn test_governance_update_bound_to_proposal() {
+        let config = Pubkey::new_unique();
+        let data = crate::instruction::UpdateConfig {
+            max_slash_percentage: MAX_SLASH_PERCENTAGE,
+            appeal_period: 86400,
+            min_evidence_validators: MIN_EVIDENCE_VALIDATORS,
+        }
+        .data();
+        let proposal = executed_update(config, data.clone());
+
+        // The update the proposal carries, against the config it named
+        assert!(SlashingConfig::verify_governance_update(&proposal, &config, &data).is_ok());
+
+        // Different parameters than voted on
+        let other = crate::instruction::UpdateConfig {
+            max_slash_percentage: 10000,
+            appeal_period: 0,
+            min_evidence_validators: 1,
+        }
+        .data();
+        assert!(SlashingConfig::verify_governance_update(&proposal, &config, &other).is_err());
+
+        // Some other config account
+        assert!(SlashingConfig::verify_governance_update(&proposal, &Pubkey::new_unique(), &data).is_err());
+
+        // A proposal aimed at another program, or not executed
+        let mut elsewhere = executed_update(config, data.clone());
+        elsewhere.target_program = Pubkey::new_unique();
+        assert!(SlashingConfig::verify_governance_update(&elsewhere, &config, &data).is_err());
+
+        let mut passed = executed_update(config, data.clone());
+        passed.status = ProposalStatus::Passed;
+        assert_eq!(
+            SlashingConfig::verify_governance_update(&passed, &config, &data).unwrap_err(),
+            SlashingError::Unauthorized.into()
+        );
+    }
+
+    #[test]
+    fn test_dust_stake_cannot_validate() {
+        let mut config = default_config();
+        let minimum = config.min_validator_xhyper;
+
+        assert!(config.is_eligible_validator(minimum));
+        assert!(!config.is_eligible_validator(minimum - 1));
+        assert!(!config.is_eligible_validator(1));
+
+        // Governance can lower the bar
+        config.min_validator_xhyper = 0;
+        assert!(config.is_eligible_validator(0));
+    }
+
+    #[test]
+    fn test_report_keyed_on_cited_evidence() {
+        let node = Pubkey::new_unique();
+
+        // One report per cited job, whatever evidence CID the reporter picks
+        let failed = ReportEvidence::FailedJob { node: Some(node), state: JobState::TimedOut, reversed: false };
+        let disputed = ReportEvidence::FailedJob { node: Some(node), state: JobState::Completed, reversed: true };
+        assert_eq!(failed.report_nonce(), disputed.report_nonce());
+
+        // One downtime report per outage: a new stale heartbeat is a new report
+        let outage = ReportEvidence::StaleHeartbeat { authority: node, last_heartbeat: 100 };
+        let later_outage = ReportEvidence::StaleHeartbeat { authority: node, last_heartbeat: 500_000 };
+        assert_eq!(outage.report_nonce(), 100i64.to_le_bytes());
+        assert_ne!(outage.report_nonce(), later_outage.report_nonce());
+    }
+
+    #[test]
+    fn test_unauthorized_update_rejected() {
+        let attacker = Pubkey::new_unique();
+        assert!(!SlashingConfig::is_governance_authority(&attacker));
+
+        // Out-of-range parameters are rejected even for governance
+        assert!(!SlashingConfig::validate(10001, APPEAL_PERIOD, MIN_EVIDENCE_VALIDATORS));
+        assert!(!SlashingConfig::validate(MAX_SLASH_PERCENTAGE, -1, MIN_EVIDENCE_VALIDATORS));
+        assert!(!SlashingConfig::validate(MAX_SLASH_PERCENTAGE, APPEAL_PERIOD, 0));
+        assert!(!SlashingConfig::validate(MAX_SLASH_PERCENTAGE, APPEAL_PERIOD, MAX_REPORT_VALIDATORS + 1));
+    }
+
+    #[test]
+    fn test_dismiss_pending_report() {
+        let config = default_config();
+        let report = report_with_status(ReportStatus::Pending);
+
+        assert!(config.is_resolver(&config.resolver));
+        assert!(config.is_resolver(&SlashingConfig::governance_authority()));
+        assert!(!config.is_resolver(&report.reporter));
+        assert!(report.can_dismiss());
+        assert!(report_with_status(ReportStatus::Appealed).can_dismiss());
+    }
+
+    #[test]
+    fn test_dismiss_executed_report_rejected() {
+        assert!(!report_with_status(ReportStatus::Executed).can_dismiss());
+        assert!(!report_with_status(ReportStatus::Dismissed).can_dismiss());
+    }
+
+    #[test]
+    fn test_slash_distribution_depends_on_fraud_type() {
+        let config = default_config();
+        assert!(config.slash_splits.iter().all(|split| split.is_valid()));
+
+        // InvalidResults partly compensates the client
+        let (client, treasury, rewards) = config
+            .slash_split(&FraudType::InvalidResults)
+            .distribute(1_001);
+        assert_eq!((client, treasury, rewards), (500, 251, 250));
+
+        // ProlongedDowntime goes entirely to treasury
+        let (client, treasury, rewards) = config
+            .slash_split(&FraudType::ProlongedDowntime)
+            .distribute(1_001);
+        assert_eq!((client, treasury, rewards), (0, 1_001, 0));
+    }
+
+    #[test]
+    fn test_slash_split_must_sum_to_10000() {
+        assert!(SlashSplit::new(5000, 5000, 0).is_valid());
+        assert!(!SlashSplit::new(5000, 4000, 0).is_valid());
+        assert!(!SlashSplit::new(10000, 10000, 0).is_valid());
+    }
+
+    #[test]
+    fn test_report_citing_failed_job_accepted() {
+        let node = Pubkey::new_unique();
+        let evidence = ReportEvidence::FailedJob {
+            node: Some(node),
+            state: JobState::TimedOut,
+            reversed: false,
+        };
+
+        assert_eq!(evidence.verify(&node, 0).unwrap(), FraudType::RepeatedFailures);
+
+        // Same job can't be pinned on another node
+        assert_eq!(
+            evidence.verify(&Pubkey::new_unique(), 0).unwrap_err(),
+            SlashingError::EvidenceMismatch.into()
+        );
+    }
+
+    #[test]
+    fn test_report_citing_healthy_node_rejected() {
+        let node = Pubkey::new_unique();
+        let now = 1_000_000;
+
+        // Completed job is not evidence of anything
+        let completed = ReportEvidence::FailedJob {
+            node: Some(node),
+            state: JobState::Completed,
+            reversed: false,
+        };
+        assert_eq!(
+            completed.verify(&node, now).unwrap_err(),
+            SlashingError::EvidenceNotIncriminating.into()
+        );
+
+        // Recent heartbeat: node is alive
+        let healthy = ReportEvidence::StaleHeartbeat { authority: node, last_heartbeat: now - 60 };
+        assert_eq!(
+            healthy.verify(&node, now).unwrap_err(),
+            SlashingError::EvidenceNotIncriminating.into()
+        );
+
+        // Heartbeat older than the threshold supports a downtime report
+        let stale = ReportEvidence::StaleHeartbeat {
+            authority: node,
+            last_heartbeat: now - DOWNTIME_REPORT_THRESHOLD - 1,
+        };
+        assert_eq!(stale.verify(&node, now).unwrap(), FraudType::ProlongedDowntime);
+    }
+
+    #[test]
+    fn test_invalid_results_restitution_to_job_client() {
+        let node = Pubkey::new_unique();
+        let client = Pubkey::new_unique();
+
+        // An open dispute is only the client's claim
+        let open_dispute = ReportEvidence::FailedJob {
+            node: Some(node),
+            state: JobState::Disputed,
+            reversed: false,
+        };
+        assert_eq!(
+            open_dispute.verify(&node, 0).unwrap_err(),
+            SlashingError::EvidenceNotIncriminating.into()
+        );
+
+        // A result the market authority reversed on dispute supports an InvalidResults report
+        let evidence = ReportEvidence::FailedJob {
+            node: Some(node),
+            state: JobState::Completed,
+            reversed: true,
+        };
+        assert_eq!(evidence.verify(&node, 0).unwrap(), FraudType::InvalidResults);
+
+        let mut report = report_with_status(ReportStatus::Confirmed);
+        report.node = node;
+        report.affected_client = Some(client);
+        assert_eq!(report.restitution_recipient(), client);
+
+        let (client_share, _, _) = default_config()
+            .slash_split(&FraudType::InvalidResults)
+            .distribute(10_000);
+        assert_eq!(client_share, 5_000);
+
+        // Loss above the client share: the whole share is restitution
+        report.client_loss = 8_000;
+        assert_eq!(report.restitution(client_share), (5_000, 0));
+
+        // Smaller loss: restitution matches the loss, the rest goes to treasury
+        report.client_loss = 1_200;
+        assert_eq!(report.restitution(client_share), (1_200, 3_800));
+
+        // No linked job: the reporter keeps the full client share
+        report.affected_client = None;
+        assert_eq!(report.restitution_recipient(), report.reporter);
+        assert_eq!(report.restitution(client_share), (client_share, 0));
+
+        // The job price (lamports) is converted to HYPER before it caps restitution
+        let config = default_config();
+        assert_eq!(config.lamports_to_hyper(1_000_000_000), DEFAULT_HYPER_PER_SOL);
+        assert_eq!(config.lamports_to_hyper(10_000_000), DEFAULT_HYPER_PER_SOL / 100);
+    }
+
+    #[test]
+    fn test_three_validator_proposals_yield_median_slash() {
+        // Flat threshold (no slash-size tiers): min_evidence_validators applies
+        let mut config = default_config();
+        config.validator_tiers = [ValidatorTier::new(0, 0); VALIDATOR_TIER_COUNT];
+        let mut report = report_with_status(ReportStatus::Pending);
+
+        report.add_confirmation(Pubkey::new_unique(), 900, &config).unwrap();
+        report.add_confirmation(Pubkey::new_unique(), 100, &config).unwrap();
+        assert!(report.status == ReportStatus::Pending);
+        assert_eq!(report.slash_amount, 500); // mean of the middle two
+
+        // Third validator confirms: median of {100, 300, 900}, outliers ignored
+        report.add_confirmation(Pubkey::new_unique(), 300, &config).unwrap();
+        assert_eq!(report.validator_count, 3);
+        assert!(report.status == ReportStatus::Confirmed);
+        assert_eq!(report.slash_amount, 300);
+
+        // Capped at max_slash_percentage (50%) of the stake
+        assert_eq!(report.capped_slash_amount(10_000, &config), 300);
+        assert_eq!(report.capped_slash_amount(400, &config), 200);
+    }
+
+    #[test]
+    fn test_confirmation_rules() {
+        let config = default_config();
+        let mut report = report_with_status(ReportStatus::Pending);
+        let validator = Pubkey::new_unique();
+
+        report.add_confirmation(validator, 100, &config).unwrap();
+        assert_eq!(
+            report.add_confirmation(validator, 200, &config).err(),
+            Some(SlashingError::ValidatorAlreadyConfirmed.into())
+        );
+
+        // The accused node and the reporter can't vote
+        let node = report.node;
+        let reporter = report.reporter;
+        assert!(report.add_confirmation(node, 0, &config).is_err());
+        assert!(report.add_confirmation(reporter, 10_000, &config).is_err());
+
+        let mut dismissed = report_with_status(ReportStatus::Dismissed);
+        assert_eq!(
+            dismissed.add_confirmation(validator, 100, &config).err(),
+            Some(SlashingError::ReportNotActive.into())
+        );
+    }
+
+    const HYPER: u64 = 1_000_000;
+
+    #[test]
+    fn test_large_slash_needs_five_confirmations() {
+        let config = default_config();
+        let mut report = report_with_status(ReportStatus::Pending);
+
+        for _ in 0..4 {
+            report.add_confirmation(Pubkey::new_unique(), 50_000 * HYPER, &config).unwrap();
+            assert!(!report.is_confirmed(&config));
+            assert!(report.status == ReportStatus::Pending);
+        }
+
+        report.add_confirmation(Pubkey::new_unique(), 50_000 * HYPER, &config).unwrap();
+        assert_eq!(config.required_validators(report.slash_amount), 5);
+        assert!(report.is_confirmed(&config));
+        assert!(report.status == ReportStatus::Confirmed);
+    }
+
+    #[test]
+    fn test_small_slash_needs_two_confirmations() {
+        let config = default_config();
+        let mut report = report_with_status(ReportStatus::Pending);
+
+        report.add_confirmation(Pubkey::new_unique(), 100 * HYPER, &config).unwrap();
+        assert!(report.status == ReportStatus::Pending);
+
+        report.add_confirmation(Pubkey::new_unique(), 100 * HYPER, &config).unwrap();
+        assert_eq!(config.required_validators(report.slash_amount), 2);
+        assert!(report.status == ReportStatus::Confirmed);
+
+        // Two confirmations aren't enough once the median is a large slash
+        let mut report = report_with_status(ReportStatus::Pending);
+        report.add_confirmation(Pubkey::new_unique(), 100 * HYPER, &config).unwrap();
+        report.add_confirmation(Pubkey::new_unique(), 40_000 * HYPER, &config).unwrap();
+        assert_eq!(config.required_validators(report.slash_amount), 5);
+        assert!(report.status == ReportStatus::Pending);
+    }
+
+    #[test]
+    fn test_validator_tiers_validated() {
+        assert!(SlashingConfig::validate_tiers(&DEFAULT_VALIDATOR_TIERS));
+
+        // Disabled tiers fall back to min_evidence_validators
+        let mut config = default_config();
+        config.validator_tiers = [ValidatorTier::new(0, 0); VALIDATOR_TIER_COUNT];
+        assert!(SlashingConfig::validate_tiers(&config.validator_tiers));
+        assert_eq!(config.required_validators(50_000 * HYPER), MIN_EVIDENCE_VALIDATORS);
+
+        // Out of order or above the per-report maximum
+        assert!(!SlashingConfig::validate_tiers(&[
+            ValidatorTier::new(10 * HYPER, 3),
+            ValidatorTier::new(HYPER, 2),
+        ]));
+        assert!(!SlashingConfig::validate_tiers(&[ValidatorTier::new(0, MAX_REPORT_VALIDATORS + 1)]));
+    }
+
+    /// Confirmed report (flat threshold of 3) appealed by its node at t=100
+    fn appealed_report(config: &SlashingConfig) -> FraudReport {
+        let mut report = report_with_status(ReportStatus::Pending);
+        for amount in [100, 200, 300] {
+            report.add_confirmation(Pubkey::new_unique(), amount, config).unwrap();
+        }
+        assert!(report.status == ReportStatus::Confirmed);
+
+        let node = report.node;
+        report.appeal(&node, 100, config).unwrap();
+        assert!(report.status == ReportStatus::Appealed);
+        assert_eq!(report.appeal_vote_ends, 100 + DEFAULT_APPEAL_VOTING_PERIOD);
+        report
+    }
+
+    #[test]
+    fn test_upheld_appeal_lets_slash_proceed() {
+        let mut config = default_config();
+        config.validator_tiers = [ValidatorTier::new(0, 0); VALIDATOR_TIER_COUNT];
+        let mut report = appealed_report(&config);
+
+        // No slash while the appeal is open
+        assert!(!report.can_execute_slash(report.appeal_ends));
+
+        report.vote_appeal(Pubkey::new_unique(), true, 200).unwrap();
+        report.vote_appeal(Pubkey::new_unique(), true, 300).unwrap();
+        report.vote_appeal(Pubkey::new_unique(), false, 400).unwrap();
+        assert_eq!((report.uphold_votes, report.overturn_votes), (2, 1));
+
+        // Voting must run its course
+        assert_eq!(
+            report.resolve_appeal(500, &config).unwrap_err(),
+            SlashingError::AppealVotingActive.into()
+        );
+
+        let vote_ends = report.appeal_vote_ends;
+        assert!(!report.resolve_appeal(vote_ends, &config).unwrap());
+        assert!(report.status == ReportStatus::Confirmed);
+        assert!(report.is_confirmed(&config));
+        assert!(report.can_execute_slash(report.appeal_ends.max(vote_ends)));
+
+        // Only one appeal per report
+        let node = report.node;
+        assert_eq!(
+            report.appeal(&node, vote_ends, &config).unwrap_err(),
+            SlashingError::AppealWindowClosed.into()
+        );
+    }
+
+    #[test]
+    fn test_overturned_appeal_dismisses_report() {
+        let mut config = default_config();
+        config.validator_tiers = [ValidatorTier::new(0, 0); VALIDATOR_TIER_COUNT];
+        let mut report = appealed_report(&config);
+
+        // Parties to the report can't vote, nor can anyone vote twice
+        let (node, reporter, confirmer) = (report.node, report.reporter, report.validators[0]);
+        for voter in [node, reporter, confirmer] {
+            assert_eq!(
+                report.vote_appeal(voter, false, 200).unwrap_err(),
+                SlashingError::Unauthorized.into()
+            );
+        }
+        let voter = Pubkey::new_unique();
+        report.vote_appeal(voter, false, 200).unwrap();
+        assert_eq!(
+            report.vote_appeal(voter, true, 200).unwrap_err(),
+            SlashingError::ValidatorAlreadyConfirmed.into()
+        );
+
+        // An overturn majority without quorum doesn't overturn
+        assert!(!report.is_overturned(&config));
+        report.vote_appeal(Pubkey::new_unique(), false, 300).unwrap();
+        report.vote_appeal(Pubkey::new_unique(), true, 400).unwrap();
+        assert!(report.is_overturned(&config));
+
+        // Late votes are rejected
+        let vote_ends = report.appeal_vote_ends;
+        assert_eq!(
+            report.vote_appeal(Pubkey::new_unique(), true, vote_ends).unwrap_err(),
+            SlashingError::AppealWindowClosed.into()
+        );
+
+        assert!(report.resolve_appeal(vote_ends, &config).unwrap());
+        assert!(report.status == ReportStatus::Dismissed);
+        assert!(!report.can_execute_slash(i64::MAX));
+    }
+
+    #[test]
+    fn test_appeal_rules() {
+        let config = default_config();
+        let mut report = report_with_status(ReportStatus::Pending);
+        let node = report.node;
+
+        // Nothing to appeal until confirmed, and only the node can appeal
+        assert_eq!(
+            report.appeal(&node, 0, &config).unwrap_err(),
+            SlashingError::ReportNotActive.into()
+        );
+        report.status = ReportStatus::Confirmed;
+        assert_eq!(
+            report.appeal(&report.reporter.clone(), 0, &config).unwrap_err(),
+            SlashingError::Unauthorized.into()
+        );
+
+        // Too late once the appeal window has closed
+        assert_eq!(
+            report.appeal(&node, report.appeal_ends, &config).unwrap_err(),
+            SlashingError::AppealWindowClosed.into()
+        );
+
+        assert!(SlashingConfig::validate_appeal_voting(DEFAULT_APPEAL_VOTING_PERIOD, DEFAULT_APPEAL_QUORUM));
+        assert!(!SlashingConfig::validate_appeal_voting(0, DEFAULT_APPEAL_QUORUM));
+        assert!(!SlashingConfig::validate_appeal_voting(DEFAULT_APPEAL_VOTING_PERIOD, 0));
+        assert!(!SlashingConfig::validate_appeal_voting(DEFAULT_APPEAL_VOTING_PERIOD, MAX_REPORT_VALIDATORS + 1));
+    }
+
+    #[test]
+    fn test_overturned_report_penalizes_reporter_stake() {
+        let mut config = default_config();
+        let stake = 1_000_000_000;
+
+        // 10% of the reporter's stake by default
+        assert_eq!(config.reporter_penalty(stake), stake / 10);
+        assert_eq!(config.reporter_penalty(0), 0);
+
+        assert!(SlashingConfig::validate_reporter_penalty(MAX_REPORTER_PENALTY_BPS));
+        assert!(!SlashingConfig::validate_reporter_penalty(MAX_REPORTER_PENALTY_BPS + 1));
+
+        config.reporter_penalty_bps = 0;
+        assert_eq!(config.reporter_penalty(stake), 0);
+    }
+
+    #[test]
+    fn test_under_staked_reporter_rejected() {
+        let mut config = default_config();
+        let minimum = config.min_reporter_xhyper;
+
+        // Below the minimum and no bond posted
+        assert_eq!(
+            config.reporter_bond_due(minimum - 1, false).unwrap_err(),
+            SlashingError::ReporterStakeTooLow.into()
+        );
+        assert_eq!(
+            config.reporter_bond_due(0, false).unwrap_err(),
+            SlashingError::ReporterStakeTooLow.into()
+        );
+
+        // Enough xHYPER staked: no bond needed either way
+        assert_eq!(config.reporter_bond_due(minimum, false).unwrap(), 0);
+        assert_eq!(config.reporter_bond_due(minimum, true).unwrap(), 0);
+
+        // Governance can turn the bond option off...
+        config.reporter_bond = 0;
+        assert_eq!(
+            config.reporter_bond_due(minimum - 1, true).unwrap_err(),
+            SlashingError::ReporterStakeTooLow.into()
+        );
+
+        // ...or drop the stake requirement
+        config.min_reporter_xhyper = 0;
+        assert_eq!(config.reporter_bond_due(0, false).unwrap(), 0);
+
+        assert!(SlashingConfig::validate_reporter_requirements(MAX_REPORTER_BOND));
+        assert!(!SlashingConfig::validate_reporter_requirements(MAX_REPORTER_BOND + 1));
+    }
+
+    #[test]
+    fn test_bonded_reporter_forfeits_bond_on_frivolous_report() {
+        let config = default_config();
+
+        // No stake: the reporter posts the bond with the report
+        let bond = config.reporter_bond_due(0, true).unwrap();
+        assert_eq!(bond, DEFAULT_REPORTER_BOND);
+        let mut report = report_with_status(ReportStatus::Pending);
+        report.bond = bond;
+
+        // Dismissed as frivolous: the bond is forfeited with the rent
+        let mut frivolous = report.clone();
+        assert_eq!(frivolous.dismiss(true).unwrap(), DEFAULT_REPORTER_BOND);
+        assert!(frivolous.status == ReportStatus::Dismissed);
+        assert_eq!(
+            frivolous.dismiss(true).unwrap_err(),
+            SlashingError::ReportNotActive.into()
+        );
+
+        // Dismissed in good faith: nothing forfeited, the bond goes back with the rent
+        let mut honest = report.clone();
+        assert_eq!(honest.dismiss(false).unwrap(), 0);
+
+        // Slash executed: the bond is refunded exactly once
+        assert_eq!(report.release_bond(), DEFAULT_REPORTER_BOND);
+        assert_eq!(report.release_bond(), 0);
+    }
+}
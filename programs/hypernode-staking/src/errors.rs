@@ -79,4 +79,31 @@ pub enum StakingError {
 
     #[msg("Rewards claim failed: Failed to claim pending rewards")]
     RewardsClaimFailed = 6025,
+
+    #[msg("Invalid beneficiary: Beneficiary must be a valid key other than the authority")]
+    InvalidBeneficiary = 6026,
+
+    #[msg("Transfer not pending: No stake transfer has been proposed to this owner")]
+    TransferNotPending = 6027,
+
+    #[msg("Invalid new owner: New owner must be a valid key other than the current authority")]
+    InvalidNewOwner = 6028,
+
+    #[msg("Invalid multiplier curve: Max multiplier must be between 1x and 10x")]
+    InvalidMultiplierCurve = 6029,
+
+    #[msg("Cooldown passed: Unstake can no longer be cancelled, withdraw instead")]
+    CooldownPassed = 6030,
+
+    #[msg("Invalid commission: Commission must be at most 10000 bps")]
+    InvalidCommission = 6031,
+
+    #[msg("Invalid operator: Operator must be a valid key other than the authority")]
+    InvalidOperator = 6032,
+
+    #[msg("Invalid slash payouts: Payouts exceed the slashed amount")]
+    InvalidSlashPayouts = 6033,
+
+    #[msg("Missing slash destination: Token accounts for a non-zero payout were not passed")]
+    MissingSlashDestination = 6034,
 }
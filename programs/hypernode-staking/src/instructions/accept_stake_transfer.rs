@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use crate::events::AuthorizationChanged;
+use hypernode_rewards::program::HypernodeRewards;
+use hypernode_rewards::{ReflectionAccount, cpi::accounts::RegisterStake as RewardsRegisterStake};
+use hypernode_rewards::cpi::accounts::UnregisterStake as RewardsUnregisterStake;
+
+/// Accept a proposed stake transfer
+/// Moves the position to the new owner's stake PDA and closes the old one,
+/// along with the previous owner's rewards registration
+#[derive(Accounts)]
+pub struct AcceptStakeTransfer<'info> {
+    /// Position being transferred (closed, rent back to the previous owner)
+    #[account(
+        mut,
+        seeds = [b"stake", previous_authority.key().as_ref()],
+        bump = previous_stake_account.bump,
+        constraint = previous_stake_account.pending_authority == Some(authority.key()) @ StakingError::TransferNotPending,
+        close = previous_authority
+    )]
+    pub previous_stake_account: Account<'info, StakeAccount>,
+
+    /// New owner's stake account
+    #[account(
+        init,
+        payer = authority,
+        space = StakeAccount::LEN,
+        seeds = [b"stake", authority.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Previous owner (receives rent refund)
+    /// CHECK: Must be the authority of the previous stake account
+    #[account(
+        mut,
+        address = previous_stake_account.authority @ StakingError::Unauthorized
+    )]
+    pub previous_authority: AccountInfo<'info>,
+
+    /// New owner accepting the transfer
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Reflection account from Rewards Program (optional)
+    #[account(mut)]
+    pub reflection_account: Option<Account<'info, ReflectionAccount>>,
+
+    /// Previous owner's rewards registration (closed, rent back to the previous owner)
+    /// Must be closed here if it exists: its xHYPER leaves with the position
+    /// CHECK: PDA is checked; the Rewards Program checks the rest
+    #[account(
+        mut,
+        seeds = [b"user_rewards", previous_authority.key().as_ref()],
+        bump,
+        seeds::program = hypernode_rewards::ID
+    )]
+    pub previous_user_rewards_account: UncheckedAccount<'info>,
+
+    /// New owner's rewards registration (optional, will be created)
+    /// CHECK: Initialized by the Rewards Program
+    #[account(mut)]
+    pub user_rewards_account: Option<AccountInfo<'info>>,
+
+    /// Rewards program (optional)
+    pub rewards_program: Option<Program<'info, HypernodeRewards>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<AcceptStakeTransfer>) -> Result<()> {
+    let clock = Clock::get()?;
+    let previous_authority = ctx.accounts.previous_authority.key();
+    let new_authority = ctx.accounts.authority.key();
+
+    // Carry the position over (amount, lock timing, xHYPER)
+    let transferred = ctx
+        .accounts
+        .previous_stake_account
+        .transferred_to(new_authority, ctx.bumps.stake_account)?;
+    ctx.accounts.stake_account.set_inner(transferred);
+
+    // Rewards follow the position: the previous owner earned on it until now
+    if !ctx.accounts.previous_user_rewards_account.data_is_empty() {
+        let (Some(reflection), Some(rewards_program)) =
+            (&ctx.accounts.reflection_account, &ctx.accounts.rewards_program)
+        else {
+            return err!(StakingError::AccountNotInitialized);
+        };
+
+        // The previous owner doesn't sign the accept; their stake PDA signs for them
+        let previous_stake_bump = ctx.accounts.previous_stake_account.bump;
+        let seeds: &[&[u8]] = &[b"stake", previous_authority.as_ref(), &[previous_stake_bump]];
+        let signer_seeds = &[seeds];
+        let cpi_accounts = RewardsUnregisterStake {
+            reflection_account: reflection.to_account_info(),
+            user_rewards_account: ctx.accounts.previous_user_rewards_account.to_account_info(),
+            authority: ctx.accounts.previous_authority.to_account_info(),
+            stake_account: Some(ctx.accounts.previous_stake_account.to_account_info()),
+        };
+        let cpi_ctx = CpiContext::new_with_signer(rewards_program.to_account_info(), cpi_accounts, signer_seeds);
+        hypernode_rewards::cpi::unregister_stake(cpi_ctx)?;
+
+        msg!("Previous owner's rewards registration closed");
+    }
+
+    // Re-register in Rewards Program under the new owner if provided
+    if let (Some(reflection), Some(user_rewards), Some(rewards_program)) = (
+        &ctx.accounts.reflection_account,
+        &ctx.accounts.user_rewards_account,
+        &ctx.accounts.rewards_program,
+    ) {
+        // Persist the new position: the Rewards Program reads it from the stake account
+        ctx.accounts.stake_account.exit(&crate::ID)?;
+
+        let cpi_accounts = RewardsRegisterStake {
+            reflection_account: reflection.to_account_info(),
+            user_rewards_account: user_rewards.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            stake_account: ctx.accounts.stake_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(rewards_program.to_account_info(), cpi_accounts);
+        hypernode_rewards::cpi::register_stake(cpi_ctx)?;
+
+        msg!("Re-registered in Rewards Program with xHYPER: {}", ctx.accounts.stake_account.xhyper);
+    }
+
+    emit!(AuthorizationChanged {
+        stake_account: ctx.accounts.stake_account.key(),
+        new_authority,
+        old_authority: previous_authority,
+        timestamp: clock.unix_timestamp,
+    });
+
+    msg!("Stake transfer accepted");
+    msg!("From: {}", previous_authority);
+    msg!("To: {}", new_authority);
+    msg!("Amount: {}", ctx.accounts.stake_account.amount);
+
+    Ok(())
+}
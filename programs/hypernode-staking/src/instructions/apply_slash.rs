@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+use crate::constants::MIN_STAKE_AMOUNT;
+use hypernode_rewards::program::HypernodeRewards;
+use hypernode_rewards::{ReflectionAccount, RewardsConfig};
+use hypernode_rewards::cpi::accounts::AddRewards;
+
+/// Reduce a node's stake by a slashed amount
+/// Only callable via CPI from the slashing program: slash_node, which routes
+/// the slashed tokens (`payouts`), and resolve_appeal, which penalizes the
+/// reporter of an overturned report. This keeps the position (and global
+/// stats) in step so a later withdraw returns the remainder.
+/// The vault's tokens can only leave under this program's vault_authority,
+/// so the payouts are made here; the slashing program checks the destinations
+#[derive(Accounts)]
+pub struct ApplySlash<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", stake_account.authority.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Slashing authority PDA (only the slashing program can sign for it)
+    pub slash_authority: Signer<'info>,
+
+    /// Staking vault (pays out the slashed tokens), required for any payout
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Option<Account<'info, TokenAccount>>,
+
+    /// Vault authority PDA
+    /// CHECK: PDA signer
+    #[account(
+        seeds = [b"vault_authority"],
+        bump
+    )]
+    pub vault_authority: Option<UncheckedAccount<'info>>,
+
+    /// Protocol treasury (treasury share)
+    #[account(mut)]
+    pub treasury: Option<Account<'info, TokenAccount>>,
+
+    /// Client (or reporter) token account (client share)
+    #[account(mut)]
+    pub client_token_account: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards Program reflection account (rewards share)
+    #[account(mut)]
+    pub reflection_account: Option<Account<'info, ReflectionAccount>>,
+
+    /// Rewards Program vault (rewards share)
+    #[account(mut)]
+    pub rewards_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards Program config (staker/treasury fee split)
+    pub rewards_config: Option<Account<'info, RewardsConfig>>,
+
+    /// Rewards Program treasury (treasury share of the rewards portion)
+    #[account(mut)]
+    pub rewards_treasury: Option<Account<'info, TokenAccount>>,
+
+    /// Rewards program
+    pub rewards_program: Option<Program<'info, HypernodeRewards>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+pub fn handler(ctx: Context<ApplySlash>, amount: u64, payouts: SlashPayouts) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let clock = Clock::get()?;
+
+    // Verify caller is the slashing program
+    require!(
+        StakeAccount::is_slash_authority(&ctx.accounts.slash_authority.key()),
+        StakingError::Unauthorized
+    );
+    require!(payouts.fits(amount), StakingError::InvalidSlashPayouts);
+
+    let (burned_xhyper, unbonded) = stake_account.apply_slash(amount, clock.unix_timestamp)?;
+
+    // Update global aggregates
+    ctx.accounts.global_stats.record_slash(amount, burned_xhyper, unbonded)?;
+
+    if payouts.total() != Some(0) {
+        pay_out(&ctx, &payouts)?;
+    }
+
+    msg!("Stake slashed");
+    msg!("Amount slashed: {}", amount);
+    msg!("Client: {} | Treasury: {} | Rewards: {}", payouts.client, payouts.treasury, payouts.rewards);
+    msg!("Remaining stake: {}", ctx.accounts.stake_account.amount);
+    msg!("xHYPER burned: {}", burned_xhyper);
+    if ctx.accounts.stake_account.amount < MIN_STAKE_AMOUNT {
+        msg!("Stake below minimum: unbonded, withdrawable now");
+    }
+
+    Ok(())
+}
+
+/// Move the slashed tokens out of the vault, signed by the vault authority
+fn pay_out(ctx: &Context<ApplySlash>, payouts: &SlashPayouts) -> Result<()> {
+    let (Some(vault), Some(vault_authority), Some(token_program)) = (
+        &ctx.accounts.vault,
+        &ctx.accounts.vault_authority,
+        &ctx.accounts.token_program,
+    ) else {
+        return Err(StakingError::MissingSlashDestination.into());
+    };
+
+    let vault_authority_bump = ctx.bumps.vault_authority.ok_or(StakingError::InvalidVault)?;
+    let seeds: &[&[u8]] = &[b"vault_authority", &[vault_authority_bump]];
+    let signer_seeds = &[seeds];
+
+    let token_program = token_program.to_account_info();
+    let vault = vault.to_account_info();
+    let vault_authority = vault_authority.to_account_info();
+
+    // Treasury share
+    if payouts.treasury > 0 {
+        let treasury = ctx
+            .accounts
+            .treasury
+            .as_ref()
+            .ok_or(StakingError::MissingSlashDestination)?;
+        let cpi_accounts = Transfer {
+            from: vault.clone(),
+            to: treasury.to_account_info(),
+            authority: vault_authority.clone(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds),
+            payouts.treasury,
+        )?;
+    }
+
+    // Client compensation share
+    if payouts.client > 0 {
+        let client_token_account = ctx
+            .accounts
+            .client_token_account
+            .as_ref()
+            .ok_or(StakingError::MissingSlashDestination)?;
+        let cpi_accounts = Transfer {
+            from: vault.clone(),
+            to: client_token_account.to_account_info(),
+            authority: vault_authority.clone(),
+        };
+        token::transfer(
+            CpiContext::new_with_signer(token_program.clone(), cpi_accounts, signer_seeds),
+            payouts.client,
+        )?;
+    }
+
+    // Rewards share (distributed to stakers via reflection)
+    if payouts.rewards > 0 {
+        let (
+            Some(reflection_account),
+            Some(rewards_vault),
+            Some(rewards_config),
+            Some(rewards_treasury),
+            Some(rewards_program),
+        ) = (
+            &ctx.accounts.reflection_account,
+            &ctx.accounts.rewards_vault,
+            &ctx.accounts.rewards_config,
+            &ctx.accounts.rewards_treasury,
+            &ctx.accounts.rewards_program,
+        ) else {
+            return Err(StakingError::MissingSlashDestination.into());
+        };
+
+        let cpi_accounts = AddRewards {
+            reflection_account: reflection_account.to_account_info(),
+            rewards_config: rewards_config.to_account_info(),
+            source_token_account: vault.clone(),
+            rewards_vault: rewards_vault.to_account_info(),
+            treasury_token_account: rewards_treasury.to_account_info(),
+            authority: vault_authority.clone(),
+            token_program: token_program.clone(),
+        };
+        hypernode_rewards::cpi::add_rewards(
+            CpiContext::new_with_signer(rewards_program.to_account_info(), cpi_accounts, signer_seeds),
+            payouts.rewards,
+        )?;
+    }
+
+    Ok(())
+}
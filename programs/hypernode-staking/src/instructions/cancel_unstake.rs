@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+use hypernode_rewards::program::HypernodeRewards;
+use hypernode_rewards::{ReflectionAccount, cpi::accounts::RegisterStake as RewardsRegisterStake};
+
+/// Cancel an unstake while still in cooldown
+/// The stake becomes active again with its xHYPER restored
+#[derive(Accounts)]
+pub struct CancelUnstake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", authority.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.authority == authority.key() @ StakingError::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Staking config (governance multiplier curve)
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// User's authority
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// Staker's NodeReputation PDA from the Markets Program
+    /// Nodes that have completed jobs get a tier-based xHYPER boost; required so the boost
+    /// always follows the current tier
+    /// CHECK: PDA of the Markets Program, parsed with node_tier (uninitialized: no boost)
+    #[account(
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump,
+        seeds::program = MARKETS_PROGRAM_ID
+    )]
+    pub node_reputation: UncheckedAccount<'info>,
+
+    /// Reflection account from Rewards Program (optional)
+    #[account(mut)]
+    pub reflection_account: Option<Account<'info, ReflectionAccount>>,
+
+    /// User's rewards registration (optional, will be created)
+    /// CHECK: Initialized by the Rewards Program
+    #[account(mut)]
+    pub user_rewards_account: Option<AccountInfo<'info>>,
+
+    /// Rewards program (optional)
+    pub rewards_program: Option<Program<'info, HypernodeRewards>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<CancelUnstake>) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let clock = Clock::get()?;
+
+    // Only while the cooldown is still running
+    let config = &ctx.accounts.staking_config;
+    let xhyper = stake_account.cancel_unstake(clock.unix_timestamp, config)?;
+
+    // Restore the node tier boost, if the staker still has one
+    let node_tier = node_tier(&ctx.accounts.node_reputation, &ctx.accounts.authority.key());
+    let xhyper = apply_node_boost(xhyper, stake_account.amount, node_tier, config.max_multiplier_bps);
+    stake_account.xhyper = xhyper;
+
+    // Update global aggregates
+    ctx.accounts.global_stats.record_cancel_unstake(xhyper)?;
+
+    msg!("Unstake cancelled");
+    msg!("xHYPER restored: {}", xhyper);
+
+    // Re-register in Rewards Program if provided
+    if let (Some(reflection), Some(user_rewards), Some(rewards_program)) = (
+        &ctx.accounts.reflection_account,
+        &ctx.accounts.user_rewards_account,
+        &ctx.accounts.rewards_program,
+    ) {
+        // Persist the restored xHYPER: the Rewards Program reads it from the stake account
+        ctx.accounts.stake_account.exit(&crate::ID)?;
+
+        let cpi_accounts = RewardsRegisterStake {
+            reflection_account: reflection.to_account_info(),
+            user_rewards_account: user_rewards.to_account_info(),
+            authority: ctx.accounts.authority.to_account_info(),
+            stake_account: ctx.accounts.stake_account.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+        };
+        let cpi_ctx = CpiContext::new(rewards_program.to_account_info(), cpi_accounts);
+        hypernode_rewards::cpi::register_stake(cpi_ctx)?;
+
+        msg!("Re-registered in Rewards Program with xHYPER: {}", xhyper);
+    }
+
+    Ok(())
+}
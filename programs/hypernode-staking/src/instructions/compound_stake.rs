@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+/// Add restaked rewards to an active stake
+/// Signed by the Rewards Program's vault authority PDA during claim_and_compound;
+/// the tokens come straight from the rewards vault
+#[derive(Accounts)]
+pub struct CompoundStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", stake_account.authority.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Staking vault (holds staked tokens)
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Staking config (governance multiplier curve)
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// Staker's NodeReputation PDA from the Markets Program
+    /// The compounded tokens get the node tier boost like the rest of the position
+    /// CHECK: PDA of the Markets Program, parsed with node_tier (uninitialized: no boost)
+    #[account(
+        seeds = [b"reputation", stake_account.authority.as_ref()],
+        bump,
+        seeds::program = MARKETS_PROGRAM_ID
+    )]
+    pub node_reputation: UncheckedAccount<'info>,
+
+    /// Rewards vault (source of the restaked rewards)
+    #[account(
+        mut,
+        constraint = source_token_account.owner == rewards_authority.key() @ StakingError::InvalidOwner,
+        constraint = source_token_account.mint == vault.mint @ StakingError::InvalidToken
+    )]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    /// Rewards Program vault authority PDA
+    #[account(
+        seeds = [b"vault_authority"],
+        bump,
+        seeds::program = hypernode_rewards::ID
+    )]
+    pub rewards_authority: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<CompoundStake>, amount: u64) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    // Compounded tokens lock for the position's existing duration, with the
+    // node tier boost, capped at the curve's maximum multiplier
+    let config = &ctx.accounts.staking_config;
+    let node_tier = node_tier(&ctx.accounts.node_reputation, &stake_account.authority);
+    let added_xhyper = apply_node_boost(
+        config.calculate_xhyper(amount, stake_account.duration),
+        amount,
+        node_tier,
+        config.max_multiplier_bps,
+    );
+
+    stake_account.compound(amount, added_xhyper)?;
+    ctx.accounts.global_stats.record_compound(amount, added_xhyper)?;
+
+    // Transfer rewards into the staking vault
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.source_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.rewards_authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    );
+
+    token::transfer(cpi_ctx, amount)?;
+
+    msg!("Stake compounded");
+    msg!("Amount added: {}", amount);
+    msg!("xHYPER added: {}", added_xhyper);
+    msg!("Total xHYPER: {}", stake_account.xhyper);
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Delegate a stake to a node operator
+/// The operator takes their `commission_bps` of the stake's rewards when
+/// they are claimed; the Rewards Program picks the delegation up on the
+/// stake's next `sync_rewards`
+#[derive(Accounts)]
+pub struct DelegateStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", authority.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.authority == authority.key() @ StakingError::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Owner of the stake
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DelegateStake>, operator: Option<Pubkey>) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    // Active positions only; None undelegates
+    stake_account.delegate(operator)?;
+
+    match operator {
+        Some(operator) => msg!("Stake delegated to operator {}", operator),
+        None => msg!("Stake undelegated"),
+    }
+
+    Ok(())
+}
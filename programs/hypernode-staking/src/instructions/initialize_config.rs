@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Initialize the staking config with the default 1x-4x linear curve
+/// Permissionless, can only succeed once (PDA init); changed only through governance
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = StakingConfig::LEN,
+        seeds = [b"staking_config"],
+        bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
+    let staking_config = &mut ctx.accounts.staking_config;
+
+    staking_config.max_multiplier_bps = DEFAULT_MAX_MULTIPLIER_BPS;
+    staking_config.curve = MultiplierCurve::Linear;
+    staking_config.bump = ctx.bumps.staking_config;
+
+    msg!("Staking config initialized");
+    msg!("Max multiplier: {} bps", staking_config.max_multiplier_bps);
+    msg!("Curve: {:?}", staking_config.curve);
+
+    Ok(())
+}
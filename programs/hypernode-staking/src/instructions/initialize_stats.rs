@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+/// Initialize global staking statistics
+/// Permissionless, can only succeed once (PDA init)
+#[derive(Accounts)]
+pub struct InitializeStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = GlobalStakingStats::LEN,
+        seeds = [b"global_stats"],
+        bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeStats>) -> Result<()> {
+    let global_stats = &mut ctx.accounts.global_stats;
+
+    global_stats.total_staked = 0;
+    global_stats.total_xhyper = 0;
+    global_stats.active_stakers = 0;
+    global_stats.bump = ctx.bumps.global_stats;
+
+    msg!("Global staking stats initialized");
+
+    Ok(())
+}
@@ -1,7 +1,32 @@
+pub mod accept_stake_transfer;
+pub mod apply_slash;
+pub mod cancel_unstake;
+pub mod compound_stake;
+pub mod delegate_stake;
+pub mod initialize_config;
+pub mod initialize_stats;
+pub mod set_commission;
 pub mod stake;
+pub mod stake_tiered;
+pub mod sync_node_boost;
+pub mod top_up;
+pub mod transfer_stake;
 pub mod unstake;
+pub mod update_curve;
 pub mod withdraw;
 
+pub use accept_stake_transfer::*;
+pub use apply_slash::*;
+pub use cancel_unstake::*;
+pub use compound_stake::*;
+pub use delegate_stake::*;
+pub use initialize_config::*;
+pub use initialize_stats::*;
+pub use set_commission::*;
 pub use stake::*;
+pub use sync_node_boost::*;
+pub use top_up::*;
+pub use transfer_stake::*;
 pub use unstake::*;
+pub use update_curve::*;
 pub use withdraw::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Set the commission a node operator takes on delegated stake's rewards
+/// Stored on the operator's own stake account
+#[derive(Accounts)]
+pub struct SetCommission<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", authority.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.authority == authority.key() @ StakingError::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Node operator (owner of the stake)
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetCommission>, commission_bps: u16) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    stake_account.set_commission(commission_bps)?;
+
+    msg!("Operator commission: {} bps", commission_bps);
+
+    Ok(())
+}
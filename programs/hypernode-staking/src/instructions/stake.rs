@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::validation::validate_beneficiary;
 use hypernode_rewards::program::HypernodeRewards;
 use hypernode_rewards::{ReflectionAccount, UserRewardsAccount, cpi::accounts::RegisterStake as RewardsRegisterStake};
 
@@ -37,6 +38,32 @@ pub struct Stake<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
 
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Staking config (governance multiplier curve)
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// Staker's NodeReputation PDA from the Markets Program
+    /// Nodes that have completed jobs get a tier-based xHYPER boost; required so the boost
+    /// always follows the current tier
+    /// CHECK: PDA of the Markets Program, parsed with node_tier (uninitialized: no boost)
+    #[account(
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump,
+        seeds::program = MARKETS_PROGRAM_ID
+    )]
+    pub node_reputation: UncheckedAccount<'info>,
+
     /// Reflection account from Rewards Program (optional)
     pub reflection_account: Option<Account<'info, ReflectionAccount>>,
 
@@ -54,6 +81,7 @@ pub fn handler(
     ctx: Context<Stake>,
     amount: u64,
     duration: i64,
+    beneficiary: Option<Pubkey>,
 ) -> Result<()> {
     let stake_account = &mut ctx.accounts.stake_account;
     let clock = Clock::get()?;
@@ -70,16 +98,32 @@ pub fn handler(
         StakingError::DurationTooShort
     );
 
+    // Validate beneficiary (independent of the signing authority)
+    validate_beneficiary(&beneficiary, &ctx.accounts.authority.key())?;
+
     // Initialize stake account
     stake_account.authority = ctx.accounts.authority.key();
     stake_account.amount = amount;
     stake_account.time_stake = clock.unix_timestamp;
     stake_account.time_unstake = 0;
     stake_account.duration = duration;
+    stake_account.beneficiary = beneficiary;
+    stake_account.pending_authority = None;
+    stake_account.operator = None;
+    stake_account.commission_bps = 0;
+    stake_account.lock_end = clock.unix_timestamp.saturating_add(duration);
     stake_account.bump = ctx.bumps.stake_account;
 
-    // Calculate xHYPER
-    stake_account.update_xhyper();
+    // Calculate xHYPER on the governance-configured curve
+    let config = &ctx.accounts.staking_config;
+    stake_account.xhyper = config.calculate_xhyper(amount, duration);
+
+    // Node tier boost, capped at the curve's maximum multiplier
+    let node_tier = node_tier(&ctx.accounts.node_reputation, &ctx.accounts.authority.key());
+    stake_account.xhyper = apply_node_boost(stake_account.xhyper, amount, node_tier, config.max_multiplier_bps);
+
+    // Update global aggregates
+    ctx.accounts.global_stats.record_stake(amount, stake_account.xhyper)?;
 
     // Transfer tokens to vault
     let cpi_accounts = Transfer {
@@ -100,6 +144,12 @@ pub fn handler(
     msg!("Duration: {} seconds", duration);
     msg!("xHYPER: {}", stake_account.xhyper);
     msg!("Multiplier: {}x", stake_account.get_multiplier_bps() as f64 / 100.0);
+    if let Some(tier) = node_tier {
+        msg!("Node tier boost: tier {}", tier);
+    }
+    if let Some(beneficiary) = beneficiary {
+        msg!("Beneficiary: {}", beneficiary);
+    }
 
     // Register in Rewards Program if provided
     if let (Some(_reflection), Some(_user_rewards), Some(_rewards_program)) = (
@@ -119,7 +169,7 @@ pub fn handler(
         //     system_program: ctx.accounts.system_program.to_account_info(),
         // };
         // let cpi_ctx = CpiContext::new(rewards_program.to_account_info(), cpi_accounts);
-        // hypernode_rewards::cpi::register_stake(cpi_ctx, stake_account.xhyper)?;
+        // hypernode_rewards::cpi::register_stake(cpi_ctx)?;
     }
 
     Ok(())
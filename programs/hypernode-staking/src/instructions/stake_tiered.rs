@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use super::stake::{self, Stake};
+
+/// Stake HYPER for a preset DurationTier
+/// Same accounts and checks as `stake`, with the duration taken from the tier
+pub fn handler(
+    ctx: Context<Stake>,
+    amount: u64,
+    tier: DurationTier,
+    beneficiary: Option<Pubkey>,
+) -> Result<()> {
+    msg!("Duration tier: {:?}", tier);
+    stake::handler(ctx, amount, tier.seconds(), beneficiary)
+}
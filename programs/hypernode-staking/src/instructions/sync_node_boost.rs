@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Re-evaluate an active stake's node tier boost against the staker's
+/// current NodeReputation, so a boost earned at a higher tier doesn't
+/// outlive it. Permissionless; call the Rewards Program's `sync_rewards`
+/// afterwards so rewards follow the new xHYPER
+#[derive(Accounts)]
+pub struct SyncNodeBoost<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", stake_account.authority.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Staking config (governance multiplier curve)
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// Staker's NodeReputation PDA from the Markets Program
+    /// CHECK: PDA of the Markets Program, parsed with node_tier (uninitialized: no boost)
+    #[account(
+        seeds = [b"reputation", stake_account.authority.as_ref()],
+        bump,
+        seeds::program = MARKETS_PROGRAM_ID
+    )]
+    pub node_reputation: UncheckedAccount<'info>,
+}
+
+pub fn handler(ctx: Context<SyncNodeBoost>) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    require!(stake_account.is_active(), StakingError::StakeNotActive);
+
+    // Recompute the whole position at the current tier
+    let old_xhyper = stake_account.xhyper;
+    let node_tier = node_tier(&ctx.accounts.node_reputation, &stake_account.authority);
+    stake_account.xhyper = stake_account.boosted_xhyper(&ctx.accounts.staking_config, node_tier);
+
+    ctx.accounts.global_stats.record_top_up(0, old_xhyper, stake_account.xhyper)?;
+
+    msg!("Node boost synced");
+    msg!("Node tier: {:?}", node_tier);
+    msg!("xHYPER: {} -> {}", old_xhyper, stake_account.xhyper);
+
+    Ok(())
+}
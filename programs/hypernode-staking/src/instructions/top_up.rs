@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::errors::*;
+
+/// Add HYPER tokens to an active stake
+/// The whole position is re-locked: the added tokens commit to what is left of
+/// the lock term (at least 2 weeks) and the duration becomes the amount-weighted
+/// blend, so topping up just before unlock can't buy the original multiplier
+#[derive(Accounts)]
+pub struct TopUp<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", authority.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.authority == authority.key() @ StakingError::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// User's authority
+    pub authority: Signer<'info>,
+
+    /// User's token account (source)
+    #[account(
+        mut,
+        constraint = user_token_account.owner == authority.key() @ StakingError::Unauthorized
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    /// Staking vault (holds staked tokens)
+    #[account(
+        mut,
+        seeds = [b"vault"],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
+    /// Staking config (governance multiplier curve)
+    #[account(
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// Staker's NodeReputation PDA from the Markets Program
+    /// Keeps the tier-based xHYPER boost on the recomputed position; required so the boost
+    /// always follows the current tier
+    /// CHECK: PDA of the Markets Program, parsed with node_tier (uninitialized: no boost)
+    #[account(
+        seeds = [b"reputation", authority.key().as_ref()],
+        bump,
+        seeds::program = MARKETS_PROGRAM_ID
+    )]
+    pub node_reputation: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<TopUp>, amount: u64) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let clock = Clock::get()?;
+
+    // Validate user has sufficient balance
+    require!(
+        ctx.accounts.user_token_account.amount >= amount,
+        StakingError::InsufficientBalance
+    );
+
+    // Blend the duration and re-lock the whole position
+    let old_xhyper = stake_account.xhyper;
+    let added_lock = stake_account.top_up(amount, clock.unix_timestamp)?;
+
+    // Recompute xHYPER over the blended position, with the node tier boost
+    // capped at the curve's maximum multiplier
+    let node_tier = node_tier(&ctx.accounts.node_reputation, &ctx.accounts.authority.key());
+    stake_account.xhyper = stake_account.boosted_xhyper(&ctx.accounts.staking_config, node_tier);
+
+    ctx.accounts.global_stats.record_top_up(amount, old_xhyper, stake_account.xhyper)?;
+
+    // Transfer tokens to vault
+    let cpi_accounts = Transfer {
+        from: ctx.accounts.user_token_account.to_account_info(),
+        to: ctx.accounts.vault.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        cpi_accounts,
+    );
+
+    token::transfer(cpi_ctx, amount)?;
+
+    msg!("Stake topped up");
+    msg!("Amount added: {}", amount);
+    msg!("Added tokens locked for: {} seconds", added_lock);
+    msg!("Blended duration: {} seconds", stake_account.duration);
+    msg!("Locked until: {}", stake_account.lock_end);
+    msg!("xHYPER: {}", stake_account.xhyper);
+
+    Ok(())
+}
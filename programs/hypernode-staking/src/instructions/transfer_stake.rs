@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Propose transferring a stake position to a new owner
+/// The new owner completes the transfer with `accept_stake_transfer`; the
+/// current owner keeps earning rewards until then
+#[derive(Accounts)]
+pub struct TransferStake<'info> {
+    #[account(
+        mut,
+        seeds = [b"stake", authority.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.authority == authority.key() @ StakingError::Unauthorized
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+
+    /// Current owner of the stake
+    pub authority: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<TransferStake>, new_authority: Option<Pubkey>) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+
+    // Active positions only; None cancels a pending transfer
+    stake_account.propose_transfer(new_authority)?;
+
+    let Some(new_authority) = new_authority else {
+        msg!("Stake transfer cancelled");
+        return Ok(());
+    };
+
+    msg!("Stake transfer proposed");
+    msg!("From: {}", stake_account.authority);
+    msg!("To: {}", new_authority);
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::errors::*;
+use crate::validation::validate_unstake_instruction;
 
 /// Initiate unstake process
 /// Starts cooldown period equal to staking duration
@@ -15,6 +16,14 @@ pub struct Unstake<'info> {
     )]
     pub stake_account: Account<'info, StakeAccount>,
 
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
     /// User's authority
     pub authority: Signer<'info>,
 }
@@ -23,21 +32,29 @@ pub fn handler(ctx: Context<Unstake>) -> Result<()> {
     let stake_account = &mut ctx.accounts.stake_account;
     let clock = Clock::get()?;
 
-    // Verify stake is active
-    require!(
-        stake_account.is_active(),
-        StakingError::AlreadyUnstaking
-    );
+    // Verify stake is active and owned by the signer
+    validate_unstake_instruction(
+        stake_account.state_code(),
+        &stake_account.authority,
+        &ctx.accounts.authority.key(),
+    )?;
+
+    // time_unstake == 0 means "active", so a zero clock would silently undo the unstake
+    require!(clock.unix_timestamp > 0, StakingError::InvalidTimestamp);
 
     // Mark unstake time
+    let burned_xhyper = stake_account.xhyper;
     stake_account.time_unstake = clock.unix_timestamp;
 
     // Update xHYPER (will be 0 now)
     stake_account.update_xhyper();
 
+    // Update global aggregates
+    ctx.accounts.global_stats.record_unstake(burned_xhyper)?;
+
     msg!("Unstake initiated");
     msg!("Cooldown: {} seconds", stake_account.duration);
-    msg!("Withdraw available at: {}", stake_account.time_unstake + stake_account.duration);
+    msg!("Withdraw available at: {}", stake_account.cooldown_end());
     msg!("xHYPER balance: 0 (burned)");
 
     Ok(())
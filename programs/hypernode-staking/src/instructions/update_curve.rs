@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::errors::*;
+
+/// Update the xHYPER multiplier curve
+/// Only callable via CPI from the governance program while executing a passed proposal
+/// The governance authority only signs a ParameterUpdate's own execution_data,
+/// so the call is bound to the curve that was voted on
+/// Applies to new stakes; existing stakes keep the xHYPER they were created with
+#[derive(Accounts)]
+pub struct UpdateCurve<'info> {
+    #[account(
+        mut,
+        seeds = [b"staking_config"],
+        bump = staking_config.bump
+    )]
+    pub staking_config: Account<'info, StakingConfig>,
+
+    /// Governance authority PDA (only the governance program can sign for it)
+    pub governance_authority: Signer<'info>,
+}
+
+pub fn handler(
+    ctx: Context<UpdateCurve>,
+    max_multiplier_bps: u32,
+    curve: MultiplierCurve,
+) -> Result<()> {
+    let staking_config = &mut ctx.accounts.staking_config;
+
+    // Verify caller is the governance program
+    require!(
+        StakingConfig::is_governance_authority(&ctx.accounts.governance_authority.key()),
+        StakingError::Unauthorized
+    );
+
+    require!(
+        StakingConfig::is_valid_max_multiplier(max_multiplier_bps),
+        StakingError::InvalidMultiplierCurve
+    );
+
+    staking_config.max_multiplier_bps = max_multiplier_bps;
+    staking_config.curve = curve;
+
+    msg!("Multiplier curve updated");
+    msg!("Max multiplier: {} bps", max_multiplier_bps);
+    msg!("Curve: {:?}", curve);
+
+    Ok(())
+}
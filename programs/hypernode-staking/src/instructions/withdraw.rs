@@ -2,6 +2,7 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Token, TokenAccount, Transfer};
 use crate::state::*;
 use crate::errors::*;
+use crate::validation::validate_withdraw_instruction;
 
 /// Withdraw staked tokens after cooldown period
 /// Can only be called after cooldown has passed
@@ -36,6 +37,14 @@ pub struct Withdraw<'info> {
     )]
     pub vault: Account<'info, TokenAccount>,
 
+    /// Global staking statistics
+    #[account(
+        mut,
+        seeds = [b"global_stats"],
+        bump = global_stats.bump
+    )]
+    pub global_stats: Account<'info, GlobalStakingStats>,
+
     /// Vault authority PDA
     /// CHECK: PDA signer
     #[account(
@@ -51,7 +60,14 @@ pub fn handler(ctx: Context<Withdraw>) -> Result<()> {
     let stake_account = &ctx.accounts.stake_account;
     let clock = Clock::get()?;
 
-    // Verify cooldown has passed
+    // Verify stake is unstaking, cooldown has passed and signer owns it
+    validate_withdraw_instruction(
+        stake_account.state_code(),
+        stake_account.cooldown_end(),
+        &stake_account.authority,
+        &ctx.accounts.authority.key(),
+        &clock,
+    )?;
     require!(
         stake_account.can_withdraw(clock.unix_timestamp),
         StakingError::CooldownNotPassed
@@ -76,6 +92,9 @@ pub fn handler(ctx: Context<Withdraw>) -> Result<()> {
 
     token::transfer(cpi_ctx, stake_account.amount)?;
 
+    // Update global aggregates
+    ctx.accounts.global_stats.record_withdraw(stake_account.amount)?;
+
     msg!("Withdrawal completed");
     msg!("Amount: {}", stake_account.amount);
     msg!("Stake account closed");
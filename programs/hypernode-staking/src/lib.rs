@@ -9,7 +9,7 @@ pub mod state;
 pub mod validation;
 
 use instructions::*;
-pub use state::StakeAccount;
+pub use state::{DurationTier, GlobalStakingStats, MultiplierCurve, SlashPayouts, StakeAccount, StakingConfig};
 pub use constants::*;
 
 declare_id!("3fw9eQN1KHarGcYVETvF7FDt2BYGuDPMjuhoE45RJnTJ");
@@ -21,14 +21,60 @@ declare_id!("3fw9eQN1KHarGcYVETvF7FDt2BYGuDPMjuhoE45RJnTJ");
 pub mod hypernode_staking {
     use super::*;
 
+    /// Initialize global staking statistics
+    /// Must be called once before the first stake
+    pub fn initialize_stats(ctx: Context<InitializeStats>) -> Result<()> {
+        instructions::initialize_stats::handler(ctx)
+    }
+
+    /// Initialize the staking config (default 1x-4x linear multiplier curve)
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config::handler(ctx)
+    }
+
+    /// Update the multiplier curve (governance only)
+    pub fn update_curve(
+        ctx: Context<UpdateCurve>,
+        max_multiplier_bps: u32,
+        curve: MultiplierCurve,
+    ) -> Result<()> {
+        instructions::update_curve::handler(ctx, max_multiplier_bps, curve)
+    }
+
     /// Stake HYPER tokens with specified duration
     /// Longer durations receive higher xHYPER multipliers
+    /// Optional beneficiary receives voting power and rewards
     pub fn stake(
         ctx: Context<Stake>,
         amount: u64,
         duration: i64,
+        beneficiary: Option<Pubkey>,
     ) -> Result<()> {
-        instructions::stake::handler(ctx, amount, duration)
+        instructions::stake::handler(ctx, amount, duration, beneficiary)
+    }
+
+    /// Stake HYPER tokens for a preset duration tier (2 weeks to 1 year)
+    /// Same as `stake` with the tier's exact duration in seconds
+    pub fn stake_tiered(
+        ctx: Context<Stake>,
+        amount: u64,
+        tier: DurationTier,
+        beneficiary: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::stake_tiered::handler(ctx, amount, tier, beneficiary)
+    }
+
+    /// Add HYPER tokens to an active stake
+    /// Re-locks the whole position at the amount-weighted blend of its duration
+    /// and the time left on its lock (at least 2 weeks) for the added tokens
+    pub fn top_up(ctx: Context<TopUp>, amount: u64) -> Result<()> {
+        instructions::top_up::handler(ctx, amount)
+    }
+
+    /// Add restaked rewards to an active stake (Rewards Program vault authority only)
+    /// Used by the Rewards Program's claim_and_compound
+    pub fn compound_stake(ctx: Context<CompoundStake>, amount: u64) -> Result<()> {
+        instructions::compound_stake::handler(ctx, amount)
     }
 
     /// Initiate unstake process
@@ -37,9 +83,52 @@ pub mod hypernode_staking {
         instructions::unstake::handler(ctx)
     }
 
+    /// Cancel an unstake while still in cooldown
+    /// Restores xHYPER and re-registers in the Rewards Program
+    pub fn cancel_unstake(ctx: Context<CancelUnstake>) -> Result<()> {
+        instructions::cancel_unstake::handler(ctx)
+    }
+
     /// Withdraw tokens after cooldown period
     /// Closes stake account and returns tokens
     pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
         instructions::withdraw::handler(ctx)
     }
+
+    /// Propose transferring an active stake to a new owner
+    /// None cancels a pending transfer
+    pub fn transfer_stake(ctx: Context<TransferStake>, new_authority: Option<Pubkey>) -> Result<()> {
+        instructions::transfer_stake::handler(ctx, new_authority)
+    }
+
+    /// Accept a proposed stake transfer (signed by the new owner)
+    /// Moves the position and its rewards registration to the new owner
+    pub fn accept_stake_transfer(ctx: Context<AcceptStakeTransfer>) -> Result<()> {
+        instructions::accept_stake_transfer::handler(ctx)
+    }
+
+    /// Reduce a slashed node's stake and pay the slashed tokens out of the vault
+    /// (slashing program CPI only)
+    /// A remainder below the minimum stake is unbonded and withdrawable at once
+    pub fn apply_slash(ctx: Context<ApplySlash>, amount: u64, payouts: SlashPayouts) -> Result<()> {
+        instructions::apply_slash::handler(ctx, amount, payouts)
+    }
+
+    /// Delegate an active stake to a node operator (None undelegates)
+    /// The operator takes their commission on the stake's rewards
+    pub fn delegate_stake(ctx: Context<DelegateStake>, operator: Option<Pubkey>) -> Result<()> {
+        instructions::delegate_stake::handler(ctx, operator)
+    }
+
+    /// Set the commission taken, as a node operator, on delegated stake's rewards
+    /// At most 10000 bps
+    pub fn set_commission(ctx: Context<SetCommission>, commission_bps: u16) -> Result<()> {
+        instructions::set_commission::handler(ctx, commission_bps)
+    }
+
+    /// Re-evaluate an active stake's node tier boost (permissionless)
+    /// Drops a boost the staker's current reputation tier no longer earns
+    pub fn sync_node_boost(ctx: Context<SyncNodeBoost>) -> Result<()> {
+        instructions::sync_node_boost::handler(ctx)
+    }
 }
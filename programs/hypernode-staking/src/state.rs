@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*;
+use crate::constants::MIN_STAKE_AMOUNT;
+use crate::errors::StakingError;
 
 /// HYPER Token Configuration
 /// Mint Address: 92s9qna3djkMncZzkacyNQ38UKnNXZFh4Jgqe3Cmpump
@@ -19,6 +21,94 @@ pub const DURATION_MAX: i64 = 365 * 86400;
 /// Gives 4x multiplier at 1 year
 pub const XHYPER_DIV: i64 = (4 * DURATION_MAX) / 12;
 
+/// Governance program (referenced by ID: governance depends on this crate)
+pub const GOVERNANCE_PROGRAM_ID: Pubkey = pubkey!("BYGEToSgdrpmbZt2uapsW6s7NnFuCmVabJzfd8uFT4dE");
+
+/// Seed of the governance authority PDA (signs CPIs for passed proposals)
+pub const GOVERNANCE_AUTHORITY_SEED: &[u8] = b"governance_authority";
+
+/// Slashing program (referenced by ID: slashing depends on this crate)
+pub const SLASHING_PROGRAM_ID: Pubkey = pubkey!("83rLt9YBCTkaAX6vLUuEAQE7QdhofvQWUhjybXVr7nCL");
+
+/// Seed of the slashing authority PDA (signs apply_slash CPIs from slash_node)
+pub const SLASH_AUTHORITY_SEED: &[u8] = b"slash_authority";
+
+/// Markets Program (referenced by ID: markets depends on this crate)
+/// Owner of the NodeReputation accounts read for the node tier boost
+pub const MARKETS_PROGRAM_ID: Pubkey = pubkey!("67UE2LconF9QU5Vobsaf5sXnW9yUisebLj8VmgGWLSdb");
+
+/// Anchor discriminator of the Markets Program's NodeReputation account
+/// (first 8 bytes of sha256("account:NodeReputation"))
+pub const NODE_REPUTATION_DISCRIMINATOR: [u8; 8] = [24, 67, 40, 23, 123, 151, 211, 254];
+
+/// Offsets into a Markets Program NodeReputation
+/// (discriminator 8 + authority 32 + total_jobs 8 + five u64/i64 fields 40 + reputation_score 2)
+const REPUTATION_AUTHORITY_OFFSET: usize = 8;
+const REPUTATION_TOTAL_JOBS_OFFSET: usize = 40;
+const REPUTATION_TIER_OFFSET: usize = 90;
+
+/// xHYPER bonus for stakers who also run nodes, by NodeReputation tier
+/// (Starter, Bronze, Silver, Gold, Diamond), in MULTIPLIER_BPS_ONE units
+pub const NODE_TIER_BOOST_BPS: [u32; 5] = [0, 250, 500, 750, 1_000];
+
+/// Node tier of `node` from NodeReputation data
+/// None unless the data is a reputation account of that node with at least
+/// one completed job (fresh accounts start at the top tier before any work)
+pub fn read_node_tier(data: &[u8], node: &Pubkey) -> Option<u8> {
+    if data.get(..8)? != NODE_REPUTATION_DISCRIMINATOR {
+        return None;
+    }
+    let authority = data.get(REPUTATION_AUTHORITY_OFFSET..REPUTATION_AUTHORITY_OFFSET + 32)?;
+    if authority != node.as_ref() {
+        return None;
+    }
+    let total_jobs = data.get(REPUTATION_TOTAL_JOBS_OFFSET..REPUTATION_TOTAL_JOBS_OFFSET + 8)?;
+    if u64::from_le_bytes(total_jobs.try_into().ok()?) == 0 {
+        return None;
+    }
+    data.get(REPUTATION_TIER_OFFSET).copied()
+}
+
+/// Node tier of `node` from its NodeReputation account
+/// None unless it is an initialized Markets Program account (see read_node_tier)
+pub fn node_tier(reputation: &AccountInfo, node: &Pubkey) -> Option<u8> {
+    if reputation.owner != &MARKETS_PROGRAM_ID {
+        return None;
+    }
+    let data = reputation.try_borrow_data().ok()?;
+    read_node_tier(&data, node)
+}
+
+/// Apply a node tier boost to a stake's xHYPER
+/// The combined multiplier never exceeds `max_multiplier_bps` (the curve's
+/// top multiplier), so running a node can't out-earn the longest lock
+pub fn apply_node_boost(xhyper: u128, amount: u64, tier: Option<u8>, max_multiplier_bps: u32) -> u128 {
+    let boost = tier
+        .and_then(|tier| NODE_TIER_BOOST_BPS.get(tier as usize))
+        .copied()
+        .unwrap_or(0);
+    let one = MULTIPLIER_BPS_ONE as u128;
+    let boosted = xhyper * (one + boost as u128) / one;
+    let cap = amount as u128 * max_multiplier_bps as u128 / one;
+    boosted.min(cap.max(xhyper))
+}
+
+/// Multiplier basis points: 10_000 = 1x
+pub const MULTIPLIER_BPS_ONE: u32 = 10_000;
+
+/// Default maximum multiplier (4x at DURATION_MAX)
+pub const DEFAULT_MAX_MULTIPLIER_BPS: u32 = 40_000;
+
+/// Highest maximum multiplier governance can configure (10x)
+pub const MAX_MULTIPLIER_CEILING_BPS: u32 = 100_000;
+
+/// Highest commission a node operator can take on delegated stake's rewards (bps)
+pub const MAX_COMMISSION_BPS: u16 = 10_000;
+
+/// Stake lifecycle codes used by the instruction validators
+pub const STAKE_STATE_ACTIVE: u8 = 0;
+pub const STAKE_STATE_UNSTAKING: u8 = 1;
+
 /// Stake account - tracks individual user stake
 /// Optimized field ordering to minimize padding (saves 7 bytes)
 #[account]
@@ -41,14 +131,48 @@ pub struct StakeAccount {
     /// Staking duration in seconds (lock period)
     pub duration: i64,
 
+    /// Optional beneficiary of voting power and rewards (e.g. delegator)
+    /// None = the authority itself
+    pub beneficiary: Option<Pubkey>,
+
+    /// New owner proposed by `transfer_stake`, awaiting `accept_stake_transfer`
+    pub pending_authority: Option<Pubkey>,
+
+    /// Node operator this stake is delegated to (takes its commission on the rewards)
+    pub operator: Option<Pubkey>,
+
+    /// Commission this staker charges, as a node operator, on the rewards of
+    /// stake delegated to them (bps)
+    pub commission_bps: u16,
+
+    /// End of the current lock term: time_stake + duration, moved out by top_up
+    /// (which re-locks the whole position from the time of the top-up)
+    pub lock_end: i64,
+
     /// PDA bump seed
     pub bump: u8,
 }
 
 impl StakeAccount {
     /// Account size: 8 (discriminator) + size of fields
-    /// Optimized: 8 + 32 + 16 + 8 + 8 + 8 + 8 + 1 = 89 bytes (was 96 with padding)
-    pub const LEN: usize = 8 + 32 + 16 + 8 + 8 + 8 + 8 + 1;
+    /// 8 + 32 + 16 + 8 + 8 + 8 + 8 + 33 + 33 + 33 + 2 + 8 + 1 = 198 bytes
+    pub const LEN: usize = 8 + 32 + 16 + 8 + 8 + 8 + 8 + (1 + 32) + (1 + 32) + (1 + 32) + 2 + 8 + 1;
+
+    /// Account that votes with this stake and receives its rewards
+    #[inline(always)]
+    pub fn beneficiary_or_authority(&self) -> Pubkey {
+        self.beneficiary.unwrap_or(self.authority)
+    }
+
+    /// Slashing authority PDA allowed to apply slashes
+    pub fn slash_authority() -> Pubkey {
+        Pubkey::find_program_address(&[SLASH_AUTHORITY_SEED], &SLASHING_PROGRAM_ID).0
+    }
+
+    /// Check if a signer is the slashing authority PDA
+    pub fn is_slash_authority(key: &Pubkey) -> bool {
+        *key == Self::slash_authority()
+    }
 
     /// Calculate xHYPER based on staking amount and duration
     /// Multiplier ranges from 1x (2 weeks) to 4x (1 year)
@@ -97,6 +221,37 @@ impl StakeAccount {
         self.time_unstake == 0
     }
 
+    /// Lifecycle code (STAKE_STATE_ACTIVE or STAKE_STATE_UNSTAKING)
+    /// Withdrawn stakes are closed, so they never reach a third state
+    #[inline(always)]
+    pub fn state_code(&self) -> u8 {
+        if self.is_active() {
+            STAKE_STATE_ACTIVE
+        } else {
+            STAKE_STATE_UNSTAKING
+        }
+    }
+
+    /// Timestamp at which the unstake cooldown ends
+    /// Cooldown period = staking duration, but never before lock_end
+    #[inline(always)]
+    pub fn cooldown_end(&self) -> i64 {
+        self.cooldown_end_at(self.time_unstake)
+    }
+
+    /// When the position would unlock if unstaked at `unstake_time`
+    #[inline(always)]
+    pub fn cooldown_end_at(&self, unstake_time: i64) -> i64 {
+        unstake_time.saturating_add(self.duration).max(self.lock_end)
+    }
+
+    /// xHYPER of the whole position on `config`'s curve, with the node tier
+    /// boost for `node_tier` (capped at the curve maximum)
+    pub fn boosted_xhyper(&self, config: &StakingConfig, node_tier: Option<u8>) -> u128 {
+        let xhyper = config.calculate_xhyper(self.amount, self.duration);
+        apply_node_boost(xhyper, self.amount, node_tier, config.max_multiplier_bps)
+    }
+
     /// Check if unstake cooldown has passed
     #[inline(always)]
     pub fn can_withdraw(&self, current_time: i64) -> bool {
@@ -104,8 +259,177 @@ impl StakeAccount {
             return false;
         }
 
-        // Cooldown period = staking duration
-        current_time >= self.time_unstake + self.duration
+        current_time > self.time_unstake && current_time >= self.cooldown_end()
+    }
+
+    /// Undo an unstake while still in cooldown
+    /// The position becomes active again and its xHYPER is recomputed
+    /// on the configured curve; returns the restored xHYPER
+    pub fn cancel_unstake(&mut self, current_time: i64, config: &StakingConfig) -> Result<u128> {
+        require!(!self.is_active(), StakingError::NotUnstaking);
+        require!(current_time < self.cooldown_end(), StakingError::CooldownPassed);
+
+        self.time_unstake = 0;
+        self.xhyper = config.calculate_xhyper(self.amount, self.duration);
+        Ok(self.xhyper)
+    }
+
+    /// Propose a new owner for this position (step 1 of a transfer)
+    /// Unstaking positions can't be transferred; None cancels a pending transfer
+    pub fn propose_transfer(&mut self, new_authority: Option<Pubkey>) -> Result<()> {
+        if let Some(new_authority) = new_authority {
+            require!(self.is_active(), StakingError::StakeNotActive);
+            require!(
+                new_authority != Pubkey::default() && new_authority != self.authority,
+                StakingError::InvalidNewOwner
+            );
+        }
+        self.pending_authority = new_authority;
+        Ok(())
+    }
+
+    /// Delegate this stake to a node operator; None undelegates
+    /// Only active stakes can be delegated, and not to their own authority
+    pub fn delegate(&mut self, operator: Option<Pubkey>) -> Result<()> {
+        if let Some(operator) = operator {
+            require!(self.is_active(), StakingError::StakeNotActive);
+            require!(
+                operator != Pubkey::default() && operator != self.authority,
+                StakingError::InvalidOperator
+            );
+        }
+        self.operator = operator;
+        Ok(())
+    }
+
+    /// Set the commission charged on rewards of stake delegated to this staker
+    pub fn set_commission(&mut self, commission_bps: u16) -> Result<()> {
+        require!(commission_bps <= MAX_COMMISSION_BPS, StakingError::InvalidCommission);
+        self.commission_bps = commission_bps;
+        Ok(())
+    }
+
+    /// Position as held by the accepting owner (step 2 of a transfer)
+    /// Amount, duration, lock timing and xHYPER carry over; the beneficiary,
+    /// delegation and commission were chosen by the previous owner, so they
+    /// are cleared
+    pub fn transferred_to(&self, new_authority: Pubkey, bump: u8) -> Result<StakeAccount> {
+        require!(
+            self.pending_authority == Some(new_authority),
+            StakingError::TransferNotPending
+        );
+        require!(self.is_active(), StakingError::StakeNotActive);
+
+        Ok(StakeAccount {
+            authority: new_authority,
+            xhyper: self.xhyper,
+            amount: self.amount,
+            time_stake: self.time_stake,
+            time_unstake: self.time_unstake,
+            duration: self.duration,
+            beneficiary: None,
+            pending_authority: None,
+            operator: None,
+            commission_bps: 0,
+            lock_end: self.lock_end,
+            bump,
+        })
+    }
+
+    /// Add tokens to an active position (restaked rewards)
+    /// Duration and lock timing are unchanged
+    pub fn compound(&mut self, amount: u64, added_xhyper: u128) -> Result<()> {
+        require!(self.is_active(), StakingError::StakeNotActive);
+        require!(amount > 0, StakingError::AmountTooSmall);
+
+        self.amount = self.amount
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        self.xhyper = self.xhyper
+            .checked_add(added_xhyper)
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Lock the tokens of a top-up at `current_time` commit to:
+    /// what is left of the current term, but at least DURATION_MIN
+    pub fn top_up_lock(&self, current_time: i64) -> i64 {
+        self.lock_end.saturating_sub(current_time).max(DURATION_MIN)
+    }
+
+    /// Add tokens to an active position (top_up)
+    /// A top-up only commits to top_up_lock, so it can't buy the position's
+    /// original multiplier just before the term ends. The position's duration
+    /// becomes the amount-weighted blend
+    ///   duration' = (amount * duration + added * top_up_lock) / (amount + added)
+    /// and the whole position is re-locked for it from `current_time`. The
+    /// lock never ends before the position would have unlocked had it been
+    /// unstaked instead of topped up, so a shorter blend can't release the
+    /// existing tokens early.
+    /// The caller recomputes xHYPER from the new amount and duration.
+    /// Returns the lock the added tokens committed to
+    pub fn top_up(&mut self, amount: u64, current_time: i64) -> Result<i64> {
+        require!(self.is_active(), StakingError::StakeNotActive);
+        require!(amount > 0, StakingError::AmountTooSmall);
+
+        let added_lock = self.top_up_lock(current_time);
+        let old_unlock = self.cooldown_end_at(current_time);
+        let total = self.amount
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        let weighted = (self.amount as u128 * self.duration.max(0) as u128)
+            .checked_add(amount as u128 * added_lock as u128)
+            .ok_or(StakingError::MathOverflow)?;
+
+        self.amount = total;
+        self.duration = (weighted / total as u128) as i64;
+        self.lock_end = current_time
+            .checked_add(self.duration)
+            .ok_or(StakingError::MathOverflow)?
+            .max(old_unlock);
+        Ok(added_lock)
+    }
+
+    /// Remove slashed tokens from the position
+    /// xHYPER shrinks in proportion to the amount, so the multiplier (and any
+    /// node boost) is kept. A remainder below MIN_STAKE_AMOUNT can't back a
+    /// node, so the position is grace-unbonded: it is moved to unstaking with
+    /// the lock waived, letting the node withdraw what is left right away.
+    /// Returns (burned xHYPER, whether the position was unbonded by the slash)
+    pub fn apply_slash(&mut self, amount: u64, current_time: i64) -> Result<(u128, bool)> {
+        require!(amount > 0, StakingError::AmountTooSmall);
+        require!(amount <= self.amount, StakingError::SlashingFailed);
+
+        let remaining = self.amount - amount;
+        let was_active = self.is_active();
+        let remaining_xhyper = if remaining == 0 {
+            0
+        } else {
+            self.xhyper
+                .checked_mul(remaining as u128)
+                .ok_or(StakingError::MathOverflow)?
+                / self.amount as u128
+        };
+        self.amount = remaining;
+
+        if remaining < MIN_STAKE_AMOUNT {
+            // time_unstake == 0 means "active"
+            require!(current_time > 0, StakingError::InvalidTimestamp);
+            if was_active {
+                self.time_unstake = current_time;
+            }
+            self.duration = 0;
+            self.lock_end = 0;
+            self.pending_authority = None;
+
+            let burned = self.xhyper;
+            self.xhyper = 0;
+            return Ok((burned, was_active));
+        }
+
+        let burned = self.xhyper - remaining_xhyper;
+        self.xhyper = remaining_xhyper;
+        Ok((burned, false))
     }
 
     /// Get multiplier as a decimal (e.g., 2.5x = 250)
@@ -120,6 +444,244 @@ impl StakeAccount {
     }
 }
 
+/// Where apply_slash sends the slashed tokens out of the vault
+/// Whatever the shares don't cover stays in the vault (e.g. the reporter
+/// penalty of an overturned report)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SlashPayouts {
+    /// Compensation to the job's client (or the reporter)
+    pub client: u64,
+
+    /// Protocol treasury share
+    pub treasury: u64,
+
+    /// Share distributed to stakers through the Rewards Program
+    pub rewards: u64,
+}
+
+impl SlashPayouts {
+    /// Total paid out of the vault (None on overflow)
+    pub fn total(&self) -> Option<u64> {
+        self.client.checked_add(self.treasury)?.checked_add(self.rewards)
+    }
+
+    /// Check the payouts fit within the slashed amount
+    pub fn fits(&self, amount: u64) -> bool {
+        self.total().is_some_and(|total| total <= amount)
+    }
+}
+
+/// Shape of the xHYPER multiplier between DURATION_MIN (1x) and DURATION_MAX (max)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MultiplierCurve {
+    /// Bonus grows proportionally with duration
+    Linear,
+
+    /// Mild convex curve: average of linear and quadratic growth,
+    /// so short locks earn less and the longest locks catch up
+    Convex,
+}
+
+/// Preset lock durations for `stake_tiered`
+/// Each maps to an exact number of seconds, so clients don't compute them
+/// (the raw-seconds `stake` stays available for other durations).
+/// Built-in curve multipliers: about 1.115x, 1.247x, 1.74x, 2.479x and 4x
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DurationTier {
+    /// 14 days (DURATION_MIN)
+    TwoWeeks,
+
+    /// 30 days
+    OneMonth,
+
+    /// 90 days
+    ThreeMonths,
+
+    /// 180 days
+    SixMonths,
+
+    /// 365 days (DURATION_MAX)
+    OneYear,
+}
+
+impl DurationTier {
+    /// Lock duration in seconds
+    pub fn seconds(&self) -> i64 {
+        match self {
+            DurationTier::TwoWeeks => DURATION_MIN,
+            DurationTier::OneMonth => 30 * 86400,
+            DurationTier::ThreeMonths => 90 * 86400,
+            DurationTier::SixMonths => 180 * 86400,
+            DurationTier::OneYear => DURATION_MAX,
+        }
+    }
+}
+
+/// Staking config - governance-controlled multiplier curve
+/// PDA: ["staking_config"]. When passed to `stake`, it replaces the built-in curve
+#[account]
+pub struct StakingConfig {
+    /// Multiplier at DURATION_MAX, in MULTIPLIER_BPS_ONE units
+    pub max_multiplier_bps: u32,
+
+    /// Curve shape between DURATION_MIN and DURATION_MAX
+    pub curve: MultiplierCurve,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl StakingConfig {
+    /// Account size: 8 (discriminator) + 4 + 1 + 1 = 14 bytes
+    pub const LEN: usize = 8 + 4 + 1 + 1;
+
+    /// Governance authority PDA allowed to update the curve
+    pub fn governance_authority() -> Pubkey {
+        Pubkey::find_program_address(&[GOVERNANCE_AUTHORITY_SEED], &GOVERNANCE_PROGRAM_ID).0
+    }
+
+    /// Check if a signer is the governance authority PDA
+    pub fn is_governance_authority(key: &Pubkey) -> bool {
+        *key == Self::governance_authority()
+    }
+
+    /// Check a max multiplier is within 1x..=MAX_MULTIPLIER_CEILING_BPS
+    pub fn is_valid_max_multiplier(max_multiplier_bps: u32) -> bool {
+        (MULTIPLIER_BPS_ONE..=MAX_MULTIPLIER_CEILING_BPS).contains(&max_multiplier_bps)
+    }
+
+    /// Multiplier for a lock duration, in MULTIPLIER_BPS_ONE units
+    /// Exactly 1x at (and below) DURATION_MIN, max_multiplier_bps at DURATION_MAX
+    pub fn multiplier_bps(&self, duration: i64) -> u128 {
+        let one = MULTIPLIER_BPS_ONE as u128;
+        if duration <= DURATION_MIN {
+            return one;
+        }
+
+        let span = (DURATION_MAX - DURATION_MIN) as u128;
+        let elapsed = (duration.min(DURATION_MAX) - DURATION_MIN) as u128;
+        let max_bonus = (self.max_multiplier_bps as u128).saturating_sub(one);
+
+        let bonus = match self.curve {
+            MultiplierCurve::Linear => max_bonus * elapsed / span,
+            MultiplierCurve::Convex => max_bonus * (elapsed * span + elapsed * elapsed) / (2 * span * span),
+        };
+        one + bonus
+    }
+
+    /// Calculate xHYPER for a stake under this curve
+    pub fn calculate_xhyper(&self, amount: u64, duration: i64) -> u128 {
+        amount as u128 * self.multiplier_bps(duration) / MULTIPLIER_BPS_ONE as u128
+    }
+}
+
+/// Global staking statistics - protocol-wide aggregates
+/// Updated atomically by stake/unstake/withdraw so indexers (and governance
+/// total voting power) don't need to scan every stake account
+#[account]
+pub struct GlobalStakingStats {
+    /// Total HYPER held in the staking vault
+    pub total_staked: u64,
+
+    /// Total xHYPER of active (not unstaking) stakes
+    pub total_xhyper: u128,
+
+    /// Number of active (not unstaking) stakes
+    pub active_stakers: u64,
+
+    /// PDA bump seed
+    pub bump: u8,
+}
+
+impl GlobalStakingStats {
+    /// Account size: 8 (discriminator) + 8 + 16 + 8 + 1 = 41 bytes
+    pub const LEN: usize = 8 + 8 + 16 + 8 + 1;
+
+    /// Record a new stake
+    pub fn record_stake(&mut self, amount: u64, xhyper: u128) -> Result<()> {
+        self.total_staked = self.total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        self.total_xhyper = self.total_xhyper
+            .checked_add(xhyper)
+            .ok_or(StakingError::MathOverflow)?;
+        self.active_stakers = self.active_stakers
+            .checked_add(1)
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record tokens added to an existing active stake
+    pub fn record_compound(&mut self, amount: u64, xhyper: u128) -> Result<()> {
+        self.total_staked = self.total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        self.total_xhyper = self.total_xhyper
+            .checked_add(xhyper)
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a top-up: tokens added, the position's xHYPER recomputed
+    pub fn record_top_up(&mut self, amount: u64, old_xhyper: u128, new_xhyper: u128) -> Result<()> {
+        self.total_staked = self.total_staked
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+        self.total_xhyper = self.total_xhyper
+            .checked_sub(old_xhyper)
+            .and_then(|total| total.checked_add(new_xhyper))
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record an unstake (xHYPER burned, tokens stay in vault until withdraw)
+    pub fn record_unstake(&mut self, xhyper: u128) -> Result<()> {
+        self.total_xhyper = self.total_xhyper
+            .checked_sub(xhyper)
+            .ok_or(StakingError::MathUnderflow)?;
+        self.active_stakers = self.active_stakers
+            .checked_sub(1)
+            .ok_or(StakingError::MathUnderflow)?;
+        Ok(())
+    }
+
+    /// Record a cancelled unstake (xHYPER restored, tokens never left the vault)
+    pub fn record_cancel_unstake(&mut self, xhyper: u128) -> Result<()> {
+        self.total_xhyper = self.total_xhyper
+            .checked_add(xhyper)
+            .ok_or(StakingError::MathOverflow)?;
+        self.active_stakers = self.active_stakers
+            .checked_add(1)
+            .ok_or(StakingError::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Record a slash (tokens leave the vault, the position's xHYPER shrinks)
+    /// `unbonded` when the slash grace-unbonded an active stake
+    pub fn record_slash(&mut self, amount: u64, xhyper: u128, unbonded: bool) -> Result<()> {
+        self.total_staked = self.total_staked
+            .checked_sub(amount)
+            .ok_or(StakingError::MathUnderflow)?;
+        self.total_xhyper = self.total_xhyper
+            .checked_sub(xhyper)
+            .ok_or(StakingError::MathUnderflow)?;
+        if unbonded {
+            self.active_stakers = self.active_stakers
+                .checked_sub(1)
+                .ok_or(StakingError::MathUnderflow)?;
+        }
+        Ok(())
+    }
+
+    /// Record a withdrawal (tokens leave the vault)
+    pub fn record_withdraw(&mut self, amount: u64) -> Result<()> {
+        self.total_staked = self.total_staked
+            .checked_sub(amount)
+            .ok_or(StakingError::MathUnderflow)?;
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +706,657 @@ mod tests {
         let xhyper = StakeAccount::calculate_xhyper(1000, DURATION_MAX * 2);
         assert!(xhyper >= 3900 && xhyper <= 4100); // Should still be ~4x
     }
+
+    #[test]
+    fn test_global_stats_consistent_across_stake_and_withdraw() {
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+
+        let xhyper_a = StakeAccount::calculate_xhyper(1_000, DURATION_MAX);
+        let xhyper_b = StakeAccount::calculate_xhyper(500, DURATION_MIN);
+
+        stats.record_stake(1_000, xhyper_a).unwrap();
+        stats.record_stake(500, xhyper_b).unwrap();
+        assert_eq!(stats.total_staked, 1_500);
+        assert_eq!(stats.total_xhyper, xhyper_a + xhyper_b);
+        assert_eq!(stats.active_stakers, 2);
+
+        // Unstake burns xHYPER but tokens remain in the vault
+        stats.record_unstake(xhyper_a).unwrap();
+        assert_eq!(stats.total_staked, 1_500);
+        assert_eq!(stats.total_xhyper, xhyper_b);
+        assert_eq!(stats.active_stakers, 1);
+
+        stats.record_withdraw(1_000).unwrap();
+        assert_eq!(stats.total_staked, 500);
+
+        stats.record_unstake(xhyper_b).unwrap();
+        stats.record_withdraw(500).unwrap();
+        assert_eq!(stats.total_staked, 0);
+        assert_eq!(stats.total_xhyper, 0);
+        assert_eq!(stats.active_stakers, 0);
+
+        // Aggregates can't go negative
+        assert!(stats.record_withdraw(1).is_err());
+        assert!(stats.record_unstake(0).is_err());
+    }
+
+    #[test]
+    fn test_beneficiary_receives_voting_power_and_rewards() {
+        let authority = Pubkey::new_unique();
+        let beneficiary = Pubkey::new_unique();
+        let mut stake = StakeAccount {
+            authority,
+            xhyper: 0,
+            amount: 1000,
+            time_stake: 0,
+            time_unstake: 0,
+            duration: DURATION_MIN,
+            beneficiary: None,
+            pending_authority: None,
+            operator: None,
+            commission_bps: 0,
+            lock_end: 0,
+            bump: 0,
+        };
+        assert_eq!(stake.beneficiary_or_authority(), authority);
+
+        stake.beneficiary = Some(beneficiary);
+        assert_eq!(stake.beneficiary_or_authority(), beneficiary);
+    }
+
+    fn active_stake(authority: Pubkey) -> StakeAccount {
+        let mut stake = StakeAccount {
+            authority,
+            xhyper: 0,
+            amount: 1000,
+            time_stake: 1_000,
+            time_unstake: 0,
+            duration: DURATION_MIN,
+            beneficiary: Some(Pubkey::new_unique()),
+            pending_authority: None,
+            operator: None,
+            commission_bps: 0,
+            lock_end: 0,
+            bump: 0,
+        };
+        stake.update_xhyper();
+        stake
+    }
+
+    #[test]
+    fn test_transfer_active_stake_then_new_owner_withdraws() {
+        let old_owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let mut stake = active_stake(old_owner);
+
+        // Nothing to accept until the owner proposes
+        assert_eq!(
+            stake.transferred_to(new_owner, 1).err(),
+            Some(StakingError::TransferNotPending.into())
+        );
+
+        stake.propose_transfer(Some(new_owner)).unwrap();
+
+        // Only the proposed owner can accept
+        assert_eq!(
+            stake.transferred_to(Pubkey::new_unique(), 1).err(),
+            Some(StakingError::TransferNotPending.into())
+        );
+
+        let mut moved = stake.transferred_to(new_owner, 1).unwrap();
+        assert_eq!(moved.authority, new_owner);
+        assert_eq!(moved.amount, stake.amount);
+        assert_eq!(moved.xhyper, stake.xhyper);
+        assert_eq!(moved.time_stake, stake.time_stake);
+        assert_eq!(moved.duration, stake.duration);
+        assert_eq!(moved.beneficiary, None);
+        assert_eq!(moved.pending_authority, None);
+        assert_eq!(moved.operator, None);
+        assert_eq!(moved.bump, 1);
+
+        // New owner unstakes and withdraws after the cooldown
+        moved.time_unstake = 2_000;
+        moved.update_xhyper();
+        assert!(crate::validation::validate_withdraw_instruction(
+            moved.state_code(),
+            moved.cooldown_end(),
+            &moved.authority,
+            &new_owner,
+            &Clock { unix_timestamp: moved.cooldown_end(), ..Clock::default() },
+        )
+        .is_ok());
+        assert!(crate::validation::validate_withdraw_instruction(
+            moved.state_code(),
+            moved.cooldown_end(),
+            &moved.authority,
+            &old_owner,
+            &Clock { unix_timestamp: moved.cooldown_end(), ..Clock::default() },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_transfer_of_unstaking_position_rejected() {
+        let owner = Pubkey::new_unique();
+        let new_owner = Pubkey::new_unique();
+        let mut stake = active_stake(owner);
+        stake.time_unstake = 2_000;
+
+        assert_eq!(
+            stake.propose_transfer(Some(new_owner)).unwrap_err(),
+            StakingError::StakeNotActive.into()
+        );
+
+        // Unstaked after proposing: accept is rejected too
+        let mut stake = active_stake(owner);
+        stake.propose_transfer(Some(new_owner)).unwrap();
+        stake.time_unstake = 2_000;
+        assert_eq!(
+            stake.transferred_to(new_owner, 0).err(),
+            Some(StakingError::StakeNotActive.into())
+        );
+    }
+
+    #[test]
+    fn test_transfer_proposal_validation_and_cancel() {
+        let owner = Pubkey::new_unique();
+        let mut stake = active_stake(owner);
+
+        assert_eq!(
+            stake.propose_transfer(Some(owner)).unwrap_err(),
+            StakingError::InvalidNewOwner.into()
+        );
+        assert_eq!(
+            stake.propose_transfer(Some(Pubkey::default())).unwrap_err(),
+            StakingError::InvalidNewOwner.into()
+        );
+
+        let new_owner = Pubkey::new_unique();
+        stake.propose_transfer(Some(new_owner)).unwrap();
+        stake.propose_transfer(None).unwrap();
+        assert_eq!(stake.pending_authority, None);
+        assert!(stake.transferred_to(new_owner, 0).is_err());
+    }
+
+    #[test]
+    fn test_compound_increases_stake_and_xhyper() {
+        let mut stake = active_stake(Pubkey::new_unique());
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+        stats.record_stake(stake.amount, stake.xhyper).unwrap();
+        let (amount_before, xhyper_before) = (stake.amount, stake.xhyper);
+
+        let reward = 250;
+        let added = StakeAccount::calculate_xhyper(reward, stake.duration);
+        stake.compound(reward, added).unwrap();
+        stats.record_compound(reward, added).unwrap();
+
+        assert_eq!(stake.amount, amount_before + reward);
+        assert_eq!(stake.xhyper, xhyper_before + added);
+        assert_eq!(stats.total_staked, stake.amount);
+        assert_eq!(stats.total_xhyper, stake.xhyper);
+        assert_eq!(stats.active_stakers, 1);
+
+        // Nothing to compound into once unstaking, and zero is rejected
+        assert_eq!(
+            stake.compound(0, 0).unwrap_err(),
+            StakingError::AmountTooSmall.into()
+        );
+        stake.time_unstake = 2_000;
+        assert_eq!(
+            stake.compound(reward, added).unwrap_err(),
+            StakingError::StakeNotActive.into()
+        );
+
+        // The Rewards Program builds this instruction by hand
+        assert_eq!(
+            hypernode_rewards::state::compound_stake_ix_data(reward),
+            anchor_lang::InstructionData::data(&crate::instruction::CompoundStake { amount: reward })
+        );
+    }
+
+    #[test]
+    fn test_cancel_unstake_mid_cooldown_restores_xhyper() {
+        let config = curve_config(DEFAULT_MAX_MULTIPLIER_BPS, MultiplierCurve::Linear);
+        let mut stake = active_stake(Pubkey::new_unique());
+        stake.duration = 90 * 86400;
+        stake.xhyper = config.calculate_xhyper(stake.amount, stake.duration);
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+        stats.record_stake(stake.amount, stake.xhyper).unwrap();
+        let xhyper = stake.xhyper;
+
+        // Nothing to cancel while active
+        assert_eq!(
+            stake.cancel_unstake(2_000, &config).unwrap_err(),
+            StakingError::NotUnstaking.into()
+        );
+
+        stake.time_unstake = 2_000;
+        stake.update_xhyper();
+        stats.record_unstake(xhyper).unwrap();
+        assert_eq!(stake.xhyper, 0);
+
+        // Halfway through the cooldown
+        let now = 2_000 + stake.duration / 2;
+        assert_eq!(stake.cancel_unstake(now, &config).unwrap(), xhyper);
+        stats.record_cancel_unstake(xhyper).unwrap();
+
+        assert!(stake.is_active());
+        assert_eq!(stake.xhyper, xhyper);
+        assert_eq!(stats.total_staked, stake.amount);
+        assert_eq!(stats.total_xhyper, xhyper);
+        assert_eq!(stats.active_stakers, 1);
+
+        // Once the cooldown is over, withdraw is the only way out
+        stake.time_unstake = 2_000;
+        stake.update_xhyper();
+        assert_eq!(
+            stake.cancel_unstake(stake.cooldown_end(), &config).unwrap_err(),
+            StakingError::CooldownPassed.into()
+        );
+        assert!(!stake.is_active());
+    }
+
+    #[test]
+    fn test_withdraw_after_partial_slash_returns_remainder() {
+        let mut stake = active_stake(Pubkey::new_unique());
+        stake.amount = 10 * MIN_STAKE_AMOUNT;
+        stake.update_xhyper();
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+        stats.record_stake(stake.amount, stake.xhyper).unwrap();
+        let xhyper = stake.xhyper;
+
+        // 30% slash: the multiplier is kept and the position stays active
+        let slashed = 3 * MIN_STAKE_AMOUNT;
+        let (burned, unbonded) = stake.apply_slash(slashed, 5_000).unwrap();
+        stats.record_slash(slashed, burned, unbonded).unwrap();
+
+        assert!(!unbonded && stake.is_active());
+        assert_eq!(stake.amount, 7 * MIN_STAKE_AMOUNT);
+        assert_eq!(stake.xhyper, xhyper * 7 / 10);
+        assert_eq!(stats.total_staked, stake.amount);
+        assert_eq!(stats.total_xhyper, stake.xhyper);
+        assert_eq!(stats.active_stakers, 1);
+
+        // Unstake and withdraw pay out what is left, not the original amount
+        stake.time_unstake = 6_000;
+        stats.record_unstake(stake.xhyper).unwrap();
+        stake.update_xhyper();
+        assert!(stake.can_withdraw(stake.cooldown_end()));
+        stats.record_withdraw(stake.amount).unwrap();
+        assert_eq!(stats.total_staked, 0);
+        assert_eq!(stats.total_xhyper, 0);
+
+        // Can't slash more than is staked
+        assert_eq!(
+            stake.apply_slash(stake.amount + 1, 7_000).unwrap_err(),
+            StakingError::SlashingFailed.into()
+        );
+    }
+
+    #[test]
+    fn test_slash_payouts_fit_within_slashed_amount() {
+        let payouts = SlashPayouts { client: 500, treasury: 300, rewards: 200 };
+        assert!(payouts.fits(1_000));
+        assert!(!payouts.fits(999));
+
+        // Reporter penalty: nothing paid out, the tokens stay in the vault
+        assert!(SlashPayouts::default().fits(1_000));
+        assert_eq!(SlashPayouts::default().total(), Some(0));
+
+        let overflowing = SlashPayouts { client: u64::MAX, treasury: 1, rewards: 0 };
+        assert_eq!(overflowing.total(), None);
+        assert!(!overflowing.fits(u64::MAX));
+    }
+
+    #[test]
+    fn test_slash_below_minimum_grace_unbonds() {
+        let mut stake = active_stake(Pubkey::new_unique());
+        stake.amount = 2 * MIN_STAKE_AMOUNT;
+        stake.update_xhyper();
+        stake.pending_authority = Some(Pubkey::new_unique());
+        stake.lock_end = 10_000;
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+        stats.record_stake(stake.amount, stake.xhyper).unwrap();
+
+        // Remainder can't back a node: unbonded with the lock waived
+        let slashed = MIN_STAKE_AMOUNT + 1;
+        let (burned, unbonded) = stake.apply_slash(slashed, 5_000).unwrap();
+        stats.record_slash(slashed, burned, unbonded).unwrap();
+
+        assert!(unbonded && !stake.is_active());
+        assert_eq!(stake.amount, MIN_STAKE_AMOUNT - 1);
+        assert_eq!((stake.xhyper, stake.pending_authority), (0, None));
+        assert!(!stake.can_withdraw(5_000));
+        assert!(stake.can_withdraw(5_001));
+        assert_eq!(stats.total_staked, stake.amount);
+        assert_eq!((stats.total_xhyper, stats.active_stakers), (0, 0));
+
+        // Already unstaking: the rest of the cooldown is waived too
+        let mut stake = active_stake(Pubkey::new_unique());
+        stake.time_unstake = 2_000;
+        stake.update_xhyper();
+        assert!(!stake.can_withdraw(3_000));
+        let (burned, unbonded) = stake.apply_slash(stake.amount, 3_000).unwrap();
+        assert_eq!((burned, unbonded), (0, false));
+        assert_eq!(stake.amount, 0);
+        assert!(stake.can_withdraw(3_000));
+    }
+
+    fn curve_config(max_multiplier_bps: u32, curve: MultiplierCurve) -> StakingConfig {
+        StakingConfig { max_multiplier_bps, curve, bump: 0 }
+    }
+
+    #[test]
+    fn test_configured_curves_for_identical_stakes() {
+        let linear = curve_config(DEFAULT_MAX_MULTIPLIER_BPS, MultiplierCurve::Linear);
+        let convex = curve_config(DEFAULT_MAX_MULTIPLIER_BPS, MultiplierCurve::Convex);
+        let six_months = DURATION_MIN + (DURATION_MAX - DURATION_MIN) / 2;
+
+        // 1x floor at DURATION_MIN and identical max at DURATION_MAX
+        for config in [&linear, &convex] {
+            assert_eq!(config.calculate_xhyper(1_000, DURATION_MIN), 1_000);
+            assert_eq!(config.calculate_xhyper(1_000, DURATION_MIN - 1), 1_000);
+            assert_eq!(config.calculate_xhyper(1_000, DURATION_MAX), 4_000);
+            assert_eq!(config.calculate_xhyper(1_000, DURATION_MAX * 2), 4_000);
+        }
+
+        // Halfway: linear gives 2.5x, convex 1.75 + 0.375 = 2.125x
+        assert_eq!(linear.calculate_xhyper(1_000, six_months), 2_500);
+        assert_eq!(convex.calculate_xhyper(1_000, six_months), 2_125);
+
+        // Convex stays below linear across the range
+        for days in [30, 90, 180, 270, 364] {
+            let duration = days * 86400;
+            assert!(convex.calculate_xhyper(1_000_000, duration) < linear.calculate_xhyper(1_000_000, duration));
+        }
+    }
+
+    #[test]
+    fn test_configured_max_multiplier() {
+        let two_x = curve_config(20_000, MultiplierCurve::Linear);
+        let four_x = curve_config(DEFAULT_MAX_MULTIPLIER_BPS, MultiplierCurve::Linear);
+
+        assert_eq!(two_x.calculate_xhyper(1_000, DURATION_MAX), 2_000);
+        assert!(two_x.calculate_xhyper(1_000, 90 * 86400) < four_x.calculate_xhyper(1_000, 90 * 86400));
+
+        assert!(!StakingConfig::is_valid_max_multiplier(MULTIPLIER_BPS_ONE - 1));
+        assert!(StakingConfig::is_valid_max_multiplier(MULTIPLIER_BPS_ONE));
+        assert!(StakingConfig::is_valid_max_multiplier(MAX_MULTIPLIER_CEILING_BPS));
+        assert!(!StakingConfig::is_valid_max_multiplier(MAX_MULTIPLIER_CEILING_BPS + 1));
+
+        // No overflow at the extremes
+        let ten_x = curve_config(MAX_MULTIPLIER_CEILING_BPS, MultiplierCurve::Convex);
+        assert_eq!(ten_x.calculate_xhyper(u64::MAX, DURATION_MAX), u64::MAX as u128 * 10);
+    }
+
+    fn reputation_data(node: &Pubkey, total_jobs: u64, tier: u8) -> Vec<u8> {
+        let mut data = vec![0u8; 100];
+        data[..8].copy_from_slice(&NODE_REPUTATION_DISCRIMINATOR);
+        data[8..40].copy_from_slice(node.as_ref());
+        data[40..48].copy_from_slice(&total_jobs.to_le_bytes());
+        data[90] = tier;
+        data
+    }
+
+    #[test]
+    fn test_gold_node_gets_more_xhyper_than_unranked_staker() {
+        let node = Pubkey::new_unique();
+        let amount = 1_000_000_000;
+        let duration = 90 * 86400;
+        let base = StakeAccount::calculate_xhyper(amount, duration);
+
+        let gold = read_node_tier(&reputation_data(&node, 25, 3), &node);
+        assert_eq!(gold, Some(3));
+        let boosted = apply_node_boost(base, amount, gold, DEFAULT_MAX_MULTIPLIER_BPS);
+        assert_eq!(boosted, base * 10_750 / 10_000);
+
+        // Unranked staker, same lock: no boost
+        assert_eq!(apply_node_boost(base, amount, None, DEFAULT_MAX_MULTIPLIER_BPS), base);
+
+        // Combined multiplier capped at the curve maximum
+        let longest = StakeAccount::calculate_xhyper(amount, DURATION_MAX);
+        let capped = apply_node_boost(longest, amount, gold, DEFAULT_MAX_MULTIPLIER_BPS);
+        assert!(capped <= amount as u128 * 4);
+        assert!(capped >= longest);
+    }
+
+    #[test]
+    fn test_node_boost_follows_current_tier() {
+        let node = Pubkey::new_unique();
+        let config = curve_config(DEFAULT_MAX_MULTIPLIER_BPS, MultiplierCurve::Linear);
+        let mut stake = active_stake(node);
+        stake.amount = 1_000_000_000;
+        stake.duration = 90 * 86400;
+        let base = config.calculate_xhyper(stake.amount, stake.duration);
+
+        // Staked as a Gold node
+        let gold = read_node_tier(&reputation_data(&node, 25, 3), &node);
+        stake.xhyper = stake.boosted_xhyper(&config, gold);
+        assert_eq!(stake.xhyper, base * 10_750 / 10_000);
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+        stats.record_stake(stake.amount, stake.xhyper).unwrap();
+
+        // The tier drops to Bronze: sync_node_boost shrinks the boost
+        let old_xhyper = stake.xhyper;
+        let bronze = read_node_tier(&reputation_data(&node, 40, 1), &node);
+        stake.xhyper = stake.boosted_xhyper(&config, bronze);
+        assert_eq!(stake.xhyper, base * 10_250 / 10_000);
+        stats.record_top_up(0, old_xhyper, stake.xhyper).unwrap();
+        assert_eq!((stats.total_staked, stats.total_xhyper), (stake.amount, stake.xhyper));
+
+        // No reputation any more: no boost
+        assert_eq!(stake.boosted_xhyper(&config, None), base);
+    }
+
+    #[test]
+    fn test_node_tier_requires_own_reputation_with_jobs() {
+        let node = Pubkey::new_unique();
+
+        // Someone else's reputation
+        assert_eq!(read_node_tier(&reputation_data(&Pubkey::new_unique(), 25, 3), &node), None);
+
+        // Fresh account: top tier but no completed job yet
+        assert_eq!(read_node_tier(&reputation_data(&node, 0, 4), &node), None);
+
+        // Not a reputation account
+        let mut data = reputation_data(&node, 25, 3);
+        data[0] ^= 1;
+        assert_eq!(read_node_tier(&data, &node), None);
+        assert_eq!(read_node_tier(&data[..20], &node), None);
+    }
+
+    #[test]
+    fn test_delegate_to_operator_with_valid_commission() {
+        let delegator = Pubkey::new_unique();
+        let operator = Pubkey::new_unique();
+        let mut stake = active_stake(delegator);
+        let mut operator_stake = active_stake(operator);
+
+        // Commission is capped at 100%
+        assert_eq!(
+            operator_stake.set_commission(MAX_COMMISSION_BPS + 1).unwrap_err(),
+            StakingError::InvalidCommission.into()
+        );
+        operator_stake.set_commission(1_500).unwrap();
+        assert_eq!(operator_stake.commission_bps, 1_500);
+
+        // Not to yourself or the default key
+        assert_eq!(
+            stake.delegate(Some(delegator)).unwrap_err(),
+            StakingError::InvalidOperator.into()
+        );
+        assert!(stake.delegate(Some(Pubkey::default())).is_err());
+
+        stake.delegate(Some(operator)).unwrap();
+        assert_eq!(stake.operator, Some(operator));
+
+        // Unstaking positions can undelegate but not delegate
+        stake.time_unstake = 2_000;
+        assert_eq!(
+            stake.delegate(Some(operator)).unwrap_err(),
+            StakingError::StakeNotActive.into()
+        );
+        stake.delegate(None).unwrap();
+        assert_eq!(stake.operator, None);
+
+        // The Rewards Program reads the delegation and rate from raw stake data
+        let mut data = Vec::new();
+        stake.time_unstake = 0;
+        stake.delegate(Some(operator)).unwrap();
+        stake.try_serialize(&mut data).unwrap();
+        assert_eq!(
+            hypernode_rewards::state::read_stake_delegation(&data),
+            Some((Some(operator), 0))
+        );
+
+        let mut data = Vec::new();
+        operator_stake.operator = Some(Pubkey::new_unique());
+        operator_stake.pending_authority = Some(Pubkey::new_unique());
+        operator_stake.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), StakeAccount::LEN);
+        assert_eq!(
+            hypernode_rewards::state::read_stake_delegation(&data),
+            Some((operator_stake.operator, 1_500))
+        );
+    }
+
+    #[test]
+    fn test_duration_tiers_yield_documented_multipliers() {
+        let amount = 1_000_000;
+        let default_curve = curve_config(DEFAULT_MAX_MULTIPLIER_BPS, MultiplierCurve::Linear);
+
+        // (tier, seconds, built-in curve xHYPER, default config curve xHYPER)
+        let tiers = [
+            (DurationTier::TwoWeeks, 1_209_600, 1_115_068, 1_000_000),
+            (DurationTier::OneMonth, 2_592_000, 1_246_575, 1_136_700),
+            (DurationTier::ThreeMonths, 7_776_000, 1_739_726, 1_649_500),
+            (DurationTier::SixMonths, 15_552_000, 2_479_452, 2_418_800),
+            (DurationTier::OneYear, 31_536_000, 4_000_000, 4_000_000),
+        ];
+        for (tier, seconds, xhyper, configured) in tiers {
+            assert_eq!(tier.seconds(), seconds);
+            assert_eq!(StakeAccount::calculate_xhyper(amount, tier.seconds()), xhyper);
+            assert_eq!(default_curve.calculate_xhyper(amount, tier.seconds()), configured);
+        }
+
+        // The shortest and longest tiers sit exactly on the stake bounds
+        assert_eq!(DurationTier::TwoWeeks.seconds(), DURATION_MIN);
+        assert_eq!(DurationTier::OneYear.seconds(), DURATION_MAX);
+    }
+
+    #[test]
+    fn test_top_up_near_unlock_relocks_and_blends_multiplier() {
+        let one_million = 1_000_000;
+        let mut stake = active_stake(Pubkey::new_unique());
+        stake.amount = one_million;
+        stake.time_stake = 0;
+        stake.duration = DURATION_MAX;
+        stake.lock_end = DURATION_MAX;
+        stake.update_xhyper();
+        assert_eq!(stake.xhyper, 4_000_000);
+
+        let mut stats = GlobalStakingStats {
+            total_staked: 0,
+            total_xhyper: 0,
+            active_stakers: 0,
+            bump: 0,
+        };
+        stats.record_stake(stake.amount, stake.xhyper).unwrap();
+
+        // One day before the lock ends the added tokens only commit to the minimum lock
+        let now = DURATION_MAX - 86_400;
+        assert_eq!(stake.top_up_lock(now), DURATION_MIN);
+        let old_xhyper = stake.xhyper;
+        assert_eq!(stake.top_up(one_million, now).unwrap(), DURATION_MIN);
+
+        // duration' = (1M * 365d + 1M * 14d) / 2M, but the position stays locked for
+        // the year it would have needed had it been unstaked instead
+        assert_eq!(stake.amount, 2 * one_million);
+        assert_eq!(stake.duration, (DURATION_MAX + DURATION_MIN) / 2);
+        assert_eq!(stake.lock_end, now + DURATION_MAX);
+
+        // The blended xHYPER is the old position plus a 2-week top-up, not 4x on everything
+        stake.update_xhyper();
+        let separate = old_xhyper + StakeAccount::calculate_xhyper(one_million, DURATION_MIN);
+        assert!(stake.xhyper.abs_diff(separate) <= 1);
+        assert!(stake.xhyper < StakeAccount::calculate_xhyper(2 * one_million, DURATION_MAX));
+        stats.record_top_up(one_million, old_xhyper, stake.xhyper).unwrap();
+        assert_eq!(stats.total_staked, stake.amount);
+        assert_eq!(stats.total_xhyper, stake.xhyper);
+
+        // Unstaking a day later waits out lock_end, not just the blended cooldown
+        let unstaked_at = now + 86_400;
+        stake.time_unstake = unstaked_at;
+        assert!(unstaked_at + stake.duration < stake.lock_end);
+        assert_eq!(stake.cooldown_end(), stake.lock_end);
+        assert!(!stake.can_withdraw(unstaked_at + stake.duration));
+        assert!(stake.can_withdraw(now + DURATION_MAX));
+
+        // Once lock_end has passed the blended cooldown applies
+        stake.time_unstake = stake.lock_end;
+        assert_eq!(stake.cooldown_end(), stake.lock_end + stake.duration);
+        assert_eq!(
+            stake.top_up(one_million, now).unwrap_err(),
+            StakingError::StakeNotActive.into()
+        );
+    }
+
+    #[test]
+    fn test_top_up_early_in_lock_keeps_full_multiplier() {
+        let mut stake = active_stake(Pubkey::new_unique());
+        stake.amount = 1_000_000;
+        stake.time_stake = 1_000;
+        stake.duration = DURATION_MAX;
+        stake.lock_end = 1_000 + DURATION_MAX;
+
+        // Right after staking the added tokens commit to the full remaining year
+        stake.top_up(1_000_000, 1_000).unwrap();
+        assert_eq!(stake.duration, DURATION_MAX);
+        assert_eq!(stake.lock_end, 1_000 + DURATION_MAX);
+        stake.update_xhyper();
+        assert_eq!(stake.xhyper, 8_000_000);
+
+        // Long after the lock ended a top-up re-locks for at least DURATION_MIN
+        let later = stake.lock_end + 30 * 86_400;
+        assert_eq!(stake.top_up_lock(later), DURATION_MIN);
+        stake.top_up(2_000_000, later).unwrap();
+        assert_eq!(stake.duration, (2 * DURATION_MAX + 2 * DURATION_MIN) / 4);
+        assert_eq!(stake.lock_end, later + DURATION_MAX);
+        assert_eq!(stake.top_up_lock(later), DURATION_MAX);
+
+        assert_eq!(
+            stake.top_up(0, later).unwrap_err(),
+            StakingError::AmountTooSmall.into()
+        );
+    }
 }
@@ -1,6 +1,7 @@
 use anchor_lang::prelude::*;
 use crate::constants::*;
 use crate::errors::*;
+use crate::state::{STAKE_STATE_ACTIVE, STAKE_STATE_UNSTAKING};
 
 /// Input validation utilities for staking operations
 
@@ -60,6 +61,17 @@ pub fn validate_token_account_owner(
     Ok(())
 }
 
+/// Validate an optional stake beneficiary
+/// Must be a real key distinct from the signing authority
+pub fn validate_beneficiary(beneficiary: &Option<Pubkey>, authority: &Pubkey) -> Result<()> {
+    if let Some(beneficiary) = beneficiary {
+        if *beneficiary == Pubkey::default() || beneficiary == authority {
+            return Err(StakingError::InvalidBeneficiary.into());
+        }
+    }
+    Ok(())
+}
+
 /// Validate authority signature
 pub fn validate_authority(actual: &Pubkey, expected: &Pubkey) -> Result<()> {
     if actual != expected {
@@ -144,8 +156,7 @@ pub fn validate_unstake_instruction(
     authority: &Pubkey,
     signer: &Pubkey,
 ) -> Result<()> {
-    // State: 0 = active, 1 = unstaking, 2 = done
-    if stake_account_state != 0 {
+    if stake_account_state != STAKE_STATE_ACTIVE {
         return Err(StakingError::AlreadyUnstaking.into());
     }
     validate_authority(signer, authority)?;
@@ -160,11 +171,131 @@ pub fn validate_withdraw_instruction(
     signer: &Pubkey,
     clock: &Clock,
 ) -> Result<()> {
-    // State: 0 = active, 1 = unstaking, 2 = done
-    if stake_account_state != 1 {
+    if stake_account_state != STAKE_STATE_UNSTAKING {
         return Err(StakingError::NotUnstaking.into());
     }
     validate_cooldown_passed(cooldown_end, clock.unix_timestamp)?;
     validate_authority(signer, authority)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::{StakeAccount, DURATION_MIN};
+
+    fn stake_account(authority: Pubkey) -> StakeAccount {
+        StakeAccount {
+            authority,
+            xhyper: 1000,
+            amount: 1000,
+            time_stake: 1_000,
+            time_unstake: 0,
+            duration: DURATION_MIN,
+            beneficiary: None,
+            pending_authority: None,
+            operator: None,
+            commission_bps: 0,
+            lock_end: 0,
+            bump: 0,
+        }
+    }
+
+    fn clock_at(unix_timestamp: i64) -> Clock {
+        Clock {
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn validate_withdraw(stake: &StakeAccount, signer: &Pubkey, now: i64) -> Result<()> {
+        validate_withdraw_instruction(
+            stake.state_code(),
+            stake.cooldown_end(),
+            &stake.authority,
+            signer,
+            &clock_at(now),
+        )
+    }
+
+    #[test]
+    fn test_unstake_twice_rejected() {
+        let authority = Pubkey::new_unique();
+        let mut stake = stake_account(authority);
+        assert!(validate_unstake_instruction(stake.state_code(), &stake.authority, &authority).is_ok());
+
+        stake.time_unstake = 2_000;
+        assert_eq!(
+            validate_unstake_instruction(stake.state_code(), &stake.authority, &authority).unwrap_err(),
+            StakingError::AlreadyUnstaking.into()
+        );
+    }
+
+    #[test]
+    fn test_unstake_by_other_signer_rejected() {
+        let stake = stake_account(Pubkey::new_unique());
+        assert_eq!(
+            validate_unstake_instruction(stake.state_code(), &stake.authority, &Pubkey::new_unique()).unwrap_err(),
+            StakingError::Unauthorized.into()
+        );
+    }
+
+    #[test]
+    fn test_withdraw_without_unstake_rejected() {
+        let authority = Pubkey::new_unique();
+        let stake = stake_account(authority);
+        assert_eq!(
+            validate_withdraw(&stake, &authority, 10_000_000_000).unwrap_err(),
+            StakingError::NotUnstaking.into()
+        );
+        assert!(!stake.can_withdraw(10_000_000_000));
+    }
+
+    #[test]
+    fn test_withdraw_before_cooldown_rejected() {
+        let authority = Pubkey::new_unique();
+        let mut stake = stake_account(authority);
+        stake.time_unstake = 2_000;
+
+        // Same timestamp as unstake
+        assert_eq!(
+            validate_withdraw(&stake, &authority, 2_000).unwrap_err(),
+            StakingError::CooldownNotPassed.into()
+        );
+        assert!(!stake.can_withdraw(2_000));
+
+        // One second short of the cooldown
+        let cooldown_end = stake.cooldown_end();
+        assert!(validate_withdraw(&stake, &authority, cooldown_end - 1).is_err());
+
+        // Cooldown passed
+        assert!(validate_withdraw(&stake, &authority, cooldown_end).is_ok());
+        assert!(stake.can_withdraw(cooldown_end));
+    }
+
+    #[test]
+    fn test_withdraw_zero_duration_same_timestamp_rejected() {
+        let authority = Pubkey::new_unique();
+        let mut stake = stake_account(authority);
+        stake.duration = 0;
+        stake.time_unstake = 2_000;
+
+        assert!(!stake.can_withdraw(2_000));
+        assert!(stake.can_withdraw(2_001));
+    }
+
+    #[test]
+    fn test_beneficiary_validated_separately_from_authority() {
+        let authority = Pubkey::new_unique();
+        assert!(validate_beneficiary(&None, &authority).is_ok());
+        assert!(validate_beneficiary(&Some(Pubkey::new_unique()), &authority).is_ok());
+        assert_eq!(
+            validate_beneficiary(&Some(authority), &authority).unwrap_err(),
+            StakingError::InvalidBeneficiary.into()
+        );
+        assert_eq!(
+            validate_beneficiary(&Some(Pubkey::default()), &authority).unwrap_err(),
+            StakingError::InvalidBeneficiary.into()
+        );
+    }
+}
@@ -77,7 +77,7 @@ export class MarketClient {
     const [vault, vaultBump] = await this.getVaultPda(market.publicKey);
 
     const signature = await this.program.methods
-      .createMarket(jobPrice, jobTimeout, nodeXhyperMinimum)
+      .createMarket(jobPrice, jobTimeout, nodeXhyperMinimum, params.maxQueueSize ?? 314)
       .accounts({
         market: market.publicKey,
         authority: this.provider.wallet.publicKey,
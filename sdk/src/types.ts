@@ -134,6 +134,8 @@ export interface CreateMarketParams {
   jobPrice: number | BN;
   jobTimeout: number | BN;
   nodeXhyperMinimum: number | BN;
+  /** Queue capacity, 1-314 (default 314) */
+  maxQueueSize?: number;
 }
 
 /**
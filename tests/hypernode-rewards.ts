@@ -13,6 +13,7 @@ describe("hypernode-rewards", () => {
 
   let mint: PublicKey;
   let rewardsVault: PublicKey;
+  let treasury: PublicKey;
   let reflectionAccount: PublicKey;
   let reflectionBump: number;
   let user1: Keypair;
@@ -60,6 +61,15 @@ describe("hypernode-rewards", () => {
       provider.wallet.publicKey
     );
 
+    // Create protocol treasury
+    treasury = await createAccount(
+      provider.connection,
+      user1,
+      mint,
+      provider.wallet.publicKey,
+      Keypair.generate()
+    );
+
     // Mint tokens to vault
     await mintTo(
       provider.connection,
@@ -97,6 +107,7 @@ describe("hypernode-rewards", () => {
       .initialize()
       .accounts({
         reflectionAccount: reflectionAccount,
+        rewardMint: mint,
         authority: provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -115,9 +126,24 @@ describe("hypernode-rewards", () => {
     console.log("✅ Reflection account initialized");
   });
 
+  it("Initializes rewards config (fee split)", async () => {
+    // No treasury share, so all added rewards go to stakers
+    await program.methods
+      .initializeConfig(0)
+      .accounts({
+        reflectionAccount: reflectionAccount,
+        treasury: treasury,
+        authority: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+
+    console.log("✅ Rewards config initialized");
+  });
+
   it("Registers user 1 stake (10,000 xHYPER)", async () => {
     await program.methods
-      .registerStake(user1Xhyper)
+      .registerStake(user1Xhyper, null)
       .accounts({
         reflectionAccount: reflectionAccount,
         userRewardsAccount: user1RewardsAccount,
@@ -146,7 +172,7 @@ describe("hypernode-rewards", () => {
 
   it("Registers user 2 stake (20,000 xHYPER)", async () => {
     await program.methods
-      .registerStake(user2Xhyper)
+      .registerStake(user2Xhyper, null)
       .accounts({
         reflectionAccount: reflectionAccount,
         userRewardsAccount: user2RewardsAccount,
@@ -179,6 +205,7 @@ describe("hypernode-rewards", () => {
         reflectionAccount: reflectionAccount,
         sourceTokenAccount: rewardsVault,
         rewardsVault: rewardsVault,
+        treasuryTokenAccount: treasury,
         authority: provider.wallet.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -314,6 +341,7 @@ describe("hypernode-rewards", () => {
         reflectionAccount: reflectionAccount,
         sourceTokenAccount: rewardsVault,
         rewardsVault: rewardsVault,
+        treasuryTokenAccount: treasury,
         authority: provider.wallet.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
@@ -82,7 +82,7 @@ describe("hypernode-staking", () => {
     const duration = new anchor.BN(DURATION_MIN);
 
     await program.methods
-      .stake(stakeAmount, duration)
+      .stake(stakeAmount, duration, null)
       .accounts({
         stakeAccount: stakeAccount,
         authority: user.publicKey,
@@ -203,7 +203,7 @@ describe("hypernode-staking", () => {
     const duration = new anchor.BN(DURATION_MAX);
 
     await program.methods
-      .stake(stakeAmount, duration)
+      .stake(stakeAmount, duration, null)
       .accounts({
         stakeAccount: stakeAccount2,
         authority: user2.publicKey,
@@ -264,7 +264,7 @@ describe("hypernode-staking", () => {
     const duration = new anchor.BN(180 * 86400); // 6 months
 
     await program.methods
-      .stake(stakeAmount, duration)
+      .stake(stakeAmount, duration, null)
       .accounts({
         stakeAccount: stakeAccount3,
         authority: user3.publicKey,
@@ -323,7 +323,7 @@ describe("hypernode-staking", () => {
 
     try {
       await program.methods
-        .stake(stakeAmount, duration)
+        .stake(stakeAmount, duration, null)
         .accounts({
           stakeAccount: stakeAccount4,
           authority: user4.publicKey,
@@ -340,4 +340,132 @@ describe("hypernode-staking", () => {
       console.log("✅ Correctly prevented stake with duration below minimum");
     }
   });
+
+  it("Transfers an active stake to a new owner", async () => {
+    const seller = Keypair.generate();
+    const buyer = Keypair.generate();
+
+    for (const kp of [seller, buyer]) {
+      await provider.connection.confirmTransaction(
+        await provider.connection.requestAirdrop(
+          kp.publicKey,
+          10 * anchor.web3.LAMPORTS_PER_SOL
+        )
+      );
+    }
+
+    const sellerTokenAccount = await createAccount(
+      provider.connection,
+      seller,
+      mint,
+      seller.publicKey
+    );
+
+    await mintTo(
+      provider.connection,
+      seller,
+      mint,
+      sellerTokenAccount,
+      provider.wallet.publicKey,
+      100_000_000_000
+    );
+
+    const [sellerStake] = await PublicKey.findProgramAddress(
+      [Buffer.from("stake"), seller.publicKey.toBuffer()],
+      program.programId
+    );
+    const [buyerStake] = await PublicKey.findProgramAddress(
+      [Buffer.from("stake"), buyer.publicKey.toBuffer()],
+      program.programId
+    );
+
+    await program.methods
+      .stake(stakeAmount, new anchor.BN(DURATION_MAX), null)
+      .accounts({
+        stakeAccount: sellerStake,
+        authority: seller.publicKey,
+        userTokenAccount: sellerTokenAccount,
+        vault: vault,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([seller])
+      .rpc();
+    const before = await program.account.stakeAccount.fetch(sellerStake);
+
+    // Step 1: current owner proposes the transfer
+    await program.methods
+      .transferStake(buyer.publicKey)
+      .accounts({
+        stakeAccount: sellerStake,
+        authority: seller.publicKey,
+        reflectionAccount: null,
+        userRewardsAccount: null,
+        rewardsProgram: null,
+      })
+      .signers([seller])
+      .rpc();
+
+    const pending = await program.account.stakeAccount.fetch(sellerStake);
+    assert.equal(pending.pendingAuthority.toString(), buyer.publicKey.toString());
+
+    // Step 2: new owner accepts
+    await program.methods
+      .acceptStakeTransfer()
+      .accounts({
+        previousStakeAccount: sellerStake,
+        stakeAccount: buyerStake,
+        previousAuthority: seller.publicKey,
+        authority: buyer.publicKey,
+        reflectionAccount: null,
+        userRewardsAccount: null,
+        rewardsProgram: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const after = await program.account.stakeAccount.fetch(buyerStake);
+    assert.equal(after.authority.toString(), buyer.publicKey.toString());
+    assert.equal(after.amount.toString(), before.amount.toString());
+    assert.equal(after.xhyper.toString(), before.xhyper.toString());
+    assert.equal(after.timeStake.toString(), before.timeStake.toString());
+    assert.isNull(after.pendingAuthority);
+    assert.isNull(await provider.connection.getAccountInfo(sellerStake));
+
+    // New owner controls the position (unstake, then withdraw after cooldown)
+    await program.methods
+      .unstake()
+      .accounts({
+        stakeAccount: buyerStake,
+        authority: buyer.publicKey,
+      })
+      .signers([buyer])
+      .rpc();
+
+    const unstaking = await program.account.stakeAccount.fetch(buyerStake);
+    assert.notEqual(unstaking.timeUnstake.toNumber(), 0);
+    console.log("✅ Stake transferred to new owner");
+  });
+
+  it("Cannot transfer an unstaking position", async () => {
+    try {
+      await program.methods
+        .transferStake(Keypair.generate().publicKey)
+        .accounts({
+          stakeAccount: stakeAccount,
+          authority: user.publicKey,
+          reflectionAccount: null,
+          userRewardsAccount: null,
+          rewardsProgram: null,
+        })
+        .signers([user])
+        .rpc();
+
+      assert.fail("Should have failed - stake is unstaking");
+    } catch (err) {
+      assert.include(err.toString(), "StakeNotActive");
+      console.log("✅ Correctly prevented transfer of unstaking position");
+    }
+  });
 });
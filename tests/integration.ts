@@ -126,6 +126,7 @@ describe("integration: Markets + Staking + Rewards", () => {
       .initialize()
       .accounts({
         reflectionAccount: reflectionAccount,
+        rewardMint: mint,
         authority: provider.wallet.publicKey,
         systemProgram: SystemProgram.programId,
       })
@@ -136,7 +137,7 @@ describe("integration: Markets + Staking + Rewards", () => {
 
   it("Step 2: Node stakes HYPER tokens", async () => {
     await stakingProgram.methods
-      .stake(stakeAmount, stakeDuration)
+      .stake(stakeAmount, stakeDuration, null)
       .accounts({
         stakeAccount: nodeStakeAccount,
         authority: node.publicKey,
@@ -162,7 +163,7 @@ describe("integration: Markets + Staking + Rewards", () => {
     const stakeAccountData = await stakingProgram.account.stakeAccount.fetch(nodeStakeAccount);
 
     await rewardsProgram.methods
-      .registerStake(stakeAccountData.xhyper)
+      .registerStake(stakeAccountData.xhyper, null)
       .accounts({
         reflectionAccount: reflectionAccount,
         userRewardsAccount: nodeRewardsAccount,
@@ -177,7 +178,7 @@ describe("integration: Markets + Staking + Rewards", () => {
 
   it("Step 4: Create marketplace", async () => {
     await marketsProgram.methods
-      .createMarket(jobPrice, jobTimeout, nodeXhyperMinimum)
+      .createMarket(jobPrice, jobTimeout, nodeXhyperMinimum, 64)
       .accounts({
         market: market.publicKey,
         authority: provider.wallet.publicKey,
@@ -224,7 +225,7 @@ describe("integration: Markets + Staking + Rewards", () => {
 
     // Client creates job - should auto-match with queued node
     await marketsProgram.methods
-      .createJob(jobId, Array.from(ipfsJob), 8, 1)
+      .createJob(jobId, Array.from(ipfsJob), 8, 1, new anchor.BN(0), new anchor.BN(0), 0, { estimatedDuration: null, inputSizeMb: null, modelId: null })
       .accounts({
         job: jobPda,
         market: market.publicKey,
@@ -260,7 +261,7 @@ describe("integration: Markets + Staking + Rewards", () => {
 
     // Create another job
     await marketsProgram.methods
-      .createJob(jobId, Array.from(ipfsJob), 8, 1)
+      .createJob(jobId, Array.from(ipfsJob), 8, 1, new anchor.BN(0), new anchor.BN(0), 0, { estimatedDuration: null, inputSizeMb: null, modelId: null })
       .accounts({
         job: jobPda,
         market: market.publicKey,
@@ -297,11 +298,9 @@ describe("integration: Markets + Staking + Rewards", () => {
         market: market.publicKey,
         node: node.publicKey,
         nodeAccount: node.publicKey,
+        marketAuthority: provider.wallet.publicKey,
+        client: client.publicKey,
         vault: marketVault,
-        reflectionAccount: reflectionAccount,
-        rewardsVault: rewardsVault,
-        rewardsProgram: rewardsProgram.programId,
-        tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([node])
@@ -320,7 +319,7 @@ describe("integration: Markets + Staking + Rewards", () => {
 
     console.log("✅ Job finished and payment distributed");
     console.log("   Node payment:", nodePayment, "lamports (expected:", expectedNodePayment, ")");
-    console.log("   Rewards fee:", expectedRewardsFee, "lamports (1%)");
+    console.log("   Rewards fee:", expectedRewardsFee, "lamports (1%, held until the dispute window closes)");
     console.log("   Vault decrease:", vaultDecrease, "lamports");
 
     // Note: Actual payment might differ slightly due to transaction fees